@@ -159,6 +159,11 @@ export default function solidOxc(options: SolidOxcOptions = {}): Plugin {
             sourceMap: true,
           });
 
+          // No JSX: keep the module as it is, without a source map
+          if (!result.transformed) {
+            return null;
+          }
+
           let finalCode = result.code;
 
           // Add HMR support in dev mode
@@ -1,5 +1,74 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
+/**
+ * Transform cache for watch mode: unchanged files with unchanged options
+ * return the previous output without being compiled again
+ */
+export declare class TransformCache {
+  constructor()
+  /** Same as `transformJsx`, reusing the cached output when possible */
+  transform(source: string, options?: JsTransformOptions | undefined | null): TransformResult
+  /** Number of cached outputs */
+  get size(): number
+  /** Drop every cached output */
+  clear(): void
+}
+
+/** A file of a batch transform */
+export interface JsBatchFile {
+  /** Source filename, used in place of the `filename` option */
+  filename: string
+  /** The source code to transform */
+  source: string
+}
+
+/** Result of a buffer transform */
+export interface BufferTransformResult {
+  /** The transformed code, UTF-8 encoded */
+  code: Buffer
+  /** Source map (if enabled) */
+  map?: string
+  /** `false` when the file has no JSX and `code` is the source as given */
+  transformed: boolean
+  /**
+   * Parse and unsupported-syntax errors; when present, `code` is empty and
+   * `map` is unset
+   */
+  errors?: Array<JsParseError>
+  /** The components rendered as islands, with the `islands` option */
+  islands?: Array<JsIsland>
+}
+
+/** An island of the `islands` option exposed to JavaScript */
+export interface JsIsland {
+  /** Key in the `<!--island:id-->` boundary markers */
+  id: string
+  /** Local name of the component */
+  component: string
+  /** Module the component is imported from */
+  source: string
+  /** Name the module exports it under, `default` for a default import */
+  export: string
+  /** Byte offset of the JSX element in the source */
+  start: number
+  /** Byte offset of the end of the JSX element */
+  end: number
+}
+
+/** A parse error exposed to JavaScript */
+export interface JsParseError {
+  /** Source filename */
+  filename: string
+  /** Error message from the parser */
+  message: string
+  /** 1-based line of the error */
+  line: number
+  /** 1-based column of the error */
+  column: number
+  /** Excerpt of the source around the error */
+  codeFrame: string
+}
+
 /** Transform options exposed to JavaScript */
 export interface JsTransformOptions {
   /**
@@ -13,26 +82,68 @@ export interface JsTransformOptions {
    * @default "dom"
    */
   generate?: string
+  /**
+   * How runtime helpers are imported: "esm" (`import`) or "cjs" (`require`)
+   * @default "esm"
+   */
+  moduleFormat?: string
   /**
    * Whether to enable hydration support
    * @default false
    */
   hydratable?: boolean
+  /**
+   * Inject `generateHydrationScript()` at the end of `<head>` in hydratable SSR output
+   * @default false
+   */
+  hydrationScript?: boolean
+  /**
+   * Experimental: render imported components of hydratable SSR output as
+   * islands and list them in the result's `islands` manifest
+   * @default false
+   */
+  islands?: boolean
   /**
    * Whether to delegate events
    * @default true
    */
   delegateEvents?: boolean
   /**
-   * Whether to wrap conditionals
+   * How delegated events are registered: "call" (`delegateEvents([...])` per module)
+   * or "export" (`export const _$delegatedEvents = [...]` for a bundler to collect)
+   * @default "call"
+   */
+  delegateEventsStrategy?: string
+  /**
+   * Whether to memoize the test of conditional children
    * @default true
    */
   wrapConditionals?: boolean
+  /**
+   * Keep JSX text as written instead of trimming and collapsing its whitespace
+   * @default false
+   */
+  preserveWhitespace?: boolean
+  /**
+   * Helper reactive bindings are wrapped in, imported from `module_name`
+   * @default "effect"
+   */
+  effectWrapper?: string
+  /**
+   * Helper derived values are memoized with, imported from `module_name`
+   * @default "memo"
+   */
+  memoWrapper?: string
   /**
    * Whether to pass context to custom elements
    * @default true
    */
   contextToCustomElements?: boolean
+  /**
+   * Tags treated as custom elements even without a dash; an entry ending in `*` matches by prefix
+   * @default []
+   */
+  customElements?: Array<string>
   /**
    * Source filename
    * @default "input.jsx"
@@ -43,8 +154,115 @@ export interface JsTransformOptions {
    * @default false
    */
   sourceMap?: boolean
+  /**
+   * Strip TypeScript syntax (and lower enums/namespaces) before the JSX transform
+   * @default false
+   */
+  stripTypes?: boolean
+  /**
+   * Minify the output: shorten generated locals and drop whitespace and comments
+   * @default false
+   */
+  minify?: boolean
+  /**
+   * Build templates with `document.createElement` calls instead of `template()`,
+   * for Content Security Policies that block `innerHTML`
+   * @default false
+   */
+  cspSafe?: boolean
+  /**
+   * Emit the runtime helpers that don't need Solid's reactive runtime into
+   * the DOM output instead of importing them from `solid-js/web`
+   * @default false
+   */
+  inlineHelpers?: boolean
+  /**
+   * Render SSR elements with `ssrElement()` calls instead of `ssr` tagged templates
+   * @default false
+   */
+  ssrElementOnly?: boolean
+  /**
+   * Resolve `isServer`, `isDev` and `DEV` checks at compile time
+   * @default true
+   */
+  foldEnvironment?: boolean
+  /** Whether the output targets Solid's development build (folds `isDev` and `DEV`) */
+  dev?: boolean
+  /** Constant replacements applied before the transform, e.g. `{ "import.meta.env.SSR": "true" }` */
+  define?: Record<string, string>
+  /**
+   * Only transform files with a `/** @jsxImportSource <name> *\/` pragma
+   * naming this source; other files are returned unchanged
+   */
+  requireImportSource?: string
+  /**
+   * Encoding of the source passed to `transformJsxBuffer`: "utf8" or "latin1"
+   * @default "utf8"
+   */
+  encoding?: string
 }
 
+/** A template root found in the source */
+export interface ExtractedTemplate {
+  /** 1-based line of the root element */
+  line: number
+  /** 1-based column of the root element */
+  column: number
+  root: TemplateElement
+}
+
+/** A native element inside a template */
+export interface TemplateElement {
+  tag: string
+  attributes: Array<TemplateAttribute>
+  /** Statically known class names (`class`, `classList` keys, `class:name`) */
+  classes: Array<string>
+  /** Static text children */
+  text: Array<string>
+  /** Native element children */
+  children: Array<TemplateElement>
+  /** Whether the element has expression, component or fragment children */
+  hasDynamicChildren: boolean
+}
+
+/** An attribute of a template element */
+export interface TemplateAttribute {
+  /** Attribute name as written, `...` for spreads */
+  name: string
+  /** Value when it is a static string, number or boolean */
+  value?: string
+  /** Whether the value is only known at runtime */
+  dynamic: boolean
+}
+
+/** Result of a template extraction */
+export interface ExtractTemplatesResult {
+  /** Template roots in source order */
+  templates: Array<ExtractedTemplate>
+  /** Parse errors; when present, `templates` is empty */
+  errors?: Array<JsParseError>
+}
+
+/** Collect the static templates in a file without generating code */
+export declare function extractJsxTemplates(source: string, options?: JsTransformOptions | undefined | null): ExtractTemplatesResult
+
+/**
+ * Transform many files in parallel, off the JS thread. `on_progress` is
+ * called with `(filename, index, total)` as each file finishes, `index`
+ * being its position in `files`; the promise resolves with the results in
+ * input order.
+ */
+export declare function transformJsxBatch(files: Array<JsBatchFile>, options?: JsTransformOptions | undefined | null, onProgress?: ((arg0: string, arg1: number, arg2: number) => unknown) | undefined | null): Promise<Array<TransformResult>>
+
+/**
+ * Transform JSX source code passed as bytes (a `Buffer` or `Uint8Array`
+ * straight from `fs.readFile`), returning the code as a `Buffer`.
+ *
+ * UTF-8 sources are read in place and the output buffer takes over the
+ * generated code, so neither side is copied into or out of a JS string.
+ */
+export declare function transformJsxBuffer(source: Uint8Array, options?: JsTransformOptions | undefined | null): BufferTransformResult
+
 /** Transform JSX source code */
 export declare function transformJsx(source: string, options?: JsTransformOptions | undefined | null): TransformResult
 
@@ -54,4 +272,16 @@ export interface TransformResult {
   code: string
   /** Source map (if enabled) */
   map?: string
+  /**
+   * `false` when the file has no JSX and `code` is the source as given,
+   * so a bundler can keep the module and skip its source map
+   */
+  transformed: boolean
+  /**
+   * Parse and unsupported-syntax errors; when present, `code` is empty and
+   * `map` is unset
+   */
+  errors?: Array<JsParseError>
+  /** The components rendered as islands, with the `islands` option */
+  islands?: Array<JsIsland>
 }
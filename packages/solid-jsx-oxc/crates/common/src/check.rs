@@ -1,13 +1,18 @@
 //! Check functions for JSX nodes
 //! Ported from dom-expressions/src/shared/utils.js
 
+use std::borrow::Cow;
+
 use oxc_ast::ast::{
-    Expression, JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXElement,
-    JSXElementName, JSXMemberExpression, JSXMemberExpressionObject,
+    BinaryOperator, Expression, JSXAttribute, JSXAttributeItem, JSXAttributeName,
+    JSXAttributeValue, JSXChild, JSXElement, JSXElementName, JSXExpression, JSXMemberExpression,
+    JSXMemberExpressionObject, LogicalOperator, UnaryOperator,
 };
 
-use crate::constants::{BUILT_INS, SVG_ELEMENTS};
-use crate::expression::expr_to_string;
+use crate::constants::{ALIASES, BUILT_INS, PROP_ALIASES, SVG_ELEMENTS};
+use crate::expression::{expr_to_string, trim_whitespace};
+use crate::options::TransformOptions;
+use crate::prerender::static_child_text;
 
 /// Check if a tag name represents a component (starts with uppercase or contains dot)
 pub fn is_component(tag: &str) -> bool {
@@ -23,6 +28,77 @@ pub fn is_built_in(tag: &str) -> bool {
     BUILT_INS.contains(tag)
 }
 
+/// Check if a tag is a custom element: it contains a dash, or matches an
+/// entry of `options.custom_elements` (entries ending in `*` match by prefix)
+pub fn is_custom_element(tag: &str, options: &TransformOptions<'_>) -> bool {
+    tag.contains('-')
+        || options
+            .custom_elements
+            .iter()
+            .any(|entry| match entry.strip_suffix('*') {
+                Some(prefix) => tag.starts_with(prefix),
+                None => tag == *entry,
+            })
+}
+
+/// Check if `key` is the live state of a form control (`value` on
+/// `<input>`, `<select>` and `<textarea>`, `checked` on `<input>`). The
+/// attribute only sets the initial state, so bindings must write the property
+/// to keep a controlled input in sync.
+pub fn is_controlled_property(tag: &str, key: &str) -> bool {
+    match key {
+        "value" => matches!(tag, "input" | "select" | "textarea"),
+        "checked" => tag == "input",
+        _ => false,
+    }
+}
+
+/// Check if `<tag value="...">` can't be expressed in markup: `<select>` and
+/// `<textarea>` ignore a `value` attribute
+pub fn is_value_property_only(tag: &str) -> bool {
+    matches!(tag, "select" | "textarea")
+}
+
+/// Check if `key` is a `data-*` or `aria-*` attribute, or `slot`. These have
+/// no reflecting property (or one that isn't kebab-case), so they are always
+/// written with `setAttribute`, under the name as written. `slot` has to be
+/// an attribute for shadow DOM to assign the element, even on custom elements.
+pub fn is_attribute_only(key: &str) -> bool {
+    key.starts_with("data-") || key.starts_with("aria-") || key == "slot"
+}
+
+/// Name an attribute is written under in markup and with `setAttribute`.
+/// `className` and `htmlFor` resolve to `class` and `for`, and HTML
+/// attributes are lowercased (`readOnly` -> `readonly`). SVG attributes are
+/// case-sensitive, so `viewBox` and `preserveAspectRatio` keep their case, as
+/// do namespaced and attribute-only ones. Custom elements keep every name as
+/// written.
+pub fn attribute_name(key: &str, is_svg: bool, is_custom_element: bool) -> Cow<'_, str> {
+    if is_custom_element {
+        return Cow::Borrowed(key);
+    }
+    if let Some(alias) = ALIASES.get(key) {
+        return Cow::Borrowed(alias);
+    }
+    if is_svg || key.contains(':') || is_attribute_only(key) {
+        return Cow::Borrowed(key);
+    }
+    if key.bytes().any(|b| b.is_ascii_uppercase()) {
+        Cow::Owned(key.to_ascii_lowercase())
+    } else {
+        Cow::Borrowed(key)
+    }
+}
+
+/// Property an attribute is set through on `tag`, where the names differ
+/// (`readonly` -> `readOnly` on `<input>`)
+pub fn get_prop_alias(key: &str, tag: &str) -> Option<&'static str> {
+    PROP_ALIASES
+        .get(key)
+        .filter(|(_, tags)| tags.contains(&tag))
+        .map(|(prop, _)| *prop)
+}
+
 /// Check if this is an SVG element
 pub fn is_svg_element(tag: &str) -> bool {
     SVG_ELEMENTS.contains(tag)
@@ -112,6 +188,140 @@ pub fn is_dynamic(expr: &Expression) -> bool {
     }
 }
 
+/// Value of an expression known at compile time
+#[derive(Debug, Clone, PartialEq)]
+pub enum StaticValue {
+    String(String),
+    Number(f64),
+    Boolean(bool),
+    Null,
+}
+
+impl StaticValue {
+    /// JS `String(value)`. `None` for numbers this can't print the way JS
+    /// does (exponent notation).
+    pub fn to_js_string(&self) -> Option<String> {
+        match self {
+            StaticValue::String(value) => Some(value.clone()),
+            StaticValue::Number(value) => number_to_js_string(*value),
+            StaticValue::Boolean(value) => Some(value.to_string()),
+            StaticValue::Null => Some("null".to_string()),
+        }
+    }
+
+    /// JS truthiness
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            StaticValue::String(value) => !value.is_empty(),
+            StaticValue::Number(value) => *value != 0.0 && !value.is_nan(),
+            StaticValue::Boolean(value) => *value,
+            StaticValue::Null => false,
+        }
+    }
+
+    /// JS `Number(value)`, for the operands of arithmetic
+    fn to_number(&self) -> Option<f64> {
+        match self {
+            StaticValue::Number(value) => Some(*value),
+            StaticValue::Boolean(value) => Some(f64::from(u8::from(*value))),
+            StaticValue::Null => Some(0.0),
+            // String to number conversion has too many edge cases to mirror
+            StaticValue::String(_) => None,
+        }
+    }
+}
+
+/// JS `String(value)` for a number, when it's printed without an exponent
+fn number_to_js_string(value: f64) -> Option<String> {
+    if value.is_nan() {
+        return Some("NaN".to_string());
+    }
+    if value.is_infinite() {
+        return Some(if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string());
+    }
+    if value == 0.0 {
+        return Some("0".to_string());
+    }
+    let magnitude = value.abs();
+    (1e-6..1e21).contains(&magnitude).then(|| value.to_string())
+}
+
+/// Evaluate an expression built only from literals: `"a" + " b"`,
+/// `` `hello` ``, `1 + 1`, `-1`, `!0`, `cond ? "a" : "b"` with a literal
+/// `cond`, `a || b` and template literals with literal expressions.
+/// `None` as soon as anything depends on runtime values.
+pub fn evaluate_static(expr: &Expression<'_>) -> Option<StaticValue> {
+    match expr.without_parentheses() {
+        Expression::StringLiteral(lit) => Some(StaticValue::String(lit.value.to_string())),
+        Expression::NumericLiteral(lit) => Some(StaticValue::Number(lit.value)),
+        Expression::BooleanLiteral(lit) => Some(StaticValue::Boolean(lit.value)),
+        Expression::NullLiteral(_) => Some(StaticValue::Null),
+        Expression::TemplateLiteral(lit) => {
+            let mut text = String::new();
+            for (index, quasi) in lit.quasis.iter().enumerate() {
+                text.push_str(quasi.value.cooked?.as_str());
+                if let Some(expr) = lit.expressions.get(index) {
+                    text.push_str(&evaluate_static(expr)?.to_js_string()?);
+                }
+            }
+            Some(StaticValue::String(text))
+        }
+        Expression::UnaryExpression(unary) => {
+            let value = evaluate_static(&unary.argument)?;
+            match unary.operator {
+                UnaryOperator::UnaryNegation => Some(StaticValue::Number(-value.to_number()?)),
+                UnaryOperator::UnaryPlus => Some(StaticValue::Number(value.to_number()?)),
+                UnaryOperator::LogicalNot => Some(StaticValue::Boolean(!value.is_truthy())),
+                _ => None,
+            }
+        }
+        Expression::BinaryExpression(binary) => {
+            let left = evaluate_static(&binary.left)?;
+            let right = evaluate_static(&binary.right)?;
+            if binary.operator == BinaryOperator::Addition
+                && (matches!(left, StaticValue::String(_))
+                    || matches!(right, StaticValue::String(_)))
+            {
+                return Some(StaticValue::String(
+                    left.to_js_string()? + &right.to_js_string()?,
+                ));
+            }
+            let (left, right) = (left.to_number()?, right.to_number()?);
+            let value = match binary.operator {
+                BinaryOperator::Addition => left + right,
+                BinaryOperator::Subtraction => left - right,
+                BinaryOperator::Multiplication => left * right,
+                BinaryOperator::Division => left / right,
+                BinaryOperator::Remainder => left % right,
+                BinaryOperator::Exponential => left.powf(right),
+                _ => return None,
+            };
+            Some(StaticValue::Number(value))
+        }
+        Expression::LogicalExpression(logical) => {
+            let left = evaluate_static(&logical.left)?;
+            let use_left = match logical.operator {
+                LogicalOperator::And => !left.is_truthy(),
+                LogicalOperator::Or => left.is_truthy(),
+                LogicalOperator::Coalesce => left != StaticValue::Null,
+            };
+            if use_left {
+                Some(left)
+            } else {
+                evaluate_static(&logical.right)
+            }
+        }
+        Expression::ConditionalExpression(conditional) => {
+            if evaluate_static(&conditional.test)?.is_truthy() {
+                evaluate_static(&conditional.consequent)
+            } else {
+                evaluate_static(&conditional.alternate)
+            }
+        }
+        _ => None,
+    }
+}
+
 /// Find a JSX attribute by name on an element.
 ///
 /// Returns the attribute if found, allowing access to both the name and value.
@@ -128,6 +338,111 @@ pub fn find_prop<'a>(element: &'a JSXElement<'a>, name: &str) -> Option<&'a JSXA
     None
 }
 
+/// Check if an element has JSX children that render something; whitespace
+/// that JSX trims away and empty `{}` or comment-only containers don't count
+pub fn has_children(element: &JSXElement<'_>) -> bool {
+    element.children.iter().any(|child| match child {
+        JSXChild::Text(text) => !trim_whitespace(&text.value).is_empty(),
+        JSXChild::ExpressionContainer(container) => {
+            !matches!(container.expression, JSXExpression::EmptyExpression(_))
+        }
+        JSXChild::Element(_) | JSXChild::Fragment(_) | JSXChild::Spread(_) => true,
+    })
+}
+
+/// Check if an element's children are a single dynamic expression, which is
+/// inserted into the element directly instead of between markers
+pub fn is_single_dynamic_child(children: &[JSXChild<'_>]) -> bool {
+    let mut expr_count = 0;
+    let mut other_content = false;
+
+    for child in children {
+        match child {
+            JSXChild::Text(text) => {
+                let content = trim_whitespace(&text.value);
+                if !content.is_empty() {
+                    other_content = true;
+                }
+            }
+            JSXChild::Element(_) => {
+                other_content = true;
+            }
+            JSXChild::ExpressionContainer(container) => {
+                match container
+                    .expression
+                    .as_expression()
+                    .map(|expr| static_child_text(expr))
+                {
+                    Some(Some(text)) => other_content |= !text.is_empty(),
+                    Some(None) => expr_count += 1,
+                    None => {}
+                }
+            }
+            JSXChild::Spread(_) => {
+                expr_count += 1;
+            }
+            JSXChild::Fragment(fragment) => {
+                // Recurse into fragments
+                if !is_single_dynamic_child(&fragment.children) {
+                    other_content = true;
+                } else {
+                    expr_count += 1;
+                }
+            }
+        }
+    }
+
+    expr_count == 1 && !other_content
+}
+
+/// The function passed as the only child of an element
+/// (`<Show when={user()}>{u => <Profile user={u} />}</Show>`). Control-flow
+/// components call it themselves, so it is forwarded as `children` as-is
+/// rather than evaluated in a getter.
+pub fn get_function_child<'a, 'b>(element: &'b JSXElement<'a>) -> Option<&'b Expression<'a>> {
+    let mut rendered = element.children.iter().filter(|child| match child {
+        JSXChild::Text(text) => !trim_whitespace(&text.value).is_empty(),
+        JSXChild::ExpressionContainer(container) => {
+            !matches!(container.expression, JSXExpression::EmptyExpression(_))
+        }
+        _ => true,
+    });
+    let (Some(JSXChild::ExpressionContainer(container)), None) = (rendered.next(), rendered.next())
+    else {
+        return None;
+    };
+    let expr = container.expression.as_expression()?.without_parentheses();
+    matches!(
+        expr,
+        Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_)
+    )
+    .then_some(expr)
+}
+
+/// Check if a `children={...}` attribute is shadowed: JSX children take
+/// precedence over the attribute, and of repeated `children` attributes only
+/// the last one is kept
+pub fn is_shadowed_children_attr(element: &JSXElement<'_>, attr: &JSXAttribute<'_>) -> bool {
+    let is_children =
+        |attr: &JSXAttribute<'_>| matches!(&attr.name, JSXAttributeName::Identifier(id) if id.name == "children");
+    if !is_children(attr) {
+        return false;
+    }
+    if has_children(element) {
+        return true;
+    }
+    let last = element
+        .opening_element
+        .attributes
+        .iter()
+        .rev()
+        .find_map(|item| match item {
+            JSXAttributeItem::Attribute(attr) if is_children(attr) => Some(attr),
+            _ => None,
+        });
+    last.is_some_and(|last| !std::ptr::eq(last.as_ref(), attr))
+}
+
 /// Find a JSX attribute by name and return its value as a string.
 ///
 /// Handles expression containers, string literals, and boolean attributes (no value = true).
@@ -39,6 +39,35 @@ pub static PROPERTIES: Set<&'static str> = phf_set! {
     "selected",
 };
 
+/// Boolean attributes: present when truthy, removed when falsy
+pub static BOOLEAN_ATTRIBUTES: Set<&'static str> = phf_set! {
+    "allowfullscreen",
+    "async",
+    "autofocus",
+    "autoplay",
+    "checked",
+    "controls",
+    "default",
+    "disabled",
+    "formnovalidate",
+    "hidden",
+    "indeterminate",
+    "inert",
+    "ismap",
+    "loop",
+    "multiple",
+    "muted",
+    "nomodule",
+    "novalidate",
+    "open",
+    "playsinline",
+    "readonly",
+    "required",
+    "reversed",
+    "seamless",
+    "selected",
+};
+
 /// Child properties that affect children
 pub static CHILD_PROPERTIES: Set<&'static str> = phf_set! {
     "innerHTML",
@@ -53,6 +82,16 @@ pub static ALIASES: phf::Map<&'static str, &'static str> = phf::phf_map! {
     "htmlFor" => "for",
 };
 
+/// Attributes set through a differently named property on some elements
+/// (attribute name -> (property name, elements))
+pub static PROP_ALIASES: phf::Map<&'static str, (&'static str, &'static [&'static str])> = phf::phf_map! {
+    "formnovalidate" => ("formNoValidate", &["button", "input"]),
+    "ismap" => ("isMap", &["img"]),
+    "nomodule" => ("noModule", &["script"]),
+    "playsinline" => ("playsInline", &["video"]),
+    "readonly" => ("readOnly", &["input", "textarea"]),
+};
+
 /// Events that can be delegated (bubbling events)
 pub static DELEGATED_EVENTS: Set<&'static str> = phf_set! {
     "beforeinput",
@@ -19,7 +19,7 @@ pub fn stmt_to_string(stmt: &Statement<'_>) -> String {
         Statement::ExpressionStatement(expr_stmt) => expr_to_string(&expr_stmt.expression),
         _ => {
             // Fallback - this is less common
-            format!("/* unsupported statement */")
+            "/* unsupported statement */".to_string()
         }
     }
 }
@@ -52,74 +52,150 @@ impl<'a> SimpleExpression<'a> {
     }
 }
 
-/// Escape HTML special characters
+/// Escape HTML special characters the way dom-expressions' `escape()` does:
+/// `&` and `<` in text, `&` and `"` in (double-quoted) attribute values
 pub fn escape_html(text: &str, quote_escape: bool) -> String {
+    let delim = if quote_escape { '"' } else { '<' };
     let mut result = String::with_capacity(text.len());
     for c in text.chars() {
         match c {
             '&' => result.push_str("&amp;"),
-            '<' => result.push_str("&lt;"),
-            '>' => result.push_str("&gt;"),
-            '"' if quote_escape => result.push_str("&quot;"),
-            '\'' if quote_escape => result.push_str("&#39;"),
+            '"' if c == delim => result.push_str("&quot;"),
+            '<' if c == delim => result.push_str("&lt;"),
             _ => result.push(c),
         }
     }
     result
 }
 
-/// Trim whitespace from JSX text (preserving significant spaces)
+/// Decode the HTML character references the transform emits and that JSX
+/// strings may contain (`&amp;`, `&lt;`, `&#39;`, `&#x27;`, ...)
+pub fn decode_html_entities(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                _ => entity.strip_prefix('#').and_then(|number| {
+                    let code = match number.strip_prefix(['x', 'X']) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                        None => number.parse().ok(),
+                    };
+                    code.and_then(char::from_u32)
+                }),
+            };
+            c.map(|c| (c, end + 1))
+        });
+        match decoded {
+            Some((c, len)) => {
+                result.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Escape text for the raw part of a template literal (`\`, `` ` `` and `${`)
+pub fn escape_template_raw(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '`' => result.push_str("\\`"),
+            '$' if chars.peek() == Some(&'{') => result.push_str("\\$"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+/// Normalize the whitespace of JSX text like babel-plugin-jsx-dom-expressions'
+/// `trimWhitespace`
 ///
-/// JSX whitespace rules:
-/// - Text with newlines: trim leading/trailing whitespace (indentation)
-/// - Inline text (no newlines): preserve trailing space (e.g., ". " between expressions)
-/// - Multiple whitespace collapses to single space
+/// - Carriage returns are dropped
+/// - In multi-line text, lines after the first lose their indentation and
+///   blank lines are removed; the rest are joined with a space
+/// - Runs of whitespace collapse to a single space, so spaces next to
+///   expressions (`Hello {name()}`) are kept
 pub fn trim_whitespace(text: &str) -> String {
-    let has_newline = text.contains('\n');
-
-    // Collapse multiple whitespace into single space
-    let mut result = String::new();
+    let mut result = String::with_capacity(text.len());
     let mut prev_was_space = false;
-
-    for c in text.chars() {
-        if c.is_whitespace() {
-            if has_newline {
-                // Ignore leading indentation/newlines; we'll trim later.
-                if !prev_was_space && !result.is_empty() {
+    let mut push = |result: &mut String, line: &str| {
+        for c in line.chars().filter(|&c| c != '\r') {
+            if is_js_whitespace(c) {
+                if !prev_was_space {
                     result.push(' ');
                     prev_was_space = true;
                 }
-                continue;
+            } else {
+                result.push(c);
+                prev_was_space = false;
             }
+        }
+    };
 
-            // Inline text: preserve a single leading space (e.g., " Click" after an element)
-            if !prev_was_space {
-                result.push(' ');
-                prev_was_space = true;
-            }
+    if !text.contains('\n') {
+        push(&mut result, text);
+        return result;
+    }
+
+    for (i, line) in text.split('\n').enumerate() {
+        let line = if i == 0 {
+            line
         } else {
-            result.push(c);
-            prev_was_space = false;
+            line.trim_start_matches(is_js_whitespace)
+        };
+        if line.chars().all(is_js_whitespace) {
+            continue;
         }
+        if !result.is_empty() {
+            push(&mut result, " ");
+        }
+        push(&mut result, line);
     }
+    result
+}
 
-    // Only trim if text contained newlines (multi-line JSX text with indentation)
-    // Preserve trailing space for inline text like ". " between expressions
-    if has_newline {
-        result.trim().to_string()
-    } else {
-        result
-    }
+/// The string a JSX text child stands for: its whitespace trimmed, then its
+/// character references decoded, like babel-plugin-jsx-dom-expressions'
+/// `decode(trimWhitespace(raw))`. oxc keeps JSX text as written.
+pub fn decode_jsx_text(raw: &str) -> String {
+    decode_html_entities(&trim_whitespace(raw))
+}
+
+/// Whether `c` matches `\s` in a JavaScript regular expression
+fn is_js_whitespace(c: char) -> bool {
+    // `\s` is Unicode's White_Space without U+0085, plus the BOM
+    (c.is_whitespace() && c != '\u{85}') || c == '\u{feff}'
 }
 
 /// Convert event name from JSX format (onClick or on:click) to DOM format (click)
 pub fn to_event_name(name: &str) -> String {
-    if name.starts_with("on:") {
-        // Handle on:click -> click (namespaced form)
-        name[3..].to_string()
-    } else if name.starts_with("on") {
+    if let Some(event) = name
+        .strip_prefix("on:")
+        .or_else(|| name.strip_prefix("oncapture:"))
+    {
+        // Handle on:click / oncapture:click -> click (namespaced form)
+        event.to_string()
+    } else if let Some(event) = name.strip_prefix("on") {
         // Handle onClick -> click, onMouseDown -> mousedown (lowercase entire name)
-        name[2..].to_lowercase()
+        event.to_lowercase()
     } else {
         name.to_string()
     }
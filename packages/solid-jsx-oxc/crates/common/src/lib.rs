@@ -1,15 +1,26 @@
 pub mod check;
 pub mod constants;
 pub mod expression;
+pub mod module;
 pub mod options;
+pub mod prerender;
 
 pub use check::{
-    find_prop, find_prop_value, get_attr_name, get_attr_value, get_tag_name, is_built_in,
-    is_component, is_dynamic, is_namespaced_attr, is_svg_element,
+    attribute_name, evaluate_static, find_prop, find_prop_value, get_attr_name, get_attr_value,
+    get_function_child, get_prop_alias, get_tag_name, has_children, is_attribute_only, is_built_in,
+    is_component, is_controlled_property, is_custom_element, is_dynamic, is_namespaced_attr,
+    is_shadowed_children_attr, is_single_dynamic_child, is_svg_element, is_value_property_only, StaticValue,
 };
 pub use constants::*;
 pub use expression::{
-    escape_html, expr_to_string, get_children_callback, stmt_to_string, to_event_name,
-    trim_whitespace,
+    decode_html_entities, decode_jsx_text, escape_html, escape_template_raw, expr_to_string,
+    get_children_callback, stmt_to_string, to_event_name, trim_whitespace,
+};
+pub use module::{
+    build_delegated_events_export, build_require_declaration, helper_ident, helper_local_name,
 };
 pub use options::*;
+pub use prerender::{
+    prerender_element, static_attribute_markup, static_attribute_text, static_child_text,
+    static_text_content, static_truthiness,
+};
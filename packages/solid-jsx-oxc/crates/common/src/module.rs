@@ -0,0 +1,123 @@
+//! Module-level statements shared by the DOM and SSR transforms
+
+use oxc_ast::ast::{
+    Argument, AssignmentOperator, AssignmentTarget, BindingProperty, Expression,
+    ImportOrExportKind, Statement, VariableDeclarationKind,
+};
+use oxc_ast::{AstBuilder, NONE};
+use oxc_span::SPAN;
+
+use crate::options::{ModuleFormat, DELEGATED_EVENTS_EXPORT};
+
+/// Local name of the runtime helper `name` in generated code: `_$name`, as
+/// babel-plugin-jsx-dom-expressions does, so helpers never collide with the
+/// module's own `template`, `insert` or `style` bindings
+pub fn helper_local_name(name: &str) -> String {
+    format!("_${}", name)
+}
+
+/// Reference to the runtime helper `name` under its local name. It has no
+/// span: the source map would record the source text under a span as the
+/// original name of the helper; the call around it maps to the JSX instead.
+pub fn helper_ident<'a>(ast: AstBuilder<'a>, name: &str) -> Expression<'a> {
+    ast.expression_identifier(SPAN, ast.allocator.alloc_str(&helper_local_name(name)))
+}
+
+/// Build `const { a: _$a, b: _$b } = require("module");` for CommonJS output
+pub fn build_require_declaration<'a, 'h>(
+    ast: AstBuilder<'a>,
+    helpers: impl IntoIterator<Item = &'h str>,
+    module_name: &'a str,
+) -> Statement<'a> {
+    let span = SPAN;
+
+    let mut properties = ast.vec();
+    for helper in helpers {
+        let key = ast.property_key_static_identifier(span, ast.allocator.alloc_str(helper));
+        let local = ast.allocator.alloc_str(&helper_local_name(helper));
+        let value = ast.binding_pattern_binding_identifier(span, local);
+        let property: BindingProperty<'a> = ast.binding_property(span, key, value, false, false);
+        properties.push(property);
+    }
+    let pattern = ast.binding_pattern_object_pattern(span, properties, NONE);
+
+    let call = ast.expression_call(
+        span,
+        ast.expression_identifier(span, "require"),
+        NONE,
+        ast.vec1(Argument::from(ast.expression_string_literal(
+            span,
+            module_name,
+            None,
+        ))),
+        false,
+    );
+
+    let declarator = ast.variable_declarator(
+        span,
+        VariableDeclarationKind::Const,
+        pattern,
+        NONE,
+        Some(call),
+        false,
+    );
+    Statement::VariableDeclaration(ast.alloc_variable_declaration(
+        span,
+        VariableDeclarationKind::Const,
+        ast.vec1(declarator),
+        false,
+    ))
+}
+
+/// Build the module's delegated event list as an export:
+/// `export const _$delegatedEvents = [...];`, or
+/// `exports._$delegatedEvents = [...];` for CommonJS output
+pub fn build_delegated_events_export<'a>(
+    ast: AstBuilder<'a>,
+    events: Expression<'a>,
+    module_format: ModuleFormat,
+) -> Statement<'a> {
+    let span = SPAN;
+
+    match module_format {
+        ModuleFormat::Esm => {
+            let declarator = ast.variable_declarator(
+                span,
+                VariableDeclarationKind::Const,
+                ast.binding_pattern_binding_identifier(span, DELEGATED_EVENTS_EXPORT),
+                NONE,
+                Some(events),
+                false,
+            );
+            let declaration = ast.declaration_variable(
+                span,
+                VariableDeclarationKind::Const,
+                ast.vec1(declarator),
+                false,
+            );
+            Statement::from(ast.module_declaration_export_named_declaration(
+                span,
+                Some(declaration),
+                ast.vec(),
+                None,
+                ImportOrExportKind::Value,
+                NONE,
+            ))
+        }
+        ModuleFormat::Cjs => {
+            let target = ast.member_expression_static(
+                span,
+                ast.expression_identifier(span, "exports"),
+                ast.identifier_name(span, DELEGATED_EVENTS_EXPORT),
+                false,
+            );
+            let assignment = ast.expression_assignment(
+                span,
+                AssignmentOperator::Assign,
+                AssignmentTarget::from(target),
+                events,
+            );
+            ast.statement_expression(span, assignment)
+        }
+    }
+}
@@ -1,8 +1,11 @@
 //! Transform options for the Solid JSX compiler
 
-use oxc_span::SourceType;
+use oxc_span::{SourceType, Span};
 use std::cell::RefCell;
-use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// Configuration options for the JSX transform
 #[derive(Default)]
@@ -13,28 +16,58 @@ pub struct TransformOptions<'a> {
     /// Generate mode: "dom", "ssr", or "universal"
     pub generate: GenerateMode,
 
-    /// Whether to enable hydration support
+    /// How runtime helpers are imported: ESM `import` or CommonJS `require`
+    pub module_format: ModuleFormat,
+
+    /// Whether to enable hydration support: SSR output carries hydration
+    /// keys and markers, and DOM output claims the server-rendered nodes
+    /// (`getNextElement`, `getNextMarker`) instead of cloning templates
     pub hydratable: bool,
 
+    /// Inject `generateHydrationScript()` at the end of `<head>` in
+    /// hydratable SSR output, so document-rendering entry modules don't have
+    /// to add the bootstrap by hand
+    pub hydration_script: bool,
+
+    /// Experimental: in hydratable SSR output, render imported components
+    /// as islands, between `<!--island:id-->` and `<!--/island-->` markers,
+    /// and list them in [`Self::island_manifest`] so a client entry can
+    /// hydrate each one on its own instead of the whole page
+    pub islands: bool,
+
     /// Whether to delegate events
     pub delegate_events: bool,
 
     /// Custom delegated events
     pub delegated_events: Vec<&'a str>,
 
-    /// Whether to wrap conditionals
+    /// How the delegated events of a module reach the runtime
+    pub delegate_events_strategy: DelegateEventsStrategy,
+
+    /// Whether to memoize the test of conditional children
+    /// (`{cond() ? <A /> : <B />}`, `{cond() && <A />}`) so a branch is only
+    /// re-created when the condition flips
     pub wrap_conditionals: bool,
 
+    /// Keep JSX text as written instead of trimming indentation and
+    /// collapsing whitespace the way dom-expressions does
+    pub preserve_whitespace: bool,
+
     /// Whether to pass context to custom elements
     pub context_to_custom_elements: bool,
 
+    /// Tags treated as custom elements even without a dash (e.g. from a
+    /// registry); an entry ending in `*` matches every tag with that prefix
+    pub custom_elements: Vec<&'a str>,
+
     /// Built-in components (For, Show, etc.)
     pub built_ins: Vec<&'a str>,
 
-    /// Effect wrapper function name
+    /// Helper reactive bindings run in (`effect(() => ...)`), imported from
+    /// `module_name` like the other helpers
     pub effect_wrapper: &'a str,
 
-    /// Memo wrapper function name
+    /// Helper derived values are memoized with (`memo(() => ...)`)
     pub memo_wrapper: &'a str,
 
     /// Source filename
@@ -43,12 +76,53 @@ pub struct TransformOptions<'a> {
     /// Source type (tsx, jsx, etc.)
     pub source_type: SourceType,
 
+    /// Strip TypeScript syntax (and lower enums/namespaces) before the JSX
+    /// transform, so TypeScript input comes out as plain JavaScript
+    pub strip_types: bool,
+
+    /// Resolve `isServer`, `isDev` and `DEV` checks at compile time and drop
+    /// the branches they make unreachable
+    pub fold_environment: bool,
+
+    /// Whether the output targets Solid's development build, used to fold
+    /// `isDev` and `DEV`; `None` leaves those checks to runtime
+    pub dev: Option<bool>,
+
+    /// Constant replacements applied before the JSX transform, keyed by a
+    /// global identifier or member chain (`__DEV__`, `import.meta.env.SSR`)
+    /// with a JavaScript expression as the value (`"false"`, `"\"1.0\""`)
+    pub define: HashMap<String, String>,
+
+    /// Build templates with `document.createElement`/`appendChild` calls
+    /// instead of `template()`, which parses HTML through `innerHTML` and is
+    /// blocked by strict Content Security Policies
+    pub csp_safe: bool,
+
+    /// Emit the runtime helpers that don't need Solid's reactive runtime
+    /// (`template`, `setAttribute`, `style`, ...) into the DOM output instead
+    /// of importing them, so snippets using only those run without
+    /// `solid-js/web`
+    pub inline_helpers: bool,
+
+    /// Render every SSR element with an `ssrElement()` call instead of `ssr`
+    /// tagged templates, so the output is built from props objects a server
+    /// can post-process or stream
+    pub ssr_element_only: bool,
+
     /// Whether to generate source maps
     pub source_map: bool,
 
+    /// Minify the output: shorten generated locals and drop whitespace
+    /// and comments
+    pub minify: bool,
+
     /// Static marker comment
     pub static_marker: &'a str,
 
+    /// Only transform files with a `/** @jsxImportSource <name> */` pragma
+    /// naming this source, leaving the JSX of the others to another tool
+    pub require_import_source: Option<&'a str>,
+
     /// Collected templates
     pub templates: RefCell<Vec<(String, bool)>>,
 
@@ -57,9 +131,27 @@ pub struct TransformOptions<'a> {
 
     /// Collected delegated events
     pub delegates: RefCell<HashSet<String>>,
+
+    /// Collected islands, in source order
+    pub island_manifest: RefCell<Vec<Island>>,
+}
+
+/// A component rendered as an island by the `islands` option
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Island {
+    /// Key in the boundary markers, unique across modules
+    pub id: String,
+    /// Local name of the component
+    pub component: String,
+    /// Module the component is imported from
+    pub source: String,
+    /// Name the module exports it under, `default` for a default import
+    pub export: String,
+    /// Span of the JSX element in the source
+    pub span: Span,
 }
 
-#[derive(Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GenerateMode {
     #[default]
     Dom,
@@ -67,16 +159,93 @@ pub enum GenerateMode {
     Universal,
 }
 
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModuleFormat {
+    /// `import { template as _$template } from "solid-js/web";`
+    #[default]
+    Esm,
+    /// `const { template: _$template } = require("solid-js/web");`
+    Cjs,
+}
+
+/// How a module registers its delegated events
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DelegateEventsStrategy {
+    /// `delegateEvents(["click"]);` at the end of every module
+    #[default]
+    Call,
+    /// `export const _$delegatedEvents = ["click"];`, so a bundler plugin can
+    /// collect the lists and emit a single deduplicated `delegateEvents` call
+    Export,
+}
+
+/// Name of the export used by [`DelegateEventsStrategy::Export`]
+pub const DELEGATED_EVENTS_EXPORT: &str = "_$delegatedEvents";
+
+/// Module the DOM and SSR runtime helpers come from
+pub const DEFAULT_MODULE_NAME: &str = "solid-js/web";
+
+/// A combination of options the transform can't honor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionsError {
+    /// `hydratable` with `generate: "universal"`
+    HydratableUniversal,
+    /// `generate: "universal"` without a custom renderer in `module_name`
+    MissingRendererModule,
+    /// `islands` without `generate: "ssr"` and `hydratable`
+    IslandsWithoutHydratableSsr,
+    /// A string option set to a value it doesn't accept
+    UnknownValue {
+        option: &'static str,
+        value: String,
+        expected: &'static [&'static str],
+    },
+}
+
+impl fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HydratableUniversal => f.write_str(
+                "`hydratable` requires `generate` to be \"dom\" or \"ssr\"; universal renderers don't support hydration",
+            ),
+            Self::MissingRendererModule => write!(
+                f,
+                "`generate: \"universal\"` requires `module_name` to be the custom renderer module (the one exporting the `createRenderer` helpers), not \"{DEFAULT_MODULE_NAME}\"",
+            ),
+            Self::IslandsWithoutHydratableSsr => f.write_str(
+                "`islands` requires `generate: \"ssr\"` with `hydratable`; islands are hydrated from server-rendered markup",
+            ),
+            Self::UnknownValue {
+                option,
+                value,
+                expected,
+            } => write!(
+                f,
+                "unknown `{option}` \"{value}\"; expected one of \"{}\"",
+                expected.join("\", \""),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for OptionsError {}
+
 impl<'a> TransformOptions<'a> {
     pub fn solid_defaults() -> Self {
         Self {
-            module_name: "solid-js/web",
+            module_name: DEFAULT_MODULE_NAME,
             generate: GenerateMode::Dom,
+            module_format: ModuleFormat::Esm,
             hydratable: false,
+            hydration_script: false,
+            islands: false,
             delegate_events: true,
             delegated_events: vec![],
+            delegate_events_strategy: DelegateEventsStrategy::Call,
             wrap_conditionals: true,
+            preserve_whitespace: false,
             context_to_custom_elements: true,
+            custom_elements: vec![],
             built_ins: vec![
                 "For",
                 "Show",
@@ -93,14 +262,103 @@ impl<'a> TransformOptions<'a> {
             memo_wrapper: "memo",
             filename: "input.jsx",
             source_type: SourceType::tsx(),
+            strip_types: false,
+            fold_environment: true,
+            dev: None,
+            define: HashMap::new(),
+            csp_safe: false,
+            inline_helpers: false,
+            ssr_element_only: false,
             source_map: false,
+            minify: false,
             static_marker: "@once",
+            require_import_source: None,
             templates: RefCell::new(vec![]),
             helpers: RefCell::new(HashSet::new()),
             delegates: RefCell::new(HashSet::new()),
+            island_manifest: RefCell::new(vec![]),
+        }
+    }
+
+    /// Start from [`Self::solid_defaults`] and set options one by one;
+    /// [`TransformOptionsBuilder::build`] checks that they fit together
+    pub fn builder() -> TransformOptionsBuilder<'a> {
+        TransformOptionsBuilder {
+            options: Self::solid_defaults(),
         }
     }
 
+    /// Check the options that depend on each other
+    pub fn validate(&self) -> Result<(), OptionsError> {
+        if self.generate == GenerateMode::Universal {
+            if self.hydratable {
+                return Err(OptionsError::HydratableUniversal);
+            }
+            if self.module_name.is_empty() || self.module_name == DEFAULT_MODULE_NAME {
+                return Err(OptionsError::MissingRendererModule);
+            }
+        }
+        if self.islands && !(self.generate == GenerateMode::Ssr && self.hydratable) {
+            return Err(OptionsError::IslandsWithoutHydratableSsr);
+        }
+        Ok(())
+    }
+
+    /// Hash of every option that affects the output, so results can be
+    /// cached per configuration. The collected templates, helpers, delegates
+    /// and islands are transform state and don't take part.
+    pub fn cache_key(&self) -> u64 {
+        // Destructured so that a new option can't be left out of the key
+        let Self {
+            module_name,
+            generate,
+            module_format,
+            hydratable,
+            hydration_script,
+            islands,
+            delegate_events,
+            delegated_events,
+            delegate_events_strategy,
+            wrap_conditionals,
+            preserve_whitespace,
+            context_to_custom_elements,
+            custom_elements,
+            built_ins,
+            effect_wrapper,
+            memo_wrapper,
+            filename,
+            source_type,
+            strip_types,
+            fold_environment,
+            dev,
+            define,
+            csp_safe,
+            inline_helpers,
+            ssr_element_only,
+            source_map,
+            minify,
+            static_marker,
+            require_import_source,
+            templates: _,
+            helpers: _,
+            delegates: _,
+            island_manifest: _,
+        } = self;
+
+        let mut hasher = DefaultHasher::new();
+        (module_name, generate, module_format, hydratable, hydration_script).hash(&mut hasher);
+        (islands, preserve_whitespace).hash(&mut hasher);
+        (delegate_events, delegated_events, delegate_events_strategy).hash(&mut hasher);
+        (wrap_conditionals, context_to_custom_elements, custom_elements, built_ins).hash(&mut hasher);
+        (effect_wrapper, memo_wrapper, filename, source_type).hash(&mut hasher);
+        (strip_types, fold_environment, dev, csp_safe, source_map, minify).hash(&mut hasher);
+        (inline_helpers, ssr_element_only, static_marker, require_import_source).hash(&mut hasher);
+        let mut define: Vec<_> = define.iter().collect();
+        define.sort_unstable();
+        define.hash(&mut hasher);
+        hasher.finish()
+    }
+
     /// Register a helper import
     pub fn register_helper(&self, name: &str) {
         self.helpers.borrow_mut().insert(name.to_string());
@@ -119,3 +377,172 @@ impl<'a> TransformOptions<'a> {
         index
     }
 }
+
+/// Builder for [`TransformOptions`], created by [`TransformOptions::builder`]
+pub struct TransformOptionsBuilder<'a> {
+    options: TransformOptions<'a>,
+}
+
+impl<'a> TransformOptionsBuilder<'a> {
+    /// The module to import runtime helpers from; for universal output, the
+    /// custom renderer
+    pub fn module_name(mut self, module_name: &'a str) -> Self {
+        self.options.module_name = module_name;
+        self
+    }
+
+    pub fn generate(mut self, generate: GenerateMode) -> Self {
+        self.options.generate = generate;
+        self
+    }
+
+    pub fn module_format(mut self, module_format: ModuleFormat) -> Self {
+        self.options.module_format = module_format;
+        self
+    }
+
+    pub fn hydratable(mut self, hydratable: bool) -> Self {
+        self.options.hydratable = hydratable;
+        self
+    }
+
+    pub fn hydration_script(mut self, hydration_script: bool) -> Self {
+        self.options.hydration_script = hydration_script;
+        self
+    }
+
+    /// Experimental; requires `generate: Ssr` and `hydratable`
+    pub fn islands(mut self, islands: bool) -> Self {
+        self.options.islands = islands;
+        self
+    }
+
+    pub fn delegate_events(mut self, delegate_events: bool) -> Self {
+        self.options.delegate_events = delegate_events;
+        self
+    }
+
+    pub fn delegated_events(mut self, events: impl IntoIterator<Item = &'a str>) -> Self {
+        self.options.delegated_events = events.into_iter().collect();
+        self
+    }
+
+    pub fn delegate_events_strategy(mut self, strategy: DelegateEventsStrategy) -> Self {
+        self.options.delegate_events_strategy = strategy;
+        self
+    }
+
+    pub fn wrap_conditionals(mut self, wrap_conditionals: bool) -> Self {
+        self.options.wrap_conditionals = wrap_conditionals;
+        self
+    }
+
+    pub fn preserve_whitespace(mut self, preserve_whitespace: bool) -> Self {
+        self.options.preserve_whitespace = preserve_whitespace;
+        self
+    }
+
+    pub fn context_to_custom_elements(mut self, context_to_custom_elements: bool) -> Self {
+        self.options.context_to_custom_elements = context_to_custom_elements;
+        self
+    }
+
+    pub fn custom_elements(mut self, tags: impl IntoIterator<Item = &'a str>) -> Self {
+        self.options.custom_elements = tags.into_iter().collect();
+        self
+    }
+
+    pub fn built_ins(mut self, components: impl IntoIterator<Item = &'a str>) -> Self {
+        self.options.built_ins = components.into_iter().collect();
+        self
+    }
+
+    pub fn effect_wrapper(mut self, effect_wrapper: &'a str) -> Self {
+        self.options.effect_wrapper = effect_wrapper;
+        self
+    }
+
+    pub fn memo_wrapper(mut self, memo_wrapper: &'a str) -> Self {
+        self.options.memo_wrapper = memo_wrapper;
+        self
+    }
+
+    pub fn filename(mut self, filename: &'a str) -> Self {
+        self.options.filename = filename;
+        self
+    }
+
+    pub fn source_type(mut self, source_type: SourceType) -> Self {
+        self.options.source_type = source_type;
+        self
+    }
+
+    pub fn strip_types(mut self, strip_types: bool) -> Self {
+        self.options.strip_types = strip_types;
+        self
+    }
+
+    pub fn fold_environment(mut self, fold_environment: bool) -> Self {
+        self.options.fold_environment = fold_environment;
+        self
+    }
+
+    /// `None` leaves `isDev` and `DEV` checks to runtime
+    pub fn dev(mut self, dev: impl Into<Option<bool>>) -> Self {
+        self.options.dev = dev.into();
+        self
+    }
+
+    /// Replace the constant replacements with `define`
+    pub fn define(mut self, define: HashMap<String, String>) -> Self {
+        self.options.define = define;
+        self
+    }
+
+    /// Add one constant replacement, e.g. `("__DEV__", "false")`
+    pub fn define_constant(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.options.define.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn csp_safe(mut self, csp_safe: bool) -> Self {
+        self.options.csp_safe = csp_safe;
+        self
+    }
+
+    pub fn inline_helpers(mut self, inline_helpers: bool) -> Self {
+        self.options.inline_helpers = inline_helpers;
+        self
+    }
+
+    pub fn ssr_element_only(mut self, ssr_element_only: bool) -> Self {
+        self.options.ssr_element_only = ssr_element_only;
+        self
+    }
+
+    pub fn source_map(mut self, source_map: bool) -> Self {
+        self.options.source_map = source_map;
+        self
+    }
+
+    pub fn minify(mut self, minify: bool) -> Self {
+        self.options.minify = minify;
+        self
+    }
+
+    pub fn static_marker(mut self, static_marker: &'a str) -> Self {
+        self.options.static_marker = static_marker;
+        self
+    }
+
+    pub fn require_import_source(mut self, import_source: Option<&'a str>) -> Self {
+        self.options.require_import_source = import_source;
+        self
+    }
+
+    /// Finish the options, rejecting combinations the transform can't honor
+    pub fn build(self) -> Result<TransformOptions<'a>, OptionsError> {
+        self.options.validate()?;
+        Ok(self.options)
+    }
+}
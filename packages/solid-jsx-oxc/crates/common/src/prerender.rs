@@ -0,0 +1,243 @@
+//! Static subtree prerendering
+//!
+//! Detects native element subtrees without components, bindings or dynamic
+//! expressions and renders them to HTML at compile time. The DOM transform
+//! inlines the result into the enclosing template and the SSR transform into
+//! a single static string, so no per-node work is left for runtime.
+
+use oxc_ast::ast::{
+    Expression, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXChild, JSXElement,
+    UnaryOperator,
+};
+
+use crate::check::{
+    attribute_name, evaluate_static, get_tag_name, is_component, is_custom_element, is_svg_element,
+    is_value_property_only, StaticValue,
+};
+use crate::constants::{BOOLEAN_ATTRIBUTES, VOID_ELEMENTS};
+use crate::expression::{decode_jsx_text, escape_html};
+use crate::options::TransformOptions;
+
+/// Attributes that are set through properties or helpers instead of markup
+const NON_MARKUP_ATTRIBUTES: &[&str] = &[
+    "ref",
+    "children",
+    "innerHTML",
+    "textContent",
+    "innerText",
+    "classList",
+];
+
+/// Render a native element to HTML if its whole subtree is static.
+///
+/// Returns `None` as soon as anything needs runtime work: components,
+/// spreads, event handlers, namespaced attributes, expression attributes or
+/// child expressions other than literals.
+pub fn prerender_element(
+    element: &JSXElement<'_>,
+    options: &TransformOptions<'_>,
+) -> Option<String> {
+    let mut html = String::new();
+    render_element(element, options, &mut html).then_some(html)
+}
+
+/// Text rendered for a child expression with a value known at compile time
+/// (`{"text"}`, `{42}`, `{-1}`, `` {`text`} ``, `{"a" + 1}`); `null` and
+/// booleans render nothing
+pub fn static_child_text(expr: &Expression<'_>) -> Option<String> {
+    match evaluate_static(expr)? {
+        StaticValue::Boolean(_) | StaticValue::Null => Some(String::new()),
+        value => value.to_js_string(),
+    }
+}
+
+/// Text of a literal `textContent` (`textContent="hi"`, `textContent={"hi"}`)
+/// on an element without children, which can live in the markup instead of
+/// being assigned at runtime
+pub fn static_text_content(element: &JSXElement<'_>) -> Option<String> {
+    if !element.children.is_empty() {
+        return None;
+    }
+    element
+        .opening_element
+        .attributes
+        .iter()
+        .find_map(|attr| match attr {
+            JSXAttributeItem::Attribute(attr)
+                if matches!(&attr.name, JSXAttributeName::Identifier(name) if name.name == "textContent") =>
+            {
+                Some(attr)
+            }
+            _ => None,
+        })
+        .and_then(|attr| match &attr.value {
+            Some(JSXAttributeValue::StringLiteral(lit)) => Some(lit.value.to_string()),
+            Some(JSXAttributeValue::ExpressionContainer(container)) => container
+                .expression
+                .as_expression()
+                .and_then(static_child_text),
+            _ => None,
+        })
+}
+
+/// Text `setAttribute` would store for a value known at compile time
+/// (`{"x"}`, `{42}`, `` {`x`} ``, `{true}`, `{"a" + " b"}`)
+pub fn static_attribute_text(expr: &Expression<'_>) -> Option<String> {
+    match evaluate_static(expr)? {
+        StaticValue::Null => None,
+        value => value.to_js_string(),
+    }
+}
+
+/// Truthiness of a value known at compile time (`{true}`, `{0}`, `{""}`,
+/// `{null}`), which decides whether a `bool:` attribute is present
+pub fn static_truthiness(expr: &Expression<'_>) -> Option<bool> {
+    match expr.without_parentheses() {
+        Expression::Identifier(ident) if ident.name == "undefined" => Some(false),
+        expr => evaluate_static(expr).map(|value| value.is_truthy()),
+    }
+}
+
+/// Markup for an expression attribute whose rendered form is known at
+/// compile time: literal strings and numbers (` tabindex="0"`), boolean
+/// attributes set to a boolean literal (` disabled`, or nothing for `false`)
+/// and other booleans as their text (` contenteditable="true"`). `null` and
+/// `undefined` remove the attribute, so they render nothing. `None` means
+/// the attribute must be set at runtime.
+pub fn static_attribute_markup(key: &str, expr: &Expression<'_>) -> Option<String> {
+    if key.starts_with("on") || key.contains(':') || NON_MARKUP_ATTRIBUTES.contains(&key) {
+        return None;
+    }
+    if is_nullish_literal(expr) {
+        return Some(String::new());
+    }
+    if BOOLEAN_ATTRIBUTES.contains(key) {
+        let Some(StaticValue::Boolean(value)) = evaluate_static(expr) else {
+            return None;
+        };
+        return Some(if value { format!(" {}", key) } else { String::new() });
+    }
+    let text = static_attribute_text(expr)?;
+    Some(format!(" {}=\"{}\"", key, escape_html(&text, true)))
+}
+
+/// `null`, `undefined` or `void 0`
+fn is_nullish_literal(expr: &Expression<'_>) -> bool {
+    match expr.without_parentheses() {
+        Expression::NullLiteral(_) => true,
+        Expression::Identifier(ident) => ident.name == "undefined",
+        Expression::UnaryExpression(unary) => {
+            unary.operator == UnaryOperator::Void && evaluate_static(&unary.argument).is_some()
+        }
+        _ => false,
+    }
+}
+
+fn render_element(
+    element: &JSXElement<'_>,
+    options: &TransformOptions<'_>,
+    html: &mut String,
+) -> bool {
+    let tag_name = get_tag_name(element);
+    // Custom elements may upgrade (and slots get the reactive owner), and
+    // script/style content is raw text
+    if is_component(&tag_name)
+        || is_custom_element(&tag_name, options)
+        || (tag_name == "slot" && options.context_to_custom_elements)
+        || tag_name == "script"
+        || tag_name == "style"
+    {
+        return false;
+    }
+
+    let text_content = static_text_content(element);
+    html.push('<');
+    html.push_str(&tag_name);
+    for attr in &element.opening_element.attributes {
+        let JSXAttributeItem::Attribute(attr) = attr else {
+            return false;
+        };
+        let JSXAttributeName::Identifier(name) = &attr.name else {
+            return false;
+        };
+        let key = name.name.as_str();
+        if key == "textContent" && text_content.is_some() {
+            continue;
+        }
+        if key.starts_with("on")
+            || NON_MARKUP_ATTRIBUTES.contains(&key)
+            || (key == "value" && is_value_property_only(&tag_name))
+        {
+            return false;
+        }
+        let key = &*attribute_name(key, is_svg_element(&tag_name), false);
+        match &attr.value {
+            Some(JSXAttributeValue::StringLiteral(lit)) => {
+                html.push_str(&format!(" {}=\"{}\"", key, escape_html(&lit.value, true)));
+            }
+            Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                let Some(markup) = container
+                    .expression
+                    .as_expression()
+                    .and_then(|expr| static_attribute_markup(key, expr))
+                else {
+                    return false;
+                };
+                html.push_str(&markup);
+            }
+            None => {
+                html.push(' ');
+                html.push_str(key);
+            }
+            _ => return false,
+        }
+    }
+    html.push('>');
+
+    if VOID_ELEMENTS.contains(tag_name.as_str()) {
+        return true;
+    }
+    if let Some(text) = &text_content {
+        html.push_str(&escape_html(text, false));
+    }
+    if !render_children(&element.children, options, html) {
+        return false;
+    }
+    html.push_str(&format!("</{}>", tag_name));
+    true
+}
+
+fn render_children(
+    children: &[JSXChild<'_>],
+    options: &TransformOptions<'_>,
+    html: &mut String,
+) -> bool {
+    for child in children {
+        match child {
+            JSXChild::Text(text) => {
+                html.push_str(&escape_html(&decode_jsx_text(&text.value), false));
+            }
+            JSXChild::Element(element) => {
+                if !render_element(element, options, html) {
+                    return false;
+                }
+            }
+            JSXChild::Fragment(fragment) => {
+                if !render_children(&fragment.children, options, html) {
+                    return false;
+                }
+            }
+            JSXChild::ExpressionContainer(container) => {
+                let Some(expr) = container.expression.as_expression() else {
+                    continue;
+                };
+                let Some(text) = static_child_text(expr) else {
+                    return false;
+                };
+                html.push_str(&escape_html(&text, false));
+            }
+            JSXChild::Spread(_) => return false,
+        }
+    }
+    true
+}
@@ -14,8 +14,9 @@ use oxc_span::SPAN;
 use oxc_syntax::operator::{AssignmentOperator, BinaryOperator, UnaryOperator};
 use oxc_traverse::TraverseCtx;
 
-use common::{is_dynamic, TransformOptions};
+use common::{helper_ident, is_dynamic, TransformOptions};
 
+use crate::condition::memoize_condition_inline;
 use crate::element::is_writable_ref_target;
 use crate::ir::{BlockContext, ChildTransformer, TransformResult};
 use crate::output::build_dom_output_expr;
@@ -62,7 +63,23 @@ fn jsx_element_name_to_expression<'a>(
     }
 }
 
-fn getter_return_expr<'a>(
+/// `() => expr`
+fn arrow_return_expr<'a>(ast: AstBuilder<'a>, expr: Expression<'a>) -> Expression<'a> {
+    let params = ast.alloc_formal_parameters(
+        SPAN,
+        FormalParameterKind::ArrowFormalParameters,
+        ast.vec(),
+        NONE,
+    );
+    let mut statements = ast.vec_with_capacity(1);
+    statements.push(Statement::ExpressionStatement(
+        ast.alloc_expression_statement(SPAN, expr),
+    ));
+    let body = ast.alloc_function_body(SPAN, ast.vec(), statements);
+    ast.expression_arrow_function(SPAN, true, false, NONE, params, NONE, body)
+}
+
+pub(crate) fn getter_return_expr<'a>(
     ast: AstBuilder<'a>,
     span: oxc_span::Span,
     expr: Expression<'a>,
@@ -120,7 +137,11 @@ fn is_valid_prop_identifier(key: &str) -> bool {
     chars.all(|c| c == '$' || c == '_' || c.is_ascii_alphanumeric())
 }
 
-fn make_prop_key<'a>(ast: AstBuilder<'a>, span: oxc_span::Span, raw_key: &str) -> PropertyKey<'a> {
+pub(crate) fn make_prop_key<'a>(
+    ast: AstBuilder<'a>,
+    span: oxc_span::Span,
+    raw_key: &str,
+) -> PropertyKey<'a> {
     let _ = span;
     let key = ast.allocator.alloc_str(raw_key);
     if is_valid_prop_identifier(raw_key) {
@@ -134,27 +155,38 @@ fn make_prop_key<'a>(ast: AstBuilder<'a>, span: oxc_span::Span, raw_key: &str) -
 fn get_children_expr_transformed<'a, 'b>(
     element: &JSXElement<'a>,
     context: &BlockContext<'a>,
+    options: &TransformOptions<'a>,
     transform_child: ChildTransformer<'a, 'b>,
 ) -> Option<Expression<'a>> {
     let ast = context.ast();
     let mut children: Vec<Expression<'a>> = Vec::new();
+    // Positions of dynamic expressions, which need their own function once
+    // they share an array with other children
+    let mut dynamic_children: Vec<usize> = Vec::new();
 
     for child in &element.children {
         match child {
             JSXChild::Text(text) => {
-                let content = common::expression::trim_whitespace(&text.value);
+                let content = common::expression::decode_jsx_text(&text.value);
                 if !content.is_empty() {
-                    let escaped = common::expression::escape_html(&content, false);
+                    // Text, inserted as a text node when the component renders it
                     children.push(ast.expression_string_literal(
                         SPAN,
-                        ast.allocator.alloc_str(&escaped),
+                        ast.allocator.alloc_str(&content),
                         None,
                     ));
                 }
             }
             JSXChild::ExpressionContainer(container) => {
                 if let Some(expr) = container.expression.as_expression() {
-                    children.push(context.clone_expr(expr));
+                    let mut expr = context.clone_expr(expr);
+                    if is_dynamic(&expr) {
+                        dynamic_children.push(children.len());
+                        if options.wrap_conditionals {
+                            memoize_condition_inline(&mut expr, context);
+                        }
+                    }
+                    children.push(expr);
                 }
             }
             JSXChild::Element(_) | JSXChild::Fragment(_) => {
@@ -168,6 +200,17 @@ fn get_children_expr_transformed<'a, 'b>(
         }
     }
 
+    // `["Hello ", () => name(), "!"]` keeps the text order and stays reactive
+    if children.len() > 1 {
+        for index in dynamic_children {
+            let expr = std::mem::replace(
+                &mut children[index],
+                ast.expression_identifier(SPAN, "undefined"),
+            );
+            children[index] = arrow_return_expr(ast, expr);
+        }
+    }
+
     if children.len() == 1 {
         Some(
             children
@@ -206,7 +249,7 @@ pub fn transform_component<'a, 'b>(
     let props = build_props(element, context, options, transform_child, ctx);
 
     // Generate createComponent call
-    let callee = ast.expression_identifier(SPAN, "createComponent");
+    let callee = helper_ident(ast, "createComponent");
     let mut args = ast.vec_with_capacity(2);
     args.push(Argument::from(jsx_element_name_to_expression(
         ast,
@@ -214,7 +257,7 @@ pub fn transform_component<'a, 'b>(
     )));
     args.push(Argument::from(props));
     result.exprs.push(ast.expression_call(
-        SPAN,
+        element.span,
         callee,
         None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
         args,
@@ -228,7 +271,7 @@ pub fn transform_component<'a, 'b>(
 fn build_props<'a, 'b>(
     element: &JSXElement<'a>,
     context: &BlockContext<'a>,
-    _options: &TransformOptions<'a>,
+    options: &TransformOptions<'a>,
     transform_child: ChildTransformer<'a, 'b>,
     ctx: &TraverseCtx<'a, ()>,
 ) -> Expression<'a> {
@@ -249,9 +292,9 @@ fn build_props<'a, 'b>(
                     }
                 };
 
-                // Ignore explicit `children` prop when actual JSX children exist.
-                // This matches Solid's behavior: JSX children win over `children={...}`.
-                if raw_key == "children" && !element.children.is_empty() {
+                // JSX children win over `children={...}`, and a repeated
+                // `children` attribute only keeps its last value
+                if common::is_shadowed_children_attr(element, attr) {
                     continue;
                 }
 
@@ -282,10 +325,11 @@ fn build_props<'a, 'b>(
                             } else {
                                 // Non-const variable: generate typeof check with assignment fallback
                                 // ref(r$) { var _ref$ = expr; typeof _ref$ === "function" ? _ref$(r$) : expr = r$; }
-                                let ref_param = ast.binding_pattern_binding_identifier(
-                                    span,
-                                    ast.allocator.alloc_str("r$"),
-                                );
+                                let ref_name =
+                                    ast.allocator.alloc_str(&context.local_name("_ref$"));
+                                let param_name = ast.allocator.alloc_str(&context.local_name("r$"));
+                                let ref_param =
+                                    ast.binding_pattern_binding_identifier(span, param_name);
 
                                 let params = ast.alloc_formal_parameters(
                                     span,
@@ -300,10 +344,7 @@ fn build_props<'a, 'b>(
                                     let declarator = ast.variable_declarator(
                                         span,
                                         VariableDeclarationKind::Var,
-                                        ast.binding_pattern_binding_identifier(
-                                            span,
-                                            ast.allocator.alloc_str("_ref$"),
-                                        ),
+                                        ast.binding_pattern_binding_identifier(span, ref_name),
                                         NONE,
                                         Some(init_expr),
                                         false,
@@ -319,8 +360,8 @@ fn build_props<'a, 'b>(
                                 };
                                 body_stmts.push(var_decl);
 
-                                let ref_ident = ast.expression_identifier(span, "_ref$");
-                                let r_ident = ast.expression_identifier(span, "r$");
+                                let ref_ident = ast.expression_identifier(span, ref_name);
+                                let r_ident = ast.expression_identifier(span, param_name);
                                 let typeof_ref = ast.expression_unary(
                                     span,
                                     UnaryOperator::Typeof,
@@ -408,7 +449,7 @@ fn build_props<'a, 'b>(
                 match &attr.value {
                     Some(JSXAttributeValue::StringLiteral(lit)) => {
                         static_props.push(ast.object_property_kind_object_property(
-                            span,
+                            attr.span,
                             PropertyKind::Init,
                             key,
                             ast.expression_string_literal(
@@ -424,18 +465,22 @@ fn build_props<'a, 'b>(
                     Some(JSXAttributeValue::ExpressionContainer(container)) => {
                         if let Some(expr) = container.expression.as_expression() {
                             if is_dynamic(expr) {
+                                let mut expr = context.clone_expr(expr);
+                                if options.wrap_conditionals {
+                                    memoize_condition_inline(&mut expr, context);
+                                }
                                 dynamic_props.push(ast.object_property_kind_object_property(
-                                    span,
+                                    attr.span,
                                     PropertyKind::Get,
                                     key,
-                                    getter_return_expr(ast, span, context.clone_expr(expr)),
+                                    getter_return_expr(ast, span, expr),
                                     false,
                                     false,
                                     false,
                                 ));
                             } else {
                                 static_props.push(ast.object_property_kind_object_property(
-                                    span,
+                                    attr.span,
                                     PropertyKind::Init,
                                     key,
                                     context.clone_expr(expr),
@@ -448,7 +493,7 @@ fn build_props<'a, 'b>(
                     }
                     None => {
                         static_props.push(ast.object_property_kind_object_property(
-                            span,
+                            attr.span,
                             PropertyKind::Init,
                             key,
                             ast.expression_boolean_literal(span, true),
@@ -467,8 +512,22 @@ fn build_props<'a, 'b>(
     }
 
     // Handle children
-    if !element.children.is_empty() {
-        if let Some(children) = get_children_expr_transformed(element, context, transform_child) {
+    if let Some(callback) = common::get_function_child(element) {
+        // Callback children (`<Show>{u => ...}</Show>`) are called by the
+        // component itself
+        static_props.push(ast.object_property_kind_object_property(
+            span,
+            PropertyKind::Init,
+            make_prop_key(ast, span, "children"),
+            context.clone_expr(callback),
+            false,
+            false,
+            false,
+        ));
+    } else if common::has_children(element) {
+        if let Some(children) =
+            get_children_expr_transformed(element, context, options, transform_child)
+        {
             let key = make_prop_key(ast, span, "children");
             if is_dynamic(&children) {
                 dynamic_props.push(ast.object_property_kind_object_property(
@@ -505,7 +564,7 @@ fn build_props<'a, 'b>(
 
     if !spreads.is_empty() {
         context.register_helper("mergeProps");
-        let callee = ast.expression_identifier(span, "mergeProps");
+        let callee = helper_ident(ast, "mergeProps");
         let mut args = ast.vec_with_capacity(spreads.len() + if has_inline_props { 1 } else { 0 });
         for spread in spreads {
             args.push(Argument::from(spread));
@@ -0,0 +1,205 @@
+//! Conditional child expressions (`wrapConditionals`)
+//!
+//! `{cond() ? <A /> : <B />}` re-runs whenever anything the condition reads
+//! changes, which would re-create the chosen branch even when the condition
+//! keeps its truthiness. With `wrap_conditionals` the test is memoized
+//! (`memo(() => !!cond())`) so the branch is only rebuilt when it flips.
+
+use oxc_ast::ast::{
+    Argument, BindingPattern, Expression, FormalParameterKind, Statement, VariableDeclarationKind,
+};
+use oxc_ast::AstBuilder;
+use oxc_ast::NONE;
+use oxc_span::{GetSpan, Span, SPAN};
+use oxc_syntax::operator::{LogicalOperator, UnaryOperator};
+
+use common::{helper_ident, is_dynamic};
+
+use crate::ir::BlockContext;
+
+fn call_expr<'a>(
+    ast: AstBuilder<'a>,
+    span: Span,
+    callee: Expression<'a>,
+    args: impl IntoIterator<Item = Expression<'a>>,
+) -> Expression<'a> {
+    let mut arguments = ast.vec();
+    for arg in args {
+        arguments.push(Argument::from(arg));
+    }
+    ast.expression_call(
+        span,
+        callee,
+        None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+        arguments,
+        false,
+    )
+}
+
+/// `() => expr`, or `() => { ...statements }` for a function body
+fn arrow<'a>(
+    ast: AstBuilder<'a>,
+    span: Span,
+    expression: bool,
+    statements: oxc_allocator::Vec<'a, Statement<'a>>,
+) -> Expression<'a> {
+    let params = ast.alloc_formal_parameters(
+        SPAN,
+        FormalParameterKind::ArrowFormalParameters,
+        ast.vec(),
+        NONE,
+    );
+    let body = ast.alloc_function_body(SPAN, ast.vec(), statements);
+    ast.expression_arrow_function(span, expression, false, NONE, params, NONE, body)
+}
+
+fn arrow_return_expr<'a>(ast: AstBuilder<'a>, span: Span, expr: Expression<'a>) -> Expression<'a> {
+    let statement = Statement::ExpressionStatement(ast.alloc_expression_statement(SPAN, expr));
+    arrow(ast, span, true, ast.vec1(statement))
+}
+
+fn binding<'a>(ast: AstBuilder<'a>, name: &str) -> BindingPattern<'a> {
+    ast.binding_pattern_binding_identifier(SPAN, ast.allocator.alloc_str(name))
+}
+
+/// Whether reading `expr` can track a signal. A plain identifier is read
+/// once, as in babel-plugin-jsx-dom-expressions.
+fn is_reactive(expr: &Expression) -> bool {
+    !matches!(expr.without_parentheses(), Expression::Identifier(_)) && is_dynamic(expr)
+}
+
+/// The test worth memoizing: that of a conditional with a reactive test and
+/// branch, or the left side of the `&&` in `cond && <A />` (and in
+/// `cond && <A /> || <B />`)
+fn memoized_test<'a, 'e>(expr: &'e mut Expression<'a>) -> Option<&'e mut Expression<'a>> {
+    match expr {
+        Expression::ConditionalExpression(conditional) => {
+            let reactive_branch =
+                is_reactive(&conditional.consequent) || is_reactive(&conditional.alternate);
+            (reactive_branch && is_reactive(&conditional.test)).then_some(&mut conditional.test)
+        }
+        Expression::LogicalExpression(logical) => {
+            if logical.operator != LogicalOperator::And
+                && matches!(
+                    logical.left.without_parentheses(),
+                    Expression::LogicalExpression(_)
+                )
+            {
+                return memoized_test(&mut logical.left);
+            }
+            let memoize = logical.operator == LogicalOperator::And
+                && is_reactive(&logical.right)
+                && is_reactive(&logical.left);
+            memoize.then_some(&mut logical.left)
+        }
+        Expression::ParenthesizedExpression(parenthesized) => {
+            memoized_test(&mut parenthesized.expression)
+        }
+        _ => None,
+    }
+}
+
+/// Replace the test of `expr` with a read of a memo of its truthiness.
+/// `read` builds that read from the `memo(() => !!test)` call; `false` when
+/// there is nothing to memoize.
+fn replace_test<'a>(
+    expr: &mut Expression<'a>,
+    context: &BlockContext<'a>,
+    read: impl FnOnce(Expression<'a>) -> Expression<'a>,
+) -> bool {
+    let ast = context.ast();
+    let Some(slot) = memoized_test(expr) else {
+        return false;
+    };
+    let test = std::mem::replace(slot, ast.expression_null_literal(SPAN));
+    let span = test.span();
+    // Comparisons already produce a boolean
+    let condition = if matches!(test.without_parentheses(), Expression::BinaryExpression(_)) {
+        test
+    } else {
+        let not = ast.expression_unary(span, UnaryOperator::LogicalNot, test);
+        ast.expression_unary(span, UnaryOperator::LogicalNot, not)
+    };
+    context.register_helper(&context.memo_wrapper);
+    let memo = call_expr(
+        ast,
+        span,
+        helper_ident(ast, &context.memo_wrapper),
+        [arrow_return_expr(ast, span, condition)],
+    );
+    *slot = read(memo);
+    true
+}
+
+/// Nested conditionals read their memo inline: `memo(() => !!test)()`
+fn transform_nested<'a>(expr: &mut Expression<'a>, context: &BlockContext<'a>) {
+    let ast = context.ast();
+    if replace_test(expr, context, |memo| call_expr(ast, SPAN, memo, [])) {
+        if let Expression::ConditionalExpression(conditional) = expr {
+            transform_nested(&mut conditional.consequent, context);
+            transform_nested(&mut conditional.alternate, context);
+        }
+    }
+}
+
+/// Memoize the test of a conditional or logical `expr` in place, reading the
+/// memo inline (`memo(() => !!cond())() ? a : b`). For values a getter or an
+/// arrow already re-evaluates: component props and children, and fragment
+/// children.
+pub fn memoize_condition_inline<'a>(expr: &mut Expression<'a>, context: &BlockContext<'a>) {
+    transform_nested(expr, context);
+}
+
+/// The value to `insert` for a conditional or logical child expression:
+///
+/// ```js
+/// (() => {
+///   const _c$ = memo(() => !!cond());
+///   return () => _c$() ? a() : b();
+/// })()
+/// ```
+///
+/// or `() => expr` when the test isn't reactive or no branch is
+pub fn transform_condition<'a>(
+    mut expr: Expression<'a>,
+    span: Span,
+    context: &BlockContext<'a>,
+) -> Expression<'a> {
+    let ast = context.ast();
+    let name = context.local_name("_c$");
+    let mut memo = None;
+    replace_test(&mut expr, context, |call| {
+        memo = Some(call);
+        let id = ast.expression_identifier(SPAN, ast.allocator.alloc_str(&name));
+        call_expr(ast, SPAN, id, [])
+    });
+    let Some(memo) = memo else {
+        return arrow_return_expr(ast, span, expr);
+    };
+    if let Expression::ConditionalExpression(conditional) = &mut expr {
+        transform_nested(&mut conditional.consequent, context);
+        transform_nested(&mut conditional.alternate, context);
+    }
+
+    let declarator = ast.variable_declarator(
+        SPAN,
+        VariableDeclarationKind::Const,
+        binding(ast, &name),
+        NONE,
+        Some(memo),
+        false,
+    );
+    let declaration = Statement::VariableDeclaration(ast.alloc_variable_declaration(
+        SPAN,
+        VariableDeclarationKind::Const,
+        ast.vec1(declarator),
+        false,
+    ));
+    let body = Statement::ReturnStatement(
+        ast.alloc_return_statement(SPAN, Some(arrow_return_expr(ast, span, expr))),
+    );
+    let mut statements = ast.vec_with_capacity(2);
+    statements.push(declaration);
+    statements.push(body);
+    call_expr(ast, span, arrow(ast, span, false, statements), [])
+}
@@ -0,0 +1,341 @@
+//! CSP-safe template construction
+//!
+//! `template()` parses HTML through `innerHTML`, which strict Content
+//! Security Policies (Trusted Types) forbid. In `csp_safe` mode each template
+//! string is parsed at compile time instead and emitted as a module-level node
+//! built with `document.createElement`, `createTextNode` and `appendChild`.
+//! The node is cloned per use exactly like a `template()` node.
+
+use oxc_ast::ast::{Argument, Expression, FormalParameterKind, Statement, VariableDeclarationKind};
+use oxc_ast::{AstBuilder, NONE};
+use oxc_span::Span;
+
+use common::constants::VOID_ELEMENTS;
+use common::expression::decode_html_entities;
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Namespaces of prefixed attributes (`xlink:href`), which `setAttribute`
+/// would create without a namespace
+const ATTRIBUTE_NAMESPACES: [(&str, &str); 3] = [
+    ("xlink", "http://www.w3.org/1999/xlink"),
+    ("xml", "http://www.w3.org/XML/1998/namespace"),
+    ("xmlns", "http://www.w3.org/2000/xmlns/"),
+];
+
+/// Namespace an attribute is set in, `None` for plain attributes
+fn attribute_namespace(name: &str) -> Option<&'static str> {
+    let prefix = match name.split_once(':') {
+        Some((prefix, _)) => prefix,
+        None if name == "xmlns" => name,
+        None => return None,
+    };
+    ATTRIBUTE_NAMESPACES
+        .iter()
+        .find(|(known, _)| *known == prefix)
+        .map(|(_, namespace)| *namespace)
+}
+
+/// A node of a parsed template
+enum Node {
+    Element {
+        tag: String,
+        attributes: Vec<(String, Option<String>)>,
+        children: Vec<Node>,
+    },
+    Text(String),
+    Comment(String),
+}
+
+/// Parser for the HTML the transform emits into templates
+struct TemplateParser<'s> {
+    html: &'s str,
+    pos: usize,
+}
+
+impl<'s> TemplateParser<'s> {
+    fn rest(&self) -> &'s str {
+        &self.html[self.pos..]
+    }
+
+    fn take_until(&mut self, pattern: &str) -> &'s str {
+        let rest = self.rest();
+        let end = rest.find(pattern).unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+
+    fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'s str {
+        let rest = self.rest();
+        let end = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.pos += end;
+        &rest[..end]
+    }
+
+    fn skip(&mut self, pattern: &str) -> bool {
+        let found = self.rest().starts_with(pattern);
+        if found {
+            self.pos += pattern.len();
+        }
+        found
+    }
+
+    /// Parse nodes until the closing tag of the enclosing element (or the
+    /// end of input, for templates that omit closing tags)
+    fn parse_nodes(&mut self) -> Vec<Node> {
+        let mut nodes = Vec::new();
+        while !self.rest().is_empty() {
+            if self.skip("</") {
+                self.take_until(">");
+                self.skip(">");
+                break;
+            } else if self.skip("<!>") {
+                nodes.push(Node::Comment(String::new()));
+            } else if self.skip("<!--") {
+                let data = self.take_until("-->");
+                self.skip("-->");
+                nodes.push(Node::Comment(data.to_string()));
+            } else if self.skip("<") {
+                nodes.push(self.parse_element());
+            } else {
+                let text = self.take_until("<");
+                nodes.push(Node::Text(decode_html_entities(text)));
+            }
+        }
+        nodes
+    }
+
+    fn parse_element(&mut self) -> Node {
+        let tag = self
+            .take_while(|c| !c.is_whitespace() && c != '>' && c != '/')
+            .to_string();
+        let mut attributes = Vec::new();
+        let mut self_closing = false;
+        loop {
+            self.take_while(char::is_whitespace);
+            if self.rest().is_empty() || self.skip(">") {
+                break;
+            }
+            if self.skip("/>") {
+                self_closing = true;
+                break;
+            }
+            let name = self.take_while(|c| !c.is_whitespace() && !matches!(c, '=' | '>' | '/'));
+            if name.is_empty() {
+                // Stray `/` inside the tag
+                self.pos += 1;
+                continue;
+            }
+            let value = if self.skip("=") {
+                Some(if self.skip("\"") {
+                    let value = self.take_until("\"");
+                    self.skip("\"");
+                    decode_html_entities(value)
+                } else if self.skip("'") {
+                    let value = self.take_until("'");
+                    self.skip("'");
+                    decode_html_entities(value)
+                } else {
+                    decode_html_entities(self.take_while(|c| !c.is_whitespace() && c != '>'))
+                })
+            } else {
+                None
+            };
+            attributes.push((name.to_string(), value));
+        }
+
+        let children = if self_closing || VOID_ELEMENTS.contains(tag.as_str()) {
+            Vec::new()
+        } else {
+            self.parse_nodes()
+        };
+        Node::Element {
+            tag,
+            attributes,
+            children,
+        }
+    }
+}
+
+/// Builds the construction statements for one template
+struct NodeBuilder<'a> {
+    ast: AstBuilder<'a>,
+    span: Span,
+    statements: oxc_allocator::Vec<'a, Statement<'a>>,
+    counter: usize,
+}
+
+impl<'a> NodeBuilder<'a> {
+    fn ident(&self, name: &str) -> Expression<'a> {
+        self.ast
+            .expression_identifier(self.span, self.ast.allocator.alloc_str(name))
+    }
+
+    fn string(&self, value: &str) -> Expression<'a> {
+        self.ast
+            .expression_string_literal(self.span, self.ast.allocator.alloc_str(value), None)
+    }
+
+    fn call(
+        &self,
+        object: Expression<'a>,
+        method: &str,
+        args: impl IntoIterator<Item = Expression<'a>>,
+    ) -> Expression<'a> {
+        let property = self
+            .ast
+            .identifier_name(self.span, self.ast.allocator.alloc_str(method));
+        let callee = Expression::StaticMemberExpression(
+            self.ast
+                .alloc_static_member_expression(self.span, object, property, false),
+        );
+        let mut arguments = self.ast.vec();
+        for arg in args {
+            arguments.push(Argument::from(arg));
+        }
+        self.ast.expression_call(
+            self.span,
+            callee,
+            None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+            arguments,
+            false,
+        )
+    }
+
+    fn document_call(
+        &self,
+        method: &str,
+        args: impl IntoIterator<Item = Expression<'a>>,
+    ) -> Expression<'a> {
+        self.call(self.ident("document"), method, args)
+    }
+
+    fn push_expr(&mut self, expr: Expression<'a>) {
+        self.statements.push(Statement::ExpressionStatement(
+            self.ast.alloc_expression_statement(self.span, expr),
+        ));
+    }
+
+    /// Declare `const _n$N = init;` and return the name
+    fn declare(&mut self, init: Expression<'a>) -> String {
+        self.counter += 1;
+        let name = format!("_n${}", self.counter);
+        let declarator = self.ast.variable_declarator(
+            self.span,
+            VariableDeclarationKind::Const,
+            self.ast
+                .binding_pattern_binding_identifier(self.span, self.ast.allocator.alloc_str(&name)),
+            NONE,
+            Some(init),
+            false,
+        );
+        self.statements.push(Statement::VariableDeclaration(
+            self.ast.alloc_variable_declaration(
+                self.span,
+                VariableDeclarationKind::Const,
+                self.ast.vec1(declarator),
+                false,
+            ),
+        ));
+        name
+    }
+
+    /// Expression creating `node`; elements are declared with their
+    /// attributes and children set up before it is used
+    fn create(&mut self, node: &Node, in_svg: bool) -> Expression<'a> {
+        match node {
+            Node::Text(text) => self.document_call("createTextNode", [self.string(text)]),
+            Node::Comment(data) => self.document_call("createComment", [self.string(data)]),
+            Node::Element {
+                tag,
+                attributes,
+                children,
+            } => {
+                let in_svg = in_svg || tag == "svg";
+                let element = if in_svg {
+                    self.document_call(
+                        "createElementNS",
+                        [self.string(SVG_NAMESPACE), self.string(tag)],
+                    )
+                } else {
+                    self.document_call("createElement", [self.string(tag)])
+                };
+                let name = self.declare(element);
+                for (attribute, value) in attributes {
+                    let value = self.string(value.as_deref().unwrap_or(""));
+                    let set = match attribute_namespace(attribute) {
+                        Some(namespace) => self.call(
+                            self.ident(&name),
+                            "setAttributeNS",
+                            [self.string(namespace), self.string(attribute), value],
+                        ),
+                        None => self.call(
+                            self.ident(&name),
+                            "setAttribute",
+                            [self.string(attribute), value],
+                        ),
+                    };
+                    self.push_expr(set);
+                }
+                // foreignObject content is HTML again
+                let children_in_svg = in_svg && tag != "foreignObject";
+                for child in children {
+                    let child = self.create(child, children_in_svg);
+                    let append = self.call(self.ident(&name), "appendChild", [child]);
+                    self.push_expr(append);
+                }
+                self.ident(&name)
+            }
+        }
+    }
+}
+
+/// Build the expression for a template node without `template()`:
+/// `(() => { const _n$1 = document.createElement("div"); ...; return _n$1; })()`
+pub fn build_template_node<'a>(
+    ast: AstBuilder<'a>,
+    span: Span,
+    html: &str,
+    is_svg: bool,
+) -> Expression<'a> {
+    let nodes = TemplateParser { html, pos: 0 }.parse_nodes();
+    let mut builder = NodeBuilder {
+        ast,
+        span,
+        statements: ast.vec(),
+        counter: 0,
+    };
+
+    let root = match nodes.as_slice() {
+        [node] => builder.create(node, is_svg),
+        nodes => {
+            let fragment = builder.document_call("createDocumentFragment", []);
+            let name = builder.declare(fragment);
+            for node in nodes {
+                let child = builder.create(node, is_svg);
+                let append = builder.call(builder.ident(&name), "appendChild", [child]);
+                builder.push_expr(append);
+            }
+            builder.ident(&name)
+        }
+    };
+    builder.statements.push(Statement::ReturnStatement(
+        ast.alloc_return_statement(span, Some(root)),
+    ));
+
+    let params = ast.alloc_formal_parameters(
+        span,
+        FormalParameterKind::ArrowFormalParameters,
+        ast.vec(),
+        NONE,
+    );
+    let body = ast.alloc_function_body(span, ast.vec(), builder.statements);
+    let arrow = ast.expression_arrow_function(span, false, false, NONE, params, NONE, body);
+    ast.expression_call(
+        span,
+        arrow,
+        None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+        ast.vec(),
+        false,
+    )
+}
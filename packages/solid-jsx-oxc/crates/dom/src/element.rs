@@ -4,24 +4,33 @@
 use oxc_allocator::CloneIn;
 use oxc_ast::ast::{
     Argument, AssignmentTarget, Expression, FormalParameterKind, JSXAttribute, JSXAttributeItem,
-    JSXAttributeValue, JSXElement, Statement,
+    JSXAttributeValue, JSXElement, JSXExpression, PropertyKind, Statement, TemplateElementValue,
 };
 use oxc_ast::AstBuilder;
 use oxc_ast::NONE;
 use oxc_span::{Span, SPAN};
-use oxc_syntax::operator::{AssignmentOperator, BinaryOperator, UnaryOperator};
+use oxc_syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator};
 use oxc_syntax::symbol::SymbolFlags;
 use oxc_traverse::TraverseCtx;
 
 use common::{
-    constants::{ALIASES, DELEGATED_EVENTS, VOID_ELEMENTS},
-    expression::{escape_html, to_event_name},
-    get_attr_name, is_component, is_dynamic, is_namespaced_attr, is_svg_element, TransformOptions,
+    attribute_name,
+    constants::{DELEGATED_EVENTS, PROPERTIES, VOID_ELEMENTS},
+    expression::{escape_html, escape_template_raw, to_event_name},
+    get_attr_name, helper_ident, is_component, is_custom_element, is_dynamic, is_namespaced_attr,
+    is_single_dynamic_child, is_value_property_only, prerender_element, static_attribute_markup,
+    static_attribute_text, static_child_text, static_text_content, static_truthiness,
+    TransformOptions,
 };
 
+use crate::component::{getter_return_expr, make_prop_key};
+use crate::condition::transform_condition;
 use crate::ir::{BlockContext, ChildTransformer, Declaration, DynamicBinding, TransformResult};
+use crate::template::{binding_helper, generate_set_attr_expr};
 use crate::transform::TransformInfo;
 
+// Generated identifiers and member accesses stay unspanned, like helper
+// references; calls, functions and assignments map to the JSX they come from
 fn ident_expr<'a>(ast: AstBuilder<'a>, span: Span, name: &str) -> Expression<'a> {
     let _ = span;
     ast.expression_identifier(SPAN, ast.allocator.alloc_str(name))
@@ -46,13 +55,12 @@ fn call_expr<'a>(
     callee: Expression<'a>,
     args: impl IntoIterator<Item = Expression<'a>>,
 ) -> Expression<'a> {
-    let _ = span;
     let mut arguments = ast.vec();
     for arg in args {
         arguments.push(Argument::from(arg));
     }
     ast.expression_call(
-        SPAN,
+        span,
         callee,
         None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
         arguments,
@@ -65,7 +73,6 @@ fn arrow_zero_params_return_expr<'a>(
     span: Span,
     expr: Expression<'a>,
 ) -> Expression<'a> {
-    let _ = span;
     let params = ast.alloc_formal_parameters(
         SPAN,
         FormalParameterKind::ArrowFormalParameters,
@@ -77,7 +84,7 @@ fn arrow_zero_params_return_expr<'a>(
         ast.alloc_expression_statement(SPAN, expr),
     ));
     let body = ast.alloc_function_body(SPAN, ast.vec(), statements);
-    ast.expression_arrow_function(SPAN, true, false, NONE, params, NONE, body)
+    ast.expression_arrow_function(span, true, false, NONE, params, NONE, body)
 }
 
 fn expression_to_assignment_target<'a>(expr: Expression<'a>) -> Option<AssignmentTarget<'a>> {
@@ -99,6 +106,9 @@ fn expression_to_assignment_target<'a>(expr: Expression<'a>) -> Option<Assignmen
             expression_to_assignment_target(e.unbox().expression)
         }
         Expression::TSTypeAssertion(e) => expression_to_assignment_target(e.unbox().expression),
+        Expression::ParenthesizedExpression(e) => {
+            expression_to_assignment_target(e.unbox().expression)
+        }
         _ => None,
     }
 }
@@ -114,9 +124,9 @@ pub fn transform_element<'a, 'b>(
     ctx: &TraverseCtx<'a, ()>,
 ) -> TransformResult<'a> {
     let ast = context.ast();
-    let is_svg = is_svg_element(tag_name);
+    let is_svg = context.is_svg(tag_name);
     let is_void = VOID_ELEMENTS.contains(tag_name);
-    let is_custom_element = tag_name.contains('-');
+    let is_custom_element = is_custom_element(tag_name, options);
 
     let mut result = TransformResult {
         span: element.span,
@@ -126,28 +136,27 @@ pub fn transform_element<'a, 'b>(
         ..Default::default()
     };
 
+    // Custom elements and slots are handed the reactive owner, so their
+    // content can read the context around them
+    let needs_owner =
+        options.context_to_custom_elements && (is_custom_element || tag_name == "slot");
+
+    // Fully static subtrees are rendered straight into the template
+    let prerendered = prerender_element(element, options);
+
     // Check if this element needs runtime access (dynamic attributes, refs, events)
-    let needs_runtime_access = element_needs_runtime_access(element);
+    let needs_runtime_access =
+        prerendered.is_none() && (needs_owner || element_needs_runtime_access(element));
 
     // Generate element ID if needed
     if !info.skip_id && (info.top_level || needs_runtime_access) {
-        let elem_id = context.generate_uid("el$");
-        result.id = Some(elem_id.clone());
-
-        // If we have a path, we need to walk to this element
-        if !info.path.is_empty() {
-            if let Some(root_id) = &info.root_id {
-                result.declarations.push(Declaration {
-                    name: elem_id.clone(),
-                    init: info
-                        .path
-                        .iter()
-                        .fold(ident_expr(ast, element.span, root_id), |acc, step| {
-                            static_member(ast, element.span, acc, step)
-                        }),
-                });
-            }
-        }
+        element_id(&mut result, info, context);
+    }
+
+    if let Some(html) = prerendered {
+        result.template_with_closing_tags = html.clone();
+        result.template = html;
+        return result;
     }
 
     // Start building template
@@ -155,12 +164,35 @@ pub fn transform_element<'a, 'b>(
     result.template_with_closing_tags = result.template.clone();
 
     // Transform attributes
-    transform_attributes(element, &mut result, context, options, ctx);
+    transform_attributes(element, info, &mut result, context, options, ctx);
+
+    // _el$._$owner = getOwner()
+    if let Some(elem_id) = result.id.as_deref().filter(|_| needs_owner) {
+        context.register_helper("getOwner");
+        let span = element.span;
+        let owner = static_member(ast, span, ident_expr(ast, span, elem_id), "_$owner");
+        if let Some(target) = expression_to_assignment_target(owner) {
+            let get_owner = call_expr(ast, span, helper_ident(ast, "getOwner"), []);
+            result.exprs.push(ast.expression_assignment(
+                span,
+                AssignmentOperator::Assign,
+                target,
+                get_owner,
+            ));
+        }
+    }
 
     // Close opening tag
     result.template.push('>');
     result.template_with_closing_tags.push('>');
 
+    // A literal textContent on a childless element is plain template text
+    if let Some(text) = static_text_content(element) {
+        let text = escape_html(&text, false);
+        result.template.push_str(&text);
+        result.template_with_closing_tags.push_str(&text);
+    }
+
     // Transform children (if not void element)
     if !is_void {
         // Pass down the root ID and path for children
@@ -198,22 +230,41 @@ pub fn transform_element<'a, 'b>(
 
 /// Check if an element needs runtime access
 fn element_needs_runtime_access(element: &JSXElement) -> bool {
+    let tag_name = common::get_tag_name(element);
+
     // Check attributes
     for attr in &element.opening_element.attributes {
         match attr {
             JSXAttributeItem::Attribute(attr) => {
-                // Namespaced attributes like on:click or use:directive always need access
+                let key = get_attr_name(&attr.name);
+                // A bool: attribute or class: toggle with a literal value is
+                // decided in the template
+                if (key.starts_with("bool:") || key.starts_with("class:"))
+                    && static_presence(attr).is_some()
+                {
+                    continue;
+                }
+                // Other namespaced attributes like on:click or use:directive
+                // always need access
                 if is_namespaced_attr(&attr.name) {
                     return true;
                 }
-                let key = get_attr_name(&attr.name);
 
-                // ref and inner content setters need access
+                // ref and inner content setters need access; a literal
+                // textContent is inlined into the template instead
+                if key == "textContent" && static_text_content(element).is_some() {
+                    continue;
+                }
                 if key == "ref" || key == "innerHTML" || key == "textContent" || key == "innerText"
                 {
                     return true;
                 }
 
+                // `<select>`/`<textarea>` values are set through the property
+                if key == "value" && is_value_property_only(&tag_name) {
+                    return true;
+                }
+
                 // Event handlers need access
                 if key.starts_with("on") && key.len() > 2 {
                     return true;
@@ -221,8 +272,15 @@ fn element_needs_runtime_access(element: &JSXElement) -> bool {
 
                 // Any expression container needs runtime access (we may need to run setters/helpers).
                 // This keeps id generation consistent with the rest of the transform.
-                if matches!(&attr.value, Some(JSXAttributeValue::ExpressionContainer(_))) {
-                    return true;
+                if let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value {
+                    let inlined = container
+                        .expression
+                        .as_expression()
+                        .and_then(|expr| static_attribute_markup(&key, expr))
+                        .is_some();
+                    if !inlined {
+                        return true;
+                    }
                 }
             }
             JSXAttributeItem::SpreadAttribute(_) => {
@@ -243,14 +301,24 @@ fn element_needs_runtime_access(element: &JSXElement) -> bool {
                         return true;
                     }
                 }
-                oxc_ast::ast::JSXChild::ExpressionContainer(_) => {
-                    return true;
-                }
-                oxc_ast::ast::JSXChild::Fragment(fragment) => {
-                    if children_need_runtime_access(&fragment.children) {
+                oxc_ast::ast::JSXChild::ExpressionContainer(container) => {
+                    // Literal children are inlined into the template text
+                    let is_literal = container
+                        .expression
+                        .as_expression()
+                        .is_none_or(|expr| static_child_text(expr).is_some());
+                    if !is_literal {
                         return true;
                     }
                 }
+                oxc_ast::ast::JSXChild::Spread(_) => {
+                    return true;
+                }
+                oxc_ast::ast::JSXChild::Fragment(fragment)
+                    if children_need_runtime_access(&fragment.children) =>
+                {
+                    return true;
+                }
                 _ => {}
             }
         }
@@ -264,46 +332,400 @@ fn element_needs_runtime_access(element: &JSXElement) -> bool {
     false
 }
 
+/// The id of the element being transformed, allocated on first use. An
+/// element below the template root is walked to from the nearest ancestor
+/// with an id.
+fn element_id<'a>(
+    result: &mut TransformResult<'a>,
+    info: &TransformInfo,
+    context: &BlockContext<'a>,
+) -> String {
+    if let Some(elem_id) = &result.id {
+        return elem_id.clone();
+    }
+    let ast = context.ast();
+    let span = result.span;
+    let elem_id = context.generate_uid("el$");
+    result.id = Some(elem_id.clone());
+    if !info.path.is_empty() {
+        if let Some(root_id) = &info.root_id {
+            result.declarations.push(Declaration {
+                name: elem_id.clone(),
+                current: None,
+                init: info
+                    .path
+                    .iter()
+                    .fold(ident_expr(ast, span, root_id), |acc, step| {
+                        static_member(ast, span, acc, step)
+                    }),
+            });
+        }
+    }
+    elem_id
+}
+
 /// Transform element attributes
 fn transform_attributes<'a>(
     element: &JSXElement<'a>,
+    info: &TransformInfo,
     result: &mut TransformResult<'a>,
     context: &BlockContext<'a>,
     options: &TransformOptions<'a>,
     ctx: &TraverseCtx<'a, ()>,
 ) {
-    let ast = context.ast();
-    let elem_id = result.id.clone();
+    // A literal textContent is inlined into the template with the children
+    let inlined_text_content = static_text_content(element).is_some();
+
+    let has_spread = element
+        .opening_element
+        .attributes
+        .iter()
+        .any(|attr| matches!(attr, JSXAttributeItem::SpreadAttribute(_)));
+    if has_spread {
+        transform_spread_attributes(element, info, result, context, options, ctx);
+        return;
+    }
 
-    for attr in &element.opening_element.attributes {
+    let merged_class = merge_class_toggles(element, context);
+    for (i, attr) in element.opening_element.attributes.iter().enumerate() {
         match attr {
+            JSXAttributeItem::Attribute(_) if merged_class.index == Some(i) => {
+                if let Some(class) = &merged_class.attr {
+                    transform_attribute(class, info, result, context, options, ctx);
+                }
+            }
+            JSXAttributeItem::Attribute(_) if merged_class.folded.contains(&i) => {}
+            JSXAttributeItem::Attribute(attr)
+                if inlined_text_content && get_attr_name(&attr.name) == "textContent" => {}
             JSXAttributeItem::Attribute(attr) => {
-                transform_attribute(attr, elem_id.as_deref(), result, context, options, ctx);
+                transform_attribute(attr, info, result, context, options, ctx);
+            }
+            JSXAttributeItem::SpreadAttribute(_) => {}
+        }
+    }
+}
+
+/// The class attribute of an element with `class:name` toggles folded in
+#[derive(Default)]
+struct MergedClass<'a> {
+    /// The class attribute to compile in place of the attribute at `index`,
+    /// `None` when no class is left
+    attr: Option<JSXAttribute<'a>>,
+    index: Option<usize>,
+    /// Indices of the toggles folded into `attr`
+    folded: Vec<usize>,
+}
+
+/// Fold `class:name` toggles into the element's class attribute. Toggles
+/// with a literal value join a static class in the template. A dynamic
+/// class is set as a whole on every update, which would drop toggled
+/// classes, so all toggles are merged into its value instead:
+/// `class={`${c() || ""} big ${on() ? "on" : ""}`}`.
+fn merge_class_toggles<'a>(
+    element: &JSXElement<'a>,
+    context: &BlockContext<'a>,
+) -> MergedClass<'a> {
+    let ast = context.ast();
+    let mut class = None;
+    let mut toggles = Vec::new();
+    for (i, item) in element.opening_element.attributes.iter().enumerate() {
+        let JSXAttributeItem::Attribute(attr) = item else {
+            continue;
+        };
+        let key = get_attr_name(&attr.name);
+        if let Some(name) = key.strip_prefix("class:") {
+            toggles.push((i, name.to_string(), attr));
+        } else if (key == "class" || key == "className") && class.is_none() {
+            class = Some((i, attr));
+        }
+    }
+    if toggles.is_empty() {
+        return MergedClass::default();
+    }
+
+    let static_class = match class {
+        None => Some(String::new()),
+        Some((_, attr)) => match &attr.value {
+            Some(JSXAttributeValue::StringLiteral(lit)) => Some(lit.value.to_string()),
+            Some(JSXAttributeValue::ExpressionContainer(container)) => container
+                .expression
+                .as_expression()
+                .and_then(static_attribute_text),
+            _ => Some(String::new()),
+        },
+    };
+    let name = class.map_or_else(
+        || ast.jsx_attribute_name_identifier(SPAN, "class"),
+        |(_, attr)| attr.name.clone_in(ast.allocator),
+    );
+    let span = class.map_or(SPAN, |(_, attr)| attr.span);
+
+    if let Some(mut text) = static_class {
+        // Dynamic toggles keep their classList.toggle
+        let mut folded = Vec::new();
+        for (i, name, attr) in &toggles {
+            let Some(on) = static_presence(attr) else {
+                continue;
+            };
+            folded.push(*i);
+            if on {
+                if !text.is_empty() {
+                    text.push(' ');
+                }
+                text.push_str(name);
+            }
+        }
+        let index = class.map(|(i, _)| i).or_else(|| folded.first().copied());
+        let attr = (class.is_some() || !text.is_empty()).then(|| {
+            let value = ast.jsx_attribute_value_string_literal(SPAN, ast.atom(&text), None);
+            ast.jsx_attribute(span, name, Some(value))
+        });
+        return MergedClass {
+            attr,
+            index,
+            folded,
+        };
+    }
+
+    // `${c() || ""} big ${on() ? "on" : ""}`
+    let mut quasis = Vec::new();
+    let mut text = String::new();
+    let mut exprs = ast.vec();
+    if let Some((_, attr)) = class {
+        if let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value {
+            if let Some(expr) = container.expression.as_expression() {
+                exprs.push(ast.expression_logical(
+                    attr.span,
+                    context.clone_expr(expr),
+                    LogicalOperator::Or,
+                    ast.expression_string_literal(SPAN, "", None),
+                ));
+                quasis.push(String::new());
             }
+        }
+    }
+    for (_, name, attr) in &toggles {
+        let toggled = match static_presence(attr) {
+            Some(false) => continue,
+            Some(true) => None,
+            None => match &attr.value {
+                Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                    container.expression.as_expression()
+                }
+                _ => None,
+            },
+        };
+        if !exprs.is_empty() || !text.is_empty() {
+            text.push(' ');
+        }
+        let Some(expr) = toggled else {
+            text.push_str(name);
+            continue;
+        };
+        quasis.push(std::mem::take(&mut text));
+        exprs.push(ast.expression_conditional(
+            attr.span,
+            context.clone_expr(expr),
+            ast.expression_string_literal(SPAN, ast.atom(name), None),
+            ast.expression_string_literal(SPAN, "", None),
+        ));
+    }
+    quasis.push(text);
+    let count = quasis.len();
+    let quasis = ast.vec_from_iter(quasis.iter().enumerate().map(|(i, text)| {
+        let value = TemplateElementValue {
+            raw: ast.atom(&escape_template_raw(text)),
+            cooked: Some(ast.atom(text)),
+        };
+        ast.template_element(SPAN, value, i + 1 == count)
+    }));
+    let value = ast.expression_template_literal(span, quasis, exprs);
+    let value = ast.jsx_attribute_value_expression_container(span, JSXExpression::from(value));
+    MergedClass {
+        attr: Some(ast.jsx_attribute(span, name, Some(value))),
+        index: class.map(|(i, _)| i),
+        folded: toggles.iter().map(|(i, _, _)| *i).collect(),
+    }
+}
+
+/// Namespaces the `spread` runtime doesn't understand; these attributes are
+/// compiled as usual even when the element has spreads
+const NON_SPREAD_NAMESPACES: [&str; 6] = ["class", "style", "use", "prop", "attr", "bool"];
+
+/// Whether `spread` can apply the attribute `key` itself
+fn can_native_spread(key: &str) -> bool {
+    if let Some((namespace, _)) = key.split_once(':') {
+        if NON_SPREAD_NAMESPACES.contains(&namespace) {
+            return false;
+        }
+    }
+    key != "ref"
+}
+
+/// Whether a spread argument or attribute value has to be read reactively.
+/// A plain identifier is read once, as babel-plugin-jsx-dom-expressions does.
+fn is_dynamic_spread_value(expr: &Expression) -> bool {
+    !matches!(expr.without_parentheses(), Expression::Identifier(_)) && is_dynamic(expr)
+}
+
+/// Transform the attributes of an element with `{...spread}`s
+///
+/// Spreads and the attributes after the first one are merged, in order, into
+/// a single `spread(el, props, isSVG, skipChildren)` call so later props
+/// override earlier ones the way they would on a component:
+/// `<div class="a" {...p} onClick={h} />` passes `mergeProps(p, { onClick: h })`.
+/// Dynamic attributes before the first spread join the props as getters;
+/// static ones stay in the template. Refs and the non-spread namespaces are
+/// compiled as usual.
+fn transform_spread_attributes<'a>(
+    element: &JSXElement<'a>,
+    info: &TransformInfo,
+    result: &mut TransformResult<'a>,
+    context: &BlockContext<'a>,
+    options: &TransformOptions<'a>,
+    ctx: &TraverseCtx<'a, ()>,
+) {
+    let ast = context.ast();
+    let elem_id = element_id(result, info, context);
+    let inlined_text_content = static_text_content(element).is_some();
+
+    let mut args: Vec<Expression<'a>> = Vec::new();
+    let mut running = ast.vec();
+    let mut first_spread = false;
+    let mut dynamic_spread = false;
+    let mut span = SPAN;
+    // The spread goes where the first one was written, so the attributes
+    // compiled as usual after it are applied on top of it
+    let mut spread_index = None;
+
+    for attr in &element.opening_element.attributes {
+        match attr {
+            JSXAttributeItem::Attribute(attr)
+                if inlined_text_content && get_attr_name(&attr.name) == "textContent" => {}
             JSXAttributeItem::SpreadAttribute(spread) => {
-                // Handle {...props} spread
-                let elem_id = elem_id
-                    .as_deref()
-                    .expect("Spread attributes require an element id");
-                context.register_helper("spread");
-                let callee = ident_expr(ast, spread.span, "spread");
-                let elem = ident_expr(ast, spread.span, elem_id);
-                let args = [
-                    elem,
-                    context.clone_expr(&spread.argument),
-                    ast.expression_boolean_literal(SPAN, result.is_svg),
-                    ast.expression_boolean_literal(SPAN, !element.children.is_empty()),
-                ];
-                result.exprs.push(call_expr(ast, spread.span, callee, args));
+                if !first_spread {
+                    first_spread = true;
+                    span = spread.span;
+                    spread_index = Some(result.exprs.len());
+                }
+                if !running.is_empty() {
+                    let props = std::mem::replace(&mut running, ast.vec());
+                    args.push(ast.expression_object(SPAN, props));
+                }
+                let argument = spread.argument.without_parentheses();
+                let arg = if is_dynamic_spread_value(argument) {
+                    dynamic_spread = true;
+                    match argument {
+                        // {...props()} passes the accessor itself
+                        Expression::CallExpression(call)
+                            if call.arguments.is_empty()
+                                && matches!(call.callee, Expression::Identifier(_)) =>
+                        {
+                            context.clone_expr(&call.callee)
+                        }
+                        _ => arrow_zero_params_return_expr(
+                            ast,
+                            spread.span,
+                            context.clone_expr(argument),
+                        ),
+                    }
+                } else {
+                    context.clone_expr(&spread.argument)
+                };
+                args.push(arg);
+            }
+            JSXAttributeItem::Attribute(attr) => {
+                let key = get_attr_name(&attr.name);
+                let value = match &attr.value {
+                    Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                        container.expression.as_expression()
+                    }
+                    _ => None,
+                };
+                let dynamic = value.is_some_and(is_dynamic_spread_value);
+                if !(first_spread || dynamic) || !can_native_spread(&key) {
+                    transform_attribute(attr, info, result, context, options, ctx);
+                    continue;
+                }
+                let prop_key = make_prop_key(ast, attr.span, &key);
+                let property = match (&attr.value, value) {
+                    (_, Some(expr)) if dynamic => ast.object_property_kind_object_property(
+                        attr.span,
+                        PropertyKind::Get,
+                        prop_key,
+                        getter_return_expr(ast, attr.span, context.clone_expr(expr)),
+                        false,
+                        false,
+                        false,
+                    ),
+                    (value, expr) => {
+                        let value = match (value, expr) {
+                            (_, Some(expr)) => context.clone_expr(expr),
+                            (Some(JSXAttributeValue::StringLiteral(lit)), _) => {
+                                ast.expression_string_literal(SPAN, lit.value, None)
+                            }
+                            // A bare attribute: `true` for properties, present otherwise
+                            (None, _) if PROPERTIES.contains(key.as_str()) => {
+                                ast.expression_boolean_literal(SPAN, true)
+                            }
+                            (None, _) => ast.expression_string_literal(SPAN, "", None),
+                            _ => ast.expression_identifier(SPAN, "undefined"),
+                        };
+                        ast.object_property_kind_object_property(
+                            attr.span,
+                            PropertyKind::Init,
+                            prop_key,
+                            value,
+                            false,
+                            false,
+                            false,
+                        )
+                    }
+                };
+                running.push(property);
             }
         }
     }
+    if !running.is_empty() {
+        args.push(ast.expression_object(SPAN, running));
+    }
+
+    let props = match args.pop() {
+        Some(props) if args.is_empty() && !dynamic_spread => props,
+        last => {
+            args.extend(last);
+            context.register_helper("mergeProps");
+            call_expr(ast, span, helper_ident(ast, "mergeProps"), args)
+        }
+    };
+
+    context.register_helper("spread");
+    let callee = helper_ident(ast, "spread");
+    let elem = ident_expr(ast, span, &elem_id);
+    // Void elements never take children, so props.children is skipped
+    let skip_children = !element.children.is_empty()
+        || VOID_ELEMENTS.contains(result.tag_name.as_deref().unwrap_or(""));
+    let call = call_expr(
+        ast,
+        span,
+        callee,
+        [
+            elem,
+            props,
+            ast.expression_boolean_literal(SPAN, result.is_svg),
+            ast.expression_boolean_literal(SPAN, skip_children),
+        ],
+    );
+    let index = spread_index.unwrap_or(result.exprs.len());
+    result.exprs.insert(index, call);
+    // Spread props may carry event handlers that can't be seen here
+    result.has_hydratable_event = true;
 }
 
 /// Transform a single attribute
 fn transform_attribute<'a>(
     attr: &JSXAttribute<'a>,
-    elem_id: Option<&str>,
+    info: &TransformInfo,
     result: &mut TransformResult<'a>,
     context: &BlockContext<'a>,
     options: &TransformOptions<'a>,
@@ -313,55 +735,105 @@ fn transform_attribute<'a>(
 
     // Handle different attribute types
     if key == "ref" {
-        let elem_id = elem_id.expect("ref requires an element id");
+        let elem_id = &element_id(result, info, context);
         transform_ref(attr, elem_id, result, context, ctx);
         return;
     }
 
     if key.starts_with("on") {
-        let elem_id = elem_id.expect("event handlers require an element id");
+        let elem_id = &element_id(result, info, context);
         transform_event(attr, &key, elem_id, result, context, options);
         return;
     }
 
     if key.starts_with("use:") {
-        let elem_id = elem_id.expect("directives require an element id");
+        let elem_id = &element_id(result, info, context);
         transform_directive(attr, &key, elem_id, result, context);
         return;
     }
 
     // Handle prop: prefix - direct DOM property assignment
     if key.starts_with("prop:") {
-        let elem_id = elem_id.expect("prop: requires an element id");
+        let elem_id = &element_id(result, info, context);
         transform_prop(attr, &key, elem_id, result, context);
         return;
     }
 
+    // Handle bool: prefix - attribute present while the value is truthy
+    if let Some(name) = key.strip_prefix("bool:") {
+        transform_bool_attribute(attr, name, info, result, context);
+        return;
+    }
+
+    // Handle class: prefix - one class name toggled on the value's truthiness
+    if let Some(name) = key.strip_prefix("class:") {
+        let elem_id = &element_id(result, info, context);
+        transform_class_toggle(attr, name, elem_id, result, context);
+        return;
+    }
+
     // Handle attr: prefix - force attribute mode
     if key.starts_with("attr:") {
-        let elem_id = elem_id.expect("attr: requires an element id");
+        let elem_id = &element_id(result, info, context);
         transform_attr(attr, &key, elem_id, result, context);
         return;
     }
 
     // Handle style attribute specially
     if key == "style" {
-        transform_style(attr, elem_id, result, context);
+        transform_style(attr, info, result, context);
         return;
     }
 
-    // Handle innerHTML/textContent
-    if key == "innerHTML" || key == "textContent" {
-        let elem_id = elem_id.expect("inner content requires an element id");
+    // Handle innerHTML/textContent/innerText
+    if key == "innerHTML" || key == "textContent" || key == "innerText" {
+        let elem_id = &element_id(result, info, context);
         transform_inner_content(attr, &key, elem_id, result, context);
         return;
     }
 
+    // A select's value only sticks once its options exist, so it is assigned
+    // after the children are inserted. Like dom-expressions, reactive `value`
+    // and `checked` are deferred on every element, which also sets an
+    // option's value before its select picks one.
+    let is_select_value = key == "value" && result.tag_name.as_deref() == Some("select");
+    let is_form_state = key == "value" || key == "checked";
+
+    // `<select>` and `<textarea>` ignore a value attribute in markup
+    let is_value_property =
+        key == "value" && is_value_property_only(result.tag_name.as_deref().unwrap_or(""));
+    if is_value_property {
+        let text = match &attr.value {
+            Some(JSXAttributeValue::StringLiteral(lit)) => Some(lit.value.to_string()),
+            Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                container.expression.as_expression().and_then(static_attribute_text)
+            }
+            _ => None,
+        };
+        if let Some(text) = text {
+            let elem_id = &element_id(result, info, context);
+            let ast = context.ast();
+            let member = static_member(ast, attr.span, ident_expr(ast, attr.span, elem_id), "value");
+            if let Some(target) = expression_to_assignment_target(member) {
+                let value =
+                    ast.expression_string_literal(SPAN, ast.allocator.alloc_str(&text), None);
+                let assign =
+                    ast.expression_assignment(attr.span, AssignmentOperator::Assign, target, value);
+                if is_select_value {
+                    result.post_exprs.push(assign);
+                } else {
+                    result.exprs.push(assign);
+                }
+            }
+            return;
+        }
+    }
+
     // Regular attribute
+    let attr_key = &*attribute_name(&key, result.is_svg, result.has_custom_element);
     match &attr.value {
         Some(JSXAttributeValue::StringLiteral(lit)) => {
             // Static string attribute - inline in template
-            let attr_key = ALIASES.get(key.as_str()).copied().unwrap_or(key.as_str());
             let escaped = escape_html(&lit.value, true);
             result
                 .template
@@ -370,9 +842,39 @@ fn transform_attribute<'a>(
         Some(JSXAttributeValue::ExpressionContainer(container)) => {
             // Dynamic attribute - needs effect
             if let Some(expr) = container.expression.as_expression() {
-                if is_dynamic(expr) {
+                let markup = static_attribute_markup(attr_key, expr).filter(|_| !is_value_property);
+                if let Some(markup) = markup {
+                    // Literal strings, numbers and booleans go straight into
+                    // the template
+                    result.template.push_str(&markup);
+                } else if is_select_value || (is_form_state && is_dynamic(expr)) {
+                    let elem_id = &element_id(result, info, context);
+                    let binding = DynamicBinding {
+                        elem: elem_id.to_string(),
+                        key: key.clone(),
+                        value: context.clone_expr(expr),
+                        is_svg: result.is_svg,
+                        is_ce: result.has_custom_element,
+                        tag_name: result.tag_name.clone().unwrap_or_default(),
+                        span: attr.span,
+                    };
+                    let ast = context.ast();
+                    if let Some(helper) = binding_helper(&binding) {
+                        context.register_helper(helper);
+                    }
+                    let setter = generate_set_attr_expr(ast, attr.span, &binding, None);
+                    let setter = if is_dynamic(expr) {
+                        context.register_helper(&context.effect_wrapper);
+                        let effect = helper_ident(ast, &context.effect_wrapper);
+                        let arrow = arrow_zero_params_return_expr(ast, attr.span, setter);
+                        call_expr(ast, attr.span, effect, [arrow])
+                    } else {
+                        setter
+                    };
+                    result.post_exprs.push(setter);
+                } else if is_dynamic(expr) {
                     // Dynamic - wrap in effect
-                    let elem_id = elem_id.expect("dynamic attributes require an element id");
+                    let elem_id = &element_id(result, info, context);
                     result.dynamics.push(DynamicBinding {
                         elem: elem_id.to_string(),
                         key: key.clone(),
@@ -380,11 +882,12 @@ fn transform_attribute<'a>(
                         is_svg: result.is_svg,
                         is_ce: result.has_custom_element,
                         tag_name: result.tag_name.clone().unwrap_or_default(),
+                        span: attr.span,
                     });
                 } else {
                     // Static expression - we need to evaluate it at build time
                     // For now, treat as dynamic to be safe
-                    let elem_id = elem_id.expect("expression attributes require an element id");
+                    let elem_id = &element_id(result, info, context);
                     result.dynamics.push(DynamicBinding {
                         elem: elem_id.to_string(),
                         key: key.clone(),
@@ -392,20 +895,21 @@ fn transform_attribute<'a>(
                         is_svg: result.is_svg,
                         is_ce: result.has_custom_element,
                         tag_name: result.tag_name.clone().unwrap_or_default(),
+                        span: attr.span,
                     });
                 }
             }
         }
         None => {
             // Boolean attribute (e.g., disabled)
-            result.template.push_str(&format!(" {}", key));
+            result.template.push_str(&format!(" {}", attr_key));
         }
         _ => {}
     }
 }
 
 /// Transform ref attribute
-fn transform_ref<'a>(
+pub(crate) fn transform_ref<'a>(
     attr: &JSXAttribute<'a>,
     elem_id: &str,
     result: &mut TransformResult<'a>,
@@ -417,71 +921,93 @@ fn transform_ref<'a>(
         if let Some(expr) = container.expression.as_expression() {
             let ref_expr = context.clone_expr(expr);
             let elem = ident_expr(ast, attr.span, elem_id);
-            // Check if it's a function expression (arrow function or function expression)
-            if matches!(
-                expr,
-                Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_)
-            ) {
+            match expr.without_parentheses().get_inner_expression() {
                 // It's an inline callback: ref={el => myRef = el}
                 // Just invoke it with the element
-                result
-                    .exprs
-                    .push(call_expr(ast, attr.span, ref_expr, [elem]));
-            } else {
+                Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_) => {
+                    result
+                        .exprs
+                        .push(call_expr(ast, attr.span, ref_expr, [elem]));
+                }
                 // It's a variable reference: ref={myRef}
                 // Could be a signal setter or plain variable - check at runtime
-                if is_writable_ref_target(expr, ctx) {
-                    // Non-const variable: generate typeof check with assignment fallback
-                    let typeof_ref = ast.expression_unary(
-                        SPAN,
-                        UnaryOperator::Typeof,
-                        ref_expr.clone_in(ast.allocator),
-                    );
-                    let function_str = ast.expression_string_literal(
-                        SPAN,
-                        ast.allocator.alloc_str("function"),
-                        None,
-                    );
-                    let test = ast.expression_binary(
-                        SPAN,
-                        typeof_ref,
-                        BinaryOperator::StrictEquality,
-                        function_str,
-                    );
-
+                Expression::Identifier(_) => {
+                    if is_writable_ref_target(expr, ctx) {
+                        // Non-const variable: generate typeof check with assignment fallback
+                        let test = typeof_function_test(ast, ref_expr.clone_in(ast.allocator));
+                        let call = call_expr(
+                            ast,
+                            attr.span,
+                            ref_expr.clone_in(ast.allocator),
+                            [elem.clone_in(ast.allocator)],
+                        );
+                        let assign = ref_assignment(ast, ref_expr, elem);
+                        result
+                            .exprs
+                            .push(ast.expression_conditional(SPAN, test, call, assign));
+                    } else {
+                        // Const/import binding: must be a function (e.g., signal setter), just call it
+                        result
+                            .exprs
+                            .push(call_expr(ast, attr.span, ref_expr, [elem]));
+                    }
+                }
+                // ref={props.ref}, ref={refs.current}, ref={getRef()}: read the value once,
+                // call it through use() when it's a function, otherwise assign
+                // the element back to the member
+                inner => {
+                    context.register_helper("use");
+                    let ref_name = context.generate_uid("ref$");
+                    result.declarations.push(Declaration {
+                        name: ref_name.clone(),
+                        current: None,
+                        init: ref_expr.clone_in(ast.allocator),
+                    });
+                    let ref_ident = ident_expr(ast, attr.span, &ref_name);
+                    let test = typeof_function_test(ast, ref_ident.clone_in(ast.allocator));
                     let call = call_expr(
                         ast,
                         attr.span,
-                        ref_expr.clone_in(ast.allocator),
-                        [elem.clone_in(ast.allocator)],
+                        helper_ident(ast, "use"),
+                        [ref_ident, elem.clone_in(ast.allocator)],
                     );
-
-                    let assign =
-                        expression_to_assignment_target(ref_expr.clone_in(ast.allocator))
-                            .map(|target| {
-                                ast.expression_assignment(
-                                    SPAN,
-                                    AssignmentOperator::Assign,
-                                    target,
-                                    elem.clone_in(ast.allocator),
-                                )
-                            })
-                            .unwrap_or_else(|| ast.expression_identifier(SPAN, "undefined"));
-
-                    result
-                        .exprs
-                        .push(ast.expression_conditional(SPAN, test, call, assign));
-                } else {
-                    // Const/import binding: must be a function (e.g., signal setter), just call it
-                    result
-                        .exprs
-                        .push(call_expr(ast, attr.span, ref_expr, [elem]));
+                    let ref_call = if inner.is_member_expression() {
+                        let assign = ref_assignment(ast, ref_expr, elem);
+                        ast.expression_conditional(SPAN, test, call, assign)
+                    } else {
+                        ast.expression_logical(SPAN, test, LogicalOperator::And, call)
+                    };
+                    result.exprs.push(ref_call);
                 }
             }
         }
     }
 }
 
+/// `typeof value === "function"`
+fn typeof_function_test<'a>(ast: AstBuilder<'a>, value: Expression<'a>) -> Expression<'a> {
+    let typeof_value = ast.expression_unary(SPAN, UnaryOperator::Typeof, value);
+    let function_str =
+        ast.expression_string_literal(SPAN, ast.allocator.alloc_str("function"), None);
+    ast.expression_binary(
+        SPAN,
+        typeof_value,
+        BinaryOperator::StrictEquality,
+        function_str,
+    )
+}
+
+/// `target = elem`, or `undefined` when `target` can't be assigned to
+fn ref_assignment<'a>(
+    ast: AstBuilder<'a>,
+    target: Expression<'a>,
+    elem: Expression<'a>,
+) -> Expression<'a> {
+    expression_to_assignment_target(target)
+        .map(|target| ast.expression_assignment(SPAN, AssignmentOperator::Assign, target, elem))
+        .unwrap_or_else(|| ast.expression_identifier(SPAN, "undefined"))
+}
+
 pub(crate) fn is_writable_ref_target<'a>(expr: &Expression<'a>, ctx: &TraverseCtx<'a, ()>) -> bool {
     let Some(ident) = peel_identifier_reference(expr) else {
         return true;
@@ -523,35 +1049,42 @@ fn transform_event<'a>(
     options: &TransformOptions<'a>,
 ) {
     let ast = context.ast();
-    // Check for capture mode (onClickCapture -> click with capture=true)
-    let is_capture = key.ends_with("Capture");
-    let base_key = if is_capture {
-        &key[..key.len() - 7] // Remove "Capture" suffix
+    // `on:name` and `oncapture:name` keep the event name as written (so
+    // custom events like `on:custom-event` work) and are always attached
+    // with addEventListener; `onClickCapture` is the camelCase capture form
+    let (event_name, is_capture, namespaced) = if key.starts_with("oncapture:") {
+        (to_event_name(key), true, true)
+    } else if key.starts_with("on:") {
+        (to_event_name(key), false, true)
+    } else if let Some(base_key) = key.strip_suffix("Capture") {
+        (to_event_name(base_key), true, false)
     } else {
-        key
+        (to_event_name(key), false, false)
     };
-
-    let event_name = to_event_name(base_key);
+    result.has_hydratable_event = true;
 
     // Get the handler expression
-    let handler = attr
-        .value
-        .as_ref()
-        .and_then(|v| match v {
-            JSXAttributeValue::ExpressionContainer(container) => {
-                container.expression.as_expression()
-            }
-            _ => None,
-        })
-        .map(|e| context.clone_expr(e))
-        .unwrap_or_else(|| ast.expression_identifier(SPAN, "undefined"));
-
-    // on: prefix forces non-delegation (direct addEventListener)
-    let force_no_delegate = key.starts_with("on:");
+    let value = attr.value.as_ref().and_then(|v| match v {
+        JSXAttributeValue::ExpressionContainer(container) => container.expression.as_expression(),
+        _ => None,
+    });
+
+    // `onClick={[handler, data]}` calls `handler(data, event)`
+    let (handler, data) = match value.map(|e| e.without_parentheses()) {
+        Some(Expression::ArrayExpression(array))
+            if (1..=2).contains(&array.elements.len())
+                && array.elements.iter().all(|e| e.is_expression()) =>
+        {
+            let mut elements = array.elements.iter().filter_map(|e| e.as_expression());
+            let handler = elements.next().map(|e| context.clone_expr(e));
+            (handler, elements.next().map(|e| context.clone_expr(e)))
+        }
+        _ => (value.map(|e| context.clone_expr(e)), None),
+    };
+    let handler = handler.unwrap_or_else(|| ast.expression_identifier(SPAN, "undefined"));
 
-    // Capture events cannot be delegated
-    // Check if this event should be delegated
-    let should_delegate = !force_no_delegate
+    // Namespaced and capture events are never delegated
+    let should_delegate = !namespaced
         && !is_capture
         && options.delegate_events
         && (DELEGATED_EVENTS.contains(event_name.as_str())
@@ -559,21 +1092,36 @@ fn transform_event<'a>(
 
     if should_delegate {
         context.register_delegate(&event_name);
-        let elem = ident_expr(ast, attr.span, elem_id);
-        let prop = format!("$${}", event_name);
-        let member = static_member(ast, attr.span, elem, &prop);
-        let Some(target) = expression_to_assignment_target(member) else {
-            return;
+        let mut assign = |prop: String, value: Expression<'a>| {
+            let elem = ident_expr(ast, attr.span, elem_id);
+            let member = static_member(ast, attr.span, elem, &prop);
+            if let Some(target) = expression_to_assignment_target(member) {
+                result.exprs.push(ast.expression_assignment(
+                    attr.span,
+                    AssignmentOperator::Assign,
+                    target,
+                    value,
+                ));
+            }
         };
-        result.exprs.push(ast.expression_assignment(
-            SPAN,
-            AssignmentOperator::Assign,
-            target,
-            handler,
-        ));
+        assign(format!("$${}", event_name), handler);
+        // The delegated listener passes `$$<event>Data` as the first argument
+        if let Some(data) = data {
+            assign(format!("$${}Data", event_name), data);
+        }
     } else {
+        let handler = match data {
+            Some(data) => {
+                // (e) => handler(data, e)
+                let param = context.local_name("e");
+                let event_arg = ident_expr(ast, attr.span, &param);
+                let call = call_expr(ast, attr.span, handler, [data, event_arg]);
+                param_arrow(ast, attr.span, &param, call)
+            }
+            None => handler,
+        };
         context.register_helper("addEventListener");
-        let callee = ident_expr(ast, attr.span, "addEventListener");
+        let callee = helper_ident(ast, "addEventListener");
         let elem = ident_expr(ast, attr.span, elem_id);
         let event = ast.expression_string_literal(SPAN, ast.allocator.alloc_str(&event_name), None);
         let capture = ast.expression_boolean_literal(SPAN, is_capture);
@@ -586,8 +1134,30 @@ fn transform_event<'a>(
     }
 }
 
+/// `(param) => expr`
+fn param_arrow<'a>(
+    ast: AstBuilder<'a>,
+    span: Span,
+    param: &str,
+    expr: Expression<'a>,
+) -> Expression<'a> {
+    let pattern = ast.binding_pattern_binding_identifier(SPAN, ast.allocator.alloc_str(param));
+    let params = ast.alloc_formal_parameters(
+        SPAN,
+        FormalParameterKind::ArrowFormalParameters,
+        ast.vec1(ast.plain_formal_parameter(SPAN, pattern)),
+        NONE,
+    );
+    let mut statements = ast.vec_with_capacity(1);
+    statements.push(Statement::ExpressionStatement(
+        ast.alloc_expression_statement(SPAN, expr),
+    ));
+    let body = ast.alloc_function_body(SPAN, ast.vec(), statements);
+    ast.expression_arrow_function(span, true, false, NONE, params, NONE, body)
+}
+
 /// Transform use: directive
-fn transform_directive<'a>(
+pub(crate) fn transform_directive<'a>(
     attr: &JSXAttribute<'a>,
     key: &str,
     elem_id: &str,
@@ -610,7 +1180,7 @@ fn transform_directive<'a>(
         .map(|e| arrow_zero_params_return_expr(ast, attr.span, context.clone_expr(e)))
         .unwrap_or_else(|| ast.expression_identifier(SPAN, "undefined"));
 
-    let callee = ident_expr(ast, attr.span, "use");
+    let callee = helper_ident(ast, "use");
     result.exprs.push(call_expr(
         ast,
         attr.span,
@@ -642,15 +1212,15 @@ fn transform_prop<'a>(
                 return;
             };
             let assign = ast.expression_assignment(
-                SPAN,
+                attr.span,
                 AssignmentOperator::Assign,
                 target,
                 context.clone_expr(expr),
             );
 
             if is_dynamic(expr) {
-                context.register_helper("effect");
-                let effect = ident_expr(ast, attr.span, "effect");
+                context.register_helper(&context.effect_wrapper);
+                let effect = helper_ident(ast, &context.effect_wrapper);
                 let arrow = arrow_zero_params_return_expr(ast, attr.span, assign);
                 result
                     .exprs
@@ -675,7 +1245,7 @@ fn transform_attr<'a>(
 
     if let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value {
         if let Some(expr) = container.expression.as_expression() {
-            context.register_helper("effect");
+            context.register_helper(&context.effect_wrapper);
             context.register_helper("setAttribute");
             let elem = ident_expr(ast, attr.span, elem_id);
             let set_attr = static_member(ast, attr.span, elem, "setAttribute");
@@ -683,7 +1253,7 @@ fn transform_attr<'a>(
                 ast.expression_string_literal(SPAN, ast.allocator.alloc_str(attr_name), None);
             let call = call_expr(ast, attr.span, set_attr, [name, context.clone_expr(expr)]);
             let arrow = arrow_zero_params_return_expr(ast, attr.span, call);
-            let effect = ident_expr(ast, attr.span, "effect");
+            let effect = helper_ident(ast, &context.effect_wrapper);
             result
                 .exprs
                 .push(call_expr(ast, attr.span, effect, [arrow]));
@@ -697,10 +1267,119 @@ fn transform_attr<'a>(
     }
 }
 
+/// Whether a `bool:` attribute is present, when its value is known at
+/// compile time: a literal or no value at all
+fn static_presence(attr: &JSXAttribute<'_>) -> Option<bool> {
+    match &attr.value {
+        Some(JSXAttributeValue::StringLiteral(lit)) => Some(!lit.value.is_empty()),
+        Some(JSXAttributeValue::ExpressionContainer(container)) => container
+            .expression
+            .as_expression()
+            .and_then(static_truthiness),
+        None => Some(true),
+        _ => None,
+    }
+}
+
+/// Transform bool: prefix (attribute presence follows the value's truthiness)
+fn transform_bool_attribute<'a>(
+    attr: &JSXAttribute<'a>,
+    name: &str,
+    info: &TransformInfo,
+    result: &mut TransformResult<'a>,
+    context: &BlockContext<'a>,
+) {
+    // A literal value decides presence in the template
+    if let Some(present) = static_presence(attr) {
+        if present {
+            result.template.push_str(&format!(" {}", name));
+        }
+        return;
+    }
+    let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value else {
+        return;
+    };
+    let Some(expr) = container.expression.as_expression() else {
+        return;
+    };
+    let elem_id = &element_id(result, info, context);
+    let binding = DynamicBinding {
+        elem: elem_id.to_string(),
+        key: format!("bool:{}", name),
+        value: context.clone_expr(expr),
+        is_svg: result.is_svg,
+        is_ce: result.has_custom_element,
+        tag_name: result.tag_name.clone().unwrap_or_default(),
+        span: attr.span,
+    };
+    if is_dynamic(expr) {
+        result.dynamics.push(binding);
+    } else {
+        context.register_helper("setBoolAttribute");
+        let setter = generate_set_attr_expr(context.ast(), attr.span, &binding, None);
+        result.exprs.push(setter);
+    }
+}
+
+/// Transform class: prefix (`classList.toggle` of one class name)
+fn transform_class_toggle<'a>(
+    attr: &JSXAttribute<'a>,
+    name: &str,
+    elem_id: &str,
+    result: &mut TransformResult<'a>,
+    context: &BlockContext<'a>,
+) {
+    let ast = context.ast();
+    let (value, dynamic) = match &attr.value {
+        Some(JSXAttributeValue::ExpressionContainer(container)) => {
+            let Some(expr) = container.expression.as_expression() else {
+                return;
+            };
+            match static_truthiness(expr) {
+                Some(on) => (ast.expression_boolean_literal(SPAN, on), false),
+                // toggle() takes a boolean: anything else would flip the class
+                None if matches!(expr.without_parentheses(), Expression::UnaryExpression(_)) => {
+                    (context.clone_expr(expr), is_dynamic(expr))
+                }
+                None => {
+                    let not = ast.expression_unary(
+                        attr.span,
+                        UnaryOperator::LogicalNot,
+                        context.clone_expr(expr),
+                    );
+                    let value = ast.expression_unary(attr.span, UnaryOperator::LogicalNot, not);
+                    (value, is_dynamic(expr))
+                }
+            }
+        }
+        Some(JSXAttributeValue::StringLiteral(lit)) => {
+            let on = !lit.value.is_empty();
+            (ast.expression_boolean_literal(SPAN, on), false)
+        }
+        None => (ast.expression_boolean_literal(SPAN, true), false),
+        _ => return,
+    };
+    let binding = DynamicBinding {
+        elem: elem_id.to_string(),
+        key: format!("class:{}", name),
+        value,
+        is_svg: result.is_svg,
+        is_ce: result.has_custom_element,
+        tag_name: result.tag_name.clone().unwrap_or_default(),
+        span: attr.span,
+    };
+    if dynamic {
+        result.dynamics.push(binding);
+    } else {
+        let setter = generate_set_attr_expr(ast, attr.span, &binding, None);
+        result.exprs.push(setter);
+    }
+}
+
 /// Transform style attribute
 fn transform_style<'a>(
     attr: &JSXAttribute<'a>,
-    elem_id: Option<&str>,
+    info: &TransformInfo,
     result: &mut TransformResult<'a>,
     context: &BlockContext<'a>,
 ) {
@@ -726,20 +1405,27 @@ fn transform_style<'a>(
                 }
 
                 // Dynamic style - use style helper
-                let elem_id = elem_id.expect("style helper requires an element id");
+                let elem_id = &element_id(result, info, context);
                 context.register_helper("style");
                 let elem = ident_expr(ast, attr.span, elem_id);
-                let style = ident_expr(ast, attr.span, "style");
-                let call = call_expr(ast, attr.span, style, [elem, context.clone_expr(expr)]);
+                let style = helper_ident(ast, "style");
+                let value = context.clone_expr(expr);
                 if is_dynamic(expr) {
-                    context.register_helper("effect");
-                    let arrow = arrow_zero_params_return_expr(ast, attr.span, call);
-                    let effect = ident_expr(ast, attr.span, "effect");
+                    // The helper returns what it applied, which the effect
+                    // hands back next run: effect((_p$) => style(el, v, _p$))
+                    context.register_helper(&context.effect_wrapper);
+                    let prev = context.local_name("_p$");
+                    let prev_expr = ident_expr(ast, SPAN, &prev);
+                    let call = call_expr(ast, attr.span, style, [elem, value, prev_expr]);
+                    let arrow = param_arrow(ast, attr.span, &prev, call);
+                    let effect = helper_ident(ast, &context.effect_wrapper);
                     result
                         .exprs
                         .push(call_expr(ast, attr.span, effect, [arrow]));
                 } else {
-                    result.exprs.push(call);
+                    result
+                        .exprs
+                        .push(call_expr(ast, attr.span, style, [elem, value]));
                 }
             }
         }
@@ -853,7 +1539,7 @@ fn needs_px_suffix(prop: &str) -> bool {
     !unitless.contains(&prop)
 }
 
-/// Transform innerHTML/textContent
+/// Transform innerHTML/textContent/innerText
 fn transform_inner_content<'a>(
     attr: &JSXAttribute<'a>,
     key: &str,
@@ -870,16 +1556,16 @@ fn transform_inner_content<'a>(
                 return;
             };
             let assign = ast.expression_assignment(
-                SPAN,
+                attr.span,
                 AssignmentOperator::Assign,
                 target,
                 context.clone_expr(expr),
             );
 
             if is_dynamic(expr) {
-                context.register_helper("effect");
+                context.register_helper(&context.effect_wrapper);
                 let arrow = arrow_zero_params_return_expr(ast, attr.span, assign);
-                let effect = ident_expr(ast, attr.span, "effect");
+                let effect = helper_ident(ast, &context.effect_wrapper);
                 result
                     .exprs
                     .push(call_expr(ast, attr.span, effect, [arrow]));
@@ -901,7 +1587,22 @@ fn transform_inner_content<'a>(
                 None,
             );
             result.exprs.push(ast.expression_assignment(
-                SPAN,
+                attr.span,
+                AssignmentOperator::Assign,
+                target,
+                value,
+            ));
+        } else if key == "innerText" {
+            // innerText depends on layout (line breaks become `<br>`), so it
+            // is assigned at runtime rather than written into the template
+            let elem = ident_expr(ast, attr.span, elem_id);
+            let member = static_member(ast, attr.span, elem, "innerText");
+            let Some(target) = expression_to_assignment_target(member) else {
+                return;
+            };
+            let value = ast.expression_string_literal(SPAN, lit.value, None);
+            result.exprs.push(ast.expression_assignment(
+                attr.span,
                 AssignmentOperator::Assign,
                 target,
                 value,
@@ -932,55 +1633,84 @@ fn transform_children<'a, 'b>(
         path
     }
 
-    fn child_accessor<'a>(
+    /// Position in the children being walked
+    #[derive(Default)]
+    struct Cursor {
+        /// Index of the next node among the parent's children
+        node_index: usize,
+        last_was_text: bool,
+        /// When hydrating, the end of the last marker and its index: the
+        /// server-rendered content before it has no fixed length, so later
+        /// siblings are reached from the marker instead of the parent
+        anchor: Option<(String, usize)>,
+    }
+
+    /// The node at the cursor
+    fn node_accessor<'a>(
         ast: AstBuilder<'a>,
         span: Span,
         parent_id: &str,
-        node_index: usize,
+        cursor: &Cursor,
     ) -> Expression<'a> {
-        let mut expr = static_member(ast, span, ident_expr(ast, span, parent_id), "firstChild");
-        for _ in 0..node_index {
+        let (mut expr, steps) = match &cursor.anchor {
+            Some((anchor, index)) => (ident_expr(ast, span, anchor), cursor.node_index - index),
+            None => (
+                static_member(ast, span, ident_expr(ast, span, parent_id), "firstChild"),
+                cursor.node_index,
+            ),
+        };
+        for _ in 0..steps {
             expr = static_member(ast, span, expr, "nextSibling");
         }
         expr
     }
 
-    /// Check if children list is a single dynamic expression (no markers needed)
-    fn is_single_dynamic_child(children: &[oxc_ast::ast::JSXChild<'_>]) -> bool {
-        let mut expr_count = 0;
-        let mut other_content = false;
-
-        for child in children {
-            match child {
-                oxc_ast::ast::JSXChild::Text(text) => {
-                    let content = common::expression::trim_whitespace(&text.value);
-                    if !content.is_empty() {
-                        other_content = true;
-                    }
-                }
-                oxc_ast::ast::JSXChild::Element(_) => {
-                    other_content = true;
-                }
-                oxc_ast::ast::JSXChild::ExpressionContainer(container) => {
-                    if container.expression.as_expression().is_some() {
-                        expr_count += 1;
-                    }
-                }
-                oxc_ast::ast::JSXChild::Fragment(fragment) => {
-                    // Recurse into fragments
-                    if !is_single_dynamic_child(&fragment.children) {
-                        other_content = true;
-                    } else {
-                        expr_count += 1;
-                    }
-                }
-                _ => {}
-            }
+    /// Add the marker an inserted child goes before at the cursor, and
+    /// return the `insert` arguments that point at it
+    fn push_marker<'a>(
+        result: &mut TransformResult<'a>,
+        context: &BlockContext<'a>,
+        span: Span,
+        parent_id: &str,
+        cursor: &mut Cursor,
+    ) -> Vec<Expression<'a>> {
+        let ast = context.ast();
+        let marker_id = context.generate_uid("el$");
+        if !context.hydratable {
+            result.template.push_str("<!>");
+            result.template_with_closing_tags.push_str("<!>");
+            result.declarations.push(Declaration {
+                name: marker_id.clone(),
+                current: None,
+                init: node_accessor(ast, span, parent_id, cursor),
+            });
+            cursor.node_index += 1;
+            return vec![ident_expr(ast, span, &marker_id)];
         }
 
-        expr_count == 1 && !other_content
+        // `<!$>` and `<!/>` bracket the server-rendered content, which the
+        // insert starts from
+        result.template.push_str("<!$><!/>");
+        result.template_with_closing_tags.push_str("<!$><!/>");
+        cursor.node_index += 1;
+        let current_id = context.generate_uid("co$");
+        context.register_helper("getNextMarker");
+        let callee = helper_ident(ast, "getNextMarker");
+        let start = node_accessor(ast, span, parent_id, cursor);
+        result.declarations.push(Declaration {
+            name: marker_id.clone(),
+            current: Some(current_id.clone()),
+            init: call_expr(ast, span, callee, [start]),
+        });
+        cursor.anchor = Some((marker_id.clone(), cursor.node_index));
+        cursor.node_index += 1;
+        vec![
+            ident_expr(ast, span, &marker_id),
+            ident_expr(ast, span, &current_id),
+        ]
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn transform_children_list<'a, 'b>(
         children: &[oxc_ast::ast::JSXChild<'a>],
         result: &mut TransformResult<'a>,
@@ -989,33 +1719,43 @@ fn transform_children<'a, 'b>(
         options: &TransformOptions<'a>,
         transform_child: ChildTransformer<'a, 'b>,
         ctx: &TraverseCtx<'a, ()>,
-        node_index: &mut usize,
-        last_was_text: &mut bool,
+        cursor: &mut Cursor,
         single_dynamic: bool,
     ) {
         let ast = context.ast();
         for child in children {
-            match child {
+            // Text and literal children next to each other share one text node
+            let text = match child {
                 oxc_ast::ast::JSXChild::Text(text) => {
-                    let content = common::expression::trim_whitespace(&text.value);
-                    if !content.is_empty() {
-                        let escaped = escape_html(&content, false);
-                        result.template.push_str(&escaped);
-                        result.template_with_closing_tags.push_str(&escaped);
-                        if !*last_was_text {
-                            *node_index += 1;
-                            *last_was_text = true;
-                        }
+                    Some(common::expression::decode_jsx_text(&text.value))
+                }
+                oxc_ast::ast::JSXChild::ExpressionContainer(container) => {
+                    container.expression.as_expression().and_then(static_child_text)
+                }
+                _ => None,
+            };
+            if let Some(text) = text {
+                if !text.is_empty() {
+                    let escaped = escape_html(&text, false);
+                    result.template.push_str(&escaped);
+                    result.template_with_closing_tags.push_str(&escaped);
+                    if !cursor.last_was_text {
+                        cursor.node_index += 1;
+                        cursor.last_was_text = true;
                     }
                 }
+                continue;
+            }
+            match child {
                 oxc_ast::ast::JSXChild::Element(child_elem) => {
                     let child_tag = common::get_tag_name(child_elem);
 
                     if is_component(&child_tag) {
-                        *last_was_text = false;
+                        cursor.last_was_text = false;
                         if let (Some(parent_id), Some(child_result)) =
-                            (result.id.as_deref(), transform_child(child))
+                            (result.id.clone(), transform_child(child))
                         {
+                            let parent_id = parent_id.as_str();
                             if child_result.exprs.is_empty() {
                                 continue;
                             }
@@ -1024,7 +1764,7 @@ fn transform_children<'a, 'b>(
 
                             // Single dynamic child: no marker needed
                             if single_dynamic {
-                                let callee = ident_expr(ast, child_elem.span, "insert");
+                                let callee = helper_ident(ast, "insert");
                                 let parent = ident_expr(ast, child_elem.span, parent_id);
                                 let child_expr = child_result.exprs[0].clone_in(ast.allocator);
                                 result.exprs.push(call_expr(
@@ -1034,42 +1774,40 @@ fn transform_children<'a, 'b>(
                                     [parent, child_expr],
                                 ));
                             } else {
-                                result.template.push_str("<!>");
-                                result.template_with_closing_tags.push_str("<!>");
-
-                                let marker_id = context.generate_uid("el$");
-                                result.declarations.push(Declaration {
-                                    name: marker_id.clone(),
-                                    init: child_accessor(
-                                        ast,
-                                        child_elem.span,
-                                        parent_id,
-                                        *node_index,
-                                    ),
-                                });
-
-                                let callee = ident_expr(ast, child_elem.span, "insert");
+                                let marker = push_marker(
+                                    result,
+                                    context,
+                                    child_elem.span,
+                                    parent_id,
+                                    cursor,
+                                );
+                                let callee = helper_ident(ast, "insert");
                                 let parent = ident_expr(ast, child_elem.span, parent_id);
                                 let child_expr = child_result.exprs[0].clone_in(ast.allocator);
-                                let marker = ident_expr(ast, child_elem.span, &marker_id);
-                                result.exprs.push(call_expr(
-                                    ast,
-                                    child_elem.span,
-                                    callee,
-                                    [parent, child_expr, marker],
-                                ));
-
-                                *node_index += 1;
+                                let args = [parent, child_expr].into_iter().chain(marker);
+                                result
+                                    .exprs
+                                    .push(call_expr(ast, child_elem.span, callee, args));
                             }
                         }
                         continue;
                     }
 
-                    *last_was_text = false;
+                    cursor.last_was_text = false;
+                    let (root_id, path) = match &cursor.anchor {
+                        Some((anchor, index)) => (
+                            Some(anchor.clone()),
+                            vec!["nextSibling".to_string(); cursor.node_index - index],
+                        ),
+                        None => (
+                            info.root_id.clone(),
+                            child_path(&info.path, cursor.node_index),
+                        ),
+                    };
                     let child_info = TransformInfo {
                         top_level: false,
-                        path: child_path(&info.path, *node_index),
-                        root_id: info.root_id.clone(),
+                        path,
+                        root_id,
                         ..info.clone()
                     };
 
@@ -1096,21 +1834,41 @@ fn transform_children<'a, 'b>(
                     result.declarations.extend(child_result.declarations);
                     result.exprs.extend(child_result.exprs);
                     result.dynamics.extend(child_result.dynamics);
+                    result.post_exprs.extend(child_result.post_exprs);
                     result.has_custom_element |= child_result.has_custom_element;
+                    result.has_hydratable_event |= child_result.has_hydratable_event;
 
-                    *node_index += 1;
+                    cursor.node_index += 1;
                 }
-                oxc_ast::ast::JSXChild::ExpressionContainer(container) => {
-                    if let (Some(parent_id), Some(expr)) =
-                        (result.id.as_deref(), container.expression.as_expression())
-                    {
-                        *last_was_text = false;
+                oxc_ast::ast::JSXChild::ExpressionContainer(_)
+                | oxc_ast::ast::JSXChild::Spread(_) => {
+                    // `{...items}` inserts the array like any other child
+                    // expression; insert flattens it at runtime
+                    let inserted = match child {
+                        oxc_ast::ast::JSXChild::ExpressionContainer(container) => container
+                            .expression
+                            .as_expression()
+                            .map(|expr| (container.span, expr)),
+                        oxc_ast::ast::JSXChild::Spread(spread) => {
+                            Some((spread.span, &spread.expression))
+                        }
+                        _ => None,
+                    };
+                    if let (Some(parent_id), Some((span, expr))) = (result.id.clone(), inserted) {
+                        let parent_id = parent_id.as_str();
+                        cursor.last_was_text = false;
                         context.register_helper("insert");
 
-                        let insert_value = if is_dynamic(expr) {
+                        let is_condition = matches!(
+                            expr.without_parentheses(),
+                            Expression::ConditionalExpression(_) | Expression::LogicalExpression(_)
+                        );
+                        let insert_value = if options.wrap_conditionals && is_condition {
+                            transform_condition(context.clone_expr(expr), span, context)
+                        } else if is_dynamic(expr) {
                             arrow_zero_params_return_expr(
                                 ast,
-                                container.span,
+                                span,
                                 context.clone_expr(expr),
                             )
                         } else {
@@ -1119,35 +1877,20 @@ fn transform_children<'a, 'b>(
 
                         // Single dynamic child: no marker needed
                         if single_dynamic {
-                            let callee = ident_expr(ast, container.span, "insert");
-                            let parent = ident_expr(ast, container.span, parent_id);
+                            let callee = helper_ident(ast, "insert");
+                            let parent = ident_expr(ast, span, parent_id);
                             result.exprs.push(call_expr(
                                 ast,
-                                container.span,
+                                span,
                                 callee,
                                 [parent, insert_value],
                             ));
                         } else {
-                            result.template.push_str("<!>");
-                            result.template_with_closing_tags.push_str("<!>");
-
-                            let marker_id = context.generate_uid("el$");
-                            result.declarations.push(Declaration {
-                                name: marker_id.clone(),
-                                init: child_accessor(ast, container.span, parent_id, *node_index),
-                            });
-
-                            let callee = ident_expr(ast, container.span, "insert");
-                            let parent = ident_expr(ast, container.span, parent_id);
-                            let marker = ident_expr(ast, container.span, &marker_id);
-                            result.exprs.push(call_expr(
-                                ast,
-                                container.span,
-                                callee,
-                                [parent, insert_value, marker],
-                            ));
-
-                            *node_index += 1;
+                            let marker = push_marker(result, context, span, parent_id, cursor);
+                            let callee = helper_ident(ast, "insert");
+                            let parent = ident_expr(ast, span, parent_id);
+                            let args = [parent, insert_value].into_iter().chain(marker);
+                            result.exprs.push(call_expr(ast, span, callee, args));
                         }
                     }
                 }
@@ -1160,8 +1903,7 @@ fn transform_children<'a, 'b>(
                         options,
                         transform_child,
                         ctx,
-                        node_index,
-                        last_was_text,
+                        cursor,
                         single_dynamic,
                     );
                 }
@@ -1170,19 +1912,26 @@ fn transform_children<'a, 'b>(
         }
     }
 
-    let mut node_index = 0usize;
-    let mut last_was_text = false;
+    // Children's deferred assignments run before the element's own, as an
+    // option's value has to be there before its select's
+    let own_post_exprs = std::mem::take(&mut result.post_exprs);
+    let mut cursor = Cursor::default();
     let single_dynamic = is_single_dynamic_child(&element.children);
-    transform_children_list(
-        &element.children,
-        result,
-        info,
-        context,
-        options,
-        transform_child,
-        ctx,
-        &mut node_index,
-        &mut last_was_text,
-        single_dynamic,
-    );
+    let tag_name = result.tag_name.as_deref().unwrap_or_default();
+    // `<foreignObject>` holds HTML again
+    let svg_children = result.is_svg && tag_name != "foreignObject";
+    context.within_parent(svg_children, || {
+        transform_children_list(
+            &element.children,
+            result,
+            info,
+            context,
+            options,
+            transform_child,
+            ctx,
+            &mut cursor,
+            single_dynamic,
+        )
+    });
+    result.post_exprs.extend(own_post_exprs);
 }
@@ -6,8 +6,12 @@ use indexmap::IndexSet;
 use oxc_allocator::{Allocator, CloneIn};
 use oxc_ast::ast::{Expression, JSXChild};
 use oxc_ast::AstBuilder;
+use oxc_semantic::Scoping;
 use oxc_span::Span;
 use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+use common::{is_svg_element, TransformOptions};
 
 /// Function type for transforming child JSX elements
 pub type ChildTransformer<'a, 'b> = &'b dyn Fn(&JSXChild<'a>) -> Option<TransformResult<'a>>;
@@ -59,11 +63,18 @@ pub struct TransformResult<'a> {
 
     /// Individual child codes for fragments (when children need to be in an array)
     pub child_results: Vec<TransformResult<'a>>,
+
+    /// Whether an element binds event handlers, which hydration has to
+    /// replay once the tree is hydrated
+    pub has_hydratable_event: bool,
 }
 
 /// A variable declaration
 pub struct Declaration<'a> {
     pub name: String,
+    /// Destructures `[name, current]` instead: the end of a hydration marker
+    /// and the server-rendered nodes before it
+    pub current: Option<String>,
     pub init: Expression<'a>,
 }
 
@@ -75,6 +86,8 @@ pub struct DynamicBinding<'a> {
     pub is_svg: bool,
     pub is_ce: bool,
     pub tag_name: String,
+    /// Span of the attribute, which the effect setting it maps to
+    pub span: Span,
 }
 
 /// Context for the current block being transformed
@@ -91,35 +104,116 @@ pub struct BlockContext<'a> {
     /// Delegated events
     pub delegates: RefCell<IndexSet<String>>,
 
-    /// Variable counter for unique names
-    pub var_counter: RefCell<usize>,
+    /// Last number used for each generated name prefix
+    uid_counters: RefCell<HashMap<String, usize>>,
+
+    /// Names bound or referenced anywhere in the program, which generated
+    /// names must not shadow
+    reserved_names: RefCell<HashSet<String>>,
+
+    /// For each native element whose children are being transformed,
+    /// innermost last: whether those children are in the SVG namespace
+    svg_parents: RefCell<Vec<bool>>,
+
+    /// Whether output hydrates server-rendered markup instead of cloning
+    /// templates
+    pub hydratable: bool,
+
+    /// Helper that runs reactive bindings (`effect_wrapper`)
+    pub effect_wrapper: String,
+
+    /// Helper that memoizes derived values (`memo_wrapper`)
+    pub memo_wrapper: String,
 
     allocator: &'a Allocator,
 }
 
 pub struct TemplateInfo {
+    /// Name of the hoisted template variable (e.g. `_tmpl$1`)
+    pub name: String,
     pub content: String,
     pub is_svg: bool,
     pub span: Span,
 }
 
 impl<'a> BlockContext<'a> {
-    pub fn new(allocator: &'a Allocator) -> Self {
+    pub fn new(allocator: &'a Allocator, options: &TransformOptions<'_>) -> Self {
         Self {
             template: RefCell::new(String::new()),
             templates: RefCell::new(Vec::new()),
             helpers: RefCell::new(IndexSet::new()),
             delegates: RefCell::new(IndexSet::new()),
-            var_counter: RefCell::new(0),
+            uid_counters: RefCell::new(HashMap::new()),
+            reserved_names: RefCell::new(HashSet::new()),
+            svg_parents: RefCell::new(Vec::new()),
+            hydratable: options.hydratable,
+            effect_wrapper: options.effect_wrapper.to_string(),
+            memo_wrapper: options.memo_wrapper.to_string(),
             allocator,
         }
     }
 
-    /// Generate a unique variable name
+    /// Reserve every name bound or referenced in the program (`scoping`),
+    /// so that generated names never shadow a user binding or a global
+    pub fn reserve_names(&self, scoping: &Scoping) {
+        let mut reserved = self.reserved_names.borrow_mut();
+        reserved.extend(scoping.symbol_names().map(str::to_string));
+        reserved.extend(
+            scoping
+                .root_unresolved_references()
+                .keys()
+                .map(|name| name.to_string()),
+        );
+    }
+
+    /// Generate a unique variable name `_{prefix}{n}`, numbered per prefix
+    /// and skipping names the program already uses
     pub fn generate_uid(&self, prefix: &str) -> String {
-        let mut counter = self.var_counter.borrow_mut();
-        *counter += 1;
-        format!("_{}{}", prefix, *counter)
+        let mut counters = self.uid_counters.borrow_mut();
+        let counter = counters.entry(prefix.to_string()).or_default();
+        let reserved = self.reserved_names.borrow();
+        loop {
+            *counter += 1;
+            let name = format!("_{}{}", prefix, *counter);
+            if !reserved.contains(&name) {
+                return name;
+            }
+        }
+    }
+
+    /// `name` for a local of generated code, numbered from 2 when the
+    /// program already uses it
+    pub fn local_name(&self, name: &str) -> String {
+        let reserved = self.reserved_names.borrow();
+        if !reserved.contains(name) {
+            return name.to_string();
+        }
+        (2..)
+            .map(|n| format!("{}{}", name, n))
+            .find(|candidate| !reserved.contains(candidate))
+            .expect("unbounded range")
+    }
+
+    /// Whether an element named `tag` is created in the SVG namespace:
+    /// `<svg>` always is, other elements follow their parent, and without a
+    /// parent element the tag name decides
+    pub fn is_svg(&self, tag: &str) -> bool {
+        tag == "svg"
+            || self
+                .svg_parents
+                .borrow()
+                .last()
+                .copied()
+                .unwrap_or_else(|| is_svg_element(tag))
+    }
+
+    /// Run `f` for the children of an element, which are in the SVG
+    /// namespace when `svg_children`
+    pub fn within_parent<T>(&self, svg_children: bool, f: impl FnOnce() -> T) -> T {
+        self.svg_parents.borrow_mut().push(svg_children);
+        let result = f();
+        self.svg_parents.borrow_mut().pop();
+        result
     }
 
     /// Register a helper import
@@ -132,17 +226,27 @@ impl<'a> BlockContext<'a> {
         self.delegates.borrow_mut().insert(event.to_string());
     }
 
-    /// Push a template and return its index
-    pub fn push_template(&self, content: String, is_svg: bool, span: Span) -> usize {
+    /// Push a template and return the name of its variable. Identical
+    /// templates share one variable.
+    pub fn push_template(&self, content: String, is_svg: bool, span: Span) -> String {
         self.register_helper("template");
-        let mut templates = self.templates.borrow_mut();
-        let index = templates.len();
-        templates.push(TemplateInfo {
+        let existing = self
+            .templates
+            .borrow()
+            .iter()
+            .find(|template| template.content == content && template.is_svg == is_svg)
+            .map(|template| template.name.clone());
+        if let Some(name) = existing {
+            return name;
+        }
+        let name = self.generate_uid("tmpl$");
+        self.templates.borrow_mut().push(TemplateInfo {
+            name: name.clone(),
             content,
             is_svg,
             span,
         });
-        index
+        name
     }
 
     pub fn ast(&self) -> AstBuilder<'a> {
@@ -1,8 +1,12 @@
 pub mod component;
+pub mod condition;
+pub mod construct;
 pub mod element;
 pub mod ir;
 pub mod output;
+pub mod runtime;
 pub mod template;
 pub mod transform;
+pub mod universal;
 
 pub use transform::*;
@@ -1,10 +1,12 @@
 use oxc_allocator::CloneIn;
 use oxc_ast::ast::{
-    Argument, ArrayExpressionElement, Expression, FormalParameterKind, Statement,
+    Argument, ArrayExpressionElement, BindingPattern, Expression, FormalParameterKind, Statement,
     VariableDeclarationKind,
 };
 use oxc_ast::{AstBuilder, NONE};
-use oxc_span::{Span, SPAN};
+use oxc_span::{GetSpan, Span, SPAN};
+
+use common::helper_ident;
 
 use crate::ir::{BlockContext, TransformResult};
 
@@ -43,16 +45,20 @@ fn call_expr<'a>(
     )
 }
 
+fn binding<'a>(ast: AstBuilder<'a>, name: &str) -> BindingPattern<'a> {
+    ast.binding_pattern_binding_identifier(SPAN, ast.allocator.alloc_str(name))
+}
+
 fn const_decl_stmt<'a>(
     ast: AstBuilder<'a>,
     span: Span,
-    name: &str,
+    id: BindingPattern<'a>,
     init: Expression<'a>,
 ) -> Statement<'a> {
     let declarator = ast.variable_declarator(
-        span,
+        SPAN,
         VariableDeclarationKind::Const,
-        ast.binding_pattern_binding_identifier(span, ast.allocator.alloc_str(name)),
+        id,
         NONE,
         Some(init),
         false,
@@ -65,22 +71,29 @@ fn const_decl_stmt<'a>(
     ))
 }
 
-fn arrow_zero_params_body<'a>(
+/// `() => expr`, or `(param) => expr`
+fn arrow_body<'a>(
     ast: AstBuilder<'a>,
     span: Span,
+    param: Option<&str>,
     expr: Expression<'a>,
 ) -> Expression<'a> {
+    let mut items = ast.vec();
+    if let Some(param) = param {
+        let pattern = ast.binding_pattern_binding_identifier(SPAN, ast.allocator.alloc_str(param));
+        items.push(ast.plain_formal_parameter(SPAN, pattern));
+    }
     let params = ast.alloc_formal_parameters(
-        span,
+        SPAN,
         FormalParameterKind::ArrowFormalParameters,
-        ast.vec(),
+        items,
         NONE,
     );
     let mut statements = ast.vec_with_capacity(1);
     statements.push(Statement::ExpressionStatement(
-        ast.alloc_expression_statement(span, expr),
+        ast.alloc_expression_statement(SPAN, expr),
     ));
-    let body = ast.alloc_function_body(span, ast.vec(), statements);
+    let body = ast.alloc_function_body(SPAN, ast.vec(), statements);
     ast.expression_arrow_function(span, true, false, NONE, params, NONE, body)
 }
 
@@ -89,7 +102,9 @@ pub fn build_dom_output_expr<'a>(
     context: &BlockContext<'a>,
 ) -> Expression<'a> {
     let ast = context.ast();
-    let gen_span = SPAN;
+    // Statements and calls map to the JSX they were generated from;
+    // generated names and the template stay unspanned
+    let span = result.span;
 
     // Fragment with mixed children (array output)
     if !result.child_results.is_empty() {
@@ -98,51 +113,85 @@ pub fn build_dom_output_expr<'a>(
             let expr = build_dom_output_expr(child, context);
             elements.push(ArrayExpressionElement::from(expr));
         }
-        return ast.expression_array(gen_span, elements);
+        return ast.expression_array(span, elements);
     }
 
     // Text-only result
     if result.text && !result.template.is_empty() {
         return ast.expression_string_literal(
-            gen_span,
+            span,
             ast.allocator.alloc_str(&result.template),
             None,
         );
     }
 
-    // Template-backed result
-    if !result.template.is_empty() && !result.skip_template {
-        // Push template and get variable name
-        // The template string is generated code; don't attribute it to the source with spans.
-        let tmpl_idx = context.push_template(result.template.clone(), result.is_svg, gen_span);
-        let tmpl_var = format!("_tmpl${}", tmpl_idx + 1);
-
+    // Template-backed result, or an element a universal renderer creates in
+    // its first declaration
+    let renderer_element = result.template.is_empty() && result.id.is_some();
+    if (!result.template.is_empty() && !result.skip_template) || renderer_element {
         // Use the generated element ID when available (matches expression wiring).
         // Fall back to a local _el$ when the element didn't require a stable ID.
-        let elem_var = result.id.clone().unwrap_or_else(|| "_el$".to_string());
+        let elem_var = result
+            .id
+            .clone()
+            .unwrap_or_else(|| context.local_name("_el$"));
 
         let mut statements = ast.vec();
 
-        // const _el$ = _tmpl$1.cloneNode(true);
-        let clone_call = call_expr(
-            ast,
-            gen_span,
-            static_member(
+        if !renderer_element {
+            // Push template and get variable name
+            // The template string is generated code; don't attribute it to the source with spans.
+            let tmpl_var = context.push_template(result.template.clone(), result.is_svg, SPAN);
+
+            // const _el$ = _tmpl$1.cloneNode(true);
+            // or, hydrating, const _el$ = _$getNextElement(_tmpl$1);
+            let clone_call = if context.hydratable {
+                context.register_helper("getNextElement");
+                let callee = helper_ident(ast, "getNextElement");
+                call_expr(ast, span, callee, [ident_expr(ast, SPAN, &tmpl_var)])
+            } else {
+                call_expr(
+                    ast,
+                    span,
+                    static_member(ast, SPAN, ident_expr(ast, SPAN, &tmpl_var), "cloneNode"),
+                    [ast.expression_boolean_literal(SPAN, true)],
+                )
+            };
+
+            // A fully static tree needs no setup: clone the hoisted template
+            // directly instead of wrapping the clone in an IIFE
+            if result.declarations.is_empty()
+                && result.exprs.is_empty()
+                && result.dynamics.is_empty()
+                && result.post_exprs.is_empty()
+            {
+                return clone_call;
+            }
+            statements.push(const_decl_stmt(
                 ast,
-                gen_span,
-                ident_expr(ast, gen_span, &tmpl_var),
-                "cloneNode",
-            ),
-            [ast.expression_boolean_literal(gen_span, true)],
-        );
-        statements.push(const_decl_stmt(ast, gen_span, &elem_var, clone_call));
+                span,
+                binding(ast, &elem_var),
+                clone_call,
+            ));
+        }
 
         // const child = _el$.firstChild.nextSibling;
+        // const [_el$3, _co$1] = _$getNextMarker(_el$2.nextSibling);
         for decl in &result.declarations {
+            let id = match &decl.current {
+                Some(current) => {
+                    let elements = ast.vec_from_iter([
+                        Some(binding(ast, &decl.name)),
+                        Some(binding(ast, current)),
+                    ]);
+                    ast.binding_pattern_array_pattern(SPAN, elements, NONE)
+                }
+                None => binding(ast, &decl.name),
+            };
             statements.push(const_decl_stmt(
                 ast,
-                gen_span,
-                &decl.name,
+                SPAN,
+                id,
                 decl.init.clone_in(ast.allocator),
             ));
         }
@@ -150,67 +199,79 @@ pub fn build_dom_output_expr<'a>(
         // Expressions (effects, inserts, etc.)
         for expr in &result.exprs {
             statements.push(Statement::ExpressionStatement(
-                ast.alloc_expression_statement(gen_span, expr.clone_in(ast.allocator)),
+                ast.alloc_expression_statement(expr.span(), expr.clone_in(ast.allocator)),
             ));
         }
 
-        // Dynamic bindings (effect(() => setter))
+        // Dynamic bindings (effect(() => setter)); `style` and `classList`
+        // diff against what they applied last: effect((_p$) => style(el, v, _p$))
         for binding in &result.dynamics {
-            context.register_helper("effect");
-            if binding.key == "style" {
-                context.register_helper("style");
-            } else if binding.key == "classList" {
-                context.register_helper("classList");
-            } else {
-                context.register_helper("setAttribute");
+            context.register_helper(&context.effect_wrapper);
+            if let Some(helper) = crate::template::binding_helper(binding) {
+                context.register_helper(helper);
             }
 
-            let setter = crate::template::generate_set_attr_expr(ast, gen_span, binding);
-            let effect = ident_expr(ast, gen_span, "effect");
-            let arrow = arrow_zero_params_body(ast, gen_span, setter);
-            let effect_call = call_expr(ast, gen_span, effect, [arrow]);
+            let prev =
+                crate::template::takes_previous_value(binding).then(|| context.local_name("_p$"));
+            let setter = crate::template::generate_set_attr_expr(
+                ast,
+                binding.span,
+                binding,
+                prev.as_deref(),
+            );
+            let effect = helper_ident(ast, &context.effect_wrapper);
+            let arrow = arrow_body(ast, binding.span, prev.as_deref(), setter);
+            let effect_call = call_expr(ast, binding.span, effect, [arrow]);
             statements.push(Statement::ExpressionStatement(
-                ast.alloc_expression_statement(gen_span, effect_call),
+                ast.alloc_expression_statement(binding.span, effect_call),
             ));
         }
 
         // Post expressions
         for expr in &result.post_exprs {
             statements.push(Statement::ExpressionStatement(
-                ast.alloc_expression_statement(gen_span, expr.clone_in(ast.allocator)),
+                ast.alloc_expression_statement(expr.span(), expr.clone_in(ast.allocator)),
+            ));
+        }
+
+        // Handlers bound while hydrating missed the events that fired
+        // before; replay them
+        if context.hydratable && result.has_hydratable_event {
+            context.register_helper("runHydrationEvents");
+            let callee = helper_ident(ast, "runHydrationEvents");
+            statements.push(Statement::ExpressionStatement(
+                ast.alloc_expression_statement(SPAN, call_expr(ast, SPAN, callee, [])),
             ));
         }
 
         // return _el$;
-        statements.push(Statement::ReturnStatement(ast.alloc_return_statement(
-            gen_span,
-            Some(ident_expr(ast, gen_span, &elem_var)),
-        )));
+        statements.push(Statement::ReturnStatement(
+            ast.alloc_return_statement(SPAN, Some(ident_expr(ast, SPAN, &elem_var))),
+        ));
 
         // (() => { ... })()
         let params = ast.alloc_formal_parameters(
-            gen_span,
+            SPAN,
             FormalParameterKind::ArrowFormalParameters,
             ast.vec(),
             NONE,
         );
-        let body = ast.alloc_function_body(gen_span, ast.vec(), statements);
-        let arrow_fn =
-            ast.expression_arrow_function(gen_span, false, false, NONE, params, NONE, body);
-        return call_expr(ast, gen_span, arrow_fn, []);
+        let body = ast.alloc_function_body(SPAN, ast.vec(), statements);
+        let arrow_fn = ast.expression_arrow_function(span, false, false, NONE, params, NONE, body);
+        return call_expr(ast, span, arrow_fn, []);
     }
 
     // Expression-only result (like createComponent(...) or fragment expression)
     if !result.exprs.is_empty() {
         if result.needs_memo {
-            context.register_helper("memo");
-            let callee = ident_expr(ast, gen_span, "memo");
+            context.register_helper(&context.memo_wrapper);
+            let callee = helper_ident(ast, &context.memo_wrapper);
             let mut args = ast.vec_with_capacity(result.exprs.len());
             for expr in &result.exprs {
                 args.push(Argument::from(expr.clone_in(ast.allocator)));
             }
             return ast.expression_call(
-                gen_span,
+                span,
                 callee,
                 None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
                 args,
@@ -226,9 +287,9 @@ pub fn build_dom_output_expr<'a>(
         for expr in &result.exprs {
             exprs.push(expr.clone_in(ast.allocator));
         }
-        return ast.expression_sequence(gen_span, exprs);
+        return ast.expression_sequence(SPAN, exprs);
     }
 
     // Fallback: empty string literal (matches previous parse-fallback behavior for empty output)
-    ast.expression_string_literal(gen_span, ast.allocator.alloc_str(""), None)
+    ast.expression_string_literal(SPAN, ast.allocator.alloc_str(""), None)
 }
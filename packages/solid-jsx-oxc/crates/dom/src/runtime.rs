@@ -0,0 +1,148 @@
+//! Inlined runtime helpers
+//!
+//! With `inline_helpers`, the helpers that don't need Solid's reactive
+//! runtime are emitted into the module as plain functions (under the same
+//! `_$`-prefixed names imports get) instead of being imported from
+//! `solid-js/web`, so output that only uses them (static templates, event
+//! handlers, attributes set once) runs without any dependency. Reactive helpers (`effect`, `insert`, `createComponent`, ...)
+//! are still imported.
+//!
+//! The implementations are minimal: they cover the calls this transform
+//! generates, not every signature of the `solid-js/web` export.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::Statement;
+use oxc_ast::AstBuilder;
+use oxc_ast_visit::VisitMut;
+use oxc_parser::Parser;
+use oxc_span::{SourceType, Span, SPAN};
+
+/// `template(html, isSVG)`: the first node of `html`, parsed once
+const TEMPLATE: &str = r#"function template(html, isSVG) {
+  const t = document.createElement("template");
+  const wrap = isSVG && !html.startsWith("<svg");
+  t.innerHTML = wrap ? `<svg>${html}</svg>` : html;
+  const node = t.content.firstChild;
+  return wrap ? node.firstChild : node;
+}"#;
+
+const SET_ATTRIBUTE: &str = r#"function setAttribute(node, name, value) {
+  if (value == null) node.removeAttribute(name);
+  else node.setAttribute(name, value);
+}"#;
+
+const SET_BOOL_ATTRIBUTE: &str = r#"function setBoolAttribute(node, name, value) {
+  if (value) node.setAttribute(name, "");
+  else node.removeAttribute(name);
+}"#;
+
+/// `style(node, value, prev)`: a string replaces the inline style; an
+/// object updates the properties that changed since `prev`, passing
+/// `!important` as the priority `setProperty` expects
+const STYLE: &str = r#"function style(node, value, prev) {
+  const nodeStyle = node.style;
+  if (value == null || typeof value === "string") {
+    nodeStyle.cssText = value || "";
+    return value;
+  }
+  if (typeof prev !== "object" || prev === null) {
+    nodeStyle.cssText = "";
+    prev = {};
+  }
+  for (const name in prev) {
+    if (value[name] == null) nodeStyle.removeProperty(name);
+  }
+  for (const name in value) {
+    const v = value[name];
+    if (v != null && v !== prev[name]) {
+      const important = /\s*!important\s*$/i.exec(v);
+      if (important) nodeStyle.setProperty(name, v.slice(0, important.index), "important");
+      else nodeStyle.setProperty(name, v);
+    }
+  }
+  return value;
+}"#;
+
+const CLASS_LIST: &str = r#"function classList(node, value, prev = {}) {
+  const toggle = (name, on) => {
+    for (const token of name.split(/\s+/)) if (token) node.classList.toggle(token, on);
+  };
+  for (const name in prev) {
+    if (!(name in value)) toggle(name, false);
+  }
+  for (const name in value) toggle(name, !!value[name]);
+  return value;
+}"#;
+
+const ADD_EVENT_LISTENER: &str = r#"function addEventListener(node, name, handler, capture) {
+  node.addEventListener(name, handler, capture);
+}"#;
+
+/// Listens on the document and calls the `$$<event>` handlers set on the
+/// target and its ancestors
+const DELEGATE_EVENTS: &str = r#"function delegateEvents(eventNames, doc = window.document) {
+  const delegated = doc.$$delegated || (doc.$$delegated = new Set());
+  for (const name of eventNames) {
+    if (delegated.has(name)) continue;
+    delegated.add(name);
+    doc.addEventListener(name, (e) => {
+      const key = `$$${name}`;
+      let node = e.target;
+      Object.defineProperty(e, "currentTarget", { configurable: true, get: () => node || doc });
+      while (node) {
+        const handler = node[key];
+        if (handler && !node.disabled) {
+          const data = node[`${key}Data`];
+          data !== undefined ? handler.call(node, data, e) : handler.call(node, e);
+          if (e.cancelBubble) return;
+        }
+        node = node.parentNode || node.host;
+      }
+    });
+  }
+}"#;
+
+/// Source of the inlined implementation of `helper`, if it has one
+pub fn inline_helper(helper: &str) -> Option<&'static str> {
+    Some(match helper {
+        "template" => TEMPLATE,
+        "setAttribute" => SET_ATTRIBUTE,
+        "setBoolAttribute" => SET_BOOL_ATTRIBUTE,
+        "style" => STYLE,
+        "classList" => CLASS_LIST,
+        "addEventListener" => ADD_EVENT_LISTENER,
+        "delegateEvents" => DELEGATE_EVENTS,
+        _ => return None,
+    })
+}
+
+/// The function declaration of an inlined helper, parsed into `allocator`
+/// and named `local`
+pub fn build_inline_helper<'a>(
+    allocator: &'a Allocator,
+    source: &'static str,
+    local: &str,
+) -> Statement<'a> {
+    let mut program = Parser::new(allocator, source, SourceType::mjs())
+        .parse()
+        .program;
+    // The spans point into `source`, not the file being transformed
+    ClearSpans.visit_program(&mut program);
+    let mut statement = program
+        .body
+        .pop()
+        .expect("inlined helpers are single function declarations");
+    if let Statement::FunctionDeclaration(function) = &mut statement {
+        let ast = AstBuilder::new(allocator);
+        function.id = Some(ast.binding_identifier(SPAN, allocator.alloc_str(local)));
+    }
+    statement
+}
+
+struct ClearSpans;
+
+impl<'a> VisitMut<'a> for ClearSpans {
+    fn visit_span(&mut self, span: &mut Span) {
+        *span = SPAN;
+    }
+}
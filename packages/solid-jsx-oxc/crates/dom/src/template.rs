@@ -1,24 +1,26 @@
 use oxc_allocator::CloneIn;
 use oxc_ast::ast::{AssignmentTarget, Expression};
 use oxc_ast::AstBuilder;
-use oxc_span::Span;
+use oxc_span::{Span, SPAN};
 use oxc_syntax::operator::AssignmentOperator;
 
+use common::constants::{BOOLEAN_ATTRIBUTES, PROPERTIES};
+use common::helper_ident;
+
 use crate::ir::DynamicBinding;
 
-fn ident_expr<'a>(ast: AstBuilder<'a>, span: Span, name: &str) -> Expression<'a> {
-    ast.expression_identifier(span, ast.allocator.alloc_str(name))
+fn ident_expr<'a>(ast: AstBuilder<'a>, name: &str) -> Expression<'a> {
+    ast.expression_identifier(SPAN, ast.allocator.alloc_str(name))
 }
 
 fn static_member<'a>(
     ast: AstBuilder<'a>,
-    span: Span,
     object: Expression<'a>,
     property: &str,
 ) -> Expression<'a> {
-    let prop = ast.identifier_name(span, ast.allocator.alloc_str(property));
+    let prop = ast.identifier_name(SPAN, ast.allocator.alloc_str(property));
     Expression::StaticMemberExpression(
-        ast.alloc_static_member_expression(span, object, prop, false),
+        ast.alloc_static_member_expression(SPAN, object, prop, false),
     )
 }
 
@@ -38,81 +40,180 @@ fn expression_to_assignment_target<'a>(expr: Expression<'a>) -> Option<Assignmen
     }
 }
 
+/// Custom elements take values as properties under the name they are written
+/// with, except hyphenated names, which can't be properties, and `slot`,
+/// which the browser only reads as an attribute
+fn is_custom_element_property(binding: &DynamicBinding<'_>) -> bool {
+    let key = binding.key.as_str();
+    binding.is_ce && !key.contains('-') && !key.contains(':') && key != "slot"
+}
+
+/// Whether `binding` is set through the element's property: the names in
+/// `PROPERTIES` and the controlled `value`/`checked` of form controls. SVG
+/// elements don't reflect these, so they keep the attribute.
+fn is_dom_property(binding: &DynamicBinding<'_>) -> bool {
+    let key = binding.key.as_str();
+    !binding.is_svg
+        && (PROPERTIES.contains(key) || common::is_controlled_property(&binding.tag_name, key))
+}
+
+/// Runtime helper the setter for `binding` calls, if any
+pub fn binding_helper(binding: &DynamicBinding<'_>) -> Option<&'static str> {
+    let key = binding.key.as_str();
+    if key.starts_with("bool:") {
+        return Some("setBoolAttribute");
+    }
+    if key.starts_with("class:") {
+        return None;
+    }
+    if common::is_attribute_only(key) {
+        return Some("setAttribute");
+    }
+    match key {
+        "class" | "className" => binding.is_svg.then_some("setAttribute"),
+        "style" => Some("style"),
+        "classList" => Some("classList"),
+        "textContent" | "innerText" => None,
+        _ if is_custom_element_property(binding) => None,
+        _ if binding.is_svg && BOOLEAN_ATTRIBUTES.contains(key) => Some("setBoolAttribute"),
+        _ if is_dom_property(binding) => None,
+        _ => Some("setAttribute"),
+    }
+}
+
+/// Whether the setter for `binding` takes the value it returned last time,
+/// so it only has to touch what changed
+pub fn takes_previous_value(binding: &DynamicBinding<'_>) -> bool {
+    matches!(binding.key.as_str(), "style" | "classList")
+}
+
+/// The statement that applies `binding`. `prev` names the previous value
+/// for the setters that [`takes_previous_value`].
 pub fn generate_set_attr_expr<'a>(
     ast: AstBuilder<'a>,
     span: Span,
     binding: &DynamicBinding<'a>,
+    prev: Option<&str>,
 ) -> Expression<'a> {
     let key = binding.key.as_str();
-    let elem = ident_expr(ast, span, &binding.elem);
+    let elem = ident_expr(ast, &binding.elem);
     let value = binding.value.clone_in(ast.allocator);
 
+    // bool:name toggles the attribute on the truthiness of the value
+    if let Some(name) = key.strip_prefix("bool:") {
+        return helper_call(ast, span, "setBoolAttribute", elem, name, value);
+    }
+
+    // class:name toggles one class name: elem.classList.toggle("name", value)
+    if let Some(name) = key.strip_prefix("class:") {
+        let class_list = static_member(ast, elem, "classList");
+        let toggle = static_member(ast, class_list, "toggle");
+        let name = ast.expression_string_literal(SPAN, ast.allocator.alloc_str(name), None);
+        return ast.expression_call(
+            span,
+            toggle,
+            None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+            ast.vec_from_array([name.into(), value.into()]),
+            false,
+        );
+    }
+
+    // data-*/aria-* are attributes only, never properties
+    if common::is_attribute_only(key) {
+        return set_attribute_call(ast, span, elem, key, value);
+    }
+
     // Handle special cases
     if key == "class" || key == "className" {
         if binding.is_svg {
-            let set_attr = static_member(ast, span, elem, "setAttribute");
-            let name = ast.expression_string_literal(span, ast.allocator.alloc_str("class"), None);
-            return ast.expression_call(
-                span,
-                set_attr,
-                None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
-                ast.vec_from_array([name.into(), value.into()]),
-                false,
-            );
+            return set_attribute_call(ast, span, elem, "class", value);
         }
 
-        let member = static_member(ast, span, elem, "className");
+        let member = static_member(ast, elem, "className");
         if let Some(target) = expression_to_assignment_target(member) {
             return ast.expression_assignment(span, AssignmentOperator::Assign, target, value);
         }
-        return ast.expression_identifier(span, "undefined");
+        return ast.expression_identifier(SPAN, "undefined");
     }
 
-    if key == "style" {
-        let callee = ident_expr(ast, span, "style");
+    if key == "style" || key == "classList" {
+        let callee = helper_ident(ast, key);
+        let mut arguments = ast.vec_from_array([elem.into(), value.into()]);
+        if let Some(prev) = prev {
+            arguments.push(ident_expr(ast, prev).into());
+        }
         return ast.expression_call(
             span,
             callee,
             None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
-            ast.vec_from_array([elem.into(), value.into()]),
+            arguments,
             false,
         );
     }
 
-    if key == "classList" {
-        let callee = ident_expr(ast, span, "classList");
-        return ast.expression_call(
-            span,
-            callee,
-            None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
-            ast.vec_from_array([elem.into(), value.into()]),
-            false,
-        );
+    if key == "textContent" || key == "innerText" {
+        let member = static_member(ast, elem, "data");
+        if let Some(target) = expression_to_assignment_target(member) {
+            return ast.expression_assignment(span, AssignmentOperator::Assign, target, value);
+        }
+        return ast.expression_identifier(SPAN, "undefined");
     }
 
-    if key == "textContent" || key == "innerText" {
-        let member = static_member(ast, span, elem, "data");
+    if is_custom_element_property(binding) {
+        let member = static_member(ast, elem, key);
         if let Some(target) = expression_to_assignment_target(member) {
             return ast.expression_assignment(span, AssignmentOperator::Assign, target, value);
         }
-        return ast.expression_identifier(span, "undefined");
+        return ast.expression_identifier(SPAN, "undefined");
+    }
+
+    // SVG has no boolean properties: toggle the attribute on truthiness
+    if binding.is_svg && BOOLEAN_ATTRIBUTES.contains(key) {
+        return helper_call(ast, span, "setBoolAttribute", elem, key, value);
     }
 
-    if common::constants::PROPERTIES.contains(key) {
-        let member = static_member(ast, span, elem, key);
+    // Form control state must go through the property to stay controlled
+    if is_dom_property(binding) {
+        let prop = common::get_prop_alias(key, &binding.tag_name).unwrap_or(key);
+        let member = static_member(ast, elem, prop);
         if let Some(target) = expression_to_assignment_target(member) {
             return ast.expression_assignment(span, AssignmentOperator::Assign, target, value);
         }
-        return ast.expression_identifier(span, "undefined");
+        return ast.expression_identifier(SPAN, "undefined");
     }
 
-    let set_attr = static_member(ast, span, elem, "setAttribute");
-    let name = ast.expression_string_literal(span, ast.allocator.alloc_str(key), None);
+    let name = common::attribute_name(key, binding.is_svg, binding.is_ce);
+    set_attribute_call(ast, span, elem, &name, value)
+}
+
+/// `setAttribute(elem, "key", value)`; the helper removes the attribute when
+/// the value is `null` or `undefined` instead of storing `"null"`
+fn set_attribute_call<'a>(
+    ast: AstBuilder<'a>,
+    span: Span,
+    elem: Expression<'a>,
+    key: &str,
+    value: Expression<'a>,
+) -> Expression<'a> {
+    helper_call(ast, span, "setAttribute", elem, key, value)
+}
+
+/// `helper(elem, "key", value)`
+fn helper_call<'a>(
+    ast: AstBuilder<'a>,
+    span: Span,
+    helper: &str,
+    elem: Expression<'a>,
+    key: &str,
+    value: Expression<'a>,
+) -> Expression<'a> {
+    let callee = helper_ident(ast, helper);
+    let name = ast.expression_string_literal(SPAN, ast.allocator.alloc_str(key), None);
     ast.expression_call(
         span,
-        set_attr,
+        callee,
         None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
-        ast.vec_from_array([name.into(), value.into()]),
+        ast.vec_from_array([elem.into(), name.into(), value.into()]),
         false,
     )
 }
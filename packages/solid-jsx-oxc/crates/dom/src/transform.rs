@@ -4,20 +4,28 @@
 use oxc_allocator::Allocator;
 use oxc_ast::ast::{
     Argument, ArrayExpressionElement, Expression, ImportDeclarationSpecifier, ImportOrExportKind,
-    JSXChild, JSXElement, JSXExpressionContainer, JSXFragment, JSXText, ModuleExportName, Program,
-    Statement, TemplateElementValue, VariableDeclarationKind,
+    JSXChild, JSXElement, JSXElementName, JSXExpressionContainer, JSXFragment, JSXText,
+    ModuleExportName, Program, Statement, TemplateElementValue, VariableDeclarationKind,
 };
-use oxc_ast::NONE;
-use oxc_semantic::SemanticBuilder;
+use oxc_ast::{AstBuilder, NONE};
+use oxc_semantic::{Scoping, SemanticBuilder};
 use oxc_span::SPAN;
-use oxc_traverse::{traverse_mut, Traverse, TraverseCtx};
+use oxc_traverse::{traverse_mut, Ancestor, Traverse, TraverseCtx};
 
-use common::{get_tag_name, is_component, TransformOptions};
+use common::{
+    build_delegated_events_export, build_require_declaration, escape_template_raw, get_tag_name,
+    helper_ident, helper_local_name, is_component, is_svg_element, DelegateEventsStrategy,
+    GenerateMode, ModuleFormat, TransformOptions,
+};
 
 use crate::component::transform_component;
+use crate::condition::memoize_condition_inline;
 use crate::element::transform_element;
-use crate::ir::{BlockContext, TransformResult};
+use crate::construct::build_template_node;
+use crate::ir::{BlockContext, TemplateInfo, TransformResult};
 use crate::output::build_dom_output_expr;
+use crate::runtime::{build_inline_helper, inline_helper};
+use crate::universal;
 
 /// The main Solid JSX transformer
 pub struct SolidTransform<'a> {
@@ -31,12 +39,26 @@ impl<'a> SolidTransform<'a> {
         Self {
             allocator,
             options,
-            context: BlockContext::new(allocator),
+            context: BlockContext::new(allocator, options),
         }
     }
 
     /// Run the transform on a program
-    pub fn transform(mut self, program: &mut Program<'a>) {
+    pub fn transform(self, program: &mut Program<'a>) {
+        let scoping = SemanticBuilder::new()
+            .build(program)
+            .semantic
+            .into_scoping();
+        self.transform_with_scoping(program, scoping);
+    }
+
+    /// Run the transform with scoping the caller already built for `program`
+    /// (e.g. from the `Semantic` a linter ran on), instead of building it again
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "dom_transform", skip_all)
+    )]
+    pub fn transform_with_scoping(mut self, program: &mut Program<'a>, scoping: Scoping) {
         // SAFETY: We convert the allocator reference to a raw pointer and back to a reference
         // to satisfy oxc_traverse's API which requires `&Allocator` while we hold `&mut self`.
         // This is safe because:
@@ -44,17 +66,9 @@ impl<'a> SolidTransform<'a> {
         // 2. oxc_traverse only uses the allocator for read-only arena access
         // 3. We don't mutate the allocator through any path during traversal
         // 4. The pointer is never escaped or stored beyond this call
+        self.context.reserve_names(&scoping);
         let allocator = self.allocator as *const Allocator;
-        traverse_mut(
-            &mut self,
-            unsafe { &*allocator },
-            program,
-            SemanticBuilder::new()
-                .build(program)
-                .semantic
-                .into_scoping(),
-            (),
-        );
+        traverse_mut(&mut self, unsafe { &*allocator }, program, scoping, ());
     }
 
     /// Transform a JSX node and return the result
@@ -72,12 +86,13 @@ impl<'a> SolidTransform<'a> {
                 self.transform_expression_container(container, info)
             }
             JSXChild::Spread(spread) => {
-                // Spread children are rare, treat as dynamic
-                let expr = self.context.ast().expression_string_literal(
-                    SPAN,
-                    self.context.ast().allocator.alloc_str("/* spread child */"),
-                    None,
-                );
+                // `{...items}` is the array itself; insert flattens it
+                let expr = self.context.clone_expr(&spread.expression);
+                let expr = if common::is_dynamic(&spread.expression) {
+                    self.arrow_return_expr(expr)
+                } else {
+                    expr
+                };
                 Some(TransformResult {
                     span: spread.span,
                     exprs: vec![expr],
@@ -110,6 +125,16 @@ impl<'a> SolidTransform<'a> {
                 &child_transformer,
                 ctx,
             )
+        } else if self.options.generate == GenerateMode::Universal {
+            universal::transform_element(
+                element,
+                &tag_name,
+                info,
+                &self.context,
+                self.options,
+                &child_transformer,
+                ctx,
+            )
         } else {
             transform_element(
                 element,
@@ -123,6 +148,35 @@ impl<'a> SolidTransform<'a> {
         }
     }
 
+    /// Transform a root JSX element or fragment into the expression building it
+    fn transform_jsx_expression(
+        &self,
+        node: &Expression<'a>,
+        ctx: &TraverseCtx<'a, ()>,
+    ) -> Expression<'a> {
+        let result = match node {
+            Expression::JSXElement(element) => self.transform_jsx_element(
+                element,
+                &TransformInfo {
+                    top_level: true,
+                    last_element: true,
+                    ..Default::default()
+                },
+                ctx,
+            ),
+            Expression::JSXFragment(fragment) => self.transform_fragment(
+                fragment,
+                &TransformInfo {
+                    top_level: true,
+                    ..Default::default()
+                },
+                ctx,
+            ),
+            _ => unreachable!("only JSX is transformed"),
+        };
+        build_dom_output_expr(&result, &self.context)
+    }
+
     /// Transform a JSX fragment
     fn transform_fragment(
         &self,
@@ -187,7 +241,7 @@ impl<'a> SolidTransform<'a> {
 
     /// Transform JSX text
     fn transform_text(&self, text: &JSXText<'a>) -> Option<TransformResult<'a>> {
-        let content = common::expression::trim_whitespace(&text.value);
+        let content = common::expression::decode_jsx_text(&text.value);
         if content.is_empty() {
             return None;
         }
@@ -200,6 +254,24 @@ impl<'a> SolidTransform<'a> {
         })
     }
 
+    /// `() => expr`
+    fn arrow_return_expr(&self, expr: Expression<'a>) -> Expression<'a> {
+        let ast = self.context.ast();
+        let span = SPAN;
+        let params = ast.alloc_formal_parameters(
+            span,
+            oxc_ast::ast::FormalParameterKind::ArrowFormalParameters,
+            ast.vec(),
+            NONE,
+        );
+        let mut statements = ast.vec_with_capacity(1);
+        statements.push(Statement::ExpressionStatement(
+            ast.alloc_expression_statement(span, expr),
+        ));
+        let body = ast.alloc_function_body(span, ast.vec(), statements);
+        ast.expression_arrow_function(span, true, false, NONE, params, NONE, body)
+    }
+
     /// Transform a JSX expression container
     fn transform_expression_container(
         &self,
@@ -209,22 +281,12 @@ impl<'a> SolidTransform<'a> {
         // Use as_expression() to get the expression if it exists
         if let Some(expr) = container.expression.as_expression() {
             if common::is_dynamic(expr) {
+                let mut expr = self.context.clone_expr(expr);
+                if self.options.wrap_conditionals {
+                    memoize_condition_inline(&mut expr, &self.context);
+                }
                 // Wrap in arrow function for reactivity
-                let ast = self.context.ast();
-                let span = SPAN;
-                let params = ast.alloc_formal_parameters(
-                    span,
-                    oxc_ast::ast::FormalParameterKind::ArrowFormalParameters,
-                    ast.vec(),
-                    NONE,
-                );
-                let mut statements = ast.vec_with_capacity(1);
-                statements.push(Statement::ExpressionStatement(
-                    ast.alloc_expression_statement(span, self.context.clone_expr(expr)),
-                ));
-                let body = ast.alloc_function_body(span, ast.vec(), statements);
-                let arrow =
-                    ast.expression_arrow_function(span, true, false, NONE, params, NONE, body);
+                let arrow = self.arrow_return_expr(expr);
                 Some(TransformResult {
                     span: container.span,
                     exprs: vec![arrow],
@@ -245,6 +307,26 @@ impl<'a> SolidTransform<'a> {
     }
 }
 
+/// Whether the children of the innermost native element around the current
+/// node are in the SVG namespace, or `None` outside any. Components are
+/// skipped: their children end up in the element around them.
+fn svg_parent(ctx: &TraverseCtx<'_, ()>) -> Option<bool> {
+    let tags: Vec<&str> = ctx
+        .ancestors()
+        .filter_map(|ancestor| match ancestor {
+            Ancestor::JSXElementChildren(element) => match &element.opening_element().name {
+                JSXElementName::Identifier(ident) => Some(ident.name.as_str()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    tags.iter().rev().fold(None, |svg_parent, &tag| {
+        let is_svg = tag == "svg" || svg_parent.unwrap_or_else(|| is_svg_element(tag));
+        Some(is_svg && tag != "foreignObject")
+    })
+}
+
 /// Additional info passed during transform
 #[derive(Default, Clone)]
 pub struct TransformInfo {
@@ -263,36 +345,18 @@ impl<'a> Traverse<'a, ()> for SolidTransform<'a> {
     // Use exit_expression instead of enter_expression to avoid
     // oxc_traverse walking into our newly created nodes (which lack scope info)
     fn exit_expression(&mut self, node: &mut Expression<'a>, ctx: &mut TraverseCtx<'a, ()>) {
-        let new_expr = match node {
-            Expression::JSXElement(element) => {
-                let result = self.transform_jsx_element(
-                    element,
-                    &TransformInfo {
-                        top_level: true,
-                        last_element: true,
-                        ..Default::default()
-                    },
-                    ctx,
-                );
-                Some(build_dom_output_expr(&result, &self.context))
-            }
-            Expression::JSXFragment(fragment) => {
-                let result = self.transform_fragment(
-                    fragment,
-                    &TransformInfo {
-                        top_level: true,
-                        ..Default::default()
-                    },
-                    ctx,
-                );
-                Some(build_dom_output_expr(&result, &self.context))
-            }
-            _ => None,
-        };
-
-        if let Some(expr) = new_expr {
-            *node = expr;
+        if !matches!(node, Expression::JSXElement(_) | Expression::JSXFragment(_)) {
+            return;
         }
+        // JSX in an expression child is transformed before the elements
+        // around it, so it looks them up to know its namespace
+        let new_expr = match svg_parent(ctx) {
+            Some(svg_children) => self
+                .context
+                .within_parent(svg_children, || self.transform_jsx_expression(node, ctx)),
+            None => self.transform_jsx_expression(node, ctx),
+        };
+        *node = new_expr;
     }
 
     fn exit_program(&mut self, program: &mut Program<'a>, ctx: &mut TraverseCtx<'a, ()>) {
@@ -307,10 +371,8 @@ impl<'a> Traverse<'a, ()> for SolidTransform<'a> {
         let ast = ctx.ast;
         let span = SPAN;
 
-        // Insert delegateEvents call if needed
+        // Insert delegateEvents call (or the exported event list) if needed
         if !delegates.is_empty() {
-            self.context.register_helper("delegateEvents");
-
             let mut elements = ast.vec_with_capacity(delegates.len());
             for event in delegates.iter() {
                 elements.push(ArrayExpressionElement::from(ast.expression_string_literal(
@@ -320,24 +382,35 @@ impl<'a> Traverse<'a, ()> for SolidTransform<'a> {
                 )));
             }
             let array = ast.expression_array(span, elements);
-            let callee = ast.expression_identifier(span, "delegateEvents");
-            let call = ast.expression_call(
-                span,
-                callee,
-                None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
-                ast.vec1(Argument::from(array)),
-                false,
-            );
-            program.body.push(Statement::ExpressionStatement(
-                ast.alloc_expression_statement(span, call),
-            ));
+            let statement = match self.options.delegate_events_strategy {
+                DelegateEventsStrategy::Call => {
+                    self.context.register_helper("delegateEvents");
+                    let callee = helper_ident(ast, "delegateEvents");
+                    let call = ast.expression_call(
+                        span,
+                        callee,
+                        None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+                        ast.vec1(Argument::from(array)),
+                        false,
+                    );
+                    Statement::ExpressionStatement(ast.alloc_expression_statement(span, call))
+                }
+                DelegateEventsStrategy::Export => {
+                    build_delegated_events_export(ast, array, self.options.module_format)
+                }
+            };
+            program.body.push(statement);
         }
 
+        if self.options.csp_safe {
+            self.context.helpers.borrow_mut().shift_remove("template");
+        }
         let helpers = self.context.helpers.borrow();
 
+        let mut import = None;
         let mut prepend = Vec::new();
 
-        // Build import statement: import { template, effect, ... } from 'solid-js/web';
+        // Build import statement: import { template as _$template, ... } from 'solid-js/web';
         // NOTE: This import building logic is duplicated with SSR transform.
         // Extraction is non-trivial due to OXC's lifetime requirements.
         if !helpers.is_empty() {
@@ -384,94 +457,98 @@ impl<'a> Traverse<'a, ()> for SolidTransform<'a> {
                 }
             }
 
-            // Build specifiers
-            let mut specifiers = ast.vec();
-            for helper in helpers.iter().filter(|h| !existing_helper_locals.contains(*h)) {
-                let helper_str = ast.allocator.alloc_str(helper);
-                let imported =
-                    ModuleExportName::IdentifierName(ast.identifier_name(span, helper_str));
-                let local = ast.binding_identifier(span, helper_str);
-                let specifier =
-                    ast.import_specifier(span, imported, local, ImportOrExportKind::Value);
-                specifiers.push(ImportDeclarationSpecifier::ImportSpecifier(
-                    ast.alloc(specifier),
-                ));
-            }
-
-            if !specifiers.is_empty() {
-                // Prefer augmenting the first existing import from the module to avoid extra imports.
-                if let Some(import_index) = first_module_import_index {
-                    if let Statement::ImportDeclaration(import_decl) = &mut program.body[import_index]
-                    {
-                        let decl_specifiers =
-                            import_decl.specifiers.get_or_insert_with(|| ast.vec());
-                        decl_specifiers.extend(specifiers);
-                    } else {
-                        debug_assert!(false, "stored import index should still be an import");
-                    }
-                } else {
-                    // Build source string literal
-                    let source = ast.string_literal(span, module_name, None);
+            let (inlined_helpers, missing_helpers): (Vec<_>, Vec<_>) = helpers
+                .iter()
+                .filter(|h| !existing_helper_locals.contains(&helper_local_name(h)))
+                .map(String::as_str)
+                .partition(|h| self.options.inline_helpers && inline_helper(h).is_some());
 
-                    // Build import declaration
-                    let import_decl = ast.import_declaration(
+            if self.options.module_format == ModuleFormat::Cjs {
+                // CommonJS output: const { template: _$template, ... } = require('solid-js/web');
+                if !missing_helpers.is_empty() {
+                    import = Some(build_require_declaration(ast, missing_helpers, module_name));
+                }
+            } else {
+                // Build specifiers
+                let mut specifiers = ast.vec();
+                for helper in missing_helpers {
+                    let helper_str = ast.allocator.alloc_str(helper);
+                    let imported =
+                        ModuleExportName::IdentifierName(ast.identifier_name(span, helper_str));
+                    let local = ast.binding_identifier(
                         span,
-                        Some(specifiers),
-                        source,
-                        None,                                 // phase
-                        None::<oxc_ast::ast::WithClause<'a>>, // with_clause
-                        ImportOrExportKind::Value,
+                        ast.allocator.alloc_str(&helper_local_name(helper)),
                     );
+                    let specifier =
+                        ast.import_specifier(span, imported, local, ImportOrExportKind::Value);
+                    specifiers.push(ImportDeclarationSpecifier::ImportSpecifier(
+                        ast.alloc(specifier),
+                    ));
+                }
 
-                    // Create the statement
-                    let import_stmt = Statement::ImportDeclaration(ast.alloc(import_decl));
+                if !specifiers.is_empty() {
+                    // Prefer augmenting the first existing import from the module to avoid extra imports.
+                    if let Some(import_index) = first_module_import_index {
+                        if let Statement::ImportDeclaration(import_decl) =
+                            &mut program.body[import_index]
+                        {
+                            let decl_specifiers =
+                                import_decl.specifiers.get_or_insert_with(|| ast.vec());
+                            decl_specifiers.extend(specifiers);
+                        } else {
+                            debug_assert!(false, "stored import index should still be an import");
+                        }
+                    } else {
+                        // Build source string literal
+                        let source = ast.string_literal(span, module_name, None);
+
+                        // Build import declaration
+                        let import_decl = ast.import_declaration(
+                            span,
+                            Some(specifiers),
+                            source,
+                            None,                                 // phase
+                            None::<oxc_ast::ast::WithClause<'a>>, // with_clause
+                            ImportOrExportKind::Value,
+                        );
+
+                        // Create the statement
+                        let import_stmt = Statement::ImportDeclaration(ast.alloc(import_decl));
+
+                        import = Some(import_stmt);
+                    }
+                }
+            }
 
-                    prepend.push(import_stmt);
+            // function _$template(html, isSVG) { ... }
+            for helper in inlined_helpers {
+                if let Some(source) = inline_helper(helper) {
+                    let local = helper_local_name(helper);
+                    prepend.push(build_inline_helper(ast.allocator, source, &local));
                 }
             }
         }
 
         // Insert template declarations
-        // const _tmpl$1 = template(`<div></div>`);
-        for (i, tmpl) in templates.iter().enumerate() {
+        // const _tmpl$1 = _$template(`<div></div>`);
+        for tmpl in templates.iter() {
             let tmpl_span = tmpl.span;
-            let tmpl_var = format!("_tmpl${}", i + 1);
 
-            let mut quasis = ast.vec_with_capacity(1);
-            let part_str = ast.allocator.alloc_str(&tmpl.content);
-            let value = TemplateElementValue {
-                raw: ast.atom(part_str),
-                cooked: Some(ast.atom(part_str)),
+            let init = if self.options.csp_safe {
+                build_template_node(ast, tmpl_span, &tmpl.content, tmpl.is_svg)
+            } else {
+                self.build_template_call(ast, tmpl)
             };
-            quasis.push(ast.template_element(tmpl_span, value, true));
-            let template_lit = ast.template_literal(tmpl_span, quasis, ast.vec());
-            let template_expr = Expression::TemplateLiteral(ast.alloc(template_lit));
-
-            let mut args = ast.vec_with_capacity(if tmpl.is_svg { 2 } else { 1 });
-            args.push(Argument::from(template_expr));
-            if tmpl.is_svg {
-                args.push(Argument::from(
-                    ast.expression_boolean_literal(tmpl_span, true),
-                ));
-            }
-
-            let call = ast.expression_call(
-                tmpl_span,
-                ast.expression_identifier(tmpl_span, "template"),
-                None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
-                args,
-                false,
-            );
 
             let declarator = ast.variable_declarator(
                 tmpl_span,
                 VariableDeclarationKind::Const,
                 ast.binding_pattern_binding_identifier(
                     tmpl_span,
-                    ast.allocator.alloc_str(&tmpl_var),
+                    ast.allocator.alloc_str(&tmpl.name),
                 ),
                 NONE,
-                Some(call),
+                Some(init),
                 false,
             );
 
@@ -485,9 +562,49 @@ impl<'a> Traverse<'a, ()> for SolidTransform<'a> {
             ));
         }
 
-        // Prepend statements in correct order
+        // Templates and inlined helpers follow the imports, including the
+        // existing one the helpers may have been added to
+        let after_imports = program
+            .body
+            .iter()
+            .take_while(|stmt| matches!(stmt, Statement::ImportDeclaration(_)))
+            .count();
         for stmt in prepend.into_iter().rev() {
-            program.body.insert(0, stmt);
+            program.body.insert(after_imports, stmt);
+        }
+        if let Some(import) = import {
+            program.body.insert(0, import);
         }
     }
 }
+
+impl<'a> SolidTransform<'a> {
+    /// `template(`<div></div>`)`, or `template(`<path></path>`, true)` for SVG
+    fn build_template_call(&self, ast: AstBuilder<'a>, tmpl: &TemplateInfo) -> Expression<'a> {
+        let tmpl_span = tmpl.span;
+        let mut quasis = ast.vec_with_capacity(1);
+        let part_str = ast.allocator.alloc_str(&tmpl.content);
+        let raw_str = ast.allocator.alloc_str(&escape_template_raw(&tmpl.content));
+        let value = TemplateElementValue {
+            raw: ast.atom(raw_str),
+            cooked: Some(ast.atom(part_str)),
+        };
+        quasis.push(ast.template_element(tmpl_span, value, true));
+        let template_lit = ast.template_literal(tmpl_span, quasis, ast.vec());
+        let template_expr = Expression::TemplateLiteral(ast.alloc(template_lit));
+
+        let mut args = ast.vec_with_capacity(if tmpl.is_svg { 2 } else { 1 });
+        args.push(Argument::from(template_expr));
+        if tmpl.is_svg {
+            args.push(Argument::from(ast.expression_boolean_literal(tmpl_span, true)));
+        }
+
+        ast.expression_call(
+            tmpl_span,
+            helper_ident(ast, "template"),
+            None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+            args,
+            false,
+        )
+    }
+}
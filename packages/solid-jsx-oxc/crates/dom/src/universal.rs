@@ -0,0 +1,416 @@
+//! Universal element transform
+//! Handles <div>, <text>, etc. -> createElement + setProp calls against a
+//! custom renderer (`generate: "universal"`)
+//!
+//! A custom renderer has no markup to clone, so every node is created and
+//! attached with the renderer's `createRenderer` helpers, and every
+//! attribute, event handler included, is a prop the renderer interprets.
+
+use oxc_ast::ast::{
+    Argument, Expression, FormalParameterKind, JSXAttribute, JSXAttributeItem, JSXAttributeValue,
+    JSXChild, JSXElement, Statement,
+};
+use oxc_ast::{AstBuilder, NONE};
+use oxc_span::{GetSpan, Span, SPAN};
+use oxc_traverse::TraverseCtx;
+
+use common::{
+    expression::decode_jsx_text, get_attr_name, helper_ident, is_component, is_dynamic,
+    static_child_text, TransformOptions,
+};
+
+use crate::element::{transform_directive, transform_ref};
+use crate::ir::{BlockContext, ChildTransformer, Declaration, TransformResult};
+use crate::transform::TransformInfo;
+
+fn ident_expr<'a>(ast: AstBuilder<'a>, name: &str) -> Expression<'a> {
+    ast.expression_identifier(SPAN, ast.allocator.alloc_str(name))
+}
+
+fn string_expr<'a>(ast: AstBuilder<'a>, value: &str) -> Expression<'a> {
+    ast.expression_string_literal(SPAN, ast.allocator.alloc_str(value), None)
+}
+
+fn call_expr<'a>(
+    ast: AstBuilder<'a>,
+    span: Span,
+    callee: Expression<'a>,
+    args: impl IntoIterator<Item = Expression<'a>>,
+) -> Expression<'a> {
+    let mut arguments = ast.vec();
+    for arg in args {
+        arguments.push(Argument::from(arg));
+    }
+    ast.expression_call(
+        span,
+        callee,
+        None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+        arguments,
+        false,
+    )
+}
+
+/// `() => expr`, or `(param) => expr`
+fn arrow_expr<'a>(
+    ast: AstBuilder<'a>,
+    span: Span,
+    param: Option<&str>,
+    expr: Expression<'a>,
+) -> Expression<'a> {
+    let mut items = ast.vec();
+    if let Some(param) = param {
+        let pattern = ast.binding_pattern_binding_identifier(SPAN, ast.allocator.alloc_str(param));
+        items.push(ast.formal_parameter(
+            SPAN,
+            ast.vec(),
+            pattern,
+            NONE,
+            NONE,
+            false,
+            None,
+            false,
+            false,
+        ));
+    }
+    let params = ast.alloc_formal_parameters(
+        SPAN,
+        FormalParameterKind::ArrowFormalParameters,
+        items,
+        NONE,
+    );
+    let mut statements = ast.vec_with_capacity(1);
+    statements.push(Statement::ExpressionStatement(
+        ast.alloc_expression_statement(SPAN, expr),
+    ));
+    let body = ast.alloc_function_body(SPAN, ast.vec(), statements);
+    ast.expression_arrow_function(span, true, false, NONE, params, NONE, body)
+}
+
+/// `onClick`, `on:click` or `oncapture:click`
+fn is_event_handler(key: &str) -> bool {
+    key.strip_prefix("on")
+        .is_some_and(|rest| rest.starts_with(|c: char| c == ':' || c.is_ascii_uppercase()))
+        || key.starts_with("oncapture:")
+}
+
+/// A child of an element, in order
+enum Child<'a> {
+    /// A node created up front and attached with `insertNode`
+    Node(String),
+    /// A value `insert` renders
+    Insert(Span, Expression<'a>),
+}
+
+/// Transform a native element for a custom renderer
+pub fn transform_element<'a, 'b>(
+    element: &JSXElement<'a>,
+    tag_name: &str,
+    info: &TransformInfo,
+    context: &BlockContext<'a>,
+    options: &TransformOptions<'a>,
+    transform_child: ChildTransformer<'a, 'b>,
+    ctx: &TraverseCtx<'a, ()>,
+) -> TransformResult<'a> {
+    let ast = context.ast();
+    let elem_id = context.generate_uid("el$");
+    let mut result = TransformResult {
+        span: element.span,
+        tag_name: Some(tag_name.to_string()),
+        id: Some(elem_id.clone()),
+        ..Default::default()
+    };
+
+    // const _el$1 = _$createElement("div");
+    context.register_helper("createElement");
+    result.declarations.push(Declaration {
+        name: elem_id.clone(),
+        current: None,
+        init: call_expr(
+            ast,
+            element.span,
+            helper_ident(ast, "createElement"),
+            [string_expr(ast, tag_name)],
+        ),
+    });
+
+    for attr in &element.opening_element.attributes {
+        match attr {
+            JSXAttributeItem::Attribute(attr) => {
+                transform_attribute(attr, &elem_id, &mut result, context, ctx);
+            }
+            JSXAttributeItem::SpreadAttribute(spread) => {
+                context.register_helper("spread");
+                let props = context.clone_expr(&spread.argument);
+                let skip_children =
+                    ast.expression_boolean_literal(SPAN, !element.children.is_empty());
+                result.exprs.push(call_expr(
+                    ast,
+                    spread.span,
+                    helper_ident(ast, "spread"),
+                    [ident_expr(ast, &elem_id), props, skip_children],
+                ));
+            }
+        }
+    }
+
+    let mut children = Vec::new();
+    collect_children(
+        &element.children,
+        &mut children,
+        &mut result,
+        info,
+        context,
+        options,
+        transform_child,
+        ctx,
+    );
+    attach_children(children, &elem_id, &mut result, context);
+
+    result
+}
+
+/// Set an attribute as a prop: once when static, in an effect that passes
+/// the previous value back to `setProp` when dynamic
+fn transform_attribute<'a>(
+    attr: &JSXAttribute<'a>,
+    elem_id: &str,
+    result: &mut TransformResult<'a>,
+    context: &BlockContext<'a>,
+    ctx: &TraverseCtx<'a, ()>,
+) {
+    let ast = context.ast();
+    let key = get_attr_name(&attr.name);
+    if key == "ref" {
+        transform_ref(attr, elem_id, result, context, ctx);
+        return;
+    }
+    if key.starts_with("use:") {
+        transform_directive(attr, &key, elem_id, result, context);
+        return;
+    }
+
+    let value = match &attr.value {
+        Some(JSXAttributeValue::StringLiteral(lit)) => string_expr(ast, &lit.value),
+        Some(JSXAttributeValue::ExpressionContainer(container)) => {
+            let Some(expr) = container.expression.as_expression() else {
+                return;
+            };
+            // Event handlers are props too; the handler is passed as is
+            if is_dynamic(expr) && !is_event_handler(&key) {
+                // _$effect((_p$) => _$setProp(_el$1, "title", title(), _p$));
+                context.register_helper(&context.effect_wrapper);
+                context.register_helper("setProp");
+                let prev = context.local_name("_p$");
+                let set_prop = call_expr(
+                    ast,
+                    attr.span,
+                    helper_ident(ast, "setProp"),
+                    [
+                        ident_expr(ast, elem_id),
+                        string_expr(ast, &key),
+                        context.clone_expr(expr),
+                        ident_expr(ast, &prev),
+                    ],
+                );
+                let arrow = arrow_expr(ast, attr.span, Some(&prev), set_prop);
+                result.exprs.push(call_expr(
+                    ast,
+                    attr.span,
+                    helper_ident(ast, &context.effect_wrapper),
+                    [arrow],
+                ));
+                return;
+            }
+            context.clone_expr(expr)
+        }
+        // `<input disabled />`
+        None => ast.expression_boolean_literal(SPAN, true),
+        _ => return,
+    };
+
+    // _$setProp(_el$1, "id", "main");
+    context.register_helper("setProp");
+    result.exprs.push(call_expr(
+        ast,
+        attr.span,
+        helper_ident(ast, "setProp"),
+        [ident_expr(ast, elem_id), string_expr(ast, &key), value],
+    ));
+}
+
+/// Create the static children and collect the dynamic ones, flattening
+/// fragments
+#[allow(clippy::too_many_arguments)]
+fn collect_children<'a, 'b>(
+    jsx_children: &[JSXChild<'a>],
+    children: &mut Vec<Child<'a>>,
+    result: &mut TransformResult<'a>,
+    info: &TransformInfo,
+    context: &BlockContext<'a>,
+    options: &TransformOptions<'a>,
+    transform_child: ChildTransformer<'a, 'b>,
+    ctx: &TraverseCtx<'a, ()>,
+) {
+    let ast = context.ast();
+    // Text and literal children next to each other share one text node
+    let mut text = String::new();
+    let mut text_span = SPAN;
+
+    for child in jsx_children {
+        let literal = match child {
+            JSXChild::Text(jsx_text) => Some(decode_jsx_text(&jsx_text.value)),
+            JSXChild::ExpressionContainer(container) => container
+                .expression
+                .as_expression()
+                .and_then(static_child_text),
+            _ => None,
+        };
+        if let Some(literal) = literal {
+            if text.is_empty() {
+                text_span = child.span();
+            }
+            text.push_str(&literal);
+            continue;
+        }
+        push_text_node(&mut text, text_span, children, result, context);
+
+        match child {
+            JSXChild::Element(child_elem) => {
+                let child_tag = common::get_tag_name(child_elem);
+                if is_component(&child_tag) {
+                    if let Some(component) = transform_child(child) {
+                        if let Some(expr) = component.exprs.into_iter().next() {
+                            children.push(Child::Insert(child_elem.span, expr));
+                        }
+                    }
+                    continue;
+                }
+                let child_result = transform_element(
+                    child_elem,
+                    &child_tag,
+                    info,
+                    context,
+                    options,
+                    transform_child,
+                    ctx,
+                );
+                children.push(Child::Node(child_result.id.clone().unwrap_or_default()));
+                result.declarations.extend(child_result.declarations);
+                result.exprs.extend(child_result.exprs);
+            }
+            JSXChild::ExpressionContainer(container) => {
+                if let Some(expr) = container.expression.as_expression() {
+                    let value = context.clone_expr(expr);
+                    let value = if is_dynamic(expr) {
+                        arrow_expr(ast, container.span, None, value)
+                    } else {
+                        value
+                    };
+                    children.push(Child::Insert(container.span, value));
+                }
+            }
+            JSXChild::Spread(spread) => {
+                let value = context.clone_expr(&spread.expression);
+                let value = if is_dynamic(&spread.expression) {
+                    arrow_expr(ast, spread.span, None, value)
+                } else {
+                    value
+                };
+                children.push(Child::Insert(spread.span, value));
+            }
+            JSXChild::Fragment(fragment) => {
+                collect_children(
+                    &fragment.children,
+                    children,
+                    result,
+                    info,
+                    context,
+                    options,
+                    transform_child,
+                    ctx,
+                );
+            }
+            JSXChild::Text(_) => {}
+        }
+    }
+    push_text_node(&mut text, text_span, children, result, context);
+}
+
+/// `const _el$2 = _$createTextNode("Hello");` for the pending text, if any
+fn push_text_node<'a>(
+    text: &mut String,
+    span: Span,
+    children: &mut Vec<Child<'a>>,
+    result: &mut TransformResult<'a>,
+    context: &BlockContext<'a>,
+) {
+    if text.is_empty() {
+        return;
+    }
+    let ast = context.ast();
+    context.register_helper("createTextNode");
+    let text_id = context.generate_uid("el$");
+    result.declarations.push(Declaration {
+        name: text_id.clone(),
+        current: None,
+        init: call_expr(
+            ast,
+            span,
+            helper_ident(ast, "createTextNode"),
+            [string_expr(ast, &std::mem::take(text))],
+        ),
+    });
+    children.push(Child::Node(text_id));
+}
+
+/// Attach the created children with `insertNode`, then `insert` the dynamic
+/// ones before the node that follows them
+fn attach_children<'a>(
+    children: Vec<Child<'a>>,
+    elem_id: &str,
+    result: &mut TransformResult<'a>,
+    context: &BlockContext<'a>,
+) {
+    let ast = context.ast();
+    // A dynamic child goes before the next created node, or is appended
+    // with a `null` marker so it doesn't replace its siblings
+    let mut markers = vec![None; children.len()];
+    let mut next_node = None;
+    for (index, child) in children.iter().enumerate().rev() {
+        match child {
+            Child::Node(node_id) => next_node = Some(node_id.clone()),
+            Child::Insert(..) => markers[index] = next_node.clone(),
+        }
+    }
+
+    let single = children.len() == 1;
+    let mut inserts = Vec::new();
+    for (child, marker) in children.into_iter().zip(markers) {
+        match child {
+            Child::Node(node_id) => {
+                // _$insertNode(_el$1, _el$2);
+                context.register_helper("insertNode");
+                result.exprs.push(call_expr(
+                    ast,
+                    SPAN,
+                    helper_ident(ast, "insertNode"),
+                    [ident_expr(ast, elem_id), ident_expr(ast, &node_id)],
+                ));
+            }
+            Child::Insert(span, value) => {
+                // _$insert(_el$1, () => count(), _el$3);
+                context.register_helper("insert");
+                let mut args = vec![ident_expr(ast, elem_id), value];
+                if !single {
+                    args.push(match marker {
+                        Some(marker) => ident_expr(ast, &marker),
+                        None => ast.expression_null_literal(SPAN),
+                    });
+                }
+                inserts.push(call_expr(ast, span, helper_ident(ast, "insert"), args));
+            }
+        }
+    }
+    // Inserted before their markers, so only once those are attached
+    result.exprs.extend(inserts);
+}
@@ -0,0 +1,459 @@
+//! Linting many files at once
+//!
+//! `BatchLinter` resolves the config for every file, reuses results for
+//! files whose content did not change since they were last linted and spreads
+//! the work over threads. Files are plain `(path, content)` pairs, so bundler
+//! plugins and editors lint virtual modules and unsaved buffers the same way
+//! `solid-lint` lints files read from disk.
+//!
+//! With project analysis enabled, `lint_files` and `check_files` first build
+//! a `ProjectGraph` of the whole set so rules can follow imports between the
+//! files.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use common::GenerateMode;
+use oxc_span::SourceType;
+use rustc_hash::FxHashMap;
+
+use crate::config::LintConfig;
+use crate::diagnostic::Diagnostic;
+use crate::linter::Linter;
+use crate::project::{ProjectFile, ProjectGraph};
+use crate::visitor::LintResult;
+
+/// Outcome of linting one file
+#[derive(Debug, Clone)]
+pub struct FileLintResult {
+    /// Path the file was given with
+    pub path: PathBuf,
+    /// Parse errors are returned as `Err`, as with `Linter::lint_source`
+    pub result: Result<LintResult, Vec<Diagnostic>>,
+    /// The result was reused from an earlier run on the same content
+    pub cached: bool,
+}
+
+impl FileLintResult {
+    /// Diagnostics of the file, parse errors included
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match &self.result {
+            Ok(result) => &result.diagnostics,
+            Err(parse_errors) => parse_errors,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    source_text: String,
+    /// Fingerprint of the project the file was linted in
+    project: Option<u64>,
+    result: Result<LintResult, Vec<Diagnostic>>,
+}
+
+/// Lints sets of in-memory files with per-file config and result caching
+#[derive(Debug)]
+pub struct BatchLinter {
+    base: Linter,
+    config: Option<LintConfig>,
+    /// Directory the config globs are relative to
+    root: PathBuf,
+    threads: usize,
+    project_analysis: bool,
+    cache: Mutex<FxHashMap<PathBuf, CacheEntry>>,
+}
+
+impl BatchLinter {
+    /// Lint every file with `base`
+    pub fn new(base: Linter) -> Self {
+        Self {
+            base,
+            config: None,
+            root: PathBuf::new(),
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            project_analysis: false,
+            cache: Mutex::new(FxHashMap::default()),
+        }
+    }
+
+    /// Apply `config` on top of the base linter. Its globs match paths
+    /// relative to `root`: relative file paths are taken as they are and
+    /// absolute ones have `root` stripped.
+    pub fn with_config(
+        mut self,
+        config: LintConfig,
+        root: impl Into<PathBuf>,
+    ) -> Result<Self, String> {
+        config.validate()?;
+        self.config = Some(config);
+        self.root = root.into();
+        self.clear_cache();
+        Ok(self)
+    }
+
+    /// Number of threads used by `lint_files` (at least one)
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Link the files given to `lint_files` and `check_files` into a
+    /// `ProjectGraph`, so rules resolve relative imports between them.
+    /// Cached results are only reused while no file of the set changes.
+    pub fn with_project_analysis(mut self, enabled: bool) -> Self {
+        self.project_analysis = enabled;
+        self.clear_cache();
+        self
+    }
+
+    /// The linter configured for `path`
+    pub fn linter_for(&self, path: &Path) -> Linter {
+        let Some(config) = &self.config else {
+            return self.base.clone();
+        };
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        config
+            .linter_for(&self.base, relative)
+            .expect("config is validated in `with_config`")
+    }
+
+    /// Lint one file, reusing the cached result when its content is unchanged
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(path = %path.as_ref().display()))
+    )]
+    pub fn lint_file(&self, path: impl AsRef<Path>, source_text: &str) -> FileLintResult {
+        self.lint_file_with(path.as_ref(), source_text, None)
+    }
+
+    fn lint_file_with(
+        &self,
+        path: &Path,
+        source_text: &str,
+        project: Option<ProjectFile<'_>>,
+    ) -> FileLintResult {
+        let fingerprint = project.map(|project| project.graph().fingerprint());
+        let cached = self
+            .cache
+            .lock()
+            .unwrap()
+            .get(path)
+            .filter(|entry| entry.source_text == source_text && entry.project == fingerprint)
+            .map(|entry| entry.result.clone());
+        if let Some(result) = cached {
+            return FileLintResult {
+                path: path.to_path_buf(),
+                result,
+                cached: true,
+            };
+        }
+
+        let source_type = SourceType::from_path(path).unwrap_or(SourceType::jsx());
+        let result = self
+            .linter_for(path)
+            .lint_source_with(source_text, source_type, project);
+        self.cache.lock().unwrap().insert(
+            path.to_path_buf(),
+            CacheEntry {
+                source_text: source_text.to_string(),
+                project: fingerprint,
+                result: result.clone(),
+            },
+        );
+        FileLintResult {
+            path: path.to_path_buf(),
+            result,
+            cached: false,
+        }
+    }
+
+    /// Lint `(path, content)` pairs in parallel; results keep the input order
+    pub fn lint_files<P, S>(&self, files: &[(P, S)]) -> Vec<FileLintResult>
+    where
+        P: AsRef<Path> + Sync,
+        S: AsRef<str> + Sync,
+    {
+        let graph = self.project_graph(files);
+        self.map_files(files, |path, source_text| {
+            let project = graph.as_ref().and_then(|graph| graph.file(path));
+            self.lint_file_with(path, source_text, project)
+        })
+    }
+
+    /// Lint one file and dry-run the Solid transform on it, as
+    /// `Linter::check_source`. Results are not cached.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip_all, fields(path = %path.as_ref().display()))
+    )]
+    pub fn check_file(
+        &self,
+        path: impl AsRef<Path>,
+        source_text: &str,
+        generate: GenerateMode,
+    ) -> FileLintResult {
+        self.check_file_with(path.as_ref(), source_text, generate, None)
+    }
+
+    fn check_file_with(
+        &self,
+        path: &Path,
+        source_text: &str,
+        generate: GenerateMode,
+        project: Option<ProjectFile<'_>>,
+    ) -> FileLintResult {
+        let source_type = SourceType::from_path(path).unwrap_or(SourceType::jsx());
+        FileLintResult {
+            path: path.to_path_buf(),
+            result: self.linter_for(path).check_source_with(
+                source_text,
+                source_type,
+                generate,
+                project,
+            ),
+            cached: false,
+        }
+    }
+
+    /// `check_file` over `(path, content)` pairs in parallel; results keep
+    /// the input order
+    pub fn check_files<P, S>(&self, files: &[(P, S)], generate: GenerateMode) -> Vec<FileLintResult>
+    where
+        P: AsRef<Path> + Sync,
+        S: AsRef<str> + Sync,
+    {
+        let graph = self.project_graph(files);
+        self.map_files(files, |path, source_text| {
+            let project = graph.as_ref().and_then(|graph| graph.file(path));
+            self.check_file_with(path, source_text, generate, project)
+        })
+    }
+
+    fn project_graph<P, S>(&self, files: &[(P, S)]) -> Option<ProjectGraph>
+    where
+        P: AsRef<Path>,
+        S: AsRef<str>,
+    {
+        self.project_analysis.then(|| ProjectGraph::new(files))
+    }
+
+    fn map_files<P, S, F>(&self, files: &[(P, S)], run: F) -> Vec<FileLintResult>
+    where
+        P: AsRef<Path> + Sync,
+        S: AsRef<str> + Sync,
+        F: Fn(&Path, &str) -> FileLintResult + Sync,
+    {
+        let threads = self.threads.min(files.len());
+        if threads <= 1 {
+            return files
+                .iter()
+                .map(|(path, source_text)| run(path.as_ref(), source_text.as_ref()))
+                .collect();
+        }
+
+        let next = AtomicUsize::new(0);
+        let mut results: Vec<(usize, FileLintResult)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut results = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some((path, source_text)) = files.get(index) else {
+                                break;
+                            };
+                            results.push((index, run(path.as_ref(), source_text.as_ref())));
+                        }
+                        results
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().unwrap())
+                .collect()
+        });
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Forget the cached result of one file (e.g. when an editor closes it)
+    pub fn invalidate(&self, path: &Path) {
+        self.cache.lock().unwrap().remove(path);
+    }
+
+    pub fn clear_cache(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RuleSetting;
+
+    const CONFIG: &str = r#"{
+        "rules": { "no-react-specific-props": "error" },
+        "overrides": [{ "files": ["src/server/**"], "rules": { "no-react-specific-props": "off" } }]
+    }"#;
+
+    fn has_rule(result: &FileLintResult, rule: &str) -> bool {
+        result.diagnostics().iter().any(|d| d.rule == rule)
+    }
+
+    #[test]
+    fn test_lint_virtual_files_with_config() {
+        let batch = BatchLinter::new(Linter::new())
+            .with_config(LintConfig::from_json(CONFIG).unwrap(), "/project")
+            .unwrap()
+            .with_threads(2);
+        let source = r#"const a = <div className="a" />;"#;
+        let files = [
+            ("/project/src/App.tsx", source),
+            ("src/server/api.tsx", source),
+            ("virtual:broken.jsx", "const a = <div>;"),
+        ];
+
+        let results = batch.lint_files(&files);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].path, PathBuf::from("/project/src/App.tsx"));
+        assert!(has_rule(&results[0], "no-react-specific-props"));
+        assert!(!has_rule(&results[1], "no-react-specific-props"));
+        assert!(results[2].result.is_err());
+        assert!(results.iter().all(|result| !result.cached));
+    }
+
+    #[test]
+    fn test_cache_reuses_unchanged_files() {
+        let batch = BatchLinter::new(Linter::new());
+        let first = batch.lint_file("App.jsx", "const a = <div className=\"a\" />;");
+        assert!(!first.cached);
+
+        let second = batch.lint_file("App.jsx", "const a = <div className=\"a\" />;");
+        assert!(second.cached);
+        assert_eq!(second.diagnostics().len(), first.diagnostics().len());
+
+        let changed = batch.lint_file("App.jsx", "const a = <div class=\"a\" />;");
+        assert!(!changed.cached);
+        assert!(!has_rule(&changed, "no-react-specific-props"));
+
+        batch.invalidate(Path::new("App.jsx"));
+        assert!(
+            !batch
+                .lint_file("App.jsx", "const a = <div class=\"a\" />;")
+                .cached
+        );
+    }
+
+    #[test]
+    fn test_invalid_config_is_rejected() {
+        let mut config = LintConfig::default();
+        config.rules.insert(
+            "no-such-rule".to_string(),
+            RuleSetting {
+                severity: None,
+                options: None,
+            },
+        );
+        assert!(BatchLinter::new(Linter::new())
+            .with_config(config, "")
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_files_reports_transform_errors() {
+        let batch = BatchLinter::new(Linter::new()).with_threads(2);
+        let files = [
+            ("App.jsx", r#"const a = <div className="a" title=<b /> />;"#),
+            ("Ok.jsx", "const a = <div class=\"a\" />;"),
+        ];
+
+        let results = batch.check_files(&files, GenerateMode::Dom);
+        assert!(has_rule(&results[0], "no-react-specific-props"));
+        assert!(has_rule(&results[0], crate::linter::TRANSFORM_ERROR));
+        assert!(results[1].diagnostics().is_empty());
+    }
+
+    const PROJECT: &[(&str, &str)] = &[
+        (
+            "src/App.tsx",
+            r#"import { Buton } from "./ui";
+import { createEffect } from "./solid";
+import { ItemList } from "./ItemList";
+export function App(props) {
+  createEffect(5);
+  return <div><Buton /><ItemList items={props.items} /></div>;
+}"#,
+        ),
+        ("src/ui/index.ts", r#"export * from "./Button";"#),
+        (
+            "src/ui/Button.tsx",
+            "export function Button(props) { return <button>{props.children}</button>; }",
+        ),
+        (
+            "src/solid.ts",
+            r#"export { createEffect } from "solid-js";"#,
+        ),
+        (
+            "src/ItemList.tsx",
+            r#"import { ids } from "./lib/items";
+import { useTitle } from "./lib/title.js";
+export function ItemList(props) {
+  return <p title={useTitle(props)}>{ids(props).join()}</p>;
+}"#,
+        ),
+        (
+            "src/lib/items.ts",
+            "export function ids(props) { return props.items.map(({ id }) => id); }",
+        ),
+        (
+            "src/lib/title.ts",
+            "export const useTitle = ({ title }) => title;",
+        ),
+    ];
+
+    fn messages(result: &FileLintResult) -> Vec<String> {
+        result
+            .diagnostics()
+            .iter()
+            .map(|d| format!("{}: {}", d.rule, d.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_project_analysis() {
+        let batch = BatchLinter::new(Linter::new()).with_project_analysis(true);
+        let results = batch.lint_files(PROJECT);
+
+        let app = messages(&results[0]);
+        assert_eq!(app.len(), 2, "{app:?}");
+        assert!(app[0].starts_with("reactivity:") && app[0].contains("createEffect"));
+        assert_eq!(app[1], "jsx-no-undef: 'Buton' is not exported by './ui'.");
+        let items = messages(&results[5]);
+        assert_eq!(items.len(), 1, "{items:?}");
+        assert!(items[0].contains("`props.items`"));
+        let title = messages(&results[6]);
+        assert_eq!(title.len(), 1, "{title:?}");
+        assert!(title[0].starts_with("no-destructure:"));
+
+        let results = BatchLinter::new(Linter::new()).lint_files(PROJECT);
+        assert!(results.iter().all(|result| result.diagnostics().is_empty()));
+    }
+
+    #[test]
+    fn test_project_changes_invalidate_cache() {
+        let batch = BatchLinter::new(Linter::new()).with_project_analysis(true);
+        let first = batch.lint_files(PROJECT);
+        assert!(first.iter().all(|result| !result.cached));
+        assert!(batch.lint_files(PROJECT).iter().all(|result| result.cached));
+
+        let mut files = PROJECT.to_vec();
+        files[2].1 = "export function Buton() { return <button />; }";
+        let results = batch.lint_files(&files);
+        assert!(results.iter().all(|result| !result.cached));
+        assert!(!has_rule(&results[0], "jsx-no-undef"));
+    }
+}
@@ -0,0 +1,743 @@
+//! `solid-lint` command line interface
+//!
+//! Lints the given files and directories with every Solid rule and prints
+//! the diagnostics grouped by file. Rules are configured through
+//! `.solidlintrc.json` in the working directory or `--config`.
+//!
+//! `solid-lint check` also runs the Solid transform on every file, without
+//! writing its output, and reports what it rejects next to the lint results.
+
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use oxc_span::SourceType;
+use solid_jsx_oxc::GenerateMode;
+use solid_linter::utils::offset_to_line_col;
+use solid_linter::{
+    BatchLinter, Diagnostic, DiagnosticSeverity, FileDiff, LintConfig, LintResult, Linter,
+    RuleTimings, CONFIG_FILE_NAME,
+};
+
+/// Number of rules listed by `--timing`
+const TIMING_ROWS: usize = 10;
+
+/// File extensions picked up when walking directories
+const EXTENSIONS: &[&str] = &["js", "jsx", "mjs", "cjs", "ts", "tsx", "mts", "cts"];
+
+const HELP: &str = "\
+Usage: solid-lint [check] [OPTIONS] <PATH>...
+       solid-lint [check] [OPTIONS] --stdin [--stdin-filename <PATH>]
+       solid-lint --print-config-schema
+
+Lint Solid source files. Directories are searched recursively.
+
+With `check`, every file is also compiled with the Solid transform (the
+output is discarded) and code the transform rejects is reported as
+`transform-error`, sharing the parse with the lint rules.
+
+Options:
+  -c, --config <PATH>      Rule configuration (default: ./.solidlintrc.json if present)
+  --fix                    Apply fixes and write the results back to disk
+  --fix-dry-run            Print the fixes as a unified diff instead of writing them
+  --stdin                  Lint source text read from standard input
+  --stdin-filename <PATH>  File name used for stdin (picks the source type)
+  -f, --format <pretty|compact>
+                           Output format (default: pretty); compact prints one
+                           `file:line:col: severity rule message` line per diagnostic
+  --project                Resolve imports between the linted files, so rules know
+                           what other files export and pass props to
+  --timing                 Print the slowest rules after linting
+  --quiet                  Report errors only
+  --max-warnings <N>       Fail when there are more than N warnings
+  --report-unused-disable-directives
+                           Report disable directives that suppress nothing as errors
+  --report-unused-disable-directives-severity <off|hint|info|warn|error>
+                           Same, with the given severity
+  --generate <dom|ssr>     Transform output checked by `check` (default: dom)
+  --print-config-schema    Print the JSON Schema of the config file and exit
+  -h, --help               Print this help
+
+Info and hint diagnostics are listed but never fail the run. Exit status is
+0 when linting passed, 1 when there were errors or more warnings than
+`--max-warnings` allows, and 2 on usage or I/O errors.
+";
+
+/// How diagnostics are printed
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    /// Grouped by file, with a header per file
+    #[default]
+    Pretty,
+    /// One `file:line:col: severity rule message` line per diagnostic
+    Compact,
+}
+
+#[derive(Debug, Default)]
+struct CliOptions {
+    paths: Vec<PathBuf>,
+    config: Option<PathBuf>,
+    fix: bool,
+    /// Print the fixes as diffs without writing them
+    fix_dry_run: bool,
+    stdin: bool,
+    stdin_filename: Option<PathBuf>,
+    /// Lint the files as one project (`BatchLinter::with_project_analysis`)
+    project: bool,
+    timing: bool,
+    quiet: bool,
+    format: OutputFormat,
+    max_warnings: Option<usize>,
+    report_unused_directives: Option<DiagnosticSeverity>,
+    print_config_schema: bool,
+    /// `check`: also dry-run the transform with this output mode
+    check: Option<GenerateMode>,
+    /// `--generate`, only valid with `check`
+    generate: Option<GenerateMode>,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Option<CliOptions>, String> {
+    let mut options = CliOptions::default();
+    let mut args = args.peekable();
+    if args.next_if(|arg| arg == "check").is_some() {
+        options.check = Some(GenerateMode::Dom);
+    }
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-c" | "--config" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "`--config` requires a value".to_string())?;
+                options.config = Some(PathBuf::from(value));
+            }
+            "--fix" => options.fix = true,
+            "--fix-dry-run" => options.fix_dry_run = true,
+            "--stdin" => options.stdin = true,
+            "--stdin-filename" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "`--stdin-filename` requires a value".to_string())?;
+                options.stdin_filename = Some(PathBuf::from(value));
+            }
+            "--project" => options.project = true,
+            "--timing" => options.timing = true,
+            "--quiet" => options.quiet = true,
+            "-f" | "--format" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "`--format` requires a value".to_string())?;
+                options.format = parse_format(&value)?;
+            }
+            "--max-warnings" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "`--max-warnings` requires a value".to_string())?;
+                options.max_warnings = Some(parse_max_warnings(&value)?);
+            }
+            "--report-unused-disable-directives" => {
+                options.report_unused_directives = Some(DiagnosticSeverity::Error);
+            }
+            "--report-unused-disable-directives-severity" => {
+                let value = args.next().ok_or_else(|| {
+                    "`--report-unused-disable-directives-severity` requires a value".to_string()
+                })?;
+                options.report_unused_directives = parse_severity(&value)?;
+            }
+            "--generate" => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| "`--generate` requires a value".to_string())?;
+                options.generate = Some(parse_generate(&value)?);
+            }
+            "--print-config-schema" => options.print_config_schema = true,
+            "-h" | "--help" => return Ok(None),
+            flag if flag.starts_with("--config=") => {
+                options.config = Some(PathBuf::from(&flag["--config=".len()..]));
+            }
+            flag if flag.starts_with("--stdin-filename=") => {
+                options.stdin_filename =
+                    Some(PathBuf::from(&flag["--stdin-filename=".len()..]));
+            }
+            flag if flag.starts_with("--max-warnings=") => {
+                options.max_warnings =
+                    Some(parse_max_warnings(&flag["--max-warnings=".len()..])?);
+            }
+            flag if flag.starts_with("--format=") => {
+                options.format = parse_format(&flag["--format=".len()..])?;
+            }
+            flag if flag.starts_with("--generate=") => {
+                options.generate = Some(parse_generate(&flag["--generate=".len()..])?);
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option `{}`", flag)),
+            path => options.paths.push(PathBuf::from(path)),
+        }
+    }
+
+    if options.print_config_schema {
+        return Ok(Some(options));
+    }
+    if options.fix && options.fix_dry_run {
+        return Err("`--fix` cannot be combined with `--fix-dry-run`".to_string());
+    }
+    if let Some(generate) = options.generate {
+        if options.check.is_none() {
+            return Err("`--generate` requires `check`".to_string());
+        }
+        options.check = Some(generate);
+    }
+    if options.stdin {
+        if !options.paths.is_empty() {
+            return Err("paths cannot be combined with `--stdin`".to_string());
+        }
+        if options.fix {
+            return Err("`--fix` cannot write back to stdin".to_string());
+        }
+    } else if options.stdin_filename.is_some() {
+        return Err("`--stdin-filename` requires `--stdin`".to_string());
+    } else if options.paths.is_empty() {
+        return Err("no paths given".to_string());
+    }
+    Ok(Some(options))
+}
+
+fn parse_severity(value: &str) -> Result<Option<DiagnosticSeverity>, String> {
+    match value {
+        "off" | "0" => Ok(None),
+        "hint" => Ok(Some(DiagnosticSeverity::Hint)),
+        "info" => Ok(Some(DiagnosticSeverity::Info)),
+        "warn" | "1" => Ok(Some(DiagnosticSeverity::Warning)),
+        "error" | "2" => Ok(Some(DiagnosticSeverity::Error)),
+        _ => Err(format!("invalid severity `{}`", value)),
+    }
+}
+
+fn parse_format(value: &str) -> Result<OutputFormat, String> {
+    match value {
+        "pretty" => Ok(OutputFormat::Pretty),
+        "compact" => Ok(OutputFormat::Compact),
+        _ => Err(format!("invalid format `{}`", value)),
+    }
+}
+
+fn parse_generate(value: &str) -> Result<GenerateMode, String> {
+    match value {
+        "dom" => Ok(GenerateMode::Dom),
+        "ssr" => Ok(GenerateMode::Ssr),
+        _ => Err(format!("invalid output mode `{}`", value)),
+    }
+}
+
+fn parse_max_warnings(value: &str) -> Result<usize, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid warning count `{}`", value))
+}
+
+fn source_type_for(path: &Path) -> SourceType {
+    SourceType::from_path(path).unwrap_or(SourceType::jsx())
+}
+
+fn has_lintable_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| EXTENSIONS.contains(&ext))
+}
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        files.push(path.to_path_buf());
+        return Ok(());
+    }
+
+    let mut entries: Vec<_> = fs::read_dir(path)?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|entry| entry.path());
+    for entry in entries {
+        let entry_path = entry.path();
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if name.starts_with('.') || name == "node_modules" {
+            continue;
+        }
+        if entry_path.is_dir() {
+            collect_files(&entry_path, files)?;
+        } else if has_lintable_extension(&entry_path) {
+            files.push(entry_path);
+        }
+    }
+    Ok(())
+}
+
+/// Batch linter with `--config`, or `.solidlintrc.json` when it exists, applied
+fn batch_linter(base: Linter, config_path: Option<&Path>) -> Result<BatchLinter, String> {
+    let batch = BatchLinter::new(base);
+    let path = match config_path {
+        Some(path) => path.to_path_buf(),
+        None if Path::new(CONFIG_FILE_NAME).is_file() => PathBuf::from(CONFIG_FILE_NAME),
+        None => return Ok(batch),
+    };
+    let config = LintConfig::load(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    for warning in config.warnings() {
+        eprintln!("solid-lint: {}: warning: {}", path.display(), warning);
+    }
+    batch.with_config(config, absolute(path.parent().unwrap_or(Path::new(""))))
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::env::current_dir()
+        .map(|cwd| cwd.join(path))
+        .unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// `path` relative to the working directory, or as given when it is outside
+fn relative(path: &Path) -> PathBuf {
+    std::env::current_dir()
+        .ok()
+        .and_then(|cwd| absolute(path).strip_prefix(cwd).ok().map(Path::to_path_buf))
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+fn severity_label(severity: DiagnosticSeverity) -> &'static str {
+    match severity {
+        DiagnosticSeverity::Error => "error",
+        DiagnosticSeverity::Warning => "warning",
+        DiagnosticSeverity::Info => "info",
+        DiagnosticSeverity::Hint => "hint",
+    }
+}
+
+fn print_file_diagnostics(path: &Path, source_text: &str, diagnostics: &[Diagnostic]) {
+    if diagnostics.is_empty() {
+        return;
+    }
+    println!("{}", path.display());
+    for diagnostic in diagnostics {
+        let (line, column) = offset_to_line_col(source_text, diagnostic.start);
+        println!(
+            "  {}:{}  {}  {}  {}",
+            line,
+            column,
+            severity_label(diagnostic.severity),
+            diagnostic.message,
+            diagnostic.rule
+        );
+    }
+    println!();
+}
+
+fn print_compact_diagnostics(path: &Path, source_text: &str, diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        println!("{}", compact_line(path, source_text, diagnostic));
+    }
+}
+
+fn compact_line(path: &Path, source_text: &str, diagnostic: &Diagnostic) -> String {
+    let (line, column) = offset_to_line_col(source_text, diagnostic.start);
+    format!(
+        "{}:{}:{}: {} {} {}",
+        path.display(),
+        line,
+        column,
+        severity_label(diagnostic.severity),
+        diagnostic.rule,
+        diagnostic.message
+    )
+}
+
+/// Totals accumulated while linting every input
+#[derive(Default)]
+struct Summary {
+    /// Drop everything but errors (`--quiet`)
+    quiet: bool,
+    format: OutputFormat,
+    errors: usize,
+    /// Counted even when `--quiet` hides them, for `--max-warnings`
+    warnings: usize,
+    /// Info and hint diagnostics
+    notes: usize,
+    timings: RuleTimings,
+}
+
+impl Summary {
+    /// Print the diagnostics of one source text and add them to the totals
+    fn record(
+        &mut self,
+        path: &Path,
+        source_text: &str,
+        result: Result<LintResult, Vec<Diagnostic>>,
+    ) {
+        let mut diagnostics = match result {
+            Ok(result) => {
+                if let Some(file_timings) = &result.timings {
+                    self.timings.merge(file_timings);
+                }
+                result.diagnostics
+            }
+            Err(parse_errors) => parse_errors,
+        };
+        for diagnostic in &diagnostics {
+            match diagnostic.severity {
+                DiagnosticSeverity::Error => self.errors += 1,
+                DiagnosticSeverity::Warning => self.warnings += 1,
+                DiagnosticSeverity::Info | DiagnosticSeverity::Hint => self.notes += 1,
+            }
+        }
+        if self.quiet {
+            diagnostics.retain(|diagnostic| diagnostic.severity == DiagnosticSeverity::Error);
+        }
+        match self.format {
+            OutputFormat::Pretty => print_file_diagnostics(path, source_text, &diagnostics),
+            OutputFormat::Compact => print_compact_diagnostics(path, source_text, &diagnostics),
+        }
+    }
+
+    /// Whether there are more warnings than `--max-warnings` allows
+    fn too_many_warnings(&self, max_warnings: Option<usize>) -> bool {
+        max_warnings.is_some_and(|max| self.warnings > max)
+    }
+}
+
+/// Print the diff `--fix` would write for `path` and return the fixed text,
+/// which is then linted in place of the original
+fn fix_dry_run(batch: &BatchLinter, path: &Path, source_text: String) -> String {
+    let linter = batch.linter_for(&absolute(path));
+    let fixed = linter.fix_source(&source_text, source_type_for(path));
+    if !fixed.fixed() {
+        return source_text;
+    }
+    let diff = FileDiff::new(
+        relative(path).to_string_lossy(),
+        &source_text,
+        &fixed.output,
+    );
+    print!("{}", diff);
+    fixed.output
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{}", HELP);
+            return ExitCode::SUCCESS;
+        }
+        Err(message) => {
+            eprintln!("solid-lint: {}\n\n{}", message, HELP);
+            return ExitCode::from(2);
+        }
+    };
+
+    if options.print_config_schema {
+        let schema = LintConfig::json_schema();
+        println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+        return ExitCode::SUCCESS;
+    }
+
+    let base_linter = Linter::new()
+        .with_timing(options.timing)
+        .with_report_unused_directives(options.report_unused_directives);
+    let batch = match batch_linter(base_linter, options.config.as_deref()) {
+        Ok(batch) => batch.with_project_analysis(options.project),
+        Err(message) => {
+            eprintln!("solid-lint: {}", message);
+            return ExitCode::from(2);
+        }
+    };
+    let mut summary = Summary {
+        quiet: options.quiet,
+        format: options.format,
+        ..Summary::default()
+    };
+
+    if options.stdin {
+        let mut source_text = String::new();
+        if let Err(err) = std::io::stdin().read_to_string(&mut source_text) {
+            eprintln!("solid-lint: <stdin>: {}", err);
+            return ExitCode::from(2);
+        }
+        let path = options
+            .stdin_filename
+            .clone()
+            .unwrap_or_else(|| PathBuf::from("<stdin>"));
+        if options.fix_dry_run {
+            source_text = fix_dry_run(&batch, &path, source_text);
+        }
+        let linted = match options.check {
+            Some(generate) => batch.check_file(absolute(&path), &source_text, generate),
+            None => batch.lint_file(absolute(&path), &source_text),
+        };
+        summary.record(&path, &source_text, linted.result);
+    }
+
+    let mut files = Vec::new();
+    for path in &options.paths {
+        if let Err(err) = collect_files(path, &mut files) {
+            eprintln!("solid-lint: {}: {}", path.display(), err);
+            return ExitCode::from(2);
+        }
+    }
+
+    let mut sources = Vec::with_capacity(files.len());
+    for file in &files {
+        let mut source_text = match fs::read_to_string(file) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("solid-lint: {}: {}", file.display(), err);
+                return ExitCode::from(2);
+            }
+        };
+
+        if options.fix_dry_run {
+            source_text = fix_dry_run(&batch, file, source_text);
+        } else if options.fix {
+            let linter = batch.linter_for(&absolute(file));
+            let fixed = linter.fix_source(&source_text, source_type_for(file));
+            if fixed.fixed() {
+                if let Err(err) = fs::write(file, &fixed.output) {
+                    eprintln!("solid-lint: {}: {}", file.display(), err);
+                    return ExitCode::from(2);
+                }
+                source_text = fixed.output;
+            }
+        }
+
+        sources.push((absolute(file), source_text));
+    }
+
+    let results = match options.check {
+        Some(generate) => batch.check_files(&sources, generate),
+        None => batch.lint_files(&sources),
+    };
+    for ((file, (_, source_text)), linted) in files.iter().zip(&sources).zip(results) {
+        summary.record(file, source_text, linted.result);
+    }
+
+    let too_many_warnings = summary.too_many_warnings(options.max_warnings);
+    let Summary {
+        errors,
+        warnings,
+        notes,
+        timings,
+        ..
+    } = summary;
+    // The totals only cover what was printed
+    let (warnings, notes) = if options.quiet {
+        (0, 0)
+    } else {
+        (warnings, notes)
+    };
+    let problems = errors + warnings;
+    if problems > 0 {
+        println!(
+            "{} {} ({} {}, {} {})",
+            problems,
+            if problems == 1 { "problem" } else { "problems" },
+            errors,
+            if errors == 1 { "error" } else { "errors" },
+            warnings,
+            if warnings == 1 { "warning" } else { "warnings" },
+        );
+    }
+    if notes > 0 {
+        println!("{} {} (info and hints)", notes, if notes == 1 { "note" } else { "notes" });
+    }
+
+    if options.timing {
+        if problems + notes > 0 {
+            println!();
+        }
+        print!("{}", timings.format_table(TIMING_ROWS));
+    }
+
+    if too_many_warnings && errors == 0 {
+        println!(
+            "solid-lint found too many warnings (maximum: {})",
+            options.max_warnings.unwrap_or_default()
+        );
+    }
+
+    if errors > 0 || too_many_warnings {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<CliOptions>, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_paths() {
+        let options = parse(&["--fix", "--project", "src", "App.tsx"]).unwrap().unwrap();
+        assert!(options.fix);
+        assert!(options.project);
+        assert_eq!(options.paths, vec![PathBuf::from("src"), PathBuf::from("App.tsx")]);
+    }
+
+    #[test]
+    fn test_parse_fix_dry_run() {
+        let options = parse(&["--fix-dry-run", "src"]).unwrap().unwrap();
+        assert!(options.fix_dry_run);
+        assert!(!options.fix);
+
+        // Nothing is written, so stdin works too
+        let options = parse(&["--stdin", "--fix-dry-run"]).unwrap().unwrap();
+        assert!(options.fix_dry_run);
+    }
+
+    #[test]
+    fn test_parse_config() {
+        let options = parse(&["-c", "lint.json", "src"]).unwrap().unwrap();
+        assert_eq!(options.config, Some(PathBuf::from("lint.json")));
+
+        let options = parse(&["--config=lint.json", "src"]).unwrap().unwrap();
+        assert_eq!(options.config, Some(PathBuf::from("lint.json")));
+
+        assert!(parse(&["src", "--config"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_print_config_schema() {
+        let options = parse(&["--print-config-schema"]).unwrap().unwrap();
+        assert!(options.print_config_schema);
+        assert!(options.paths.is_empty());
+    }
+
+    #[test]
+    fn test_parse_stdin() {
+        let options = parse(&["--stdin", "--stdin-filename", "foo.tsx"]).unwrap().unwrap();
+        assert!(options.stdin);
+        assert_eq!(options.stdin_filename, Some(PathBuf::from("foo.tsx")));
+
+        let options = parse(&["--stdin", "--stdin-filename=bar.jsx"]).unwrap().unwrap();
+        assert_eq!(options.stdin_filename, Some(PathBuf::from("bar.jsx")));
+    }
+
+    #[test]
+    fn test_parse_unused_directives() {
+        let options = parse(&["--report-unused-disable-directives", "src"]).unwrap().unwrap();
+        assert_eq!(options.report_unused_directives, Some(DiagnosticSeverity::Error));
+
+        let options = parse(&["--report-unused-disable-directives-severity", "warn", "src"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.report_unused_directives, Some(DiagnosticSeverity::Warning));
+
+        let options = parse(&["--report-unused-disable-directives-severity", "hint", "src"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.report_unused_directives, Some(DiagnosticSeverity::Hint));
+
+        assert!(parse(&["--report-unused-disable-directives-severity", "loud", "src"]).is_err());
+    }
+
+    #[test]
+    fn test_parse_quiet_and_max_warnings() {
+        let options = parse(&["--quiet", "--max-warnings", "3", "src"]).unwrap().unwrap();
+        assert!(options.quiet);
+        assert_eq!(options.max_warnings, Some(3));
+
+        let options = parse(&["--max-warnings=0", "src"]).unwrap().unwrap();
+        assert_eq!(options.max_warnings, Some(0));
+
+        assert!(parse(&["--max-warnings", "-1", "src"]).is_err());
+        assert!(parse(&["--max-warnings", "src"]).is_err());
+    }
+
+    #[test]
+    fn test_quiet_with_max_warnings() {
+        let source_text = "const a = <div className=\"a\" />;";
+        let mut summary = Summary {
+            quiet: true,
+            format: OutputFormat::Compact,
+            ..Summary::default()
+        };
+        let diagnostics = vec![Diagnostic::warning(
+            "no-react-specific-props",
+            oxc_span::Span::new(15, 24),
+            "Prefer `class`.",
+        )];
+        summary.record(
+            Path::new("App.jsx"),
+            source_text,
+            Ok(LintResult {
+                diagnostics,
+                used_vars: Vec::new(),
+                timings: None,
+            }),
+        );
+
+        // `--quiet --max-warnings 0` fails on the warning it doesn't print
+        assert_eq!(summary.errors, 0);
+        assert!(summary.too_many_warnings(Some(0)));
+        assert!(!summary.too_many_warnings(Some(1)));
+    }
+
+    #[test]
+    fn test_parse_stdin_conflicts() {
+        assert!(parse(&["--stdin", "src"]).is_err());
+        assert!(parse(&["--stdin", "--fix"]).is_err());
+        assert!(parse(&["--fix", "--fix-dry-run", "src"]).is_err());
+        assert!(parse(&["--stdin-filename", "foo.tsx", "src"]).is_err());
+        assert!(parse(&["--stdin", "--stdin-filename"]).is_err());
+        assert!(parse(&[]).is_err());
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(parse(&["src"]).unwrap().unwrap().format, OutputFormat::Pretty);
+
+        let options = parse(&["--format", "compact", "src"]).unwrap().unwrap();
+        assert_eq!(options.format, OutputFormat::Compact);
+
+        let options = parse(&["-f", "pretty", "src"]).unwrap().unwrap();
+        assert_eq!(options.format, OutputFormat::Pretty);
+
+        let options = parse(&["--format=compact", "src"]).unwrap().unwrap();
+        assert_eq!(options.format, OutputFormat::Compact);
+
+        assert!(parse(&["--format", "xml", "src"]).is_err());
+        assert!(parse(&["src", "--format"]).is_err());
+    }
+
+    #[test]
+    fn test_compact_line() {
+        let source_text = "const a = 1;\nconst b = <div className=\"b\" />;";
+        let diagnostic = Diagnostic::warning(
+            "no-react-specific-props",
+            oxc_span::Span::new(28, 37),
+            "Prefer `class`.",
+        );
+        assert_eq!(
+            compact_line(Path::new("src/App.jsx"), source_text, &diagnostic),
+            "src/App.jsx:2:16: warning no-react-specific-props Prefer `class`."
+        );
+    }
+
+    #[test]
+    fn test_parse_check() {
+        let options = parse(&["check", "src"]).unwrap().unwrap();
+        assert_eq!(options.check, Some(GenerateMode::Dom));
+        assert_eq!(options.paths, vec![PathBuf::from("src")]);
+
+        let options = parse(&["check", "--generate", "ssr", "--stdin"]).unwrap().unwrap();
+        assert_eq!(options.check, Some(GenerateMode::Ssr));
+
+        let options = parse(&["check", "--generate=dom", "src"]).unwrap().unwrap();
+        assert_eq!(options.check, Some(GenerateMode::Dom));
+
+        // Only the first argument is the subcommand
+        let options = parse(&["src", "check"]).unwrap().unwrap();
+        assert_eq!(options.check, None);
+        assert_eq!(options.paths, vec![PathBuf::from("src"), PathBuf::from("check")]);
+
+        assert!(parse(&["--generate", "ssr", "src"]).is_err());
+        assert!(parse(&["check", "--generate", "universal", "src"]).is_err());
+        assert!(parse(&["check", "--generate"]).is_err());
+    }
+}
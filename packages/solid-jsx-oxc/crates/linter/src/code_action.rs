@@ -0,0 +1,226 @@
+//! Editor-neutral code actions
+//!
+//! Converts diagnostics and their fixes into a serializable payload with
+//! line/character ranges, so editor integrations (LSP server, VSCode
+//! extension) don't have to map byte offsets themselves.
+//!
+//! Positions follow the LSP convention: lines and characters are 0-based and
+//! characters are counted in UTF-16 code units.
+
+use serde::Serialize;
+
+use crate::diagnostic::Diagnostic;
+
+/// Kind of a code action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeActionKind {
+    /// Safe fix that can be applied automatically
+    QuickFix,
+    /// Change that may alter behavior and should be reviewed by the user
+    Suggestion,
+}
+
+/// A position in a text document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Position {
+    /// 0-based line
+    pub line: u32,
+    /// 0-based character offset in UTF-16 code units
+    pub character: u32,
+}
+
+/// A range in a text document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// A single text replacement
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextEdit {
+    pub range: Range,
+    pub new_text: String,
+}
+
+/// A code action for one diagnostic
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeAction {
+    /// Human-readable title shown in the editor
+    pub title: String,
+    pub kind: CodeActionKind,
+    /// Rule that produced the diagnostic
+    pub rule: String,
+    /// Range of the diagnostic this action fixes
+    pub diagnostic_range: Range,
+    /// Whether the editor should prefer this action (e.g. for "fix all")
+    pub is_preferred: bool,
+    /// Edits to apply together, in document order
+    pub edits: Vec<TextEdit>,
+}
+
+/// Maps byte offsets to positions
+struct LineIndex<'a> {
+    source_text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    fn new(source_text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source_text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            source_text,
+            line_starts,
+        }
+    }
+
+    fn position(&self, offset: u32) -> Position {
+        let mut offset = (offset as usize).min(self.source_text.len());
+        while !self.source_text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let character = self.source_text[line_start..offset].encode_utf16().count();
+        Position {
+            line: line as u32,
+            character: character as u32,
+        }
+    }
+
+    fn range(&self, start: u32, end: u32) -> Range {
+        Range {
+            start: self.position(start),
+            end: self.position(end),
+        }
+    }
+}
+
+fn build_code_action(index: &LineIndex, diagnostic: &Diagnostic) -> Option<CodeAction> {
+    if diagnostic.fixes.is_empty() {
+        return None;
+    }
+
+    let kind = if diagnostic.fixes.iter().any(|fix| fix.is_suggestion()) {
+        CodeActionKind::Suggestion
+    } else {
+        CodeActionKind::QuickFix
+    };
+
+    let title = diagnostic
+        .fixes
+        .iter()
+        .filter_map(|fix| fix.message.as_deref())
+        .find(|message| !message.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| format!("Fix this {} problem", diagnostic.rule));
+
+    let mut fixes: Vec<_> = diagnostic.fixes.iter().collect();
+    fixes.sort_by_key(|fix| (fix.start, fix.end));
+    let edits = fixes
+        .into_iter()
+        .map(|fix| TextEdit {
+            range: index.range(fix.start, fix.end),
+            new_text: fix.replacement.clone(),
+        })
+        .collect();
+
+    Some(CodeAction {
+        title,
+        kind,
+        rule: diagnostic.rule.clone(),
+        diagnostic_range: index.range(diagnostic.start, diagnostic.end),
+        is_preferred: kind == CodeActionKind::QuickFix,
+        edits,
+    })
+}
+
+/// Build the code action for a single diagnostic, if it has a fix
+pub fn code_action(source_text: &str, diagnostic: &Diagnostic) -> Option<CodeAction> {
+    build_code_action(&LineIndex::new(source_text), diagnostic)
+}
+
+/// Build code actions for every diagnostic that has a fix
+pub fn code_actions(source_text: &str, diagnostics: &[Diagnostic]) -> Vec<CodeAction> {
+    let index = LineIndex::new(source_text);
+    diagnostics
+        .iter()
+        .filter_map(|diagnostic| build_code_action(&index, diagnostic))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Fix;
+    use oxc_span::Span;
+
+    #[test]
+    fn test_positions() {
+        let index = LineIndex::new("ab\n😀cd\nx");
+        assert_eq!(index.position(0), Position { line: 0, character: 0 });
+        assert_eq!(index.position(3), Position { line: 1, character: 0 });
+        // The emoji is 4 bytes and 2 UTF-16 code units
+        assert_eq!(index.position(7), Position { line: 1, character: 2 });
+        assert_eq!(index.position(10), Position { line: 2, character: 0 });
+        assert_eq!(index.position(100), Position { line: 2, character: 1 });
+    }
+
+    #[test]
+    fn test_quick_fix_action() {
+        let source = "const a = 1;\n<div className=\"a\" />";
+        let diagnostic = Diagnostic::warning("no-react-specific-props", Span::new(18, 27), "msg")
+            .with_fix(Fix::new(Span::new(18, 27), "class").with_message("Replace with class"));
+
+        let action = code_action(source, &diagnostic).expect("should have action");
+        assert_eq!(action.title, "Replace with class");
+        assert_eq!(action.kind, CodeActionKind::QuickFix);
+        assert!(action.is_preferred);
+        assert_eq!(action.edits.len(), 1);
+        assert_eq!(
+            action.edits[0].range,
+            Range {
+                start: Position { line: 1, character: 5 },
+                end: Position { line: 1, character: 14 },
+            }
+        );
+        assert_eq!(action.edits[0].new_text, "class");
+    }
+
+    #[test]
+    fn test_suggestion_and_default_title() {
+        let diagnostic = Diagnostic::warning("no-innerhtml", Span::new(0, 3), "msg")
+            .with_fix(Fix::new(Span::new(0, 3), "x").as_suggestion());
+        let action = code_action("abc", &diagnostic).unwrap();
+        assert_eq!(action.kind, CodeActionKind::Suggestion);
+        assert!(!action.is_preferred);
+        assert_eq!(action.title, "Fix this no-innerhtml problem");
+    }
+
+    #[test]
+    fn test_skips_diagnostics_without_fixes() {
+        let diagnostics = vec![
+            Diagnostic::warning("a", Span::new(0, 1), "msg"),
+            Diagnostic::warning("b", Span::new(0, 1), "msg").with_fix(Fix::new(Span::new(0, 1), "")),
+        ];
+        let actions = code_actions("abc", &diagnostics);
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].rule, "b");
+    }
+
+    #[test]
+    fn test_serialize() {
+        let diagnostic = Diagnostic::warning("rule", Span::new(0, 1), "msg")
+            .with_fix(Fix::new(Span::new(0, 1), "b").with_message("Fix it"));
+        let json = serde_json::to_value(code_action("a", &diagnostic).unwrap()).unwrap();
+        assert_eq!(json["kind"], "quickfix");
+        assert_eq!(json["isPreferred"], true);
+        assert_eq!(json["edits"][0]["newText"], "b");
+        assert_eq!(json["diagnosticRange"]["end"]["character"], 1);
+    }
+}
@@ -0,0 +1,905 @@
+//! Lint configuration files
+//!
+//! `solid-lint` reads an eslint-style JSON config, `.solidlintrc.json` by
+//! default:
+//!
+//! ```json
+//! {
+//!   "rules": {
+//!     "prefer-for": "off",
+//!     "self-closing-comp": ["warn", { "html": "void" }]
+//!   },
+//!   "settings": { "componentModules": ["@kobalte/core"] },
+//!   "overrides": [
+//!     { "files": ["**/*.test.tsx"], "rules": { "no-innerhtml": "off" } },
+//!     { "files": ["src/server/**"], "excludedFiles": ["*.d.ts"], "rules": { "reactivity": "error" } }
+//!   ]
+//! }
+//! ```
+//!
+//! A rule is set to `"off"`, `"hint"`, `"info"`, `"warn"` or `"error"` (or
+//! `0`/`1`/`2`), optionally followed by its options in an array. Rule names may keep
+//! eslint-plugin-solid's `solid/` prefix. Overrides apply in order on top of
+//! `rules` for files matching one of `files` and none of `excludedFiles`;
+//! a later setting without options keeps the options set before it.
+//! `settings` ([`LintSettings`]) apply to every file and rule.
+//! Enabling a deprecated rule works, but shows up in `LintConfig::warnings`.
+//!
+//! `LintConfig::json_schema` describes this format, rule options included, for
+//! editors and config validators.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use schemars::generate::SchemaSettings;
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::diagnostic::DiagnosticSeverity;
+use crate::glob::glob_match;
+use crate::linter::Linter;
+use crate::rules::jsx_no_undef::JsxNoUndef;
+use crate::rules::components_return_once::ComponentsReturnOnceConfig;
+use crate::rules::jsx_no_duplicate_props::JsxNoDuplicatePropsConfig;
+use crate::rules::no_proxy_apis::NoProxyApisConfig;
+use crate::rules::self_closing_comp::SelfClosingCompConfig;
+use crate::rules::{
+    AltText, AnchorHasContent, AriaProps, AriaRole, ComponentsReturnOnce, Imports,
+    JsxNoDuplicateProps, JsxNoScriptUrl, JsxUsesVars, NoAutofocus, NoConditionalPrimitiveCreation,
+    NoDestructure, NoInnerhtml, NoProxyApis, NoReactApis, NoReactImports, NoReactSpecificProps,
+    NoReactTypes, NoUnknownNamespaces, PreferClasslist, PreferFor, PreferShow, Reactivity,
+    SelfClosingComp, StyleProp,
+};
+use crate::semantic_visitor::SemanticRulesConfig;
+use crate::settings::LintSettings;
+use crate::visitor::RulesConfig;
+use crate::RuleMeta;
+
+/// File name looked up in the working directory when no config is given
+pub const CONFIG_FILE_NAME: &str = ".solidlintrc.json";
+
+/// A parsed lint configuration
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LintConfig {
+    /// Schema reference for editors, ignored by the linter
+    #[serde(rename = "$schema", default)]
+    pub schema: Option<String>,
+    /// Settings for every file
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleSetting>,
+    /// Settings for files matching a set of globs
+    #[serde(default)]
+    pub overrides: Vec<ConfigOverride>,
+    /// Modules shared by every rule
+    #[serde(default)]
+    pub settings: LintSettings,
+}
+
+/// Rule settings for the files matching `files`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ConfigOverride {
+    /// Globs relative to the config file
+    pub files: Vec<String>,
+    /// Globs of files to leave out even when they match `files`
+    #[serde(default)]
+    pub excluded_files: Vec<String>,
+    #[serde(default)]
+    pub rules: BTreeMap<String, RuleSetting>,
+}
+
+impl ConfigOverride {
+    pub fn matches(&self, path: &str) -> bool {
+        self.files.iter().any(|glob| glob_match(glob, path))
+            && !self
+                .excluded_files
+                .iter()
+                .any(|glob| glob_match(glob, path))
+    }
+}
+
+/// Severity and options of one rule
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "Value")]
+pub struct RuleSetting {
+    /// `None` turns the rule off
+    pub severity: Option<DiagnosticSeverity>,
+    pub options: Option<Value>,
+}
+
+impl TryFrom<Value> for RuleSetting {
+    type Error = String;
+
+    fn try_from(value: Value) -> Result<Self, String> {
+        let (level, options) = match value {
+            Value::Array(mut items) if !items.is_empty() && items.len() <= 2 => {
+                let options = (items.len() == 2).then(|| items.pop()).flatten();
+                (items.pop().unwrap_or_default(), options)
+            }
+            level => (level, None),
+        };
+        let severity = match &level {
+            Value::String(level) if level == "off" => None,
+            Value::String(level) if level == "hint" => Some(DiagnosticSeverity::Hint),
+            Value::String(level) if level == "info" => Some(DiagnosticSeverity::Info),
+            Value::String(level) if level == "warn" => Some(DiagnosticSeverity::Warning),
+            Value::String(level) if level == "error" => Some(DiagnosticSeverity::Error),
+            Value::Number(level) if level.as_u64() == Some(0) => None,
+            Value::Number(level) if level.as_u64() == Some(1) => Some(DiagnosticSeverity::Warning),
+            Value::Number(level) if level.as_u64() == Some(2) => Some(DiagnosticSeverity::Error),
+            _ => {
+                return Err(format!(
+                    "invalid rule setting `{}`, expected \"off\", \"hint\", \"info\", \"warn\" or \"error\"",
+                    level
+                ))
+            }
+        };
+        Ok(Self { severity, options })
+    }
+}
+
+impl LintConfig {
+    /// Parse a config from JSON, checking every rule name and its options
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        let config: Self = serde_json::from_str(text).map_err(|err| err.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check every rule name and its options, including those of overrides
+    pub fn validate(&self) -> Result<(), String> {
+        self.apply(&Linter::new(), &self.rules)?;
+        for (index, config_override) in self.overrides.iter().enumerate() {
+            if config_override.files.is_empty() {
+                return Err(format!("overrides[{}]: `files` must not be empty", index));
+            }
+            self.apply(&Linter::new(), &config_override.rules)
+                .map_err(|err| format!("overrides[{}]: {}", index, err))?;
+        }
+        Ok(())
+    }
+
+    /// JSON Schema (draft 7) of the config format, with the options of every
+    /// rule derived from their serde types
+    pub fn json_schema() -> Value {
+        let mut generator = SchemaSettings::draft07().for_deserialize().into_generator();
+        let mut definitions = Map::new();
+        let mut rules = Map::new();
+        for (name, options) in rule_options(&mut generator) {
+            let mut setting = rule_setting_schema(options);
+            if let Some(replaced_by) = deprecated_rule(name) {
+                setting.insert("deprecated".to_string(), Value::Bool(true));
+                let message = match replaced_by {
+                    Some(replacement) => format!("Deprecated, use `{}` instead", replacement),
+                    None => "Deprecated".to_string(),
+                };
+                setting.insert("deprecationMessage".to_string(), Value::String(message));
+            }
+            let reference = json_schema!({
+                "$ref": format!("#/definitions/{}", name.replace('~', "~0").replace('/', "~1"))
+            });
+            rules.insert(format!("solid/{}", name), reference.clone().to_value());
+            rules.insert(name.to_string(), reference.to_value());
+            definitions.insert(name.to_string(), setting.to_value());
+        }
+        definitions.extend(generator.take_definitions(true));
+        definitions.insert(
+            "RuleSeverity".to_string(),
+            json_schema!({ "enum": ["off", "hint", "info", "warn", "error", 0, 1, 2] }).to_value(),
+        );
+        definitions.insert(
+            "Rules".to_string(),
+            json_schema!({
+                "type": "object",
+                "properties": rules,
+                "additionalProperties": false
+            })
+            .to_value(),
+        );
+
+        let globs = json_schema!({ "type": "array", "items": { "type": "string" } });
+        let settings = generator.subschema_for::<LintSettings>();
+        definitions.extend(generator.take_definitions(true));
+        json_schema!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "solid-lint config",
+            "type": "object",
+            "properties": {
+                "$schema": { "type": "string" },
+                "rules": { "$ref": "#/definitions/Rules" },
+                "settings": settings,
+                "overrides": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "files": { "type": "array", "items": { "type": "string" }, "minItems": 1 },
+                            "excludedFiles": globs,
+                            "rules": { "$ref": "#/definitions/Rules" }
+                        },
+                        "required": ["files"],
+                        "additionalProperties": false
+                    }
+                }
+            },
+            "additionalProperties": false,
+            "definitions": definitions
+        })
+        .to_value()
+    }
+
+    /// Problems that don't stop the config from loading, such as enabled
+    /// deprecated rules
+    pub fn warnings(&self) -> Vec<String> {
+        let layers = std::iter::once(&self.rules)
+            .chain(self.overrides.iter().map(|config_override| &config_override.rules));
+        let mut warnings = BTreeSet::new();
+        for rules in layers {
+            for (name, setting) in rules {
+                let name = rule_name(name);
+                if setting.severity.is_none() {
+                    continue;
+                }
+                let Some(replaced_by) = deprecated_rule(name) else {
+                    continue;
+                };
+                warnings.insert(match replaced_by {
+                    Some(replacement) => format!(
+                        "rule `{}` is deprecated, use `{}` instead",
+                        name, replacement
+                    ),
+                    None => format!("rule `{}` is deprecated", name),
+                });
+            }
+        }
+        warnings.into_iter().collect()
+    }
+
+    /// Read and parse a config file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+        Self::from_json(&text)
+    }
+
+    /// Effective rule settings for a file, given relative to the config file
+    pub fn settings_for(&self, path: &Path) -> BTreeMap<String, RuleSetting> {
+        let path = path.to_string_lossy().replace('\\', "/");
+        let mut settings = BTreeMap::new();
+        let layers = std::iter::once(&self.rules).chain(
+            self.overrides
+                .iter()
+                .filter(|config_override| config_override.matches(&path))
+                .map(|config_override| &config_override.rules),
+        );
+        for rules in layers {
+            for (name, setting) in rules {
+                let name = rule_name(name).to_string();
+                let previous: Option<&RuleSetting> = settings.get(&name);
+                let options = setting
+                    .options
+                    .clone()
+                    .or_else(|| previous.and_then(|previous| previous.options.clone()));
+                settings.insert(
+                    name,
+                    RuleSetting {
+                        severity: setting.severity,
+                        options,
+                    },
+                );
+            }
+        }
+        settings
+    }
+
+    /// `base` with the settings that apply to `path` (relative to the config
+    /// file) on top
+    pub fn linter_for(&self, base: &Linter, path: &Path) -> Result<Linter, String> {
+        self.apply(base, &self.settings_for(path))
+    }
+
+    fn apply(
+        &self,
+        base: &Linter,
+        settings: &BTreeMap<String, RuleSetting>,
+    ) -> Result<Linter, String> {
+        let mut rules = base.rules().clone();
+        let mut semantic_rules = base.semantic_rules().clone();
+        if self.settings != LintSettings::default() {
+            semantic_rules.settings = self.settings.clone();
+        }
+        let mut severities = Vec::new();
+        for (name, setting) in settings {
+            let name = rule_name(name);
+            configure_rule(&mut rules, &mut semantic_rules, name, setting)
+                .map_err(|err| format!("rule `{}`: {}", name, err))?;
+            if let Some(severity) = setting.severity {
+                severities.push((name.to_string(), severity));
+            }
+        }
+        Ok(severities.into_iter().fold(
+            base.clone()
+                .with_rules(rules)
+                .with_semantic_rules(semantic_rules),
+            |linter, (name, severity)| linter.with_rule_severity(name, severity),
+        ))
+    }
+}
+
+/// Rule name without eslint-plugin-solid's `solid/` prefix
+fn rule_name(name: &str) -> &str {
+    name.strip_prefix("solid/").unwrap_or(name)
+}
+
+fn parse_options<T: serde::de::DeserializeOwned + Default>(
+    options: Option<&Value>,
+) -> Result<T, String> {
+    options.map_or_else(
+        || Ok(T::default()),
+        |options| serde_json::from_value(options.clone()).map_err(|err| err.to_string()),
+    )
+}
+
+/// Options of no-innerhtml
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct NoInnerhtmlOptions {
+    /// Allow `innerHTML` set to a static string
+    allow_static: Option<bool>,
+}
+
+/// Options of no-unknown-namespaces
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct NoUnknownNamespacesOptions {
+    /// Namespaces to accept on top of Solid's own
+    allowed_namespaces: Option<Vec<String>>,
+}
+
+/// Options of style-prop
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct StylePropOptions {
+    /// Props checked as styles, `["style"]` by default
+    style_props: Option<Vec<String>>,
+    /// Allow string values for style props
+    allow_string: Option<bool>,
+}
+
+type Deprecation = Option<(&'static str, Option<&'static str>)>;
+
+fn deprecation<R: RuleMeta>() -> Deprecation {
+    R::DEPRECATED.then_some((R::NAME, R::REPLACED_BY))
+}
+
+/// Name and replacement of every rule the config accepts, for deprecated ones
+const DEPRECATIONS: &[fn() -> Deprecation] = &[
+    deprecation::<Imports>,
+    deprecation::<JsxNoDuplicateProps>,
+    deprecation::<JsxNoScriptUrl>,
+    deprecation::<JsxUsesVars>,
+    deprecation::<NoInnerhtml>,
+    deprecation::<NoReactSpecificProps>,
+    deprecation::<NoUnknownNamespaces>,
+    deprecation::<PreferClasslist>,
+    deprecation::<PreferFor>,
+    deprecation::<PreferShow>,
+    deprecation::<SelfClosingComp>,
+    deprecation::<StyleProp>,
+    deprecation::<NoProxyApis>,
+    deprecation::<JsxNoUndef>,
+    deprecation::<ComponentsReturnOnce>,
+    deprecation::<Reactivity>,
+    deprecation::<NoDestructure>,
+    deprecation::<NoConditionalPrimitiveCreation>,
+    deprecation::<AltText>,
+    deprecation::<AnchorHasContent>,
+    deprecation::<AriaProps>,
+    deprecation::<AriaRole>,
+    deprecation::<NoAutofocus>,
+    deprecation::<NoReactApis>,
+    deprecation::<NoReactImports>,
+    deprecation::<NoReactTypes>,
+];
+
+/// The replacement of `name` if it is a deprecated rule (`Some(None)` when it
+/// has none)
+fn deprecated_rule(name: &str) -> Option<Option<&'static str>> {
+    DEPRECATIONS
+        .iter()
+        .filter_map(|deprecation| deprecation())
+        .find(|(deprecated, _)| *deprecated == name)
+        .map(|(_, replaced_by)| replaced_by)
+}
+
+/// Every rule the config accepts, with the schema of its options if it has any
+fn rule_options(generator: &mut SchemaGenerator) -> Vec<(&'static str, Option<Schema>)> {
+    vec![
+        (
+            JsxNoDuplicateProps::NAME,
+            Some(generator.subschema_for::<JsxNoDuplicatePropsConfig>()),
+        ),
+        (JsxNoScriptUrl::NAME, None),
+        (JsxUsesVars::NAME, None),
+        (
+            NoInnerhtml::NAME,
+            Some(generator.subschema_for::<NoInnerhtmlOptions>()),
+        ),
+        (NoReactSpecificProps::NAME, None),
+        (
+            NoUnknownNamespaces::NAME,
+            Some(generator.subschema_for::<NoUnknownNamespacesOptions>()),
+        ),
+        (PreferClasslist::NAME, None),
+        (PreferFor::NAME, None),
+        (PreferShow::NAME, None),
+        (
+            SelfClosingComp::NAME,
+            Some(generator.subschema_for::<SelfClosingCompConfig>()),
+        ),
+        (
+            StyleProp::NAME,
+            Some(generator.subschema_for::<StylePropOptions>()),
+        ),
+        (Imports::NAME, None),
+        (
+            NoProxyApis::NAME,
+            Some(generator.subschema_for::<NoProxyApisConfig>()),
+        ),
+        (JsxNoUndef::NAME, None),
+        (
+            ComponentsReturnOnce::NAME,
+            Some(generator.subschema_for::<ComponentsReturnOnceConfig>()),
+        ),
+        (Reactivity::NAME, None),
+        (NoDestructure::NAME, None),
+        (NoConditionalPrimitiveCreation::NAME, None),
+        (AltText::NAME, None),
+        (AnchorHasContent::NAME, None),
+        (AriaProps::NAME, None),
+        (AriaRole::NAME, None),
+        (NoAutofocus::NAME, None),
+        (NoReactApis::NAME, None),
+        (NoReactImports::NAME, None),
+        (NoReactTypes::NAME, None),
+    ]
+}
+
+/// A severity, alone or in an array followed by the rule's options
+fn rule_setting_schema(options: Option<Schema>) -> Schema {
+    let severity = json_schema!({ "$ref": "#/definitions/RuleSeverity" });
+    let array = match options {
+        Some(options) => json_schema!({
+            "type": "array",
+            "items": [severity, options],
+            "minItems": 1,
+            "maxItems": 2
+        }),
+        None => json_schema!({
+            "type": "array",
+            "items": [severity],
+            "minItems": 1,
+            "maxItems": 1
+        }),
+    };
+    json_schema!({ "oneOf": [severity, array] })
+}
+
+/// Enable, disable and configure one rule by name
+fn configure_rule(
+    rules: &mut RulesConfig,
+    semantic_rules: &mut SemanticRulesConfig,
+    name: &str,
+    setting: &RuleSetting,
+) -> Result<(), String> {
+    let enabled = setting.severity.is_some();
+    let options = setting.options.as_ref().filter(|_| enabled);
+    let no_options = || match options {
+        Some(_) => Err("this rule has no options".to_string()),
+        None => Ok(()),
+    };
+
+    match name {
+        Imports::NAME => {
+            no_options()?;
+            rules.imports = enabled;
+        }
+        JsxNoDuplicateProps::NAME => {
+            rules.jsx_no_duplicate_props = enabled
+                .then(|| parse_options(options).map(JsxNoDuplicateProps::with_config))
+                .transpose()?;
+        }
+        JsxNoScriptUrl::NAME => {
+            no_options()?;
+            rules.jsx_no_script_url = enabled.then(JsxNoScriptUrl::new);
+        }
+        JsxUsesVars::NAME => {
+            no_options()?;
+            rules.jsx_uses_vars = enabled;
+            semantic_rules.jsx_uses_vars = enabled;
+        }
+        NoInnerhtml::NAME => {
+            let options: NoInnerhtmlOptions = parse_options(options)?;
+            rules.no_innerhtml = enabled.then(|| {
+                let rule = NoInnerhtml::new();
+                match options.allow_static {
+                    Some(allow_static) => rule.with_allow_static(allow_static),
+                    None => rule,
+                }
+            });
+        }
+        NoReactSpecificProps::NAME => {
+            no_options()?;
+            rules.no_react_specific_props = enabled;
+        }
+        NoUnknownNamespaces::NAME => {
+            let options: NoUnknownNamespacesOptions = parse_options(options)?;
+            rules.no_unknown_namespaces = enabled.then(|| {
+                NoUnknownNamespaces::new()
+                    .with_allowed_namespaces(options.allowed_namespaces.unwrap_or_default())
+            });
+        }
+        PreferClasslist::NAME => {
+            no_options()?;
+            rules.prefer_classlist = enabled;
+        }
+        PreferFor::NAME => {
+            no_options()?;
+            rules.prefer_for = enabled;
+        }
+        PreferShow::NAME => {
+            no_options()?;
+            rules.prefer_show = enabled;
+        }
+        SelfClosingComp::NAME => {
+            rules.self_closing_comp = enabled
+                .then(|| parse_options(options).map(SelfClosingComp::with_config))
+                .transpose()?;
+        }
+        StyleProp::NAME => {
+            let options: StylePropOptions = parse_options(options)?;
+            rules.style_prop = enabled.then(|| {
+                let mut rule = StyleProp::new();
+                if let Some(style_props) = options.style_props {
+                    rule = rule.with_style_props(style_props);
+                }
+                if let Some(allow_string) = options.allow_string {
+                    rule = rule.with_allow_string(allow_string);
+                }
+                rule
+            });
+        }
+        NoProxyApis::NAME => {
+            rules.no_proxy_apis = enabled
+                .then(|| parse_options(options).map(NoProxyApis::with_config))
+                .transpose()?;
+        }
+        JsxNoUndef::NAME => {
+            no_options()?;
+            semantic_rules.jsx_no_undef = enabled;
+        }
+        ComponentsReturnOnce::NAME => {
+            semantic_rules.components_return_once = enabled
+                .then(|| parse_options(options).map(ComponentsReturnOnce::with_config))
+                .transpose()?;
+        }
+        Reactivity::NAME => {
+            no_options()?;
+            semantic_rules.reactivity = enabled;
+        }
+        NoDestructure::NAME => {
+            no_options()?;
+            semantic_rules.no_destructure = enabled;
+        }
+        NoConditionalPrimitiveCreation::NAME => {
+            no_options()?;
+            semantic_rules.no_conditional_primitive_creation = enabled;
+        }
+        AltText::NAME => {
+            no_options()?;
+            rules.a11y.alt_text = enabled;
+        }
+        AnchorHasContent::NAME => {
+            no_options()?;
+            rules.a11y.anchor_has_content = enabled;
+        }
+        AriaProps::NAME => {
+            no_options()?;
+            rules.a11y.aria_props = enabled;
+        }
+        AriaRole::NAME => {
+            no_options()?;
+            rules.a11y.aria_role = enabled;
+        }
+        NoAutofocus::NAME => {
+            no_options()?;
+            rules.a11y.no_autofocus = enabled;
+        }
+        NoReactApis::NAME => {
+            no_options()?;
+            semantic_rules.migration.no_react_apis = enabled;
+        }
+        NoReactImports::NAME => {
+            no_options()?;
+            semantic_rules.migration.no_react_imports = enabled;
+        }
+        NoReactTypes::NAME => {
+            no_options()?;
+            semantic_rules.migration.no_react_types = enabled;
+        }
+        _ => return Err("unknown rule".to_string()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc_span::SourceType;
+
+    const CONFIG: &str = r#"{
+        "rules": {
+            "solid/prefer-for": "off",
+            "self-closing-comp": ["warn", { "html": "void" }]
+        },
+        "overrides": [
+            { "files": ["**/*.test.tsx"], "rules": { "prefer-for": "error" } },
+            {
+                "files": ["src/server/**"],
+                "excludedFiles": ["src/server/legacy/**"],
+                "rules": { "self-closing-comp": "error", "no-react-specific-props": 0 }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_settings_for_overrides() {
+        let config = LintConfig::from_json(CONFIG).unwrap();
+
+        let settings = config.settings_for(Path::new("src/App.tsx"));
+        assert_eq!(settings["prefer-for"].severity, None);
+
+        let settings = config.settings_for(Path::new("src/App.test.tsx"));
+        assert_eq!(
+            settings["prefer-for"].severity,
+            Some(DiagnosticSeverity::Error)
+        );
+
+        // A severity-only override keeps the options set before it
+        let settings = config.settings_for(Path::new("src/server/api.tsx"));
+        let self_closing = &settings["self-closing-comp"];
+        assert_eq!(self_closing.severity, Some(DiagnosticSeverity::Error));
+        assert_eq!(
+            self_closing.options,
+            Some(serde_json::json!({ "html": "void" }))
+        );
+        assert_eq!(settings["no-react-specific-props"].severity, None);
+
+        let settings = config.settings_for(Path::new("src/server/legacy/old.tsx"));
+        assert_eq!(
+            settings["self-closing-comp"].severity,
+            Some(DiagnosticSeverity::Warning)
+        );
+    }
+
+    #[test]
+    fn test_linter_for_applies_rules() {
+        let config = LintConfig::from_json(CONFIG).unwrap();
+        let source = r#"const a = <div className="a"><img></img></div>;"#;
+
+        let linter = config
+            .linter_for(&Linter::new(), Path::new("src/App.tsx"))
+            .unwrap();
+        let result = linter.lint_source(source, SourceType::jsx()).unwrap();
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == "no-react-specific-props"));
+        let self_closing = result
+            .diagnostics
+            .iter()
+            .find(|d| d.rule == "self-closing-comp")
+            .expect("self-closing-comp should report");
+        assert_eq!(self_closing.severity, DiagnosticSeverity::Warning);
+
+        let linter = config
+            .linter_for(&Linter::new(), Path::new("src/server/api.tsx"))
+            .unwrap();
+        let result = linter.lint_source(source, SourceType::jsx()).unwrap();
+        assert!(!result
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == "no-react-specific-props"));
+        let self_closing = result
+            .diagnostics
+            .iter()
+            .find(|d| d.rule == "self-closing-comp")
+            .expect("self-closing-comp should report");
+        assert_eq!(self_closing.severity, DiagnosticSeverity::Error);
+    }
+
+    #[test]
+    fn test_info_and_hint_severities() {
+        let config = LintConfig::from_json(
+            r#"{ "rules": { "no-react-specific-props": "info", "self-closing-comp": "hint" } }"#,
+        )
+        .unwrap();
+        let linter = config
+            .linter_for(&Linter::new(), Path::new("App.jsx"))
+            .unwrap();
+        let result = linter
+            .lint_source(r#"const a = <Foo className="a"></Foo>;"#, SourceType::jsx())
+            .unwrap();
+        let severity_of = |rule: &str| {
+            result
+                .diagnostics
+                .iter()
+                .find(|d| d.rule == rule)
+                .map(|d| d.severity)
+        };
+        assert_eq!(severity_of("no-react-specific-props"), Some(DiagnosticSeverity::Info));
+        assert_eq!(severity_of("self-closing-comp"), Some(DiagnosticSeverity::Hint));
+        assert_eq!(result.warning_count(), 0);
+        assert_eq!(result.note_count(), 2);
+    }
+
+    #[test]
+    fn test_deprecated_rule_warnings() {
+        let config = LintConfig::from_json(
+            r#"{
+                "rules": { "solid/prefer-classlist": "warn", "prefer-for": "error" },
+                "overrides": [
+                    { "files": ["a/**"], "rules": { "prefer-classlist": ["error"] } },
+                    { "files": ["b/**"], "rules": { "prefer-classlist": "off" } }
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.warnings(),
+            vec!["rule `prefer-classlist` is deprecated".to_string()]
+        );
+
+        let config =
+            LintConfig::from_json(r#"{ "rules": { "prefer-classlist": "off" } }"#).unwrap();
+        assert!(config.warnings().is_empty());
+
+        let schema = LintConfig::json_schema();
+        assert_eq!(schema["definitions"]["prefer-classlist"]["deprecated"], true);
+        assert!(schema["definitions"]["prefer-for"].get("deprecated").is_none());
+    }
+
+    #[test]
+    fn test_settings_reach_semantic_rules() {
+        let config = LintConfig::from_json(
+            r#"{ "settings": { "primitiveModules": ["@solid-primitives/utils"] } }"#,
+        )
+        .unwrap();
+        let linter = config
+            .linter_for(&Linter::new(), Path::new("App.jsx"))
+            .unwrap();
+        assert_eq!(
+            linter.semantic_rules().settings.primitive_modules,
+            vec!["@solid-primitives/utils".to_string()]
+        );
+        let result = linter
+            .lint_source(
+                "import { createEffect } from '@solid-primitives/utils';\ncreateEffect(1);",
+                SourceType::jsx(),
+            )
+            .unwrap();
+        assert!(result.diagnostics.iter().any(|d| d.rule == "reactivity"));
+
+        assert!(LintConfig::from_json(r#"{ "settings": { "modules": [] } }"#).is_err());
+        let schema = LintConfig::json_schema();
+        assert_eq!(
+            schema["properties"]["settings"]["$ref"],
+            "#/definitions/LintSettings"
+        );
+    }
+
+    #[test]
+    fn test_no_proxy_apis_config() {
+        let source = "import { createStore } from 'solid-js/store';\nconst p = new Proxy({}, {});";
+        let proxy_reports = |json: &str| {
+            let config = LintConfig::from_json(json).unwrap();
+            let linter = config
+                .linter_for(&Linter::new(), Path::new("App.jsx"))
+                .unwrap();
+            let result = linter.lint_source(source, SourceType::jsx()).unwrap();
+            result
+                .diagnostics
+                .iter()
+                .filter(|d| d.rule == "no-proxy-apis")
+                .count()
+        };
+        assert_eq!(proxy_reports("{}"), 0, "no-proxy-apis is off by default");
+        assert_eq!(
+            proxy_reports(r#"{ "rules": { "solid/no-proxy-apis": "warn" } }"#),
+            2
+        );
+        assert_eq!(
+            proxy_reports(r#"{ "rules": { "no-proxy-apis": ["warn", { "allowStore": true }] } }"#),
+            1
+        );
+        assert!(LintConfig::from_json(
+            r#"{ "rules": { "no-proxy-apis": ["warn", { "allowStores": true }] } }"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_invalid_configs() {
+        assert!(LintConfig::from_json(r#"{ "rules": { "no-such-rule": "warn" } }"#).is_err());
+        assert!(LintConfig::from_json(r#"{ "rules": { "prefer-for": "loud" } }"#).is_err());
+        assert!(LintConfig::from_json(r#"{ "rules": { "prefer-for": ["warn", {}] } }"#).is_err());
+        assert!(LintConfig::from_json(
+            r#"{ "rules": { "no-innerhtml": ["warn", { "allow": 1 }] } }"#
+        )
+        .is_err());
+        assert!(LintConfig::from_json(r#"{ "overrides": [{ "files": [] }] }"#).is_err());
+        assert!(LintConfig::from_json(r#"{ "extends": "x" }"#).is_err());
+    }
+
+    #[test]
+    fn test_json_schema() {
+        let schema = LintConfig::json_schema();
+        let rules = schema["definitions"]["Rules"]["properties"]
+            .as_object()
+            .unwrap();
+        // Every rule in the schema is one the config accepts
+        for name in rules.keys() {
+            let json = format!(r#"{{ "rules": {{ "{}": "warn" }} }}"#, name);
+            assert!(LintConfig::from_json(&json).is_ok(), "{}", name);
+        }
+        assert_eq!(
+            rules["solid/a11y/alt-text"]["$ref"],
+            "#/definitions/a11y~1alt-text"
+        );
+
+        let self_closing = &schema["definitions"]["self-closing-comp"]["oneOf"][1];
+        assert_eq!(self_closing["maxItems"], 2);
+        assert_eq!(
+            self_closing["items"][1]["$ref"],
+            "#/definitions/SelfClosingCompConfig"
+        );
+        let html = &schema["definitions"]["HtmlSelfClosingOption"];
+        assert!(html.to_string().contains("void"));
+        assert_eq!(
+            schema["definitions"]["NoInnerhtmlOptions"]["additionalProperties"],
+            false
+        );
+        assert_eq!(schema["definitions"]["prefer-for"]["oneOf"][1]["maxItems"], 1);
+
+        // Configs pointing at the schema still load
+        assert!(LintConfig::from_json(r#"{ "$schema": "./schema.json" }"#).is_ok());
+    }
+
+    #[test]
+    fn test_json_schema_has_every_rule_options() {
+        let mut generator = SchemaSettings::draft07().into_generator();
+        let rules = rule_options(&mut generator);
+        // Both list every rule the config accepts
+        assert_eq!(rules.len(), DEPRECATIONS.len());
+        for (name, options) in rules {
+            let json = format!(r#"{{ "rules": {{ "{}": ["warn", {{}}] }} }}"#, name);
+            assert_eq!(
+                LintConfig::from_json(&json).is_ok(),
+                options.is_some(),
+                "options of {} in the schema",
+                name
+            );
+        }
+
+        let schema = LintConfig::json_schema();
+        assert_eq!(
+            schema["definitions"]["no-proxy-apis"]["oneOf"][1]["items"][1]["$ref"],
+            "#/definitions/NoProxyApisConfig"
+        );
+        assert_eq!(
+            schema["definitions"]["NoProxyApisConfig"]["additionalProperties"],
+            false
+        );
+    }
+}
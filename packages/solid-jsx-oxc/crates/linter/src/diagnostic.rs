@@ -7,10 +7,44 @@ use oxc_span::Span;
 pub enum DiagnosticSeverity {
     Error,
     Warning,
+    /// Worth knowing, but not a problem; not counted as a warning
     Info,
+    /// A suggestion, shown as unobtrusively as the output allows
     Hint,
 }
 
+impl DiagnosticSeverity {
+    /// The `DiagnosticSeverity` number of the Language Server Protocol
+    pub fn lsp_severity(self) -> u8 {
+        match self {
+            Self::Error => 1,
+            Self::Warning => 2,
+            Self::Info => 3,
+            Self::Hint => 4,
+        }
+    }
+
+    /// The SARIF `level` of a result
+    pub fn sarif_level(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "note",
+            Self::Hint => "none",
+        }
+    }
+}
+
+/// Whether a fix is safe to apply automatically
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FixKind {
+    /// Safe to apply automatically (e.g. with `--fix`)
+    #[default]
+    Fix,
+    /// May change behavior; only offered to the user, never auto-applied
+    Suggestion,
+}
+
 /// A suggested fix for a diagnostic
 #[derive(Debug, Clone)]
 pub struct Fix {
@@ -22,6 +56,8 @@ pub struct Fix {
     pub replacement: String,
     /// Description of what the fix does
     pub message: Option<String>,
+    /// Whether the fix may be applied automatically
+    pub kind: FixKind,
 }
 
 impl Fix {
@@ -31,9 +67,20 @@ impl Fix {
             end: span.end,
             replacement: replacement.into(),
             message: None,
+            kind: FixKind::Fix,
         }
     }
 
+    /// Mark this fix as a suggestion that must not be applied automatically
+    pub fn as_suggestion(mut self) -> Self {
+        self.kind = FixKind::Suggestion;
+        self
+    }
+
+    pub fn is_suggestion(&self) -> bool {
+        self.kind == FixKind::Suggestion
+    }
+
     pub fn with_message(mut self, message: impl Into<String>) -> Self {
         self.message = Some(message.into());
         self
@@ -110,4 +157,12 @@ impl Diagnostic {
     pub fn warning(rule: impl Into<String>, span: Span, message: impl Into<String>) -> Self {
         Self::new(rule, span, message).with_severity(DiagnosticSeverity::Warning)
     }
+
+    pub fn info(rule: impl Into<String>, span: Span, message: impl Into<String>) -> Self {
+        Self::new(rule, span, message).with_severity(DiagnosticSeverity::Info)
+    }
+
+    pub fn hint(rule: impl Into<String>, span: Span, message: impl Into<String>) -> Self {
+        Self::new(rule, span, message).with_severity(DiagnosticSeverity::Hint)
+    }
 }
@@ -0,0 +1,404 @@
+//! Line diffs of fixed source text
+//!
+//! Turns the output of the fixer into unified diff hunks, so fixes can be
+//! previewed without writing them (`solid-lint --fix-dry-run`) or posted as
+//! suggested patches by CI. [`FileDiff`] is serializable for the latter and
+//! prints as a `git apply`-compatible patch with [`FileDiff::to_unified`].
+
+use std::fmt;
+use std::path::Path;
+
+use serde::Serialize;
+
+/// Unchanged lines shown around each change
+pub const CONTEXT_LINES: usize = 3;
+
+/// Kind of a line in a hunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffLineKind {
+    /// Present in both texts
+    Context,
+    /// Only in the original text
+    Removed,
+    /// Only in the fixed text
+    Added,
+}
+
+impl DiffLineKind {
+    fn prefix(self) -> char {
+        match self {
+            DiffLineKind::Context => ' ',
+            DiffLineKind::Removed => '-',
+            DiffLineKind::Added => '+',
+        }
+    }
+}
+
+/// A line of a hunk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    /// Line text without its line break
+    pub text: String,
+    /// The line is the last one of its text and has no line break
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub no_newline_at_end: bool,
+}
+
+/// A run of changed lines with their surrounding context
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiffHunk {
+    /// 1-based first line in the original text (the line before the hunk
+    /// when it removes nothing)
+    pub old_start: usize,
+    pub old_lines: usize,
+    /// 1-based first line in the fixed text (the line before the hunk when
+    /// it adds nothing)
+    pub new_start: usize,
+    pub new_lines: usize,
+    pub lines: Vec<DiffLine>,
+}
+
+/// Changes between the original and the fixed text of one file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FileDiff {
+    /// Path shown in the `---`/`+++` headers, relative to the directory the
+    /// patch applies in
+    pub path: String,
+    pub hunks: Vec<DiffHunk>,
+}
+
+impl FileDiff {
+    /// Diff `original` against `fixed` line by line
+    pub fn new(path: impl Into<String>, original: &str, fixed: &str) -> Self {
+        let old: Vec<&str> = original.split_inclusive('\n').collect();
+        let new: Vec<&str> = fixed.split_inclusive('\n').collect();
+        let ops = diff_lines(&old, &new);
+        Self {
+            path: path.into(),
+            hunks: build_hunks(&ops, &old, &new),
+        }
+    }
+
+    /// Whether the texts are the same
+    pub fn is_empty(&self) -> bool {
+        self.hunks.is_empty()
+    }
+
+    /// The diff in unified format, with `a/` and `b/` prefixes on relative
+    /// paths; empty when nothing changed
+    pub fn to_unified(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for FileDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+        // Prefixing an absolute path would give `a//tmp/App.jsx`
+        let (old, new) = match Path::new(&self.path).is_relative() {
+            true => ("a/", "b/"),
+            false => ("", ""),
+        };
+        writeln!(f, "--- {}{}", old, self.path)?;
+        writeln!(f, "+++ {}{}", new, self.path)?;
+        for hunk in &self.hunks {
+            writeln!(
+                f,
+                "@@ -{} +{} @@",
+                hunk_range(hunk.old_start, hunk.old_lines),
+                hunk_range(hunk.new_start, hunk.new_lines)
+            )?;
+            for line in &hunk.lines {
+                writeln!(f, "{}{}", line.kind.prefix(), line.text)?;
+                if line.no_newline_at_end {
+                    writeln!(f, "\\ No newline at end of file")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn hunk_range(start: usize, lines: usize) -> String {
+    if lines == 1 {
+        start.to_string()
+    } else {
+        format!("{},{}", start, lines)
+    }
+}
+
+/// One step of the edit script, with indices into the old and new lines
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Shortest edit script from `old` to `new` (Myers' algorithm). The common
+/// prefix and suffix are matched up front, so the search only covers the
+/// region the fixes touched.
+fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Op> {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+
+    let mut ops: Vec<Op> = (0..prefix).map(|i| Op::Equal(i, i)).collect();
+    let a = &old[prefix..old.len() - suffix];
+    let b = &new[prefix..new.len() - suffix];
+    ops.extend(myers(a, b).into_iter().map(|op| match op {
+        Op::Equal(x, y) => Op::Equal(x + prefix, y + prefix),
+        Op::Delete(x) => Op::Delete(x + prefix),
+        Op::Insert(y) => Op::Insert(y + prefix),
+    }));
+    let (old_end, new_end) = (old.len() - suffix, new.len() - suffix);
+    ops.extend((0..suffix).map(|i| Op::Equal(old_end + i, new_end + i)));
+    ops
+}
+
+fn myers(a: &[&str], b: &[&str]) -> Vec<Op> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = n + m;
+    let mut v = vec![0isize; 2 * max as usize + 2];
+    let mut trace = Vec::new();
+
+    'search: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let index = (k + max) as usize;
+            let mut x = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+                v[index + 1]
+            } else {
+                v[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    // Walk back from the end through the saved frontiers
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let index = (k + max) as usize;
+        let prev_k = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[(prev_k + max) as usize];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            ops.push(Op::Equal(x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                ops.push(Op::Insert(prev_y as usize));
+            } else {
+                ops.push(Op::Delete(prev_x as usize));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    ops.reverse();
+    ops
+}
+
+/// Group the edit script into hunks with [`CONTEXT_LINES`] of context,
+/// merging changes whose context would overlap
+fn build_hunks(ops: &[Op], old: &[&str], new: &[&str]) -> Vec<DiffHunk> {
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, Op::Equal(..)))
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut hunks = Vec::new();
+    let mut i = 0;
+    while i < changes.len() {
+        let first = changes[i];
+        let mut last = first;
+        while i + 1 < changes.len() && changes[i + 1] - last <= 2 * CONTEXT_LINES + 1 {
+            i += 1;
+            last = changes[i];
+        }
+        i += 1;
+
+        let start = first.saturating_sub(CONTEXT_LINES);
+        let end = (last + CONTEXT_LINES + 1).min(ops.len());
+        hunks.push(build_hunk(&ops[start..end], old, new));
+    }
+    hunks
+}
+
+fn build_hunk(ops: &[Op], old: &[&str], new: &[&str]) -> DiffHunk {
+    // Where the hunk starts on each side, as 0-based line indices
+    let mut old_index = None;
+    let mut new_index = None;
+    let mut old_lines = 0;
+    let mut new_lines = 0;
+    let mut lines = Vec::with_capacity(ops.len());
+    let mut old_cursor = 0;
+    let mut new_cursor = 0;
+
+    for op in ops {
+        let (kind, text) = match *op {
+            Op::Equal(x, y) => {
+                old_index.get_or_insert(x);
+                new_index.get_or_insert(y);
+                old_lines += 1;
+                new_lines += 1;
+                (old_cursor, new_cursor) = (x + 1, y + 1);
+                (DiffLineKind::Context, new[y])
+            }
+            Op::Delete(x) => {
+                old_index.get_or_insert(x);
+                old_lines += 1;
+                old_cursor = x + 1;
+                (DiffLineKind::Removed, old[x])
+            }
+            Op::Insert(y) => {
+                new_index.get_or_insert(y);
+                new_lines += 1;
+                new_cursor = y + 1;
+                (DiffLineKind::Added, new[y])
+            }
+        };
+        let line = text.strip_suffix('\n');
+        lines.push(DiffLine {
+            kind,
+            text: line.unwrap_or(text).to_string(),
+            no_newline_at_end: line.is_none(),
+        });
+    }
+
+    // A side without lines in the hunk starts after the last line before it
+    let old_start = match old_index {
+        Some(index) if old_lines > 0 => index + 1,
+        _ => old_cursor,
+    };
+    let new_start = match new_index {
+        Some(index) if new_lines > 0 => index + 1,
+        _ => new_cursor,
+    };
+    DiffHunk {
+        old_start,
+        old_lines,
+        new_start,
+        new_lines,
+        lines,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_changes() {
+        let diff = FileDiff::new("a.jsx", "a\nb\n", "a\nb\n");
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_unified(), "");
+    }
+
+    #[test]
+    fn test_single_change_with_context() {
+        let original = "1\n2\n3\n4\n<div className=\"a\" />\n6\n7\n8\n9\n";
+        let fixed = "1\n2\n3\n4\n<div class=\"a\" />\n6\n7\n8\n9\n";
+        let diff = FileDiff::new("src/App.jsx", original, fixed);
+        assert_eq!(
+            diff.to_unified(),
+            "--- a/src/App.jsx\n+++ b/src/App.jsx\n@@ -2,7 +2,7 @@\n 2\n 3\n 4\n-<div className=\"a\" />\n+<div class=\"a\" />\n 6\n 7\n 8\n"
+        );
+    }
+
+    #[test]
+    fn test_distant_changes_get_separate_hunks() {
+        let text = |changed: &[usize]| -> String {
+            (1..=20)
+                .map(|i| match changed.contains(&i) {
+                    true => format!("changed {}\n", i),
+                    false => format!("{}\n", i),
+                })
+                .collect()
+        };
+        let original = text(&[]);
+        let diff = FileDiff::new("a.js", &original, &text(&[2, 19]));
+        assert_eq!(diff.hunks.len(), 2);
+        assert_eq!((diff.hunks[0].old_start, diff.hunks[0].old_lines), (1, 5));
+        assert_eq!((diff.hunks[1].old_start, diff.hunks[1].old_lines), (16, 5));
+
+        // Changes closer than twice the context share a hunk
+        let diff = FileDiff::new("a.js", &original, &text(&[2, 9]));
+        assert_eq!(diff.hunks.len(), 1);
+    }
+
+    #[test]
+    fn test_removed_and_added_lines() {
+        let diff = FileDiff::new("a.js", "import React from \"react\";\nx;\n", "x;\ny;\n");
+        assert_eq!(
+            diff.to_unified(),
+            "--- a/a.js\n+++ b/a.js\n@@ -1,2 +1,2 @@\n-import React from \"react\";\n x;\n+y;\n"
+        );
+    }
+
+    #[test]
+    fn test_pure_insertion_range() {
+        let diff = FileDiff::new("a.js", "", "x;\n");
+        assert_eq!(diff.to_unified(), "--- a/a.js\n+++ b/a.js\n@@ -0,0 +1 @@\n+x;\n");
+    }
+
+    #[test]
+    fn test_missing_newline_at_end() {
+        let diff = FileDiff::new("a.js", "a\nb", "a\nc");
+        assert_eq!(
+            diff.to_unified(),
+            "--- a/a.js\n+++ b/a.js\n@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n+c\n\\ No newline at end of file\n"
+        );
+    }
+
+    #[test]
+    fn test_absolute_path_is_not_prefixed() {
+        let diff = FileDiff::new("/tmp/a.js", "a\n", "b\n");
+        assert_eq!(
+            diff.to_unified(),
+            "--- /tmp/a.js\n+++ /tmp/a.js\n@@ -1 +1 @@\n-a\n+b\n"
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        let diff = FileDiff::new("a.js", "a\n", "b\n");
+        assert_eq!(
+            serde_json::to_string(&diff).unwrap(),
+            r#"{"path":"a.js","hunks":[{"oldStart":1,"oldLines":1,"newStart":1,"newLines":1,"lines":[{"kind":"removed","text":"a"},{"kind":"added","text":"b"}]}]}"#
+        );
+    }
+}
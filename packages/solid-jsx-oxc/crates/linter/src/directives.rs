@@ -0,0 +1,424 @@
+//! Inline disable directives
+//!
+//! Supports eslint-style comments to suppress diagnostics:
+//!
+//! ```js
+//! /* solid-lint-disable */                     // until enabled again (or EOF)
+//! /* solid-lint-enable */
+//! foo(); // solid-lint-disable-line reactivity
+//! // solid-lint-disable-next-line reactivity, prefer-for
+//! ```
+//!
+//! Rules can be listed after the directive, optionally with a `solid/`
+//! prefix; without rules the directive applies to every rule. Anything after
+//! `--` is treated as a description and ignored.
+//!
+//! Each directive remembers whether it suppressed a diagnostic, so stale
+//! directives can be reported as unused.
+
+use oxc_span::Span;
+
+use crate::diagnostic::{Diagnostic, DiagnosticSeverity, Fix};
+
+/// Rule name used for unused-directive diagnostics
+pub const UNUSED_DIRECTIVE: &str = "unused-disable-directive";
+
+const PREFIX: &str = "solid-lint-";
+
+/// Kind of an inline directive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectiveKind {
+    /// `solid-lint-disable`: disable from here until a matching enable
+    Disable,
+    /// `solid-lint-enable`: re-enable rules disabled by a block directive
+    Enable,
+    /// `solid-lint-disable-line`: disable for the line of the comment
+    DisableLine,
+    /// `solid-lint-disable-next-line`: disable for the line after the comment
+    DisableNextLine,
+}
+
+impl DirectiveKind {
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "disable" => Some(Self::Disable),
+            "enable" => Some(Self::Enable),
+            "disable-line" => Some(Self::DisableLine),
+            "disable-next-line" => Some(Self::DisableNextLine),
+            _ => None,
+        }
+    }
+
+    fn keyword(self) -> &'static str {
+        match self {
+            Self::Disable => "solid-lint-disable",
+            Self::Enable => "solid-lint-enable",
+            Self::DisableLine => "solid-lint-disable-line",
+            Self::DisableNextLine => "solid-lint-disable-next-line",
+        }
+    }
+}
+
+/// A single directive comment
+#[derive(Debug, Clone)]
+pub struct Directive {
+    pub kind: DirectiveKind,
+    /// Span of the whole comment, including delimiters
+    pub span: Span,
+    /// Rules named by the directive; empty means all rules
+    pub rules: Vec<String>,
+    /// Line the directive applies to (for line directives), 0-based
+    line: usize,
+}
+
+impl Directive {
+    fn applies_to(&self, rule: &str) -> Option<usize> {
+        if self.rules.is_empty() {
+            return Some(0);
+        }
+        self.rules.iter().position(|r| r == rule)
+    }
+}
+
+/// All directives in a file, with usage tracking
+#[derive(Debug, Clone, Default)]
+pub struct DisableDirectives {
+    directives: Vec<Directive>,
+    line_starts: Vec<usize>,
+}
+
+/// Result of applying directives to a list of diagnostics
+#[derive(Debug, Clone, Default)]
+pub struct DirectiveResult {
+    /// Diagnostics that were not suppressed
+    pub diagnostics: Vec<Diagnostic>,
+    /// Number of diagnostics suppressed by directives
+    pub suppressed: usize,
+    /// Diagnostics for directives that suppressed nothing
+    pub unused: Vec<Diagnostic>,
+}
+
+impl DisableDirectives {
+    /// Collect directives from the given comments.
+    ///
+    /// `comments` yields each comment's full span (including delimiters)
+    /// together with its text without the delimiters.
+    pub fn parse<'s>(
+        source_text: &str,
+        comments: impl IntoIterator<Item = (Span, &'s str)>,
+    ) -> Self {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(source_text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        let line_of = |offset: u32| line_starts.partition_point(|&s| s <= offset as usize) - 1;
+
+        let mut directives = Vec::new();
+        for (span, content) in comments {
+            let Some((kind, rules)) = parse_directive(content) else {
+                continue;
+            };
+            let line = match kind {
+                DirectiveKind::DisableLine => line_of(span.start),
+                DirectiveKind::DisableNextLine => line_of(span.end) + 1,
+                DirectiveKind::Disable | DirectiveKind::Enable => 0,
+            };
+            directives.push(Directive {
+                kind,
+                span,
+                rules,
+                line,
+            });
+        }
+        directives.sort_by_key(|d| d.span.start);
+
+        Self {
+            directives,
+            line_starts,
+        }
+    }
+
+    /// Collect directives from an oxc program's comments
+    pub fn from_program(source_text: &str, program: &oxc_ast::ast::Program) -> Self {
+        Self::parse(
+            source_text,
+            program.comments.iter().map(|comment| {
+                (comment.span, comment.content_span().source_text(source_text))
+            }),
+        )
+    }
+
+    pub fn directives(&self) -> &[Directive] {
+        &self.directives
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.directives.is_empty()
+    }
+
+    fn line_of(&self, offset: u32) -> usize {
+        self.line_starts.partition_point(|&s| s <= offset as usize) - 1
+    }
+
+    /// Find the directive (and the index of the matching rule within it)
+    /// suppressing a diagnostic of `rule` at `offset`.
+    fn find_suppressor(&self, rule: &str, offset: u32) -> Option<(usize, usize)> {
+        let line = self.line_of(offset);
+
+        // Line directives take precedence over block directives
+        for (i, directive) in self.directives.iter().enumerate() {
+            if matches!(
+                directive.kind,
+                DirectiveKind::DisableLine | DirectiveKind::DisableNextLine
+            ) && directive.line == line
+            {
+                if let Some(rule_index) = directive.applies_to(rule) {
+                    return Some((i, rule_index));
+                }
+            }
+        }
+
+        // The closest preceding block directive for this rule decides
+        self.directives
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| {
+                matches!(d.kind, DirectiveKind::Disable | DirectiveKind::Enable)
+                    && d.span.end <= offset
+            })
+            .rev()
+            .find_map(|(i, d)| d.applies_to(rule).map(|rule_index| (i, d.kind, rule_index)))
+            .and_then(|(i, kind, rule_index)| {
+                (kind == DirectiveKind::Disable).then_some((i, rule_index))
+            })
+    }
+
+    /// Filter out suppressed diagnostics.
+    ///
+    /// When `report_unused` is set, every disable directive (or rule named in
+    /// one) that suppressed nothing is reported with that severity.
+    pub fn apply(
+        &self,
+        diagnostics: Vec<Diagnostic>,
+        report_unused: Option<DiagnosticSeverity>,
+    ) -> DirectiveResult {
+        let mut used: Vec<Vec<bool>> = self
+            .directives
+            .iter()
+            .map(|d| vec![false; d.rules.len().max(1)])
+            .collect();
+
+        let mut result = DirectiveResult::default();
+        for diagnostic in diagnostics {
+            match self.find_suppressor(&diagnostic.rule, diagnostic.start) {
+                Some((i, rule_index)) => {
+                    used[i][rule_index] = true;
+                    result.suppressed += 1;
+                }
+                None => result.diagnostics.push(diagnostic),
+            }
+        }
+
+        if let Some(severity) = report_unused {
+            for (directive, used) in self.directives.iter().zip(&used) {
+                if directive.kind == DirectiveKind::Enable {
+                    continue;
+                }
+                result
+                    .unused
+                    .extend(unused_diagnostics(directive, used, severity));
+            }
+        }
+
+        result
+    }
+}
+
+fn unused_diagnostics(
+    directive: &Directive,
+    used: &[bool],
+    severity: DiagnosticSeverity,
+) -> Vec<Diagnostic> {
+    let keyword = directive.kind.keyword();
+    let remove = || Fix::new(directive.span, "").with_message("Remove unused directive");
+
+    if directive.rules.is_empty() {
+        if used[0] {
+            return Vec::new();
+        }
+        return vec![Diagnostic::new(
+            UNUSED_DIRECTIVE,
+            directive.span,
+            format!("Unused {} directive (no problems were reported).", keyword),
+        )
+        .with_severity(severity)
+        .with_fix(remove())];
+    }
+
+    let all_unused = used.iter().all(|u| !u);
+    directive
+        .rules
+        .iter()
+        .zip(used)
+        .filter(|(_, used)| !**used)
+        .map(|(rule, _)| {
+            let diagnostic = Diagnostic::new(
+                UNUSED_DIRECTIVE,
+                directive.span,
+                format!(
+                    "Unused {} directive (no problems were reported from '{}').",
+                    keyword, rule
+                ),
+            )
+            .with_severity(severity);
+            // Only offer to delete the comment when none of its rules are used
+            if all_unused && directive.rules.len() == 1 {
+                diagnostic.with_fix(remove())
+            } else {
+                diagnostic
+            }
+        })
+        .collect()
+}
+
+/// Parse the text of a comment into a directive kind and its rule list
+fn parse_directive(content: &str) -> Option<(DirectiveKind, Vec<String>)> {
+    let text = content.trim().trim_start_matches('*').trim();
+    let rest = text.strip_prefix(PREFIX)?;
+
+    let keyword_end = rest
+        .find(|c: char| c.is_whitespace())
+        .unwrap_or(rest.len());
+    let kind = DirectiveKind::from_keyword(&rest[..keyword_end])?;
+
+    // Strip `-- description`
+    let mut list = &rest[keyword_end..];
+    if let Some(index) = list.find("--") {
+        list = &list[..index];
+    }
+
+    let rules = list
+        .split(',')
+        .map(str::trim)
+        .filter(|rule| !rule.is_empty())
+        .map(|rule| rule.strip_prefix("solid/").unwrap_or(rule).to_string())
+        .collect();
+
+    Some((kind, rules))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn warning(rule: &str, offset: u32) -> Diagnostic {
+        Diagnostic::warning(rule, Span::new(offset, offset + 1), "msg")
+    }
+
+    /// Find `//` and `/* */` comments in a test snippet
+    fn directives(source: &str) -> DisableDirectives {
+        let mut comments = Vec::new();
+        let mut offset = 0;
+        while let Some(i) = source[offset..].find("/*").map(|i| i + offset) {
+            let end = source[i..].find("*/").unwrap() + i + 2;
+            comments.push((Span::new(i as u32, end as u32), &source[i + 2..end - 2]));
+            offset = end;
+        }
+        for (line_start, line) in source
+            .split('\n')
+            .scan(0, |pos, line| {
+                let start = *pos;
+                *pos += line.len() + 1;
+                Some((start, line))
+            })
+        {
+            if let Some(i) = line.find("//") {
+                let start = line_start + i;
+                let end = line_start + line.len();
+                comments.push((Span::new(start as u32, end as u32), &source[start + 2..end]));
+            }
+        }
+        DisableDirectives::parse(source, comments)
+    }
+
+    #[test]
+    fn test_parse_directive() {
+        assert_eq!(
+            parse_directive(" solid-lint-disable-next-line solid/reactivity, prefer-for -- why"),
+            Some((
+                DirectiveKind::DisableNextLine,
+                vec!["reactivity".to_string(), "prefer-for".to_string()]
+            ))
+        );
+        assert_eq!(
+            parse_directive(" solid-lint-disable "),
+            Some((DirectiveKind::Disable, vec![]))
+        );
+        assert_eq!(parse_directive(" eslint-disable "), None);
+        assert_eq!(parse_directive(" solid-lint-disabled "), None);
+    }
+
+    #[test]
+    fn test_disable_next_line() {
+        let source = "// solid-lint-disable-next-line reactivity\nfoo();\nbar();";
+        let result = directives(source).apply(
+            vec![warning("reactivity", 44), warning("reactivity", 51), warning("prefer-for", 44)],
+            None,
+        );
+        assert_eq!(result.suppressed, 1);
+        assert_eq!(result.diagnostics.len(), 2);
+    }
+
+    #[test]
+    fn test_disable_line() {
+        let source = "foo(); // solid-lint-disable-line\nbar();";
+        let result = directives(source).apply(vec![warning("a", 0), warning("b", 35)], None);
+        assert_eq!(result.suppressed, 1);
+        assert_eq!(result.diagnostics[0].rule, "b");
+    }
+
+    #[test]
+    fn test_disable_enable_block() {
+        let source = "/* solid-lint-disable */\nfoo();\n/* solid-lint-enable */\nbar();";
+        let result = directives(source).apply(vec![warning("a", 25), warning("a", 56)], None);
+        assert_eq!(result.suppressed, 1);
+        assert_eq!(result.diagnostics[0].start, 56);
+    }
+
+    #[test]
+    fn test_enable_single_rule() {
+        let source = "/* solid-lint-disable */\n/* solid-lint-enable a */\nfoo();";
+        let result = directives(source).apply(vec![warning("a", 50), warning("b", 50)], None);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].rule, "a");
+    }
+
+    #[test]
+    fn test_report_unused() {
+        let source = "// solid-lint-disable-next-line reactivity\nfoo();\n/* solid-lint-disable */";
+        let result = directives(source).apply(vec![], Some(DiagnosticSeverity::Warning));
+        assert_eq!(result.unused.len(), 2);
+        assert!(result.unused[0].message.contains("'reactivity'"));
+        assert_eq!(result.unused[0].rule, UNUSED_DIRECTIVE);
+        assert_eq!(result.unused[0].fixes.len(), 1);
+        assert!(result.unused[1].message.contains("no problems were reported)"));
+    }
+
+    #[test]
+    fn test_report_partially_unused() {
+        let source = "// solid-lint-disable-next-line a, b\nfoo();";
+        let result = directives(source).apply(vec![warning("a", 37)], Some(DiagnosticSeverity::Error));
+        assert_eq!(result.unused.len(), 1);
+        assert!(result.unused[0].message.contains("'b'"));
+        assert_eq!(result.unused[0].severity, DiagnosticSeverity::Error);
+        // Removing the comment would re-enable `a`, so no fix is offered
+        assert!(result.unused[0].fixes.is_empty());
+    }
+
+    #[test]
+    fn test_used_directive_not_reported() {
+        let source = "/* solid-lint-disable */\nfoo();";
+        let result = directives(source).apply(vec![warning("a", 25)], Some(DiagnosticSeverity::Warning));
+        assert!(result.unused.is_empty());
+        assert!(result.diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,352 @@
+//! Fix application engine
+//!
+//! Applies the fixes attached to diagnostics back onto the source text.
+//! All edits of a single diagnostic are applied together or not at all; when
+//! two diagnostics want to touch overlapping ranges, the later one is skipped
+//! for this pass. [`fix_until_stable`] then re-lints the output and repeats
+//! until nothing changes, mirroring eslint's fixer loop.
+
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use crate::diagnostic::{Diagnostic, Fix};
+use crate::visitor::{lint_with_config, RulesConfig};
+
+/// Maximum number of lint→fix passes before giving up (same as eslint)
+pub const MAX_FIX_PASSES: usize = 10;
+
+/// Result of a single fix pass over the source text
+#[derive(Debug, Clone)]
+pub struct FixPassResult {
+    /// The source text with all non-conflicting fixes applied
+    pub output: String,
+    /// Number of diagnostics whose fixes were applied
+    pub applied: usize,
+    /// Number of diagnostics whose fixes were skipped because they conflicted
+    pub skipped: usize,
+}
+
+impl FixPassResult {
+    pub fn fixed(&self) -> bool {
+        self.applied > 0
+    }
+}
+
+/// Result of running lint→fix passes until the output is stable
+#[derive(Debug, Clone)]
+pub struct FixResult {
+    /// The fixed source text
+    pub output: String,
+    /// Number of passes that applied at least one fix
+    pub passes: usize,
+    /// Total number of diagnostics fixed across all passes
+    pub applied: usize,
+    /// Diagnostics reported on the final output
+    pub remaining: Vec<Diagnostic>,
+}
+
+impl FixResult {
+    pub fn fixed(&self) -> bool {
+        self.applied > 0
+    }
+}
+
+/// A group of edits belonging to one diagnostic, sorted by position
+struct FixGroup<'d> {
+    start: u32,
+    end: u32,
+    edits: Vec<&'d Fix>,
+}
+
+impl<'d> FixGroup<'d> {
+    /// Build a group from a diagnostic, returning `None` if it has no usable fix.
+    /// Suggestions are never applied automatically.
+    fn new(diagnostic: &'d Diagnostic, source_len: u32) -> Option<Self> {
+        if diagnostic.fixes.is_empty() || diagnostic.fixes.iter().any(Fix::is_suggestion) {
+            return None;
+        }
+
+        let mut edits: Vec<&Fix> = diagnostic.fixes.iter().collect();
+        edits.sort_by_key(|fix| (fix.start, fix.end));
+
+        // Edits within a single fix must be in bounds and must not overlap each other
+        let mut last_end = 0;
+        for fix in &edits {
+            if fix.start > fix.end || fix.end > source_len || fix.start < last_end {
+                return None;
+            }
+            last_end = fix.end;
+        }
+
+        let start = edits.first().map(|fix| fix.start)?;
+        let end = edits.iter().map(|fix| fix.end).max()?;
+        Some(Self { start, end, edits })
+    }
+}
+
+/// Apply the fixes of all diagnostics in a single pass.
+///
+/// Fixes are applied in source order. A fix whose range overlaps or touches
+/// the range of an already accepted fix is skipped; it will usually be
+/// reported again (and applied) on the next pass.
+pub fn apply_fixes(source_text: &str, diagnostics: &[Diagnostic]) -> FixPassResult {
+    let source_len = source_text.len() as u32;
+    let mut groups: Vec<FixGroup> = diagnostics
+        .iter()
+        .filter_map(|d| FixGroup::new(d, source_len))
+        .filter(|group| {
+            group.edits.iter().all(|fix| {
+                source_text.is_char_boundary(fix.start as usize)
+                    && source_text.is_char_boundary(fix.end as usize)
+            })
+        })
+        .collect();
+    groups.sort_by_key(|group| (group.start, group.end));
+
+    let mut output = String::with_capacity(source_text.len());
+    let mut last_pos: Option<u32> = None;
+    let mut cursor = 0usize;
+    let mut applied = 0;
+    let mut skipped = 0;
+
+    for group in groups {
+        if last_pos.is_some_and(|pos| group.start <= pos) {
+            skipped += 1;
+            continue;
+        }
+
+        for fix in &group.edits {
+            output.push_str(&source_text[cursor..fix.start as usize]);
+            output.push_str(&fix.replacement);
+            cursor = fix.end as usize;
+        }
+        last_pos = Some(group.end);
+        applied += 1;
+    }
+    output.push_str(&source_text[cursor..]);
+
+    FixPassResult {
+        output,
+        applied,
+        skipped,
+    }
+}
+
+/// Repeatedly lint and fix `source_text` until no more fixes apply.
+///
+/// `lint` is called with the current text and returns its diagnostics. The
+/// loop stops when a pass applies nothing or after [`MAX_FIX_PASSES`] passes.
+pub fn fix_until_stable<F>(source_text: &str, mut lint: F) -> FixResult
+where
+    F: FnMut(&str) -> Option<Vec<Diagnostic>>,
+{
+    let mut output = source_text.to_string();
+    let mut passes = 0;
+    let mut applied = 0;
+
+    let Some(mut diagnostics) = lint(&output) else {
+        return FixResult {
+            output,
+            passes,
+            applied,
+            remaining: Vec::new(),
+        };
+    };
+
+    while passes < MAX_FIX_PASSES {
+        let pass = apply_fixes(&output, &diagnostics);
+        if !pass.fixed() {
+            break;
+        }
+
+        // A fix that breaks the program stops the loop and keeps the last good text
+        let Some(next) = lint(&pass.output) else {
+            break;
+        };
+
+        output = pass.output;
+        diagnostics = next;
+        applied += pass.applied;
+        passes += 1;
+    }
+
+    FixResult {
+        output,
+        passes,
+        applied,
+        remaining: diagnostics,
+    }
+}
+
+/// Parse, lint and fix `source_text` with the given rules until stable.
+///
+/// If the source does not parse, it is returned unchanged with no remaining
+/// diagnostics.
+pub fn fix_source(source_text: &str, source_type: SourceType, config: &RulesConfig) -> FixResult {
+    fix_until_stable(source_text, |text| {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, text, source_type).parse();
+        if ret.panicked || !ret.errors.is_empty() {
+            return None;
+        }
+        Some(lint_with_config(text, source_type, &ret.program, config.clone()).diagnostics)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oxc_span::Span;
+
+    fn diagnostic_with_fixes(fixes: Vec<Fix>) -> Diagnostic {
+        let mut diagnostic = Diagnostic::warning("test", Span::new(0, 0), "test");
+        diagnostic.fixes = fixes;
+        diagnostic
+    }
+
+    #[test]
+    fn test_apply_single_fix() {
+        let source = "<div className=\"a\" />";
+        let diagnostics = vec![diagnostic_with_fixes(vec![Fix::new(Span::new(5, 14), "class")])];
+        let result = apply_fixes(source, &diagnostics);
+        assert_eq!(result.output, "<div class=\"a\" />");
+        assert_eq!(result.applied, 1);
+        assert_eq!(result.skipped, 0);
+    }
+
+    #[test]
+    fn test_apply_multi_edit_fix_atomically() {
+        let source = "abcdef";
+        let diagnostics = vec![diagnostic_with_fixes(vec![
+            Fix::new(Span::new(4, 5), "E"),
+            Fix::new(Span::new(0, 1), "A"),
+        ])];
+        let result = apply_fixes(source, &diagnostics);
+        assert_eq!(result.output, "AbcdEf");
+        assert_eq!(result.applied, 1);
+    }
+
+    #[test]
+    fn test_skip_overlapping_fix() {
+        let source = "abcdef";
+        let diagnostics = vec![
+            diagnostic_with_fixes(vec![Fix::new(Span::new(0, 3), "X")]),
+            diagnostic_with_fixes(vec![Fix::new(Span::new(2, 4), "Y")]),
+            diagnostic_with_fixes(vec![Fix::new(Span::new(5, 6), "Z")]),
+        ];
+        let result = apply_fixes(source, &diagnostics);
+        assert_eq!(result.output, "XdeZ");
+        assert_eq!(result.applied, 2);
+        assert_eq!(result.skipped, 1);
+    }
+
+    #[test]
+    fn test_skip_fix_inside_multi_edit_range() {
+        // The second diagnostic only touches text between the first one's edits,
+        // but the first fix group spans the whole range so it must win.
+        let source = "abcdef";
+        let diagnostics = vec![
+            diagnostic_with_fixes(vec![
+                Fix::new(Span::new(0, 1), "A"),
+                Fix::new(Span::new(5, 6), "F"),
+            ]),
+            diagnostic_with_fixes(vec![Fix::new(Span::new(2, 3), "C")]),
+        ];
+        let result = apply_fixes(source, &diagnostics);
+        assert_eq!(result.output, "AbcdeF");
+        assert_eq!(result.skipped, 1);
+    }
+
+    #[test]
+    fn test_skip_same_position_inserts() {
+        let source = "x";
+        let diagnostics = vec![
+            diagnostic_with_fixes(vec![Fix::new(Span::new(0, 0), "a;")]),
+            diagnostic_with_fixes(vec![Fix::new(Span::new(0, 0), "b;")]),
+        ];
+        let result = apply_fixes(source, &diagnostics);
+        assert_eq!(result.output, "a;x");
+        assert_eq!(result.skipped, 1);
+    }
+
+    #[test]
+    fn test_invalid_fix_ignored() {
+        let source = "abc";
+        let diagnostics = vec![
+            diagnostic_with_fixes(vec![Fix::new(Span::new(1, 10), "X")]),
+            diagnostic_with_fixes(vec![
+                Fix::new(Span::new(0, 2), "X"),
+                Fix::new(Span::new(1, 3), "Y"),
+            ]),
+        ];
+        let result = apply_fixes(source, &diagnostics);
+        assert_eq!(result.output, "abc");
+        assert!(!result.fixed());
+    }
+
+    #[test]
+    fn test_suggestions_not_applied() {
+        let source = "abc";
+        let diagnostics = vec![diagnostic_with_fixes(vec![
+            Fix::new(Span::new(0, 1), "A").as_suggestion()
+        ])];
+        let result = apply_fixes(source, &diagnostics);
+        assert_eq!(result.output, "abc");
+        assert!(!result.fixed());
+    }
+
+    #[test]
+    fn test_fix_until_stable_resolves_conflicts_over_passes() {
+        // Each pass reports the same two overlapping fixes until the text is stable
+        let result = fix_until_stable("aaaa", |text| {
+            let mut diagnostics = Vec::new();
+            if let Some(pos) = text.find("aa") {
+                let pos = pos as u32;
+                diagnostics.push(diagnostic_with_fixes(vec![Fix::new(
+                    Span::new(pos, pos + 2),
+                    "b",
+                )]));
+                diagnostics.push(diagnostic_with_fixes(vec![Fix::new(
+                    Span::new(pos + 1, pos + 2),
+                    "c",
+                )]));
+            }
+            Some(diagnostics)
+        });
+        assert_eq!(result.output, "bb");
+        assert_eq!(result.passes, 2);
+        assert_eq!(result.applied, 2);
+        assert!(result.remaining.is_empty());
+    }
+
+    #[test]
+    fn test_fix_until_stable_stops_at_max_passes() {
+        let result = fix_until_stable("", |text| {
+            let end = text.len() as u32;
+            Some(vec![diagnostic_with_fixes(vec![Fix::new(Span::new(end, end), "x")])])
+        });
+        assert_eq!(result.passes, MAX_FIX_PASSES);
+        assert_eq!(result.output.len(), MAX_FIX_PASSES);
+    }
+
+    #[test]
+    fn test_fix_until_stable_keeps_last_good_output() {
+        let result = fix_until_stable("ok", |text| {
+            if text == "broken" {
+                return None;
+            }
+            Some(vec![diagnostic_with_fixes(vec![Fix::new(Span::new(0, 2), "broken")])])
+        });
+        assert_eq!(result.output, "ok");
+        assert!(!result.fixed());
+    }
+
+    #[test]
+    fn test_fix_source_with_rules() {
+        let source = r#"<div className="a" style={{ fontSize: "12px" }}></div>"#;
+        let result = fix_source(source, SourceType::jsx(), &RulesConfig::default());
+        assert_eq!(result.output, r#"<div class="a" style={{ "font-size": "12px" }} />"#);
+        assert!(result.remaining.is_empty());
+    }
+}
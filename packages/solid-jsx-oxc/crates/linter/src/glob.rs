@@ -0,0 +1,113 @@
+//! Minimal glob matching for config `overrides`
+//!
+//! Supports `*` (anything but `/`), `**` (any number of directories), `?`,
+//! `[...]` character classes and `{a,b}` alternatives. Like eslint, a pattern
+//! without a `/` is matched against the file name alone.
+
+/// Check whether a `/`-separated relative path matches a glob pattern
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let path = path.strip_prefix("./").unwrap_or(path);
+    let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+    expand_braces(pattern).iter().any(|pattern| {
+        let target = if pattern.contains('/') {
+            path
+        } else {
+            path.rsplit('/').next().unwrap_or(path)
+        };
+        let pattern: Vec<char> = pattern.chars().collect();
+        let target: Vec<char> = target.chars().collect();
+        matches(&pattern, &target)
+    })
+}
+
+/// Expand `{a,b}` alternatives into one pattern each
+fn expand_braces(pattern: &str) -> Vec<String> {
+    let Some(open) = pattern.find('{') else {
+        return vec![pattern.to_string()];
+    };
+    let Some(close) = pattern[open..].find('}').map(|i| open + i) else {
+        return vec![pattern.to_string()];
+    };
+    let (prefix, suffix) = (&pattern[..open], &pattern[close + 1..]);
+    pattern[open + 1..close]
+        .split(',')
+        .flat_map(|alternative| expand_braces(&format!("{}{}{}", prefix, alternative, suffix)))
+        .collect()
+}
+
+fn matches(pattern: &[char], path: &[char]) -> bool {
+    match pattern {
+        [] => path.is_empty(),
+        ['*', '*', rest @ ..] => {
+            // `**/` also matches no directory at all
+            if let ['/', after @ ..] = rest {
+                if matches(after, path) {
+                    return true;
+                }
+            }
+            (0..=path.len()).any(|i| matches(rest, &path[i..]))
+        }
+        ['*', rest @ ..] => {
+            let segment_end = path.iter().position(|&c| c == '/').unwrap_or(path.len());
+            (0..=segment_end).any(|i| matches(rest, &path[i..]))
+        }
+        ['?', rest @ ..] => path.first().is_some_and(|&c| c != '/') && matches(rest, &path[1..]),
+        ['[', rest @ ..] => match (rest.iter().position(|&c| c == ']'), path.first()) {
+            (Some(end), Some(&c)) if end > 0 => {
+                class_matches(&rest[..end], c) && matches(&rest[end + 1..], &path[1..])
+            }
+            _ => path.first() == Some(&'[') && matches(rest, &path[1..]),
+        },
+        [c, rest @ ..] => path.first() == Some(c) && matches(rest, &path[1..]),
+    }
+}
+
+/// Match one character against the inside of a `[...]` class
+fn class_matches(class: &[char], c: char) -> bool {
+    let (negated, class) = match class {
+        ['!' | '^', rest @ ..] => (true, rest),
+        _ => (false, class),
+    };
+    let mut found = false;
+    let mut i = 0;
+    while i < class.len() {
+        if i + 2 < class.len() && class[i + 1] == '-' {
+            found |= (class[i]..=class[i + 2]).contains(&c);
+            i += 3;
+        } else {
+            found |= class[i] == c;
+            i += 1;
+        }
+    }
+    found != negated && c != '/'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_double_star() {
+        assert!(glob_match("**/*.test.tsx", "src/components/App.test.tsx"));
+        assert!(glob_match("**/*.test.tsx", "App.test.tsx"));
+        assert!(!glob_match("**/*.test.tsx", "src/App.tsx"));
+        assert!(glob_match("src/server/**", "src/server/api/handler.ts"));
+        assert!(!glob_match("src/server/**", "src/client/handler.ts"));
+    }
+
+    #[test]
+    fn test_single_star_stays_in_segment() {
+        assert!(glob_match("src/*.tsx", "src/App.tsx"));
+        assert!(!glob_match("src/*.tsx", "src/nested/App.tsx"));
+        // No `/` in the pattern: match the file name anywhere
+        assert!(glob_match("*.spec.jsx", "deep/dir/a.spec.jsx"));
+    }
+
+    #[test]
+    fn test_braces_and_classes() {
+        assert!(glob_match("**/*.{test,spec}.tsx", "src/a.spec.tsx"));
+        assert!(glob_match("src/[a-c]*.ts", "src/b.ts"));
+        assert!(!glob_match("src/[!a-c]*.ts", "src/b.ts"));
+        assert!(glob_match("./src/?.ts", "src/x.ts"));
+    }
+}
@@ -6,15 +6,38 @@
 //! 2. Integrated with oxlint as a plugin (future)
 //! 3. With type-aware analysis via tsgolint integration (future)
 
+pub mod batch;
+pub mod code_action;
+pub mod config;
+pub mod diff;
+pub mod directives;
+pub mod fixer;
+pub mod linter;
+pub mod project;
 pub mod rules;
+pub mod semantic_visitor;
+pub mod settings;
+pub mod timing;
 pub mod utils;
 pub mod visitor;
 mod context;
 mod diagnostic;
+mod glob;
 
 pub use context::LintContext;
-pub use diagnostic::{Diagnostic, DiagnosticSeverity, Fix};
+pub use batch::{BatchLinter, FileLintResult};
+pub use code_action::{code_action, code_actions, CodeAction, CodeActionKind};
+pub use config::{ConfigOverride, LintConfig, RuleSetting, CONFIG_FILE_NAME};
+pub use diff::{DiffHunk, DiffLine, DiffLineKind, FileDiff};
+pub use diagnostic::{Diagnostic, DiagnosticSeverity, Fix, FixKind};
+pub use directives::DisableDirectives;
+pub use fixer::{apply_fixes, fix_source, fix_until_stable, FixPassResult, FixResult};
+pub use linter::Linter;
+pub use project::{ImportedBinding, ProjectFile, ProjectGraph};
 pub use rules::*;
+pub use semantic_visitor::{SemanticLintResult, SemanticLintRunner, SemanticRulesConfig};
+pub use settings::LintSettings;
+pub use timing::{RuleTiming, RuleTimings};
 pub use visitor::{lint, lint_with_config, LintResult, LintRunner, RulesConfig, VisitorLintContext};
 
 /// Rule category for Solid rules
@@ -28,12 +51,20 @@ pub enum RuleCategory {
     Style,
     /// Rules that may have false positives (experimental)
     Nursery,
+    /// Accessibility rules (opt-in)
+    Accessibility,
+    /// React-to-Solid migration rules (opt-in)
+    Migration,
 }
 
 /// Rule metadata
 pub trait RuleMeta {
     const NAME: &'static str;
     const CATEGORY: RuleCategory;
+    /// Deprecated rules keep working, but configs enabling them get a warning
+    const DEPRECATED: bool = false;
+    /// Rule to use instead of a deprecated one
+    const REPLACED_BY: Option<&'static str> = None;
     /// URL to documentation
     fn docs_url() -> String {
         format!(
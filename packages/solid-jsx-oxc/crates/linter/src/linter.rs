@@ -0,0 +1,457 @@
+//! High-level linter that parses a source file and runs every rule runner
+//!
+//! `LintRunner` and `SemanticLintRunner` work on an already-parsed program.
+//! `Linter` owns the parsing and semantic analysis so tools (like the
+//! `solid-lint` CLI) only have to hand it source text.
+
+use common::GenerateMode;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::Program;
+use oxc_parser::Parser;
+use oxc_semantic::{Semantic, SemanticBuilder};
+use oxc_span::{SourceType, Span};
+use rustc_hash::FxHashMap;
+use solid_jsx_oxc::{transform_program_with_scoping, TransformOptions};
+
+use crate::diagnostic::{Diagnostic, DiagnosticSeverity};
+use crate::directives::DisableDirectives;
+use crate::fixer::{fix_until_stable, FixResult};
+use crate::project::ProjectFile;
+use crate::semantic_visitor::{SemanticLintRunner, SemanticRulesConfig};
+use crate::timing;
+use crate::visitor::{LintResult, LintRunner, RulesConfig, VisitorLintContext};
+
+/// Rule name used for diagnostics produced by parse errors
+pub const PARSE_ERROR: &str = "parse-error";
+
+/// Rule name used for diagnostics produced by `Linter::check_source` for
+/// code the Solid transform rejects
+pub const TRANSFORM_ERROR: &str = "transform-error";
+
+/// Configured linter running both the syntactic and the semantic rules
+#[derive(Debug, Clone)]
+pub struct Linter {
+    rules: RulesConfig,
+    semantic_rules: SemanticRulesConfig,
+    timing: bool,
+    report_unused_directives: Option<DiagnosticSeverity>,
+    /// Severity reported for a rule instead of its default
+    rule_severities: FxHashMap<String, DiagnosticSeverity>,
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self {
+            rules: RulesConfig::default(),
+            semantic_rules: SemanticRulesConfig::all(),
+            timing: false,
+            report_unused_directives: None,
+            rule_severities: FxHashMap::default(),
+        }
+    }
+}
+
+impl Linter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rules(mut self, rules: RulesConfig) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    pub fn with_semantic_rules(mut self, rules: SemanticRulesConfig) -> Self {
+        self.semantic_rules = rules;
+        self
+    }
+
+    /// Collect per-rule timings in `LintResult::timings`
+    pub fn with_timing(mut self, enabled: bool) -> Self {
+        self.timing = enabled;
+        self
+    }
+
+    /// Report `solid-lint-disable` directives that suppressed nothing with
+    /// the given severity (`None` to not report them)
+    pub fn with_report_unused_directives(mut self, severity: Option<DiagnosticSeverity>) -> Self {
+        self.report_unused_directives = severity;
+        self
+    }
+
+    /// Report every diagnostic of `rule` with `severity`
+    pub fn with_rule_severity(mut self, rule: impl Into<String>, severity: DiagnosticSeverity) -> Self {
+        self.rule_severities.insert(rule.into(), severity);
+        self
+    }
+
+    pub fn rules(&self) -> &RulesConfig {
+        &self.rules
+    }
+
+    pub fn semantic_rules(&self) -> &SemanticRulesConfig {
+        &self.semantic_rules
+    }
+
+    /// Parse and lint `source_text`.
+    ///
+    /// Parse errors are returned as `Err` with one `parse-error` diagnostic
+    /// per error; no rules run on a program that failed to parse.
+    pub fn lint_source(
+        &self,
+        source_text: &str,
+        source_type: SourceType,
+    ) -> Result<LintResult, Vec<Diagnostic>> {
+        self.lint_source_with(source_text, source_type, None)
+    }
+
+    /// Parse and lint `source_text` as `project`, one of the files of a
+    /// `ProjectGraph`: relative imports are resolved against the other files.
+    pub fn lint_project_source(
+        &self,
+        source_text: &str,
+        source_type: SourceType,
+        project: ProjectFile<'_>,
+    ) -> Result<LintResult, Vec<Diagnostic>> {
+        self.lint_source_with(source_text, source_type, Some(project))
+    }
+
+    pub(crate) fn lint_source_with(
+        &self,
+        source_text: &str,
+        source_type: SourceType,
+        project: Option<ProjectFile<'_>>,
+    ) -> Result<LintResult, Vec<Diagnostic>> {
+        let allocator = Allocator::default();
+        let program = parse(&allocator, source_text, source_type)?;
+        let semantic_ret = timing::phase("semantic", || SemanticBuilder::new().build(&program));
+        Ok(self.lint_program_with(&program, &semantic_ret.semantic, project))
+    }
+
+    /// Lint and dry-run the Solid transform on `source_text` in one pass.
+    ///
+    /// The parse and the semantic analysis are shared: the transform reuses
+    /// the scoping built for the semantic rules. Constructs the transform
+    /// rejects are reported as `transform-error` diagnostics next to the
+    /// lint diagnostics; they are not affected by disable directives.
+    pub fn check_source(
+        &self,
+        source_text: &str,
+        source_type: SourceType,
+        generate: GenerateMode,
+    ) -> Result<LintResult, Vec<Diagnostic>> {
+        self.check_source_with(source_text, source_type, generate, None)
+    }
+
+    pub(crate) fn check_source_with(
+        &self,
+        source_text: &str,
+        source_type: SourceType,
+        generate: GenerateMode,
+        project: Option<ProjectFile<'_>>,
+    ) -> Result<LintResult, Vec<Diagnostic>> {
+        let allocator = Allocator::default();
+        let mut program = parse(&allocator, source_text, source_type)?;
+        let semantic_ret = timing::phase("semantic", || SemanticBuilder::new().build(&program));
+        let mut result = self.lint_program_with(&program, &semantic_ret.semantic, project);
+
+        let scoping = semantic_ret.semantic.into_scoping();
+        let options = TransformOptions::builder()
+            .generate(generate)
+            .strip_types(true)
+            .build()
+            .expect("the default DOM and SSR options are valid");
+        let transformed = timing::phase("transform", || {
+            transform_program_with_scoping(&mut program, &allocator, &options, scoping)
+        });
+        if let Err(error) = transformed {
+            result.diagnostics.extend(error.errors().iter().map(|error| {
+                let span = Span::new(error.start as u32, error.end as u32);
+                Diagnostic::error(TRANSFORM_ERROR, span, error.message.clone())
+            }));
+            result.diagnostics.sort_by_key(|d| (d.start, d.end));
+        }
+
+        Ok(result)
+    }
+
+    /// Run every rule on an already-parsed `program`.
+    ///
+    /// `semantic` has to be built from `program`; the source text and type
+    /// are taken from them.
+    pub fn lint_program(&self, program: &Program<'_>, semantic: &Semantic<'_>) -> LintResult {
+        self.lint_program_with(program, semantic, None)
+    }
+
+    fn lint_program_with(
+        &self,
+        program: &Program<'_>,
+        semantic: &Semantic<'_>,
+        project: Option<ProjectFile<'_>>,
+    ) -> LintResult {
+        let source_text = program.source_text;
+        let source_type = program.source_type;
+        let ctx = VisitorLintContext::new(source_text, source_type);
+        let mut result = timing::phase("rules", || {
+            LintRunner::new(ctx, self.rules.clone())
+                .with_timing(self.timing)
+                .run(program)
+        });
+
+        let semantic_result = timing::phase("semantic_rules", || {
+            let mut runner = SemanticLintRunner::new(
+                semantic,
+                source_text,
+                source_type,
+                self.semantic_rules.clone(),
+            )
+            .with_timing(self.timing);
+            if let Some(project) = project {
+                runner = runner.with_project(project);
+            }
+            runner.run(program)
+        });
+
+        result.diagnostics.extend(semantic_result.diagnostics);
+        if !self.rule_severities.is_empty() {
+            for diagnostic in &mut result.diagnostics {
+                if let Some(&severity) = self.rule_severities.get(&diagnostic.rule) {
+                    diagnostic.severity = severity;
+                }
+            }
+        }
+
+        timing::phase("directives", || {
+            let directives = DisableDirectives::from_program(source_text, program);
+            if !directives.is_empty() {
+                let applied = directives.apply(
+                    std::mem::take(&mut result.diagnostics),
+                    self.report_unused_directives,
+                );
+                result.diagnostics = applied.diagnostics;
+                result.diagnostics.extend(applied.unused);
+            }
+        });
+        result.diagnostics.sort_by_key(|d| (d.start, d.end));
+        if let (Some(timings), Some(semantic_timings)) =
+            (result.timings.as_mut(), semantic_result.timings.as_ref())
+        {
+            timings.merge(semantic_timings);
+        }
+
+        result
+    }
+
+    /// Lint and fix `source_text` until no more fixes apply
+    pub fn fix_source(&self, source_text: &str, source_type: SourceType) -> FixResult {
+        let linter = self.clone().with_timing(false);
+        fix_until_stable(source_text, |text| {
+            linter
+                .lint_source(text, source_type)
+                .ok()
+                .map(|result| result.diagnostics)
+        })
+    }
+}
+
+/// Parse `source_text`, turning parse errors into `parse-error` diagnostics
+fn parse<'a>(
+    allocator: &'a Allocator,
+    source_text: &'a str,
+    source_type: SourceType,
+) -> Result<Program<'a>, Vec<Diagnostic>> {
+    let ret = timing::phase("parse", || {
+        Parser::new(allocator, source_text, source_type).parse()
+    });
+    if ret.panicked || !ret.errors.is_empty() {
+        return Err(ret
+            .errors
+            .iter()
+            .map(|error| {
+                let span = error
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.first())
+                    .map(|label| {
+                        Span::new(label.offset() as u32, (label.offset() + label.len()) as u32)
+                    })
+                    .unwrap_or_default();
+                Diagnostic::error(PARSE_ERROR, span, error.message.to_string())
+            })
+            .collect());
+    }
+    Ok(ret.program)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::MigrationRulesConfig;
+
+    #[test]
+    fn test_runs_both_runners() {
+        let result = Linter::new()
+            .lint_source(
+                r#"
+                function App() {
+                    return <Undefined className="a" />;
+                }
+                "#,
+                SourceType::jsx(),
+            )
+            .expect("should parse");
+        assert!(result.diagnostics.iter().any(|d| d.rule == "jsx-no-undef"));
+        assert!(result
+            .diagnostics
+            .iter()
+            .any(|d| d.rule == "no-react-specific-props"));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let errors = Linter::new()
+            .lint_source("const = <div>", SourceType::jsx())
+            .expect_err("should fail to parse");
+        assert!(!errors.is_empty());
+        assert_eq!(errors[0].rule, PARSE_ERROR);
+    }
+
+    #[test]
+    fn test_disable_directives() {
+        let source = r#"
+            // solid-lint-disable-next-line no-react-specific-props
+            const a = <div className="a" />;
+            // solid-lint-disable-next-line prefer-for
+            const b = <div className="b" />;
+        "#;
+        let linter = Linter::new().with_rules(
+            RulesConfig::none()
+                .with_no_react_specific_props(true)
+                .with_prefer_for(true),
+        );
+        let result = linter.lint_source(source, SourceType::jsx()).unwrap();
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].rule, "no-react-specific-props");
+
+        let result = linter
+            .with_report_unused_directives(Some(DiagnosticSeverity::Warning))
+            .lint_source(source, SourceType::jsx())
+            .unwrap();
+        assert_eq!(result.diagnostics.len(), 2);
+        assert_eq!(result.diagnostics[0].rule, "unused-disable-directive");
+    }
+
+    #[test]
+    fn test_fix_removes_unused_directive() {
+        let source = "/* solid-lint-disable */\nconst a = <div />;\n";
+        let fixed = Linter::new()
+            .with_report_unused_directives(Some(DiagnosticSeverity::Warning))
+            .fix_source(source, SourceType::jsx());
+        assert_eq!(fixed.output, "\nconst a = <div />;\n");
+    }
+
+    #[test]
+    fn test_fix_react_migration() {
+        let linter = Linter::new().with_semantic_rules(
+            SemanticRulesConfig::none().with_migration(MigrationRulesConfig::all()),
+        );
+        let fix = |source: &str| linter.fix_source(source, SourceType::tsx()).output;
+
+        assert_eq!(
+            fix("import React from 'react';\nconst a = <div />;\n"),
+            "const a = <div />;\n"
+        );
+        assert_eq!(
+            fix("import { useContext, useId, type FC } from 'react';\nuseContext(Ctx);\n"),
+            "import { useContext, createUniqueId as useId, type Component as FC } from 'solid-js';\nuseContext(Ctx);\n"
+        );
+        assert_eq!(
+            fix("import React, { useCallback } from 'react';\nconst f = useCallback(() => go(), []);\nconst b = <React.Fragment><b /></React.Fragment>;\n"),
+            "import React, { useCallback } from 'react';\nconst f = () => go();\nconst b = <><b /></>;\n"
+        );
+        // `useState` has no drop-in replacement, so the import stays
+        let source = "import { useState } from 'react';\nconst [a] = useState(0);\n";
+        assert_eq!(fix(source), source);
+    }
+
+    #[test]
+    fn test_timing_merges_runners() {
+        let result = Linter::new()
+            .with_timing(true)
+            .lint_source(
+                r#"
+                import { createMemo } from 'solid-js';
+                const App = () => <div className={createMemo(() => 1)()} />;
+                "#,
+                SourceType::jsx(),
+            )
+            .expect("should parse");
+        let timings = result.timings.expect("timings should be collected");
+        assert!(timings.get("reactivity").is_some());
+        assert!(timings.get("no-react-specific-props").is_some());
+    }
+
+    fn rules_and_messages(source: &str, source_type: SourceType) -> Vec<(String, String)> {
+        Linter::new()
+            .lint_source(source, source_type)
+            .expect("should parse")
+            .diagnostics
+            .into_iter()
+            .map(|d| (d.rule.to_string(), d.message.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_typescript_syntax_is_looked_through() {
+        let tsx = r#"
+            import { createEffect, For, type Component } from "solid-js";
+
+
+            function List<T>({ items }: { items: T[] }) {
+                return <For each={items as T[]}>{(item: T) => <li>{String(item)}</li>}</For>;
+            }
+            const Row = <T,>(props: { value: T }) => <tr className="row">{String(props.value)}</tr>;
+            const Card: Component<{ title: string }> = ({ title }) => (<div>{title}</div>) as any;
+            function Other(props: { n: number }) {
+                createEffect(5 as unknown as () => void);
+                createEffect(run as () => void);
+                const el = (<div innerHTML={"<b>static</b>" as string} />) satisfies JSX.Element;
+                if (props.n) return <Select<string> value={props.n!} />;
+                return el;
+            }
+            function Wrapped({ a }: any) { return (<span>{a}</span>)!; }
+        "#;
+        let jsx = r#"
+            import { createEffect, For } from "solid-js";
+
+
+            function List({ items }) {
+                return <For each={items}>{(item) => <li>{String(item)}</li>}</For>;
+            }
+            const Row = (props) => <tr className="row">{String(props.value)}</tr>;
+            const Card = ({ title }) => (<div>{title}</div>);
+            function Other(props) {
+                createEffect(5);
+                createEffect(run);
+                const el = (<div innerHTML={"<b>static</b>"} />);
+                if (props.n) return <Select value={props.n} />;
+                return el;
+            }
+            function Wrapped({ a }) { return (<span>{a}</span>); }
+        "#;
+        let typescript = rules_and_messages(tsx, SourceType::tsx());
+        assert_eq!(typescript, rules_and_messages(jsx, SourceType::jsx()));
+        let rules: Vec<_> = typescript.iter().map(|(rule, _)| rule.as_str()).collect();
+        assert_eq!(
+            rules,
+            [
+                "no-destructure",
+                "no-react-specific-props",
+                "no-destructure",
+                "reactivity",
+                "jsx-no-undef",
+                "no-destructure",
+            ]
+        );
+    }
+}
@@ -0,0 +1,850 @@
+//! Project-wide import graph for cross-file analysis
+//!
+//! The semantic rules see one file at a time, so an import from `./Button` is
+//! only a name to them: whether it is a component, a Solid primitive
+//! re-exported by a local barrel or not exported at all is left to naming
+//! heuristics. `ProjectGraph` parses a whole set of files up front, records
+//! what every module exports and follows relative imports and re-exports
+//! between them. Linting a file as a [`ProjectFile`] lets the rules use that:
+//!
+//! - `jsx-no-undef` reports components imported under a name their module
+//!   does not export
+//! - components and primitives re-exported through local modules are
+//!   recognized like direct imports
+//! - `reactivity` and `no-destructure` follow props a component passes to a
+//!   function exported by another file
+//!
+//! Only relative specifiers are resolved, lexically against the paths the
+//! files were given with, trying the usual extensions and `index` files. The
+//! file system is never read; imports of modules outside the set keep the
+//! per-file behavior.
+
+use std::hash::{Hash, Hasher};
+use std::path::{Component, Path, PathBuf};
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    Argument, ArrowFunctionExpression, BindingPattern, CallExpression, Declaration,
+    ExportDefaultDeclarationKind, Expression, FormalParameters, Function,
+    ImportDeclarationSpecifier, Program, Statement,
+};
+use oxc_ast_visit::{walk, Visit};
+use oxc_parser::Parser;
+use oxc_semantic::{Semantic, SemanticBuilder, SymbolId};
+use oxc_span::SourceType;
+use oxc_syntax::scope::ScopeFlags;
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
+
+use crate::utils::{is_pascal_case, is_probably_component, returns_jsx};
+
+/// Extensions tried for specifiers without one, in order
+const EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js", "mts", "mjs"];
+
+/// What a name imported from a project module refers to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportedBinding {
+    /// A function declared in the project that looks like a component
+    Component,
+    /// Any other declaration of the project
+    Value,
+    /// A binding of a package re-exported by the project
+    /// (`export { createSignal } from "solid-js"`)
+    Package { source: String, imported: String },
+    /// The module is part of the project and does not export the name
+    Missing,
+    /// The module is not part of the project or failed to parse, or it may
+    /// get the name from a package through `export *`
+    Unknown,
+}
+
+/// Exports of every file in a set, and the imports between them
+#[derive(Debug, Default)]
+pub struct ProjectGraph {
+    modules: FxHashMap<PathBuf, Module>,
+    /// Parameters of exported functions that components of other files pass
+    /// their props to: (export name, parameter index) per declaring module
+    props_params: FxHashMap<PathBuf, FxHashSet<(String, usize)>>,
+    fingerprint: u64,
+}
+
+#[derive(Debug, Default)]
+struct Module {
+    /// Nothing is known about the exports of a module that failed to parse
+    parsed: bool,
+    exports: FxHashMap<String, Export>,
+    /// Specifiers of `export * from "..."`
+    star_exports: Vec<String>,
+    props_calls: Vec<PropsCall>,
+}
+
+#[derive(Debug, Clone)]
+enum Export {
+    /// Declared in the module
+    Local { is_component: bool },
+    /// Re-exported from another module, with its name there
+    Reexport { source: String, imported: String },
+}
+
+/// A component passing its props to a function imported from a relative module
+#[derive(Debug)]
+struct PropsCall {
+    source: String,
+    imported: String,
+    argument: usize,
+}
+
+/// Where an exported name ends up after following re-exports
+enum Resolution<'g> {
+    Declared {
+        module: &'g Path,
+        name: &'g str,
+        is_component: bool,
+    },
+    Package {
+        source: &'g str,
+        imported: &'g str,
+    },
+    Missing,
+    Unknown,
+}
+
+impl ProjectGraph {
+    /// Parse `(path, content)` pairs and link their relative imports
+    pub fn new<P, S>(files: &[(P, S)]) -> Self
+    where
+        P: AsRef<Path>,
+        S: AsRef<str>,
+    {
+        let mut hasher = FxHasher::default();
+        let modules = files
+            .iter()
+            .map(|(path, source_text)| {
+                let path = normalize(path.as_ref());
+                path.hash(&mut hasher);
+                source_text.as_ref().hash(&mut hasher);
+                let module = Module::parse(&path, source_text.as_ref());
+                (path, module)
+            })
+            .collect();
+        let mut graph = Self {
+            modules,
+            props_params: FxHashMap::default(),
+            fingerprint: hasher.finish(),
+        };
+        graph.props_params = graph.collect_props_params();
+        graph
+    }
+
+    pub fn len(&self) -> usize {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+    }
+
+    /// Hash of every path and content the graph was built from
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// The file at `path`, if it is part of the graph
+    pub fn file(&self, path: impl AsRef<Path>) -> Option<ProjectFile<'_>> {
+        let (path, _) = self.modules.get_key_value(&normalize(path.as_ref()))?;
+        Some(ProjectFile { graph: self, path })
+    }
+
+    /// The file a relative `specifier` imported by `from` refers to
+    pub fn resolve(&self, from: &Path, specifier: &str) -> Option<&Path> {
+        if !is_relative(specifier) {
+            return None;
+        }
+        let base = normalize(&from.parent().unwrap_or(Path::new("")).join(specifier));
+        let mut candidates = vec![base.clone()];
+        candidates.extend(EXTENSIONS.iter().map(|ext| with_suffix(&base, ext)));
+        // TypeScript sources are imported with the extension they compile to
+        if let Some(ext @ ("js" | "jsx" | "mjs")) = base.extension().and_then(|ext| ext.to_str()) {
+            let typescript: &[&str] = if ext == "mjs" {
+                &["mts"]
+            } else {
+                &["ts", "tsx"]
+            };
+            candidates.extend(typescript.iter().map(|ext| base.with_extension(ext)));
+        }
+        candidates.extend(
+            EXTENSIONS
+                .iter()
+                .map(|ext| base.join(format!("index.{ext}"))),
+        );
+        candidates
+            .iter()
+            .find_map(|candidate| self.modules.get_key_value(candidate))
+            .map(|(path, _)| path.as_path())
+    }
+
+    /// What `name` imported from `specifier` by the file at `from` refers to
+    pub fn imported_binding(&self, from: &Path, specifier: &str, name: &str) -> ImportedBinding {
+        let Some(target) = self.resolve(&normalize(from), specifier) else {
+            return ImportedBinding::Unknown;
+        };
+        match self.resolve_export(target, name, &mut FxHashSet::default()) {
+            Resolution::Declared { is_component, .. } => {
+                if is_component {
+                    ImportedBinding::Component
+                } else {
+                    ImportedBinding::Value
+                }
+            }
+            Resolution::Package { source, imported } => ImportedBinding::Package {
+                source: source.to_string(),
+                imported: imported.to_string(),
+            },
+            Resolution::Missing => ImportedBinding::Missing,
+            Resolution::Unknown => ImportedBinding::Unknown,
+        }
+    }
+
+    /// Follow re-exports of `name` from the module at `path`
+    fn resolve_export<'g>(
+        &'g self,
+        path: &'g Path,
+        name: &str,
+        visited: &mut FxHashSet<(&'g Path, String)>,
+    ) -> Resolution<'g> {
+        let Some(module) = self.modules.get(path).filter(|module| module.parsed) else {
+            return Resolution::Unknown;
+        };
+        if !visited.insert((path, name.to_string())) {
+            return Resolution::Unknown;
+        }
+        if let Some((exported, export)) = module.exports.get_key_value(name) {
+            return match export {
+                Export::Local { is_component } => Resolution::Declared {
+                    module: path,
+                    name: exported,
+                    is_component: *is_component,
+                },
+                Export::Reexport { source, imported } if !is_relative(source) => {
+                    Resolution::Package { source, imported }
+                }
+                Export::Reexport { source, imported } => match self.resolve(path, source) {
+                    Some(target) => self.resolve_export(target, imported, visited),
+                    None => Resolution::Unknown,
+                },
+            };
+        }
+        // `export *` does not re-export the default export
+        if name == "default" {
+            return Resolution::Missing;
+        }
+        let mut is_unknown = false;
+        for source in &module.star_exports {
+            match self.resolve(path, source) {
+                Some(target) => match self.resolve_export(target, name, visited) {
+                    Resolution::Missing => {}
+                    Resolution::Unknown => is_unknown = true,
+                    resolution => return resolution,
+                },
+                None => is_unknown = true,
+            }
+        }
+        if is_unknown {
+            Resolution::Unknown
+        } else {
+            Resolution::Missing
+        }
+    }
+
+    fn collect_props_params(&self) -> FxHashMap<PathBuf, FxHashSet<(String, usize)>> {
+        let mut params: FxHashMap<PathBuf, FxHashSet<(String, usize)>> = FxHashMap::default();
+        for (path, module) in &self.modules {
+            for call in &module.props_calls {
+                let Some(target) = self.resolve(path, &call.source) else {
+                    continue;
+                };
+                if let Resolution::Declared { module, name, .. } =
+                    self.resolve_export(target, &call.imported, &mut FxHashSet::default())
+                {
+                    params
+                        .entry(module.to_path_buf())
+                        .or_default()
+                        .insert((name.to_string(), call.argument));
+                }
+            }
+        }
+        params
+    }
+}
+
+/// A file of a [`ProjectGraph`], linted with knowledge of the others
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectFile<'g> {
+    graph: &'g ProjectGraph,
+    path: &'g Path,
+}
+
+impl<'g> ProjectFile<'g> {
+    pub fn graph(&self) -> &'g ProjectGraph {
+        self.graph
+    }
+
+    pub fn path(&self) -> &'g Path {
+        self.path
+    }
+
+    /// What `name` imported from `specifier` refers to
+    pub fn imported_binding(&self, specifier: &str, name: &str) -> ImportedBinding {
+        self.graph.imported_binding(self.path, specifier, name)
+    }
+
+    /// Parameters of this file's exported functions that receive props from
+    /// components of other files, as (export name, parameter index)
+    pub(crate) fn props_params(&self) -> impl Iterator<Item = (&'g str, usize)> {
+        self.graph
+            .props_params
+            .get(self.path)
+            .into_iter()
+            .flatten()
+            .map(|(name, index)| (name.as_str(), *index))
+    }
+}
+
+impl Module {
+    fn parse(path: &Path, source_text: &str) -> Self {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(path).unwrap_or(SourceType::jsx());
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        if ret.panicked || !ret.errors.is_empty() {
+            return Self::default();
+        }
+        let semantic = SemanticBuilder::new().build(&ret.program).semantic;
+        let mut module = Self {
+            parsed: true,
+            ..Self::default()
+        };
+        module.collect_exports(&ret.program);
+        module.props_calls = PropsCallCollector::collect(&ret.program, &semantic);
+        module
+    }
+
+    fn collect_exports(&mut self, program: &Program<'_>) {
+        let imports = imported_names(program);
+        let functions = local_functions(program);
+        let local_export = |local: &str| match imports.get(local) {
+            Some((source, Some(imported))) => Export::Reexport {
+                source: source.to_string(),
+                imported: imported.to_string(),
+            },
+            Some((_, None)) => Export::Local {
+                is_component: false,
+            },
+            None => Export::Local {
+                is_component: functions.get(local).is_some_and(|f| f.is_component),
+            },
+        };
+
+        for stmt in &program.body {
+            match stmt {
+                Statement::ExportNamedDeclaration(export) => {
+                    if let Some(declaration) = &export.declaration {
+                        for name in declared_names(declaration) {
+                            let is_component = functions.get(name).is_some_and(|f| f.is_component);
+                            self.exports
+                                .insert(name.to_string(), Export::Local { is_component });
+                        }
+                    }
+                    for spec in &export.specifiers {
+                        let local = spec.local.name();
+                        let export_kind = match &export.source {
+                            Some(source) => Export::Reexport {
+                                source: source.value.to_string(),
+                                imported: local.to_string(),
+                            },
+                            None => local_export(&local),
+                        };
+                        self.exports
+                            .insert(spec.exported.name().to_string(), export_kind);
+                    }
+                }
+                Statement::ExportDefaultDeclaration(export) => {
+                    let export_kind = match &export.declaration {
+                        ExportDefaultDeclarationKind::FunctionDeclaration(func) => Export::Local {
+                            is_component: is_probably_component(func),
+                        },
+                        ExportDefaultDeclarationKind::ArrowFunctionExpression(arrow) => {
+                            Export::Local {
+                                is_component: returns_jsx(&arrow.body),
+                            }
+                        }
+                        ExportDefaultDeclarationKind::Identifier(ident) => {
+                            local_export(&ident.name)
+                        }
+                        _ => Export::Local {
+                            is_component: false,
+                        },
+                    };
+                    self.exports.insert("default".to_string(), export_kind);
+                }
+                Statement::ExportAllDeclaration(export) => match &export.exported {
+                    Some(name) => {
+                        self.exports.insert(
+                            name.name().to_string(),
+                            Export::Local {
+                                is_component: false,
+                            },
+                        );
+                    }
+                    None => self.star_exports.push(export.source.value.to_string()),
+                },
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A function declared at the top level of a module
+pub(crate) struct LocalFunction<'p, 'a> {
+    pub params: &'p FormalParameters<'a>,
+    pub is_component: bool,
+}
+
+/// Top-level `function` declarations and `const` bindings of function
+/// expressions, exported or not, by name
+fn local_functions<'p, 'a>(program: &'p Program<'a>) -> FxHashMap<&'p str, LocalFunction<'p, 'a>> {
+    let mut functions = FxHashMap::default();
+    for stmt in &program.body {
+        let declaration = match stmt {
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(declaration) => declaration,
+                None => continue,
+            },
+            _ => match stmt.as_declaration() {
+                Some(declaration) => declaration,
+                None => continue,
+            },
+        };
+        match declaration {
+            Declaration::FunctionDeclaration(func) => {
+                if let Some(id) = &func.id {
+                    functions.insert(id.name.as_str(), function_declaration(func));
+                }
+            }
+            Declaration::VariableDeclaration(var) => {
+                for declarator in &var.declarations {
+                    let (BindingPattern::BindingIdentifier(id), Some(init)) =
+                        (&declarator.id, &declarator.init)
+                    else {
+                        continue;
+                    };
+                    let function = match init.get_inner_expression() {
+                        Expression::ArrowFunctionExpression(arrow) => arrow_function(arrow),
+                        Expression::FunctionExpression(func) => function_declaration(func),
+                        _ => continue,
+                    };
+                    functions.insert(
+                        id.name.as_str(),
+                        LocalFunction {
+                            is_component: function.is_component || is_pascal_case(&id.name),
+                            ..function
+                        },
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    functions
+}
+
+fn function_declaration<'p, 'a>(func: &'p Function<'a>) -> LocalFunction<'p, 'a> {
+    LocalFunction {
+        params: &func.params,
+        is_component: is_probably_component(func),
+    }
+}
+
+fn arrow_function<'p, 'a>(arrow: &'p ArrowFunctionExpression<'a>) -> LocalFunction<'p, 'a> {
+    LocalFunction {
+        params: &arrow.params,
+        is_component: returns_jsx(&arrow.body),
+    }
+}
+
+/// Functions `program` exports, by exported name (`default` for the default
+/// export)
+pub(crate) fn exported_functions<'p, 'a>(
+    program: &'p Program<'a>,
+) -> Vec<(String, LocalFunction<'p, 'a>)> {
+    let mut functions = local_functions(program);
+    let mut exported = Vec::new();
+    for stmt in &program.body {
+        match stmt {
+            Statement::ExportNamedDeclaration(export) => {
+                if let Some(declaration) = &export.declaration {
+                    for name in declared_names(declaration) {
+                        if let Some(function) = functions.remove(name) {
+                            exported.push((name.to_string(), function));
+                        }
+                    }
+                }
+                if export.source.is_none() {
+                    for spec in &export.specifiers {
+                        if let Some(function) = functions.remove(spec.local.name().as_str()) {
+                            exported.push((spec.exported.name().to_string(), function));
+                        }
+                    }
+                }
+            }
+            Statement::ExportDefaultDeclaration(export) => {
+                let function = match &export.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                        function_declaration(func)
+                    }
+                    ExportDefaultDeclarationKind::ArrowFunctionExpression(arrow) => {
+                        arrow_function(arrow)
+                    }
+                    ExportDefaultDeclarationKind::Identifier(ident) => {
+                        match functions.remove(ident.name.as_str()) {
+                            Some(function) => function,
+                            None => continue,
+                        }
+                    }
+                    _ => continue,
+                };
+                exported.push(("default".to_string(), function));
+            }
+            _ => {}
+        }
+    }
+    exported
+}
+
+/// Names bound by an exported declaration
+fn declared_names<'p>(declaration: &'p Declaration<'_>) -> Vec<&'p str> {
+    match declaration {
+        Declaration::VariableDeclaration(var) => var
+            .declarations
+            .iter()
+            .flat_map(|declarator| declarator.id.get_binding_identifiers())
+            .map(|id| id.name.as_str())
+            .collect(),
+        _ => declaration
+            .id()
+            .map(|id| id.name.as_str())
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Import bindings of `program`: local name -> (specifier, imported name),
+/// with `None` for namespace imports
+fn imported_names<'p>(program: &'p Program<'_>) -> FxHashMap<&'p str, (&'p str, Option<String>)> {
+    let mut imports = FxHashMap::default();
+    for stmt in &program.body {
+        let Statement::ImportDeclaration(import) = stmt else {
+            continue;
+        };
+        let source = import.source.value.as_str();
+        for spec in import.specifiers.iter().flatten() {
+            let (local, imported) = match spec {
+                ImportDeclarationSpecifier::ImportSpecifier(named) => {
+                    (&named.local, Some(named.imported.name().to_string()))
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(default) => {
+                    (&default.local, Some("default".to_string()))
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(ns) => (&ns.local, None),
+            };
+            imports.insert(local.name.as_str(), (source, imported));
+        }
+    }
+    imports
+}
+
+/// Finds calls passing the props parameter of a component to a function
+/// imported from a relative module (`useTitle(props)`)
+struct PropsCallCollector<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+    /// Relative imports by symbol: (specifier, imported name)
+    imports: FxHashMap<SymbolId, (String, String)>,
+    props_symbols: FxHashSet<SymbolId>,
+    calls: Vec<PropsCall>,
+}
+
+impl<'s, 'a> PropsCallCollector<'s, 'a> {
+    fn collect(program: &Program<'a>, semantic: &'s Semantic<'a>) -> Vec<PropsCall> {
+        let mut imports = FxHashMap::default();
+        for stmt in &program.body {
+            let Statement::ImportDeclaration(import) = stmt else {
+                continue;
+            };
+            if !is_relative(&import.source.value) {
+                continue;
+            }
+            for spec in import.specifiers.iter().flatten() {
+                let (local, imported) = match spec {
+                    ImportDeclarationSpecifier::ImportSpecifier(named) => {
+                        (&named.local, named.imported.name().to_string())
+                    }
+                    ImportDeclarationSpecifier::ImportDefaultSpecifier(default) => {
+                        (&default.local, "default".to_string())
+                    }
+                    ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => continue,
+                };
+                imports.insert(
+                    local.symbol_id(),
+                    (import.source.value.to_string(), imported),
+                );
+            }
+        }
+        if imports.is_empty() {
+            return Vec::new();
+        }
+
+        let mut collector = Self {
+            semantic,
+            imports,
+            props_symbols: FxHashSet::default(),
+            calls: Vec::new(),
+        };
+        collector.visit_program(program);
+        collector.calls
+    }
+
+    fn track_props(&mut self, params: &FormalParameters<'a>) {
+        if let Some(BindingPattern::BindingIdentifier(ident)) =
+            params.items.first().map(|param| &param.pattern)
+        {
+            self.props_symbols.insert(ident.symbol_id());
+        }
+    }
+
+    fn symbol_of(&self, expr: &Expression<'a>) -> Option<SymbolId> {
+        let Expression::Identifier(ident) = expr.get_inner_expression() else {
+            return None;
+        };
+        let scoping = self.semantic.scoping();
+        scoping.get_reference(ident.reference_id.get()?).symbol_id()
+    }
+}
+
+impl<'a> Visit<'a> for PropsCallCollector<'_, 'a> {
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        if func.body.as_ref().is_some_and(|body| returns_jsx(body)) {
+            self.track_props(&func.params);
+        }
+        walk::walk_function(self, func, flags);
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
+        if returns_jsx(&arrow.body) {
+            self.track_props(&arrow.params);
+        }
+        walk::walk_arrow_function_expression(self, arrow);
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        let callee = self
+            .symbol_of(&call.callee)
+            .and_then(|symbol_id| self.imports.get(&symbol_id));
+        if let Some((source, imported)) = callee {
+            for (argument, arg) in call.arguments.iter().enumerate() {
+                let is_props = arg
+                    .as_expression()
+                    .and_then(|expr| self.symbol_of(expr))
+                    .is_some_and(|symbol_id| self.props_symbols.contains(&symbol_id));
+                if is_props && !matches!(arg, Argument::SpreadElement(_)) {
+                    self.calls.push(PropsCall {
+                        source: source.clone(),
+                        imported: imported.clone(),
+                        argument,
+                    });
+                }
+            }
+        }
+        walk::walk_call_expression(self, call);
+    }
+}
+
+fn is_relative(specifier: &str) -> bool {
+    specifier.starts_with("./")
+        || specifier.starts_with("../")
+        || specifier == "."
+        || specifier == ".."
+}
+
+/// `path` with `.ext` appended (`./Button.styles` -> `./Button.styles.ts`)
+fn with_suffix(path: &Path, ext: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(ext);
+    path.into()
+}
+
+/// Resolve `.` and `..` without touching the file system
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(files: &[(&str, &str)]) -> ProjectGraph {
+        ProjectGraph::new(files)
+    }
+
+    #[test]
+    fn test_resolve_specifiers() {
+        let graph = graph(&[
+            ("src/App.tsx", ""),
+            ("src/ui/Button.tsx", ""),
+            ("src/ui/index.ts", ""),
+            ("src/lib/format.ts", ""),
+        ]);
+        let app = Path::new("src/App.tsx");
+        assert_eq!(
+            graph.resolve(app, "./ui/Button"),
+            Some(Path::new("src/ui/Button.tsx"))
+        );
+        assert_eq!(
+            graph.resolve(app, "./ui"),
+            Some(Path::new("src/ui/index.ts"))
+        );
+        assert_eq!(
+            graph.resolve(app, "./lib/format.js"),
+            Some(Path::new("src/lib/format.ts"))
+        );
+        assert_eq!(
+            graph.resolve(Path::new("src/ui/Button.tsx"), "../App"),
+            Some(Path::new("src/App.tsx"))
+        );
+        assert_eq!(graph.resolve(app, "./missing"), None);
+        assert_eq!(graph.resolve(app, "solid-js"), None);
+    }
+
+    #[test]
+    fn test_imported_bindings() {
+        let graph = graph(&[
+            ("src/App.tsx", ""),
+            (
+                "src/Button.tsx",
+                "export function Button(props) { return <button>{props.children}</button>; }\n\
+                 export const size = 'md';\n\
+                 export default function () { return <div />; }",
+            ),
+            (
+                "src/ui.ts",
+                "export * from './Button';\nexport { createSignal as signal } from 'solid-js';",
+            ),
+            (
+                "src/all.ts",
+                "export * from './ui';\nexport * from 'some-package';",
+            ),
+        ]);
+        let app = Path::new("src/App.tsx");
+        assert_eq!(
+            graph.imported_binding(app, "./Button", "Button"),
+            ImportedBinding::Component
+        );
+        assert_eq!(
+            graph.imported_binding(app, "./Button", "default"),
+            ImportedBinding::Component
+        );
+        assert_eq!(
+            graph.imported_binding(app, "./Button", "size"),
+            ImportedBinding::Value
+        );
+        assert_eq!(
+            graph.imported_binding(app, "./Button", "Buton"),
+            ImportedBinding::Missing
+        );
+        assert_eq!(
+            graph.imported_binding(app, "./ui", "Button"),
+            ImportedBinding::Component
+        );
+        assert_eq!(
+            graph.imported_binding(app, "./ui", "signal"),
+            ImportedBinding::Package {
+                source: "solid-js".to_string(),
+                imported: "createSignal".to_string()
+            }
+        );
+        assert_eq!(
+            graph.imported_binding(app, "./ui", "default"),
+            ImportedBinding::Missing
+        );
+        // The package may export it
+        assert_eq!(
+            graph.imported_binding(app, "./all", "Link"),
+            ImportedBinding::Unknown
+        );
+        assert_eq!(
+            graph.imported_binding(app, "./other", "Button"),
+            ImportedBinding::Unknown
+        );
+    }
+
+    #[test]
+    fn test_reexport_cycles_and_parse_errors() {
+        let graph = graph(&[
+            ("a.ts", "export * from './b';"),
+            ("b.ts", "export * from './a';\nexport { x } from './a';"),
+            ("broken.tsx", "export const = ;"),
+        ]);
+        let a = Path::new("a.ts");
+        assert_eq!(
+            graph.imported_binding(a, "./b", "x"),
+            ImportedBinding::Unknown
+        );
+        assert_eq!(
+            graph.imported_binding(a, "./b", "y"),
+            ImportedBinding::Unknown
+        );
+        assert_eq!(
+            graph.imported_binding(a, "./broken", "y"),
+            ImportedBinding::Unknown
+        );
+    }
+
+    #[test]
+    fn test_props_params() {
+        let graph = graph(&[
+            (
+                "src/Title.tsx",
+                "import { useTitle } from './hooks';\n\
+                 export function Title(props) { const title = useTitle(1, props); return <h1>{title()}</h1>; }",
+            ),
+            ("src/hooks/index.ts", "export { useTitle } from './title';"),
+            ("src/hooks/title.ts", "export const useTitle = (level, props) => () => props.title;"),
+        ]);
+        let file = graph.file("src/hooks/title.ts").unwrap();
+        assert_eq!(
+            file.props_params().collect::<Vec<_>>(),
+            vec![("useTitle", 1)]
+        );
+        assert_eq!(
+            graph
+                .file("./src/Title.tsx")
+                .unwrap()
+                .props_params()
+                .count(),
+            0
+        );
+    }
+}
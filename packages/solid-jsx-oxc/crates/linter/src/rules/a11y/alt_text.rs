@@ -0,0 +1,118 @@
+//! a11y/alt-text
+//!
+//! Require a text alternative on elements that show non-text content:
+//! `<img>`, `<area>`, `<input type="image">` and `<object>`.
+
+use oxc_ast::ast::{JSXChild, JSXOpeningElement};
+
+use super::{has_spread, is_absent_value, static_string_value};
+use crate::diagnostic::Diagnostic;
+use crate::utils::{get_attribute, get_element_name, has_children};
+use crate::{RuleCategory, RuleMeta};
+
+/// alt-text rule
+#[derive(Debug, Clone, Default)]
+pub struct AltText;
+
+impl RuleMeta for AltText {
+    const NAME: &'static str = "a11y/alt-text";
+    const CATEGORY: RuleCategory = RuleCategory::Accessibility;
+
+    fn docs_url() -> String {
+        super::docs_url(Self::NAME)
+    }
+}
+
+impl AltText {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check an element for a missing text alternative
+    pub fn check<'a>(
+        &self,
+        opening: &JSXOpeningElement<'a>,
+        children: &[JSXChild<'a>],
+    ) -> Vec<Diagnostic> {
+        self.missing_alternative(opening, children)
+            .into_iter()
+            .collect()
+    }
+
+    fn missing_alternative<'a>(
+        &self,
+        opening: &JSXOpeningElement<'a>,
+        children: &[JSXChild<'a>],
+    ) -> Option<Diagnostic> {
+        let name = get_element_name(opening)?;
+        if has_spread(opening) {
+            return None;
+        }
+        let present =
+            |attr: &str| get_attribute(opening, attr).is_some_and(|attr| !is_absent_value(attr));
+        let labelled = present("aria-label") || present("aria-labelledby");
+
+        match name.as_str() {
+            "img" => {
+                if present("alt") || labelled {
+                    return None;
+                }
+                let presentational = get_attribute(opening, "role")
+                    .and_then(static_string_value)
+                    .is_some_and(|role| matches!(role, "presentation" | "none"));
+                let diagnostic = if presentational {
+                    Diagnostic::warning(
+                        Self::NAME,
+                        opening.span,
+                        "Prefer alt=\"\" over a presentational role on <img>.",
+                    )
+                } else {
+                    Diagnostic::warning(
+                        Self::NAME,
+                        opening.span,
+                        "<img> elements must have an alt prop.",
+                    )
+                    .with_help("Describe the image, or use alt=\"\" for decorative images.")
+                };
+                Some(diagnostic)
+            }
+            "area" => (!present("alt") && !labelled).then(|| {
+                Diagnostic::warning(
+                    Self::NAME,
+                    opening.span,
+                    "<area> elements must have an alt, aria-label or aria-labelledby prop.",
+                )
+            }),
+            "input" => {
+                let is_image = get_attribute(opening, "type")
+                    .and_then(static_string_value)
+                    .is_some_and(|kind| kind.eq_ignore_ascii_case("image"));
+                (is_image && !present("alt") && !labelled).then(|| {
+                    Diagnostic::warning(
+                        Self::NAME,
+                        opening.span,
+                        "<input type=\"image\"> elements must have an alt, aria-label or aria-labelledby prop.",
+                    )
+                })
+            }
+            "object" => (!present("title") && !labelled && !has_children(children)).then(|| {
+                Diagnostic::warning(
+                    Self::NAME,
+                    opening.span,
+                    "<object> elements must have a title, aria-label or aria-labelledby prop, or text content.",
+                )
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_name() {
+        assert_eq!(AltText::NAME, "a11y/alt-text");
+    }
+}
@@ -0,0 +1,89 @@
+//! a11y/anchor-has-content
+//!
+//! Require anchors to have content that screen readers can announce.
+
+use oxc_ast::ast::{JSXChild, JSXOpeningElement};
+
+use super::{has_spread, is_absent_value, static_string_value};
+use crate::diagnostic::Diagnostic;
+use crate::utils::{get_attribute, get_element_name};
+use crate::{RuleCategory, RuleMeta};
+
+/// Props that give an anchor accessible content without children
+const CONTENT_PROPS: &[&str] = &[
+    "aria-label",
+    "aria-labelledby",
+    "title",
+    "innerHTML",
+    "textContent",
+    "innerText",
+];
+
+/// anchor-has-content rule
+#[derive(Debug, Clone, Default)]
+pub struct AnchorHasContent;
+
+impl RuleMeta for AnchorHasContent {
+    const NAME: &'static str = "a11y/anchor-has-content";
+    const CATEGORY: RuleCategory = RuleCategory::Accessibility;
+
+    fn docs_url() -> String {
+        super::docs_url(Self::NAME)
+    }
+}
+
+impl AnchorHasContent {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check an `<a>` element for missing content
+    pub fn check<'a>(
+        &self,
+        opening: &JSXOpeningElement<'a>,
+        children: &[JSXChild<'a>],
+    ) -> Vec<Diagnostic> {
+        if get_element_name(opening).as_deref() != Some("a") || has_spread(opening) {
+            return Vec::new();
+        }
+        let has_content_prop = CONTENT_PROPS
+            .iter()
+            .any(|prop| get_attribute(opening, prop).is_some_and(|attr| !is_absent_value(attr)));
+        if has_content_prop || children.iter().any(is_accessible_child) {
+            return Vec::new();
+        }
+        vec![Diagnostic::warning(
+            Self::NAME,
+            opening.span,
+            "Anchors must have content that is accessible to screen readers.",
+        )
+        .with_help("Add text, or an aria-label for icon-only links.")]
+    }
+}
+
+/// Whether a child renders content that is not hidden from screen readers
+fn is_accessible_child(child: &JSXChild) -> bool {
+    match child {
+        JSXChild::Text(text) => !text.value.trim().is_empty(),
+        JSXChild::Element(element) => {
+            let hidden =
+                get_attribute(&element.opening_element, "aria-hidden").is_some_and(|attr| {
+                    attr.value.is_none() || static_string_value(attr) == Some("true")
+                });
+            !hidden
+        }
+        JSXChild::Fragment(fragment) => fragment.children.iter().any(is_accessible_child),
+        JSXChild::ExpressionContainer(container) => container.expression.as_expression().is_some(),
+        JSXChild::Spread(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_name() {
+        assert_eq!(AnchorHasContent::NAME, "a11y/anchor-has-content");
+    }
+}
@@ -0,0 +1,157 @@
+//! a11y/aria-props
+//!
+//! Disallow `aria-*` props that are not defined by WAI-ARIA.
+
+use oxc_ast::ast::{JSXAttributeItem, JSXAttributeName, JSXOpeningElement};
+
+use crate::diagnostic::Diagnostic;
+use crate::{RuleCategory, RuleMeta};
+
+/// WAI-ARIA 1.2 states and properties
+const ARIA_ATTRIBUTES: &[&str] = &[
+    "aria-activedescendant",
+    "aria-atomic",
+    "aria-autocomplete",
+    "aria-braillelabel",
+    "aria-brailleroledescription",
+    "aria-busy",
+    "aria-checked",
+    "aria-colcount",
+    "aria-colindex",
+    "aria-colindextext",
+    "aria-colspan",
+    "aria-controls",
+    "aria-current",
+    "aria-describedby",
+    "aria-description",
+    "aria-details",
+    "aria-disabled",
+    "aria-dropeffect",
+    "aria-errormessage",
+    "aria-expanded",
+    "aria-flowto",
+    "aria-grabbed",
+    "aria-haspopup",
+    "aria-hidden",
+    "aria-invalid",
+    "aria-keyshortcuts",
+    "aria-label",
+    "aria-labelledby",
+    "aria-level",
+    "aria-live",
+    "aria-modal",
+    "aria-multiline",
+    "aria-multiselectable",
+    "aria-orientation",
+    "aria-owns",
+    "aria-placeholder",
+    "aria-posinset",
+    "aria-pressed",
+    "aria-readonly",
+    "aria-relevant",
+    "aria-required",
+    "aria-roledescription",
+    "aria-rowcount",
+    "aria-rowindex",
+    "aria-rowindextext",
+    "aria-rowspan",
+    "aria-selected",
+    "aria-setsize",
+    "aria-sort",
+    "aria-valuemax",
+    "aria-valuemin",
+    "aria-valuenow",
+    "aria-valuetext",
+];
+
+/// aria-props rule
+#[derive(Debug, Clone, Default)]
+pub struct AriaProps;
+
+impl RuleMeta for AriaProps {
+    const NAME: &'static str = "a11y/aria-props";
+    const CATEGORY: RuleCategory = RuleCategory::Accessibility;
+
+    fn docs_url() -> String {
+        super::docs_url(Self::NAME)
+    }
+}
+
+impl AriaProps {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check a JSX opening element for unknown `aria-*` props
+    pub fn check<'a>(&self, opening: &JSXOpeningElement<'a>) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for attr in &opening.attributes {
+            let JSXAttributeItem::Attribute(attr) = attr else {
+                continue;
+            };
+            let JSXAttributeName::Identifier(ident) = &attr.name else {
+                continue;
+            };
+            let name = ident.name.as_str();
+            if !name.starts_with("aria-") || ARIA_ATTRIBUTES.contains(&name) {
+                continue;
+            }
+
+            let mut diagnostic = Diagnostic::warning(
+                Self::NAME,
+                ident.span,
+                format!("`{}` is not a valid ARIA attribute.", name),
+            );
+            if let Some(suggestion) = closest_attribute(name) {
+                diagnostic = diagnostic.with_help(format!("Did you mean `{}`?", suggestion));
+            }
+            diagnostics.push(diagnostic);
+        }
+
+        diagnostics
+    }
+}
+
+/// The ARIA attribute `name` is most likely a typo of
+fn closest_attribute(name: &str) -> Option<&'static str> {
+    let lowercase = name.to_ascii_lowercase();
+    ARIA_ATTRIBUTES
+        .iter()
+        .map(|candidate| (edit_distance(&lowercase, candidate), *candidate))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_name() {
+        assert_eq!(AriaProps::NAME, "a11y/aria-props");
+    }
+
+    #[test]
+    fn test_closest_attribute() {
+        assert_eq!(closest_attribute("aria-labeledby"), Some("aria-labelledby"));
+        assert_eq!(closest_attribute("aria-Hidden"), Some("aria-hidden"));
+        assert_eq!(closest_attribute("aria-foo"), None);
+    }
+}
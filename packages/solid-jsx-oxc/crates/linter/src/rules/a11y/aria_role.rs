@@ -0,0 +1,210 @@
+//! a11y/aria-role
+//!
+//! Require `role` values on DOM elements to be valid, non-abstract ARIA
+//! roles.
+
+use oxc_ast::ast::JSXOpeningElement;
+
+use super::static_string_value;
+use crate::diagnostic::Diagnostic;
+use crate::utils::{get_attribute, get_element_name, is_dom_element};
+use crate::{RuleCategory, RuleMeta};
+
+/// Non-abstract roles of WAI-ARIA 1.2, Graphics ARIA and DPUB-ARIA
+const ARIA_ROLES: &[&str] = &[
+    "alert",
+    "alertdialog",
+    "application",
+    "article",
+    "banner",
+    "blockquote",
+    "button",
+    "caption",
+    "cell",
+    "checkbox",
+    "code",
+    "columnheader",
+    "combobox",
+    "comment",
+    "complementary",
+    "contentinfo",
+    "definition",
+    "deletion",
+    "dialog",
+    "directory",
+    "document",
+    "emphasis",
+    "feed",
+    "figure",
+    "form",
+    "generic",
+    "grid",
+    "gridcell",
+    "group",
+    "heading",
+    "img",
+    "insertion",
+    "link",
+    "list",
+    "listbox",
+    "listitem",
+    "log",
+    "main",
+    "mark",
+    "marquee",
+    "math",
+    "menu",
+    "menubar",
+    "menuitem",
+    "menuitemcheckbox",
+    "menuitemradio",
+    "meter",
+    "navigation",
+    "none",
+    "note",
+    "option",
+    "paragraph",
+    "presentation",
+    "progressbar",
+    "radio",
+    "radiogroup",
+    "region",
+    "row",
+    "rowgroup",
+    "rowheader",
+    "scrollbar",
+    "search",
+    "searchbox",
+    "separator",
+    "slider",
+    "spinbutton",
+    "status",
+    "strong",
+    "subscript",
+    "suggestion",
+    "superscript",
+    "switch",
+    "tab",
+    "table",
+    "tablist",
+    "tabpanel",
+    "term",
+    "textbox",
+    "time",
+    "timer",
+    "toolbar",
+    "tooltip",
+    "tree",
+    "treegrid",
+    "treeitem",
+    "graphics-document",
+    "graphics-object",
+    "graphics-symbol",
+    "doc-abstract",
+    "doc-acknowledgments",
+    "doc-afterword",
+    "doc-appendix",
+    "doc-backlink",
+    "doc-biblioentry",
+    "doc-bibliography",
+    "doc-biblioref",
+    "doc-chapter",
+    "doc-colophon",
+    "doc-conclusion",
+    "doc-cover",
+    "doc-credit",
+    "doc-credits",
+    "doc-dedication",
+    "doc-endnote",
+    "doc-endnotes",
+    "doc-epigraph",
+    "doc-epilogue",
+    "doc-errata",
+    "doc-example",
+    "doc-footnote",
+    "doc-foreword",
+    "doc-glossary",
+    "doc-glossref",
+    "doc-index",
+    "doc-introduction",
+    "doc-noteref",
+    "doc-notice",
+    "doc-pagebreak",
+    "doc-pagefooter",
+    "doc-pageheader",
+    "doc-pagelist",
+    "doc-part",
+    "doc-preface",
+    "doc-prologue",
+    "doc-pullquote",
+    "doc-qna",
+    "doc-subtitle",
+    "doc-tip",
+    "doc-toc",
+];
+
+/// aria-role rule
+#[derive(Debug, Clone, Default)]
+pub struct AriaRole;
+
+impl RuleMeta for AriaRole {
+    const NAME: &'static str = "a11y/aria-role";
+    const CATEGORY: RuleCategory = RuleCategory::Accessibility;
+
+    fn docs_url() -> String {
+        super::docs_url(Self::NAME)
+    }
+}
+
+impl AriaRole {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check the static `role` of a DOM element
+    pub fn check<'a>(&self, opening: &JSXOpeningElement<'a>) -> Vec<Diagnostic> {
+        if !get_element_name(opening).is_some_and(|name| is_dom_element(&name)) {
+            return Vec::new();
+        }
+        let Some(attr) = get_attribute(opening, "role") else {
+            return Vec::new();
+        };
+        let Some(value) = static_string_value(attr) else {
+            return Vec::new();
+        };
+
+        if value.trim().is_empty() {
+            return vec![Diagnostic::warning(
+                Self::NAME,
+                attr.span,
+                "The role prop must not be empty.",
+            )];
+        }
+
+        // A role list falls back from left to right, so every entry must be valid
+        let invalid: Vec<String> = value
+            .split_ascii_whitespace()
+            .filter(|role| !ARIA_ROLES.contains(role))
+            .map(|role| format!("`{}`", role))
+            .collect();
+        let message = match invalid.as_slice() {
+            [] => return Vec::new(),
+            [role] => format!("{} is not a valid, non-abstract ARIA role.", role),
+            roles => format!(
+                "{} are not valid, non-abstract ARIA roles.",
+                roles.join(", ")
+            ),
+        };
+        vec![Diagnostic::warning(Self::NAME, attr.span, message)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_name() {
+        assert_eq!(AriaRole::NAME, "a11y/aria-role");
+    }
+}
@@ -0,0 +1,102 @@
+//! Accessibility rules
+//!
+//! Ports of eslint-plugin-jsx-a11y rules for Solid JSX. They run in the same
+//! visitor pass as the other syntactic rules but are opt-in: enable them with
+//! `RulesConfig::with_a11y`.
+
+pub mod alt_text;
+pub mod anchor_has_content;
+pub mod aria_props;
+pub mod aria_role;
+pub mod no_autofocus;
+
+pub use alt_text::AltText;
+pub use anchor_has_content::AnchorHasContent;
+pub use aria_props::AriaProps;
+pub use aria_role::AriaRole;
+pub use no_autofocus::NoAutofocus;
+
+use oxc_ast::ast::{
+    Expression, JSXAttribute, JSXAttributeItem, JSXAttributeValue, JSXOpeningElement,
+};
+
+/// Documentation of the eslint-plugin-jsx-a11y rule a rule is ported from
+fn docs_url(name: &str) -> String {
+    format!(
+        "https://github.com/jsx-eslint/eslint-plugin-jsx-a11y/blob/main/docs/rules/{}.md",
+        name.trim_start_matches("a11y/")
+    )
+}
+
+/// Which accessibility rules are enabled (none by default)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct A11yRulesConfig {
+    pub alt_text: bool,
+    pub anchor_has_content: bool,
+    pub aria_props: bool,
+    pub aria_role: bool,
+    pub no_autofocus: bool,
+}
+
+impl A11yRulesConfig {
+    pub fn all() -> Self {
+        Self {
+            alt_text: true,
+            anchor_has_content: true,
+            aria_props: true,
+            aria_role: true,
+            no_autofocus: true,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::default()
+    }
+}
+
+/// Spread props may provide any attribute, so presence checks give up
+fn has_spread(opening: &JSXOpeningElement) -> bool {
+    opening
+        .attributes
+        .iter()
+        .any(|attr| matches!(attr, JSXAttributeItem::SpreadAttribute(_)))
+}
+
+/// Value of an attribute known at compile time (`a="x"`, `a={"x"}`,
+/// `` a={`x`} ``)
+fn static_string_value<'a>(attr: &'a JSXAttribute<'a>) -> Option<&'a str> {
+    match attr.value.as_ref()? {
+        JSXAttributeValue::StringLiteral(lit) => Some(lit.value.as_str()),
+        JSXAttributeValue::ExpressionContainer(container) => {
+            match container.expression.as_expression()?.get_inner_expression() {
+                Expression::StringLiteral(lit) => Some(lit.value.as_str()),
+                Expression::TemplateLiteral(lit) if lit.expressions.is_empty() => lit
+                    .quasis
+                    .first()?
+                    .value
+                    .cooked
+                    .as_ref()
+                    .map(|cooked| cooked.as_str()),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `attr={undefined}`, `attr={null}` and `attr={false}` leave the attribute out
+fn is_absent_value(attr: &JSXAttribute) -> bool {
+    let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value else {
+        return false;
+    };
+    match container
+        .expression
+        .as_expression()
+        .map(Expression::get_inner_expression)
+    {
+        Some(Expression::Identifier(ident)) => ident.name == "undefined",
+        Some(Expression::NullLiteral(_)) => true,
+        Some(Expression::BooleanLiteral(lit)) => !lit.value,
+        _ => false,
+    }
+}
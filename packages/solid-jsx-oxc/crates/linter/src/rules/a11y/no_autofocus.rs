@@ -0,0 +1,67 @@
+//! a11y/no-autofocus
+//!
+//! Disallow `autofocus` on DOM elements; moving focus on load disorients
+//! screen reader and keyboard users.
+
+use oxc_ast::ast::{JSXAttributeItem, JSXAttributeName, JSXOpeningElement};
+
+use super::is_absent_value;
+use crate::diagnostic::Diagnostic;
+use crate::utils::{get_element_name, is_dom_element};
+use crate::{RuleCategory, RuleMeta};
+
+/// no-autofocus rule
+#[derive(Debug, Clone, Default)]
+pub struct NoAutofocus;
+
+impl RuleMeta for NoAutofocus {
+    const NAME: &'static str = "a11y/no-autofocus";
+    const CATEGORY: RuleCategory = RuleCategory::Accessibility;
+
+    fn docs_url() -> String {
+        super::docs_url(Self::NAME)
+    }
+}
+
+impl NoAutofocus {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check a JSX opening element for `autofocus`/`autoFocus`
+    pub fn check<'a>(&self, opening: &JSXOpeningElement<'a>) -> Vec<Diagnostic> {
+        if !get_element_name(opening).is_some_and(|name| is_dom_element(&name)) {
+            return Vec::new();
+        }
+        opening
+            .attributes
+            .iter()
+            .filter_map(|attr| match attr {
+                JSXAttributeItem::Attribute(attr) => Some(attr),
+                JSXAttributeItem::SpreadAttribute(_) => None,
+            })
+            .filter(|attr| {
+                matches!(&attr.name, JSXAttributeName::Identifier(ident)
+                    if ident.name.eq_ignore_ascii_case("autofocus"))
+                    && !is_absent_value(attr)
+            })
+            .map(|attr| {
+                Diagnostic::warning(
+                    Self::NAME,
+                    attr.span,
+                    "The autofocus prop should not be used, as it reduces usability and accessibility.",
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_name() {
+        assert_eq!(NoAutofocus::NAME, "a11y/no-autofocus");
+    }
+}
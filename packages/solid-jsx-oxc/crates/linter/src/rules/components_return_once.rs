@@ -2,17 +2,36 @@
 //!
 //! Disallow early returns in components. Solid components only run once,
 //! and so conditionals should be inside JSX.
+//!
+//! Functions passed to other functions are callbacks (render props, `map`
+//! callbacks) rather than components, unless the callee is one of the
+//! configured `wrappers` (`memo`-like HOCs, custom component factories).
 
 use oxc_ast::ast::{
     ArrowFunctionExpression, Expression, Function, FunctionBody, Statement,
 };
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::diagnostic::Diagnostic;
 use crate::{RuleCategory, RuleMeta};
 
+/// Configuration for components-return-once
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentsReturnOnceConfig {
+    /// Functions whose function arguments are components
+    /// (`withAuth((props) => ...)`); matched against the callee name or the
+    /// last property of a member callee
+    #[serde(default)]
+    pub wrappers: Vec<String>,
+}
+
 /// components-return-once rule
 #[derive(Debug, Clone, Default)]
-pub struct ComponentsReturnOnce;
+pub struct ComponentsReturnOnce {
+    pub config: ComponentsReturnOnceConfig,
+}
 
 impl RuleMeta for ComponentsReturnOnce {
     const NAME: &'static str = "components-return-once";
@@ -21,7 +40,21 @@ impl RuleMeta for ComponentsReturnOnce {
 
 impl ComponentsReturnOnce {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_config(config: ComponentsReturnOnceConfig) -> Self {
+        Self { config }
+    }
+
+    /// Whether functions passed to `callee` are components
+    pub fn is_wrapper(&self, callee: &Expression) -> bool {
+        let name = match callee.get_inner_expression() {
+            Expression::Identifier(ident) => ident.name.as_str(),
+            Expression::StaticMemberExpression(member) => member.property.name.as_str(),
+            _ => return false,
+        };
+        self.config.wrappers.iter().any(|wrapper| wrapper == name)
     }
 
     /// Check a function for early returns and conditional returns
@@ -144,4 +177,11 @@ mod tests {
     fn test_rule_name() {
         assert_eq!(ComponentsReturnOnce::NAME, "components-return-once");
     }
+
+    #[test]
+    fn test_config_deserialize() {
+        let json = r#"{"wrappers": ["withAuth", "memo"]}"#;
+        let config: ComponentsReturnOnceConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.wrappers, vec!["withAuth", "memo"]);
+    }
 }
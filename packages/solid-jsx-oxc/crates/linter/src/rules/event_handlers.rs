@@ -10,7 +10,7 @@ use oxc_ast::ast::{
 use serde::{Deserialize, Serialize};
 
 use crate::diagnostic::{Diagnostic, Fix};
-use crate::utils::is_dom_element;
+use crate::utils::{container_expression, is_dom_element};
 use crate::{RuleCategory, RuleMeta};
 
 /// Common DOM events with correct casing
@@ -223,7 +223,7 @@ impl EventHandlers {
         match value {
             JSXAttributeValue::StringLiteral(_) => true,
             JSXAttributeValue::ExpressionContainer(container) => {
-                if let Some(expr) = container.expression.as_expression() {
+                if let Some(expr) = container_expression(container) {
                     matches!(expr, Expression::StringLiteral(_) | Expression::NumericLiteral(_))
                 } else {
                     false
@@ -1,10 +1,13 @@
 //! solid/imports
 //!
-//! Enforce consistent imports from "solid-js", "solid-js/web", and "solid-js/store".
+//! Enforce consistent imports from "solid-js", "solid-js/web", and "solid-js/store",
+//! each imported by a single declaration.
 
-use oxc_ast::ast::ImportDeclaration;
+use oxc_ast::ast::{ImportDeclaration, ImportDeclarationSpecifier, Program, Statement};
+use oxc_span::{GetSpan, Span};
 
-use crate::diagnostic::Diagnostic;
+use crate::diagnostic::{Diagnostic, Fix};
+use crate::utils::SOLID_SOURCES;
 use crate::{RuleCategory, RuleMeta};
 
 /// imports rule
@@ -16,9 +19,6 @@ impl RuleMeta for Imports {
     const CATEGORY: RuleCategory = RuleCategory::Correctness;
 }
 
-/// Valid sources for Solid imports
-const SOLID_SOURCES: &[&str] = &["solid-js", "solid-js/web", "solid-js/store"];
-
 /// Primitives that should be imported from "solid-js"
 const SOLID_JS_PRIMITIVES: &[&str] = &[
     "createSignal",
@@ -210,6 +210,135 @@ impl Imports {
 
         diagnostics
     }
+
+    /// Report Solid sources imported by more than one declaration, with a fix
+    /// merging them into the first one
+    pub fn check_duplicates<'a>(
+        &self,
+        program: &Program<'a>,
+        source_text: &str,
+    ) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for &source in SOLID_SOURCES {
+            let imports: Vec<&ImportDeclaration<'a>> = program
+                .body
+                .iter()
+                .filter_map(|stmt| match stmt {
+                    Statement::ImportDeclaration(import) => Some(&**import),
+                    _ => None,
+                })
+                .filter(|import| import.source.value == source && is_mergeable(import))
+                .collect();
+            if imports.len() < 2 {
+                continue;
+            }
+
+            let fixes = merge_fixes(&imports, source_text);
+            for import in &imports[1..] {
+                let mut diagnostic = Diagnostic::warning(
+                    Self::NAME,
+                    import.span,
+                    format!("\"{}\" is already imported above.", source),
+                )
+                .with_help("Merge the imports into a single declaration.")
+                .with_label(imports[0].span, "first imported here");
+                for fix in fixes.iter().flatten() {
+                    diagnostic = diagnostic.with_fix(fix.clone());
+                }
+                diagnostics.push(diagnostic);
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Whether a declaration can be merged with others from the same source:
+/// namespace imports, import attributes, phase imports and side-effect
+/// imports are left alone
+fn is_mergeable(import: &ImportDeclaration) -> bool {
+    import.with_clause.is_none()
+        && import.phase.is_none()
+        && import.specifiers.as_ref().is_some_and(|specifiers| {
+            !specifiers.is_empty()
+                && !specifiers.iter().any(|specifier| {
+                    matches!(
+                        specifier,
+                        ImportDeclarationSpecifier::ImportNamespaceSpecifier(_)
+                    )
+                })
+        })
+}
+
+/// Edits rewriting the first declaration to import every specifier and
+/// removing the others, or `None` when they can't be merged into one
+/// declaration (two default imports, or a type-only default import among
+/// value imports)
+fn merge_fixes(imports: &[&ImportDeclaration], source_text: &str) -> Option<Vec<Fix>> {
+    let all_types = imports.iter().all(|import| import.import_kind.is_type());
+    let mut default = None;
+    let mut named = Vec::new();
+
+    for import in imports {
+        let is_type = import.import_kind.is_type() && !all_types;
+        for specifier in import.specifiers.iter().flatten() {
+            let text = &source_text[specifier.span().start as usize..specifier.span().end as usize];
+            match specifier {
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(_) => {
+                    if default.is_some() || is_type {
+                        return None;
+                    }
+                    default = Some(text);
+                }
+                ImportDeclarationSpecifier::ImportSpecifier(_) if is_type => {
+                    named.push(format!("type {}", text));
+                }
+                _ => named.push(text.to_string()),
+            }
+        }
+    }
+
+    let first = imports[0];
+    let mut clause = default.map(str::to_string).into_iter().collect::<Vec<_>>();
+    if !named.is_empty() {
+        clause.push(format!("{{ {} }}", named.join(", ")));
+    }
+    let semicolon = if source_text[..first.span.end as usize].ends_with(';') {
+        ";"
+    } else {
+        ""
+    };
+    let merged = format!(
+        "import {}{} from {}{}",
+        if all_types { "type " } else { "" },
+        clause.join(", "),
+        first.source.span.source_text(source_text),
+        semicolon
+    );
+
+    let mut fixes = vec![Fix::new(first.span, merged).with_message("Merge imports")];
+    fixes.extend(
+        imports[1..]
+            .iter()
+            .map(|import| Fix::new(line_span(import.span, source_text), "")),
+    );
+    Some(fixes)
+}
+
+/// `span` widened to its whole line when nothing else is on it
+fn line_span(span: Span, source_text: &str) -> Span {
+    let before = &source_text[..span.start as usize];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    if !before[line_start..].trim().is_empty() {
+        return span;
+    }
+    let after = &source_text[span.end as usize..];
+    let line_end = after.find('\n').map_or(after.len(), |i| i + 1);
+    if !after[..line_end].trim().is_empty() {
+        return span;
+    }
+    Span::new(line_start as u32, span.end + line_end as u32)
 }
 
 #[cfg(test)]
@@ -424,6 +553,47 @@ mod tests {
         assert!(diagnostics.is_empty(), "unknown imports should be ignored");
     }
 
+    fn check_duplicates(source: &str) -> Vec<Diagnostic> {
+        let allocator = Allocator::default();
+        let program = parse_and_get_import(&allocator, source).expect("should parse");
+        Imports::new().check_duplicates(&program, source)
+    }
+
+    #[test]
+    fn test_duplicate_imports_merged() {
+        let source = "import { createSignal } from \"solid-js\";\nimport { render } from \"solid-js/web\";\nimport type { Component } from \"solid-js\";\nimport { Show as When, For } from \"solid-js\";\n";
+        let diagnostics = check_duplicates(source);
+        assert_eq!(diagnostics.len(), 2, "both later solid-js imports are duplicates");
+
+        let fixed = crate::fixer::apply_fixes(source, &diagnostics);
+        assert_eq!(fixed.applied, 1);
+        assert_eq!(
+            fixed.output,
+            "import { createSignal, type Component, Show as When, For } from \"solid-js\";\nimport { render } from \"solid-js/web\";\n"
+        );
+    }
+
+    #[test]
+    fn test_duplicate_type_imports_stay_type_only() {
+        let source = "import type { Component } from 'solid-js'\nimport type { JSX } from 'solid-js'\n";
+        let diagnostics = check_duplicates(source);
+        assert_eq!(diagnostics.len(), 1);
+        let fixed = crate::fixer::apply_fixes(source, &diagnostics);
+        assert_eq!(fixed.output, "import type { Component, JSX } from 'solid-js'\n");
+    }
+
+    #[test]
+    fn test_duplicate_imports_not_merged() {
+        // Namespace and side-effect imports can't be merged into named imports
+        let source = r#"
+            import * as solid from "solid-js";
+            import "solid-js";
+            import { createSignal } from "solid-js";
+            import { createStore } from "solid-js/store";
+        "#;
+        assert!(check_duplicates(source).is_empty());
+    }
+
     #[test]
     fn test_components_from_solid_js() {
         let allocator = Allocator::default();
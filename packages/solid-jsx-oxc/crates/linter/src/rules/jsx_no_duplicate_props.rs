@@ -9,6 +9,7 @@
 use oxc_ast::ast::{JSXAttributeItem, JSXAttributeName, JSXChild, JSXOpeningElement};
 use oxc_span::Span;
 use rustc_hash::FxHashMap;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::diagnostic::Diagnostic;
@@ -16,7 +17,7 @@ use crate::utils::{has_children, is_event_handler};
 use crate::{RuleCategory, RuleMeta};
 
 /// Configuration for jsx-no-duplicate-props
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct JsxNoDuplicatePropsConfig {
     /// Consider two prop names differing only by case to be the same
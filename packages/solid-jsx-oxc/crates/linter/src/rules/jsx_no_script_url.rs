@@ -1,10 +1,12 @@
 //! solid/jsx-no-script-url
 //!
-//! Disallow `javascript:` URLs in JSX attributes.
+//! Disallow `javascript:` URLs in JSX attributes, including ones built from
+//! template literals or string concatenations with a static prefix.
 
 use oxc_ast::ast::{
     Expression, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXOpeningElement,
 };
+use oxc_syntax::operator::BinaryOperator;
 
 use crate::diagnostic::Diagnostic;
 use crate::{RuleCategory, RuleMeta};
@@ -58,50 +60,71 @@ impl JsxNoScriptUrl {
         span: oxc_span::Span,
         attr_name: &str,
     ) -> Option<Diagnostic> {
-        match value {
-            JSXAttributeValue::StringLiteral(lit) => {
-                let value_str = lit.value.as_str().trim();
-                if value_str.to_lowercase().starts_with("javascript:") {
-                    return Some(
-                        Diagnostic::error(
-                            Self::NAME,
-                            span,
-                            format!(
-                                "`javascript:` URLs in the `{}` attribute are a security risk.",
-                                attr_name
-                            ),
-                        )
-                        .with_help("Use an event handler like `onClick` instead."),
-                    );
-                }
-            }
+        let prefix = match value {
+            JSXAttributeValue::StringLiteral(lit) => lit.value.to_string(),
             JSXAttributeValue::ExpressionContainer(container) => {
-                // Check string expressions
-                if let Some(expr) = container.expression.as_expression() {
-                    if let Expression::StringLiteral(lit) = expr {
-                        let value_str = lit.value.as_str().trim();
-                        if value_str.to_lowercase().starts_with("javascript:") {
-                            return Some(
-                                Diagnostic::error(
-                                    Self::NAME,
-                                    span,
-                                    format!(
-                                        "`javascript:` URLs in the `{}` attribute are a security risk.",
-                                        attr_name
-                                    ),
-                                )
-                                .with_help("Use an event handler like `onClick` instead."),
-                            );
-                        }
-                    }
+                static_prefix(container.expression.as_expression()?)?.0
+            }
+            _ => return None,
+        };
+        if !is_javascript_url(&prefix) {
+            return None;
+        }
+        Some(
+            Diagnostic::error(
+                Self::NAME,
+                span,
+                format!(
+                    "`javascript:` URLs in the `{}` attribute are a security risk.",
+                    attr_name
+                ),
+            )
+            .with_help("Use an event handler like `onClick` instead."),
+        )
+    }
+}
+
+/// The statically known start of a string expression, and whether it is the
+/// whole string: string literals, template literals up to their first
+/// `${}` and `+` concatenations of those
+fn static_prefix(expr: &Expression) -> Option<(String, bool)> {
+    match expr.get_inner_expression() {
+        Expression::StringLiteral(lit) => Some((lit.value.to_string(), true)),
+        Expression::TemplateLiteral(template) => {
+            let first = template.quasis.first()?;
+            let text = first.value.cooked.unwrap_or(first.value.raw);
+            Some((text.to_string(), template.expressions.is_empty()))
+        }
+        Expression::BinaryExpression(binary) if binary.operator == BinaryOperator::Addition => {
+            let (mut prefix, complete) = static_prefix(&binary.left)?;
+            if !complete {
+                return Some((prefix, false));
+            }
+            match static_prefix(&binary.right) {
+                Some((rest, complete)) => {
+                    prefix.push_str(&rest);
+                    Some((prefix, complete))
                 }
+                None => Some((prefix, false)),
             }
-            _ => {}
         }
-        None
+        _ => None,
     }
 }
 
+/// Whether a URL uses the `javascript:` scheme the way browsers parse it:
+/// leading whitespace and control characters are skipped, and tabs and
+/// newlines inside the scheme are ignored
+fn is_javascript_url(url: &str) -> bool {
+    let scheme: String = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|c| !matches!(c, '\t' | '\n' | '\r'))
+        .take("javascript:".len())
+        .collect();
+    scheme.eq_ignore_ascii_case("javascript:")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,7 +52,7 @@ impl RuleMeta for JsxNoUndef {
 
 /// Information about an undefined identifier
 #[derive(Debug)]
-struct UndefinedIdent {
+pub struct UndefinedIdent {
     name: String,
     span: Span,
     is_component: bool,
@@ -81,27 +81,29 @@ impl JsxNoUndef {
         match &opening.name {
             JSXElementName::Identifier(ident) => {
                 // Only check components (capitalized names), not DOM elements
-                if !is_dom_element(&ident.name) && ident.name != "this" {
-                    if !self.is_defined(scoping, scope_id, &ident.name) {
-                        undefined.push(UndefinedIdent {
-                            name: ident.name.to_string(),
-                            span: ident.span,
-                            is_component: true,
-                            is_custom_directive: false,
-                        });
-                    }
+                if !is_dom_element(&ident.name)
+                    && ident.name != "this"
+                    && !self.is_defined(scoping, scope_id, &ident.name)
+                {
+                    undefined.push(UndefinedIdent {
+                        name: ident.name.to_string(),
+                        span: ident.span,
+                        is_component: true,
+                        is_custom_directive: false,
+                    });
                 }
             }
             JSXElementName::IdentifierReference(ident) => {
-                if !is_dom_element(&ident.name) && ident.name != "this" {
-                    if !self.is_defined(scoping, scope_id, &ident.name) {
-                        undefined.push(UndefinedIdent {
-                            name: ident.name.to_string(),
-                            span: ident.span,
-                            is_component: true,
-                            is_custom_directive: false,
-                        });
-                    }
+                if !is_dom_element(&ident.name)
+                    && ident.name != "this"
+                    && !self.is_defined(scoping, scope_id, &ident.name)
+                {
+                    undefined.push(UndefinedIdent {
+                        name: ident.name.to_string(),
+                        span: ident.span,
+                        is_component: true,
+                        is_custom_directive: false,
+                    });
                 }
             }
             JSXElementName::MemberExpression(member) => {
@@ -152,10 +154,8 @@ impl JsxNoUndef {
         }
 
         // Check global scope if allowed
-        if self.options.allow_globals {
-            if scoping.get_root_binding(name).is_some() {
-                return true;
-            }
+        if self.options.allow_globals && scoping.get_root_binding(name).is_some() {
+            return true;
         }
 
         false
@@ -13,6 +13,7 @@ use oxc_ast::ast::{
     JSXOpeningElement,
 };
 
+use crate::utils::is_pascal_case;
 use crate::{RuleCategory, RuleMeta};
 
 /// jsx-uses-vars rule
@@ -38,12 +39,12 @@ impl JsxUsesVars {
         match &opening.name {
             JSXElementName::Identifier(ident) => {
                 // Only mark as used if it's a component (capitalized)
-                if ident.name.chars().next().is_some_and(|c| c.is_uppercase()) {
+                if is_pascal_case(&ident.name) {
                     used.push(ident.name.to_string());
                 }
             }
             JSXElementName::IdentifierReference(ident) => {
-                if ident.name.chars().next().is_some_and(|c| c.is_uppercase()) {
+                if is_pascal_case(&ident.name) {
                     used.push(ident.name.to_string());
                 }
             }
@@ -101,10 +102,8 @@ mod tests {
         for stmt in &program.body {
             if let Statement::VariableDeclaration(decl) = stmt {
                 for declarator in &decl.declarations {
-                    if let Some(init) = &declarator.init {
-                        if let Expression::JSXElement(elem) = init {
-                            return Some(elem);
-                        }
+                    if let Some(Expression::JSXElement(elem)) = &declarator.init {
+                        return Some(elem);
                     }
                 }
             }
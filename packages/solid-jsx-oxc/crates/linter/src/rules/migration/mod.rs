@@ -0,0 +1,195 @@
+//! React-to-Solid migration rules
+//!
+//! Rules for codebases moving from React: they report React imports, hooks
+//! and types left in Solid code, with the Solid API to use instead, and fix
+//! the ones with a drop-in replacement. They only look at bindings imported
+//! from React, so they run in the semantic pass and are opt-in: enable them
+//! with `SemanticRulesConfig::with_migration`.
+
+pub mod no_react_apis;
+pub mod no_react_imports;
+pub mod no_react_types;
+
+pub use no_react_apis::NoReactApis;
+pub use no_react_imports::NoReactImports;
+pub use no_react_types::NoReactTypes;
+
+/// Solid's guide for React developers
+fn docs_url() -> String {
+    "https://www.solidjs.com/guides/comparison#react".to_string()
+}
+
+/// Which migration rules are enabled (none by default)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MigrationRulesConfig {
+    pub no_react_apis: bool,
+    pub no_react_imports: bool,
+    pub no_react_types: bool,
+}
+
+impl MigrationRulesConfig {
+    pub fn all() -> Self {
+        Self {
+            no_react_apis: true,
+            no_react_imports: true,
+            no_react_types: true,
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.no_react_apis || self.no_react_imports || self.no_react_types
+    }
+}
+
+/// React packages whose imports the rules look at
+pub fn is_react_source(source: &str) -> bool {
+    ["react", "react-dom"].iter().any(|module| {
+        source
+            .strip_prefix(module)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+    })
+}
+
+/// What to use in Solid instead of a React export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SolidEquivalent {
+    /// Name of the React export
+    pub react: &'static str,
+    /// Solid API or pattern replacing it, as shown in diagnostics
+    pub solid: &'static str,
+    /// Module exporting `solid` under the same signature, when it is a
+    /// drop-in replacement
+    pub drop_in: Option<&'static str>,
+}
+
+impl SolidEquivalent {
+    /// The replacement as shown in diagnostics
+    pub fn describe(&self) -> String {
+        match self.drop_in {
+            Some(module) => format!("`{}` from `{}`", self.solid, module),
+            None => self.solid.to_string(),
+        }
+    }
+}
+
+const fn drop_in(
+    react: &'static str,
+    solid: &'static str,
+    module: &'static str,
+) -> SolidEquivalent {
+    SolidEquivalent {
+        react,
+        solid,
+        drop_in: Some(module),
+    }
+}
+
+const fn replaced(react: &'static str, solid: &'static str) -> SolidEquivalent {
+    SolidEquivalent {
+        react,
+        solid,
+        drop_in: None,
+    }
+}
+
+/// React values and the Solid APIs replacing them
+const VALUES: &[SolidEquivalent] = &[
+    replaced(
+        "useState",
+        "`createSignal` (read the value by calling the getter)",
+    ),
+    replaced("useEffect", "`createEffect`, or `onMount` and `onCleanup`"),
+    replaced("useLayoutEffect", "`createRenderEffect`"),
+    replaced("useInsertionEffect", "`createRenderEffect`"),
+    replaced(
+        "useMemo",
+        "`createMemo` (dependencies are tracked automatically)",
+    ),
+    replaced("useCallback", "a plain function (components run once)"),
+    replaced("useRef", "a plain variable, assigned with `ref={el}`"),
+    replaced("useReducer", "`createStore` or `createSignal`"),
+    replaced(
+        "useTransition",
+        "`useTransition` (its pending state is a getter)",
+    ),
+    replaced("useDeferredValue", "`createDeferred`"),
+    replaced("useSyncExternalStore", "`from`"),
+    replaced("useImperativeHandle", "a function passed through props"),
+    replaced("memo", "the component itself (components run once)"),
+    replaced(
+        "forwardRef",
+        "the `ref` prop, which components receive like other props",
+    ),
+    replaced("Fragment", "`<>...</>`"),
+    replaced("StrictMode", "nothing; remove it"),
+    replaced("Children", "the `children` helper"),
+    replaced("cloneElement", "props passed to the child component"),
+    replaced("createElement", "JSX"),
+    replaced("createPortal", "`<Portal>` from `solid-js/web`"),
+    replaced("createRoot", "`render` from `solid-js/web`"),
+    replaced(
+        "render",
+        "`render` from `solid-js/web`, which takes a function (`render(() => <App />, el)`)",
+    ),
+    replaced(
+        "hydrate",
+        "`hydrate` from `solid-js/web`, which takes a function",
+    ),
+    replaced("hydrateRoot", "`hydrate` from `solid-js/web`"),
+    drop_in("useContext", "useContext", "solid-js"),
+    drop_in("createContext", "createContext", "solid-js"),
+    drop_in("useId", "createUniqueId", "solid-js"),
+    drop_in("lazy", "lazy", "solid-js"),
+    drop_in("Suspense", "Suspense", "solid-js"),
+];
+
+/// React types and the Solid types replacing them
+const TYPES: &[SolidEquivalent] = &[
+    drop_in("FC", "Component", "solid-js"),
+    drop_in("FunctionComponent", "Component", "solid-js"),
+    drop_in("PropsWithChildren", "ParentProps", "solid-js"),
+    drop_in("ComponentProps", "ComponentProps", "solid-js"),
+    replaced("ReactNode", "`JSX.Element`"),
+    replaced("ReactElement", "`JSX.Element`"),
+    replaced("CSSProperties", "`JSX.CSSProperties`"),
+    replaced("RefObject", "a plain variable of the element type"),
+    replaced("MutableRefObject", "a plain variable"),
+    replaced("Dispatch", "the setter returned by `createSignal`"),
+    replaced("SetStateAction", "the setter returned by `createSignal`"),
+];
+
+/// The Solid replacement of the React value `name`
+pub fn value_equivalent(name: &str) -> Option<&'static SolidEquivalent> {
+    VALUES.iter().find(|equivalent| equivalent.react == name)
+}
+
+/// The Solid replacement of the React type `name`
+pub fn type_equivalent(name: &str) -> Option<&'static SolidEquivalent> {
+    TYPES.iter().find(|equivalent| equivalent.react == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_react_sources() {
+        assert!(is_react_source("react"));
+        assert!(is_react_source("react-dom/client"));
+        assert!(is_react_source("react/jsx-runtime"));
+        assert!(!is_react_source("react-icons"));
+        assert!(!is_react_source("solid-js"));
+    }
+
+    #[test]
+    fn test_equivalents() {
+        assert_eq!(value_equivalent("useId").unwrap().drop_in, Some("solid-js"));
+        assert_eq!(value_equivalent("useState").unwrap().drop_in, None);
+        assert_eq!(type_equivalent("FC").unwrap().solid, "Component");
+        assert!(value_equivalent("FC").is_none());
+    }
+}
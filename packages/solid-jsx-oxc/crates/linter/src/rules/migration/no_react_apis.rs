@@ -0,0 +1,126 @@
+//! migration/no-react-apis
+//!
+//! Report calls to React hooks and APIs (`useState`, `useEffect`,
+//! `React.memo`, ...) and React components (`<Fragment>`, `<StrictMode>`) with
+//! the Solid API to use instead. `useCallback(fn, deps)` is replaced with
+//! `fn`, and `<Fragment>` and `<StrictMode>` without props with `<>...</>`.
+//! Drop-in replacements imported by name are left to
+//! `migration/no-react-imports`, which fixes the import.
+
+use oxc_ast::ast::{Argument, CallExpression, Expression, JSXElement};
+use oxc_span::{GetSpan, Span};
+
+use super::value_equivalent;
+use crate::diagnostic::{Diagnostic, Fix};
+use crate::{RuleCategory, RuleMeta};
+
+/// no-react-apis rule
+#[derive(Debug, Clone, Default)]
+pub struct NoReactApis;
+
+impl RuleMeta for NoReactApis {
+    const NAME: &'static str = "migration/no-react-apis";
+    const CATEGORY: RuleCategory = RuleCategory::Migration;
+
+    fn docs_url() -> String {
+        super::docs_url()
+    }
+}
+
+impl NoReactApis {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check a call of the React export `name`, called by name or as a member
+    /// of the `React` namespace
+    pub fn check_call<'a>(
+        &self,
+        call: &CallExpression<'a>,
+        name: &str,
+        source_text: &str,
+    ) -> Option<Diagnostic> {
+        let is_member = call.callee.is_member_expression();
+        let diagnostic = self.diagnostic(call.callee.span(), name, is_member, source_text)?;
+        if name != "useCallback"
+            || call.arguments.len() > 2
+            || call
+                .arguments
+                .iter()
+                .any(|arg| matches!(arg, Argument::SpreadElement(_)))
+        {
+            return Some(diagnostic);
+        }
+        let callback = call.arguments.first()?.as_expression()?;
+        if !matches!(
+            callback.get_inner_expression(),
+            Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_)
+        ) {
+            return Some(diagnostic);
+        }
+        Some(
+            diagnostic.with_fix(
+                Fix::new(call.span, slice(source_text, callback.span()))
+                    .with_message("Use the function directly"),
+            ),
+        )
+    }
+
+    /// Check a JSX element whose tag is the React export `name`
+    pub fn check_element<'a>(
+        &self,
+        element: &JSXElement<'a>,
+        name: &str,
+        is_member: bool,
+        source_text: &str,
+    ) -> Option<Diagnostic> {
+        let opening = &element.opening_element;
+        let diagnostic = self.diagnostic(opening.name.span(), name, is_member, source_text)?;
+        if !matches!(name, "Fragment" | "StrictMode") || !opening.attributes.is_empty() {
+            return Some(diagnostic);
+        }
+        let children = match &element.closing_element {
+            Some(closing) => slice(source_text, Span::new(opening.span.end, closing.span.start)),
+            None => "",
+        };
+        Some(diagnostic.with_fix(
+            Fix::new(element.span, format!("<>{}</>", children)).with_message("Use a fragment"),
+        ))
+    }
+
+    fn diagnostic(
+        &self,
+        span: Span,
+        name: &str,
+        is_member: bool,
+        source_text: &str,
+    ) -> Option<Diagnostic> {
+        let equivalent = value_equivalent(name)?;
+        if equivalent.drop_in.is_some() && !is_member {
+            return None;
+        }
+        Some(Diagnostic::warning(
+            Self::NAME,
+            span,
+            format!(
+                "`{}` is a React API; use {} in Solid.",
+                slice(source_text, span),
+                equivalent.describe()
+            ),
+        ))
+    }
+}
+
+fn slice(source_text: &str, span: Span) -> &str {
+    &source_text[span.start as usize..span.end as usize]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_name() {
+        assert_eq!(NoReactApis::NAME, "migration/no-react-apis");
+    }
+}
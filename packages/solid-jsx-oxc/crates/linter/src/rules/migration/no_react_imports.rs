@@ -0,0 +1,170 @@
+//! migration/no-react-imports
+//!
+//! Report imports from `react` and `react-dom` in Solid code. An import whose
+//! bindings all have a drop-in Solid replacement is rewritten to import them
+//! from Solid; a `React` default or namespace import that nothing reads (it
+//! was only there for the JSX transform) is removed.
+
+use oxc_ast::ast::{BindingIdentifier, ImportDeclaration, ImportDeclarationSpecifier};
+use oxc_span::Span;
+
+use super::{is_react_source, type_equivalent, value_equivalent, SolidEquivalent};
+use crate::diagnostic::{Diagnostic, Fix};
+use crate::{RuleCategory, RuleMeta};
+
+/// no-react-imports rule
+#[derive(Debug, Clone, Default)]
+pub struct NoReactImports;
+
+impl RuleMeta for NoReactImports {
+    const NAME: &'static str = "migration/no-react-imports";
+    const CATEGORY: RuleCategory = RuleCategory::Migration;
+
+    fn docs_url() -> String {
+        super::docs_url()
+    }
+}
+
+impl NoReactImports {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check an import declaration; `is_referenced` tells whether the file
+    /// reads a binding
+    pub fn check<'a>(
+        &self,
+        import: &ImportDeclaration<'a>,
+        source_text: &str,
+        is_referenced: impl Fn(&BindingIdentifier<'a>) -> bool,
+    ) -> Option<Diagnostic> {
+        let source = import.source.value.as_str();
+        if !is_react_source(source) {
+            return None;
+        }
+
+        let mut diagnostic = Diagnostic::warning(
+            Self::NAME,
+            import.span,
+            format!("`{}` is imported in Solid code.", source),
+        );
+        let specifiers = import.specifiers.as_ref();
+        let replacements: Vec<String> = specifiers
+            .into_iter()
+            .flatten()
+            .filter_map(|spec| match spec {
+                ImportDeclarationSpecifier::ImportSpecifier(named) => {
+                    let imported = named.imported.name();
+                    equivalent(
+                        &imported,
+                        named.import_kind.is_type() || import.import_kind.is_type(),
+                    )
+                    .map(|equivalent| format!("`{}`: use {}", imported, equivalent.describe()))
+                }
+                _ => None,
+            })
+            .collect();
+        if !replacements.is_empty() {
+            diagnostic = diagnostic.with_help(replacements.join("; "));
+        }
+        if let Some(fix) = specifiers
+            .and_then(|specifiers| self.fix(import, specifiers, source_text, &is_referenced))
+        {
+            diagnostic = diagnostic.with_fix(fix);
+        }
+        Some(diagnostic)
+    }
+
+    fn fix<'a>(
+        &self,
+        import: &ImportDeclaration<'a>,
+        specifiers: &[ImportDeclarationSpecifier<'a>],
+        source_text: &str,
+        is_referenced: &impl Fn(&BindingIdentifier<'a>) -> bool,
+    ) -> Option<Fix> {
+        let mut module = None;
+        let mut names = Vec::new();
+        for spec in specifiers {
+            match spec {
+                ImportDeclarationSpecifier::ImportSpecifier(named) => {
+                    let is_type = named.import_kind.is_type() || import.import_kind.is_type();
+                    let equivalent = equivalent(&named.imported.name(), is_type)?;
+                    let drop_in = equivalent.drop_in?;
+                    if module.is_some_and(|module| module != drop_in) {
+                        return None;
+                    }
+                    module = Some(drop_in);
+                    let prefix = if named.import_kind.is_type() {
+                        "type "
+                    } else {
+                        ""
+                    };
+                    names.push(if equivalent.solid == named.local.name {
+                        format!("{}{}", prefix, equivalent.solid)
+                    } else {
+                        format!("{}{} as {}", prefix, equivalent.solid, named.local.name)
+                    });
+                }
+                // `React` kept in scope for the JSX transform
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(default) => {
+                    if is_referenced(&default.local) {
+                        return None;
+                    }
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(ns) => {
+                    if is_referenced(&ns.local) {
+                        return None;
+                    }
+                }
+            }
+        }
+
+        let Some(module) = module else {
+            let end = import.span.end as usize;
+            let end = end + usize::from(source_text[end..].starts_with('\n'));
+            return Some(
+                Fix::new(Span::new(import.span.start, end as u32), "")
+                    .with_message("Remove the unused React import"),
+            );
+        };
+        let quote = &source_text[import.source.span.start as usize..][..1];
+        let keyword = if import.import_kind.is_type() {
+            "import type"
+        } else {
+            "import"
+        };
+        Some(
+            Fix::new(
+                Span::new(import.span.start, import.source.span.end),
+                format!(
+                    "{} {{ {} }} from {}{}{}",
+                    keyword,
+                    names.join(", "),
+                    quote,
+                    module,
+                    quote
+                ),
+            )
+            .with_message(format!("Import from `{}`", module)),
+        )
+    }
+}
+
+/// Replacement of an imported React name; value imports may also name types
+fn equivalent(name: &str, is_type: bool) -> Option<&'static SolidEquivalent> {
+    if is_type {
+        type_equivalent(name)
+    } else {
+        value_equivalent(name).or_else(|| type_equivalent(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_name() {
+        assert_eq!(NoReactImports::NAME, "migration/no-react-imports");
+    }
+}
@@ -0,0 +1,65 @@
+//! migration/no-react-types
+//!
+//! Report React types (`React.FC`, `ReactNode`, `PropsWithChildren`, ...) in
+//! Solid code with the Solid type to use instead. Drop-in replacements
+//! imported by name are left to `migration/no-react-imports`, which fixes the
+//! import.
+
+use oxc_span::Span;
+
+use super::type_equivalent;
+use crate::diagnostic::Diagnostic;
+use crate::{RuleCategory, RuleMeta};
+
+/// no-react-types rule
+#[derive(Debug, Clone, Default)]
+pub struct NoReactTypes;
+
+impl RuleMeta for NoReactTypes {
+    const NAME: &'static str = "migration/no-react-types";
+    const CATEGORY: RuleCategory = RuleCategory::Migration;
+
+    fn docs_url() -> String {
+        super::docs_url()
+    }
+}
+
+impl NoReactTypes {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check a type reference at `span` to the React type `name`, referenced
+    /// by name or as a member of the `React` namespace
+    pub fn check(
+        &self,
+        span: Span,
+        name: &str,
+        is_member: bool,
+        source_text: &str,
+    ) -> Option<Diagnostic> {
+        let equivalent = type_equivalent(name)?;
+        if equivalent.drop_in.is_some() && !is_member {
+            return None;
+        }
+        Some(Diagnostic::warning(
+            Self::NAME,
+            span,
+            format!(
+                "`{}` is a React type; use {} in Solid.",
+                &source_text[span.start as usize..span.end as usize],
+                equivalent.describe()
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_name() {
+        assert_eq!(NoReactTypes::NAME, "migration/no-react-types");
+    }
+}
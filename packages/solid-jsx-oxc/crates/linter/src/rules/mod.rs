@@ -1,7 +1,9 @@
 //! Solid lint rules
 //!
-//! Rules ported from eslint-plugin-solid
+//! Rules ported from eslint-plugin-solid, plus the opt-in `a11y` and
+//! `migration` groups
 
+pub mod a11y;
 pub mod components_return_once;
 pub mod event_handlers;
 pub mod imports;
@@ -9,7 +11,9 @@ pub mod jsx_no_duplicate_props;
 pub mod jsx_no_script_url;
 pub mod jsx_no_undef;
 pub mod jsx_uses_vars;
+pub mod migration;
 pub mod no_array_handlers;
+pub mod no_conditional_primitive_creation;
 pub mod no_destructure;
 pub mod no_innerhtml;
 pub mod no_proxy_apis;
@@ -25,13 +29,16 @@ pub mod style_prop;
 pub mod validate_jsx_nesting;
 
 // Re-export rule structs
+pub use a11y::{A11yRulesConfig, AltText, AnchorHasContent, AriaProps, AriaRole, NoAutofocus};
 pub use components_return_once::ComponentsReturnOnce;
 pub use event_handlers::EventHandlers;
 pub use imports::Imports;
 pub use jsx_no_duplicate_props::JsxNoDuplicateProps;
 pub use jsx_no_script_url::JsxNoScriptUrl;
 pub use jsx_uses_vars::JsxUsesVars;
+pub use migration::{MigrationRulesConfig, NoReactApis, NoReactImports, NoReactTypes};
 pub use no_array_handlers::NoArrayHandlers;
+pub use no_conditional_primitive_creation::NoConditionalPrimitiveCreation;
 pub use no_destructure::NoDestructure;
 pub use no_innerhtml::NoInnerhtml;
 pub use no_proxy_apis::NoProxyApis;
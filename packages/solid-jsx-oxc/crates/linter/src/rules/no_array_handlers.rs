@@ -8,7 +8,7 @@ use oxc_ast::ast::{
 };
 
 use crate::diagnostic::Diagnostic;
-use crate::utils::is_dom_element;
+use crate::utils::{container_expression, is_dom_element};
 use crate::{RuleCategory, RuleMeta};
 
 /// no-array-handlers rule
@@ -65,7 +65,7 @@ impl NoArrayHandlers {
 
             // Check if value is an array expression
             if let Some(JSXAttributeValue::ExpressionContainer(container)) = &jsx_attr.value {
-                if let Some(expr) = container.expression.as_expression() {
+                if let Some(expr) = container_expression(container) {
                     if matches!(expr, Expression::ArrayExpression(_)) {
                         diagnostics.push(Diagnostic::warning(
                             Self::NAME,
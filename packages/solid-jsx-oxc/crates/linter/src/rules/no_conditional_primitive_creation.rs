@@ -0,0 +1,207 @@
+//! solid/no-conditional-primitive-creation
+//!
+//! Disallow creating reactive primitives inside loops, conditionals or event
+//! handlers of a component. A component body runs once, so primitives belong
+//! at its top level; created anywhere else they are made per iteration or per
+//! event and are never disposed with the component. This is a frequent habit
+//! carried over from React hooks.
+
+use oxc_ast::ast::{
+    ArrowFunctionExpression, CallExpression, ConditionalExpression, DoWhileStatement, Expression,
+    ForInStatement, ForOfStatement, ForStatement, Function, FunctionBody, IfStatement,
+    JSXAttribute, JSXAttributeName, JSXAttributeValue, LogicalExpression, SwitchStatement,
+    WhileStatement,
+};
+use oxc_ast_visit::{walk, Visit};
+use oxc_syntax::scope::ScopeFlags;
+
+use crate::diagnostic::Diagnostic;
+use crate::utils::container_expression;
+use crate::{RuleCategory, RuleMeta};
+
+/// Primitives that must be created once per component
+const PRIMITIVES: &[&str] = &[
+    "createSignal",
+    "createMemo",
+    "createEffect",
+    "createComputed",
+    "createRenderEffect",
+    "createResource",
+    "createStore",
+];
+
+/// no-conditional-primitive-creation rule
+#[derive(Debug, Clone, Default)]
+pub struct NoConditionalPrimitiveCreation;
+
+impl RuleMeta for NoConditionalPrimitiveCreation {
+    const NAME: &'static str = "no-conditional-primitive-creation";
+    const CATEGORY: RuleCategory = RuleCategory::Nursery;
+}
+
+impl NoConditionalPrimitiveCreation {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Check the body of a component. Nested functions other than event
+    /// handlers are skipped since it is unknown when they run.
+    pub fn check_component_body<'a>(&self, body: &FunctionBody<'a>) -> Vec<Diagnostic> {
+        let mut finder = PrimitiveFinder::default();
+        finder.visit_function_body(body);
+        finder.diagnostics
+    }
+}
+
+#[derive(Default)]
+struct PrimitiveFinder {
+    loops: usize,
+    conditionals: usize,
+    handlers: usize,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl PrimitiveFinder {
+    fn visit_handler(&mut self, expr: &Expression) {
+        let body = match expr.get_inner_expression() {
+            Expression::ArrowFunctionExpression(arrow) => &arrow.body,
+            Expression::FunctionExpression(func) => match &func.body {
+                Some(body) => body,
+                None => return,
+            },
+            _ => return,
+        };
+        self.handlers += 1;
+        self.visit_function_body(body);
+        self.handlers -= 1;
+    }
+}
+
+impl<'a> Visit<'a> for PrimitiveFinder {
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        if let Expression::Identifier(callee) = &call.callee {
+            let name = callee.name.as_str();
+            let place = if self.handlers > 0 {
+                Some("an event handler")
+            } else if self.loops > 0 {
+                Some("a loop")
+            } else if self.conditionals > 0 {
+                Some("a conditional")
+            } else {
+                None
+            };
+            if let Some(place) = place.filter(|_| PRIMITIVES.contains(&name)) {
+                self.diagnostics.push(
+                    Diagnostic::warning(
+                        NoConditionalPrimitiveCreation::NAME,
+                        call.span,
+                        format!(
+                            "`{}` is called inside {}, so a new primitive is created every time it runs and never disposed with the component.",
+                            name, place
+                        ),
+                    )
+                    .with_help("Create it once at the top level of the component."),
+                );
+            }
+        }
+        walk::walk_call_expression(self, call);
+    }
+
+    fn visit_jsx_attribute(&mut self, attr: &JSXAttribute<'a>) {
+        let is_handler = match &attr.name {
+            JSXAttributeName::Identifier(ident) => ident.name.starts_with("on"),
+            JSXAttributeName::NamespacedName(ns) => {
+                matches!(ns.namespace.name.as_str(), "on" | "oncapture")
+            }
+        };
+        match &attr.value {
+            Some(JSXAttributeValue::ExpressionContainer(container)) if is_handler => {
+                match container_expression(container) {
+                    Some(Expression::ArrayExpression(array)) => {
+                        if let Some(handler) =
+                            array.elements.first().and_then(|el| el.as_expression())
+                        {
+                            self.visit_handler(handler);
+                        }
+                    }
+                    Some(expr) => self.visit_handler(expr),
+                    None => {}
+                }
+            }
+            _ => walk::walk_jsx_attribute(self, attr),
+        }
+    }
+
+    // Nested functions run at unknown times (or are owned, like `<For>` callbacks)
+    fn visit_function(&mut self, _func: &Function<'a>, _flags: ScopeFlags) {}
+
+    fn visit_arrow_function_expression(&mut self, _arrow: &ArrowFunctionExpression<'a>) {}
+
+    fn visit_if_statement(&mut self, stmt: &IfStatement<'a>) {
+        self.conditionals += 1;
+        walk::walk_if_statement(self, stmt);
+        self.conditionals -= 1;
+    }
+
+    fn visit_switch_statement(&mut self, stmt: &SwitchStatement<'a>) {
+        self.conditionals += 1;
+        walk::walk_switch_statement(self, stmt);
+        self.conditionals -= 1;
+    }
+
+    fn visit_conditional_expression(&mut self, expr: &ConditionalExpression<'a>) {
+        self.conditionals += 1;
+        walk::walk_conditional_expression(self, expr);
+        self.conditionals -= 1;
+    }
+
+    fn visit_logical_expression(&mut self, expr: &LogicalExpression<'a>) {
+        self.visit_expression(&expr.left);
+        self.conditionals += 1;
+        self.visit_expression(&expr.right);
+        self.conditionals -= 1;
+    }
+
+    fn visit_for_statement(&mut self, stmt: &ForStatement<'a>) {
+        self.loops += 1;
+        walk::walk_for_statement(self, stmt);
+        self.loops -= 1;
+    }
+
+    fn visit_for_in_statement(&mut self, stmt: &ForInStatement<'a>) {
+        self.loops += 1;
+        walk::walk_for_in_statement(self, stmt);
+        self.loops -= 1;
+    }
+
+    fn visit_for_of_statement(&mut self, stmt: &ForOfStatement<'a>) {
+        self.loops += 1;
+        walk::walk_for_of_statement(self, stmt);
+        self.loops -= 1;
+    }
+
+    fn visit_while_statement(&mut self, stmt: &WhileStatement<'a>) {
+        self.loops += 1;
+        walk::walk_while_statement(self, stmt);
+        self.loops -= 1;
+    }
+
+    fn visit_do_while_statement(&mut self, stmt: &DoWhileStatement<'a>) {
+        self.loops += 1;
+        walk::walk_do_while_statement(self, stmt);
+        self.loops -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_name() {
+        assert_eq!(
+            NoConditionalPrimitiveCreation::NAME,
+            "no-conditional-primitive-creation"
+        );
+    }
+}
@@ -3,12 +3,11 @@
 //! Disallow destructuring props. In Solid, props must be used with property accesses
 //! (`props.foo`) to preserve reactivity.
 
-use oxc_ast::ast::{
-    ArrowFunctionExpression, Expression, Function, FunctionBody, Statement,
-};
+use oxc_ast::ast::{ArrowFunctionExpression, FormalParameter, Function, FunctionBody};
 use oxc_span::GetSpan;
 
 use crate::diagnostic::Diagnostic;
+use crate::utils::returns_jsx;
 use crate::{RuleCategory, RuleMeta};
 
 /// no-destructure rule
@@ -64,7 +63,7 @@ impl NoDestructure {
     fn check_params(
         &self,
         params: &oxc_ast::ast::FormalParameters,
-        params_span: oxc_span::Span,
+        _params_span: oxc_span::Span,
     ) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
@@ -90,54 +89,22 @@ impl NoDestructure {
         diagnostics
     }
 
-    /// Helper to check if a function body contains JSX
-    pub fn body_has_jsx(body: &FunctionBody) -> bool {
-        for stmt in &body.statements {
-            if Self::statement_has_jsx(stmt) {
-                return true;
-            }
-        }
-        false
+    /// Check a parameter that components of other files pass their props to
+    /// (found with project analysis)
+    pub fn check_forwarded_props(&self, param: &FormalParameter) -> Option<Diagnostic> {
+        param.pattern.is_destructuring_pattern().then(|| {
+            Diagnostic::warning(
+                Self::NAME,
+                param.span,
+                "Destructuring props passed from a component breaks Solid's reactivity; use property access instead.",
+            )
+            .with_help("Keep the parameter and use `props.propertyName` where the value is needed.")
+        })
     }
 
-    fn statement_has_jsx(stmt: &Statement) -> bool {
-        match stmt {
-            Statement::ExpressionStatement(expr_stmt) => Self::expression_has_jsx(&expr_stmt.expression),
-            Statement::ReturnStatement(ret) => {
-                ret.argument.as_ref().is_some_and(|e| Self::expression_has_jsx(e))
-            }
-            Statement::BlockStatement(block) => {
-                block.body.iter().any(|s| Self::statement_has_jsx(s))
-            }
-            Statement::IfStatement(if_stmt) => {
-                Self::statement_has_jsx(&if_stmt.consequent)
-                    || if_stmt.alternate.as_ref().is_some_and(|s| Self::statement_has_jsx(s))
-            }
-            _ => false,
-        }
-    }
-
-    fn expression_has_jsx(expr: &Expression) -> bool {
-        match expr {
-            Expression::JSXElement(_) | Expression::JSXFragment(_) => true,
-            Expression::ParenthesizedExpression(paren) => Self::expression_has_jsx(&paren.expression),
-            Expression::ConditionalExpression(cond) => {
-                Self::expression_has_jsx(&cond.consequent)
-                    || Self::expression_has_jsx(&cond.alternate)
-            }
-            Expression::LogicalExpression(logical) => {
-                Self::expression_has_jsx(&logical.left) || Self::expression_has_jsx(&logical.right)
-            }
-            Expression::ArrowFunctionExpression(arrow) => {
-                if arrow.expression {
-                    if let Some(Statement::ExpressionStatement(expr_stmt)) = arrow.body.statements.first() {
-                        return Self::expression_has_jsx(&expr_stmt.expression);
-                    }
-                }
-                Self::body_has_jsx(&arrow.body)
-            }
-            _ => false,
-        }
+    /// Helper to check if a function body contains JSX
+    pub fn body_has_jsx(body: &FunctionBody) -> bool {
+        returns_jsx(body)
     }
 }
 
@@ -9,7 +9,7 @@ use oxc_ast::ast::{
 use oxc_span::{GetSpan, Span};
 
 use crate::diagnostic::{Diagnostic, Fix};
-use crate::utils::has_children;
+use crate::utils::{container_expression, has_children};
 use crate::{RuleCategory, RuleMeta};
 
 /// no-innerhtml rule
@@ -95,7 +95,8 @@ impl NoInnerhtml {
                                 )
                                 .with_fix(
                                     Fix::new(attr_name_span, "innerText")
-                                        .with_message("Use innerText for text content"),
+                                        .with_message("Use innerText for text content")
+                                        .as_suggestion(),
                                 ),
                             );
                         }
@@ -132,7 +133,7 @@ impl NoInnerhtml {
     ) -> Diagnostic {
         // Check if it's the pattern: dangerouslySetInnerHTML={{ __html: value }}
         if let Some(JSXAttributeValue::ExpressionContainer(container)) = value {
-            if let Some(Expression::ObjectExpression(obj)) = container.expression.as_expression() {
+            if let Some(Expression::ObjectExpression(obj)) = container_expression(container) {
                 if obj.properties.len() == 1 {
                     if let Some(ObjectPropertyKind::ObjectProperty(prop)) =
                         obj.properties.first()
@@ -179,7 +180,7 @@ fn get_static_string_value(value: &Option<JSXAttributeValue<'_>>) -> Option<Stri
     match value {
         Some(JSXAttributeValue::StringLiteral(lit)) => Some(lit.value.to_string()),
         Some(JSXAttributeValue::ExpressionContainer(container)) => {
-            if let Some(expr) = container.expression.as_expression() {
+            if let Some(expr) = container_expression(container) {
                 match expr {
                     Expression::StringLiteral(lit) => Some(lit.value.to_string()),
                     Expression::TemplateLiteral(tpl) if tpl.expressions.is_empty() => {
@@ -1,19 +1,44 @@
 //! solid/no-proxy-apis
 //!
 //! Disallow usage of APIs that use ES6 Proxies, for environments that don't support them.
+//!
+//! Each kind of Proxy usage can be allowed separately when only some of them
+//! are a problem (for example when a polyfill covers `mergeProps`).
 
 use oxc_ast::ast::{
     Argument, CallExpression, Expression, ImportDeclaration, JSXSpreadAttribute,
     NewExpression,
 };
 use oxc_span::GetSpan;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
 use crate::diagnostic::Diagnostic;
 use crate::{RuleCategory, RuleMeta};
 
+/// Configuration for no-proxy-apis
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NoProxyApisConfig {
+    /// Allow importing `solid-js/store`
+    #[serde(default)]
+    pub allow_store: bool,
+    /// Allow property accesses and calls in JSX spreads (`{...store.user}`)
+    #[serde(default)]
+    pub allow_spreads: bool,
+    /// Allow passing functions and variables to `mergeProps`
+    #[serde(default)]
+    pub allow_merge_props_functions: bool,
+    /// Allow `new Proxy()` and `Proxy.revocable()`
+    #[serde(default)]
+    pub allow_proxy: bool,
+}
+
 /// no-proxy-apis rule
 #[derive(Debug, Clone, Default)]
-pub struct NoProxyApis;
+pub struct NoProxyApis {
+    pub config: NoProxyApisConfig,
+}
 
 impl RuleMeta for NoProxyApis {
     const NAME: &'static str = "no-proxy-apis";
@@ -22,14 +47,18 @@ impl RuleMeta for NoProxyApis {
 
 impl NoProxyApis {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_config(config: NoProxyApisConfig) -> Self {
+        Self { config }
     }
 
     /// Check an import declaration for solid-js/store
     pub fn check_import<'a>(&self, import: &ImportDeclaration<'a>) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
 
-        if import.source.value == "solid-js/store" {
+        if !self.config.allow_store && import.source.value == "solid-js/store" {
             diagnostics.push(Diagnostic::warning(
                 Self::NAME,
                 import.span,
@@ -43,6 +72,9 @@ impl NoProxyApis {
     /// Check a JSX spread attribute for proxy-creating patterns
     pub fn check_spread<'a>(&self, spread: &JSXSpreadAttribute<'a>) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
+        if self.config.allow_spreads {
+            return diagnostics;
+        }
 
         // Check if expression is a member expression or call expression
         if spread.argument.is_member_expression() {
@@ -65,6 +97,9 @@ impl NoProxyApis {
     /// Check a new expression for `new Proxy()`
     pub fn check_new_expression<'a>(&self, new_expr: &NewExpression<'a>) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
+        if self.config.allow_proxy {
+            return diagnostics;
+        }
 
         if let Expression::Identifier(ident) = &new_expr.callee {
             if ident.name == "Proxy" {
@@ -86,7 +121,10 @@ impl NoProxyApis {
         // Check for Proxy.revocable()
         if let Expression::StaticMemberExpression(member) = &call.callee {
             if let Expression::Identifier(obj) = &member.object {
-                if obj.name == "Proxy" && member.property.name == "revocable" {
+                if !self.config.allow_proxy
+                    && obj.name == "Proxy"
+                    && member.property.name == "revocable"
+                {
                     diagnostics.push(Diagnostic::warning(
                         Self::NAME,
                         call.span,
@@ -98,14 +136,14 @@ impl NoProxyApis {
 
         // Check for mergeProps with function/variable arguments
         if let Expression::Identifier(callee) = &call.callee {
-            if callee.name == "mergeProps" {
+            if callee.name == "mergeProps" && !self.config.allow_merge_props_functions {
                 for arg in &call.arguments {
                     let is_problematic = match arg {
                         Argument::SpreadElement(_) => true,
                         arg => {
                             if let Some(expr) = arg.as_expression() {
                                 matches!(
-                                    expr,
+                                    expr.get_inner_expression(),
                                     Expression::Identifier(_)
                                         | Expression::ArrowFunctionExpression(_)
                                         | Expression::FunctionExpression(_)
@@ -139,4 +177,14 @@ mod tests {
     fn test_rule_name() {
         assert_eq!(NoProxyApis::NAME, "no-proxy-apis");
     }
+
+    #[test]
+    fn test_config_deserialize() {
+        let json = r#"{"allowSpreads": true, "allowMergePropsFunctions": true}"#;
+        let config: NoProxyApisConfig = serde_json::from_str(json).unwrap();
+        assert!(config.allow_spreads);
+        assert!(config.allow_merge_props_functions);
+        assert!(!config.allow_store);
+        assert!(!config.allow_proxy);
+    }
 }
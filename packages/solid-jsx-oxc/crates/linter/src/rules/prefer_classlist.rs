@@ -2,6 +2,9 @@
 //!
 //! Enforce using the classlist prop over importing a classnames helper.
 //! The classlist prop accepts an object `{ [class: string]: boolean }` just like classnames.
+//!
+//! Deprecated, as upstream: `classList` is on its way out of Solid in favor of
+//! the `class` prop.
 
 use oxc_ast::ast::{
     Argument, Expression, JSXAttributeName, JSXAttributeValue, JSXOpeningElement,
@@ -9,7 +12,7 @@ use oxc_ast::ast::{
 use oxc_span::Span;
 
 use crate::diagnostic::{Diagnostic, Fix};
-use crate::utils::has_attribute;
+use crate::utils::{container_expression, has_attribute};
 use crate::{RuleCategory, RuleMeta};
 
 /// Default classnames helper function names
@@ -33,6 +36,7 @@ impl Default for PreferClasslist {
 impl RuleMeta for PreferClasslist {
     const NAME: &'static str = "prefer-classlist";
     const CATEGORY: RuleCategory = RuleCategory::Style;
+    const DEPRECATED: bool = true;
 }
 
 impl PreferClasslist {
@@ -68,7 +72,7 @@ impl PreferClasslist {
 
                 // Check for expression container with classnames call
                 if let Some(JSXAttributeValue::ExpressionContainer(container)) = &jsx_attr.value {
-                    if let Some(expr) = container.expression.as_expression() {
+                    if let Some(expr) = container_expression(container) {
                         if let Some((callee_name, object_span)) =
                             self.get_classnames_call_info(expr)
                         {
@@ -9,6 +9,7 @@ use oxc_ast::ast::{
 use oxc_span::{GetSpan, Span};
 
 use crate::diagnostic::{Diagnostic, Fix};
+use crate::utils::container_expression;
 use crate::{RuleCategory, RuleMeta};
 
 /// prefer-for rule
@@ -39,7 +40,7 @@ impl PreferFor {
         let mut diagnostics = Vec::new();
 
         // Get the expression (handle ChainExpression)
-        let expr = match container.expression.as_expression() {
+        let expr = match container_expression(container) {
             Some(e) => e,
             None => return diagnostics,
         };
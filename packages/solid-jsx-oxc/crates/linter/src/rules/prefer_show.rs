@@ -9,6 +9,7 @@ use oxc_ast::ast::{
 use oxc_span::{GetSpan, Span};
 
 use crate::diagnostic::{Diagnostic, Fix};
+use crate::utils::container_expression;
 use crate::{RuleCategory, RuleMeta};
 
 /// prefer-show rule
@@ -38,7 +39,7 @@ impl PreferShow {
 
         let mut diagnostics = Vec::new();
 
-        let expr = match container.expression.as_expression() {
+        let expr = match container_expression(container) {
             Some(e) => e,
             None => return diagnostics,
         };
@@ -51,30 +52,28 @@ impl PreferShow {
                     source,
                 ));
             }
-            Expression::ArrowFunctionExpression(arrow) => {
-                // For arrow functions, check if the body is an expression (not block body)
-                if arrow.expression {
-                    // Get the expression from the function body
-                    if let Some(oxc_ast::ast::Statement::ExpressionStatement(expr_stmt)) =
-                        arrow.body.statements.first()
-                    {
-                        match &expr_stmt.expression {
-                            Expression::LogicalExpression(logical) => {
-                                diagnostics.extend(self.check_logical_expression(
-                                    logical,
-                                    logical.span,
-                                    source,
-                                ));
-                            }
-                            Expression::ConditionalExpression(cond) => {
-                                diagnostics.extend(self.check_conditional_expression(
-                                    cond,
-                                    cond.span,
-                                    source,
-                                ));
-                            }
-                            _ => {}
+            // For arrow functions, check if the body is an expression (not block body)
+            Expression::ArrowFunctionExpression(arrow) if arrow.expression => {
+                // Get the expression from the function body
+                if let Some(oxc_ast::ast::Statement::ExpressionStatement(expr_stmt)) =
+                    arrow.body.statements.first()
+                {
+                    match &expr_stmt.expression {
+                        Expression::LogicalExpression(logical) => {
+                            diagnostics.extend(self.check_logical_expression(
+                                logical,
+                                logical.span,
+                                source,
+                            ));
                         }
+                        Expression::ConditionalExpression(cond) => {
+                            diagnostics.extend(self.check_conditional_expression(
+                                cond,
+                                cond.span,
+                                source,
+                            ));
+                        }
+                        _ => {}
                     }
                 }
             }
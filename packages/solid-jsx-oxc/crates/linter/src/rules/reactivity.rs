@@ -8,12 +8,13 @@
 //! and tracks control flow, function scopes, and more.
 
 use oxc_ast::ast::{
-    Argument, CallExpression, Expression, JSXAttributeItem, JSXAttributeName,
+    Argument, CallExpression, Expression, FormalParameters, JSXAttributeItem, JSXAttributeName,
     JSXAttributeValue, JSXExpressionContainer, JSXOpeningElement, VariableDeclarator,
 };
-use oxc_span::GetSpan;
+use oxc_span::{GetSpan, Span};
 
 use crate::diagnostic::Diagnostic;
+use crate::utils::container_expression;
 use crate::{RuleCategory, RuleMeta};
 
 /// reactivity rule
@@ -46,6 +47,34 @@ const REACTIVE_PRIMITIVES: &[&str] = &[
 /// Solid primitives that create stores
 const STORE_CREATORS: &[&str] = &["createStore", "createMutable"];
 
+/// Array methods whose callbacks receive the elements of the receiver
+pub const ITERATION_METHODS: &[&str] = &[
+    "map",
+    "flatMap",
+    "forEach",
+    "filter",
+    "find",
+    "findIndex",
+    "findLast",
+    "findLastIndex",
+    "some",
+    "every",
+];
+
+/// Span of a callback's first parameter when it destructures its argument
+fn destructured_first_param(callback: &Expression) -> Option<Span> {
+    let params = match callback.get_inner_expression() {
+        Expression::ArrowFunctionExpression(arrow) => &arrow.params,
+        Expression::FunctionExpression(func) => &func.params,
+        _ => return None,
+    };
+    params
+        .items
+        .first()
+        .filter(|param| param.pattern.is_destructuring_pattern())
+        .map(|param| param.span)
+}
+
 impl Reactivity {
     pub fn new() -> Self {
         Self
@@ -53,7 +82,7 @@ impl Reactivity {
 
     /// Check a variable declarator for signal/store destructuring issues
     pub fn check_variable<'a>(&self, declarator: &VariableDeclarator<'a>) -> Vec<Diagnostic> {
-        let mut diagnostics = Vec::new();
+        let diagnostics = Vec::new();
 
         let Some(init) = &declarator.init else {
             return diagnostics;
@@ -101,7 +130,7 @@ impl Reactivity {
                         if let Some(expr) = arg.as_expression() {
                             // Check if it's not a function
                             if !matches!(
-                                expr,
+                                expr.get_inner_expression(),
                                 Expression::ArrowFunctionExpression(_)
                                     | Expression::FunctionExpression(_)
                                     | Expression::Identifier(_)
@@ -126,15 +155,71 @@ impl Reactivity {
         diagnostics
     }
 
+    /// Check a callback that receives `source`, a value derived from props
+    /// (`<For each={props.items}>{({ id }) => ...}</For>`,
+    /// `props.items.map(({ id }) => ...)`). Destructuring its parameter reads
+    /// the properties once, outside of any tracking scope.
+    pub fn check_destructured_callback<'a>(
+        &self,
+        callback: &Expression<'a>,
+        source: &str,
+    ) -> Vec<Diagnostic> {
+        let Some(span) = destructured_first_param(callback) else {
+            return Vec::new();
+        };
+        vec![Diagnostic::warning(
+            Self::NAME,
+            span,
+            format!(
+                "Destructuring values from `{}` reads their properties once, so they will not update.",
+                source
+            ),
+        )
+        .with_help("Keep the parameter and access its properties where they are used.")]
+    }
+
+    /// Check the parameters of a function nested in a component for
+    /// destructuring defaults that read props (`({ id } = props.item) => ...`)
+    pub fn check_destructured_defaults<'a>(
+        &self,
+        params: &FormalParameters<'a>,
+        source_text: &str,
+        is_props_derived: impl Fn(&Expression<'a>) -> bool,
+    ) -> Vec<Diagnostic> {
+        params
+            .items
+            .iter()
+            .filter(|param| param.pattern.is_destructuring_pattern())
+            .filter_map(|param| {
+                let default = param.initializer.as_deref()?;
+                if !is_props_derived(default) {
+                    return None;
+                }
+                let source = default.span().source_text(source_text);
+                Some(
+                    Diagnostic::warning(
+                        Self::NAME,
+                        param.span,
+                        format!(
+                            "Destructuring `{}` in a default parameter reads its properties once, so they will not update.",
+                            source
+                        ),
+                    )
+                    .with_help("Access the properties where they are used instead."),
+                )
+            })
+            .collect()
+    }
+
     /// Check JSX expression for potential reactivity loss
     pub fn check_jsx_expression<'a>(
         &self,
         container: &JSXExpressionContainer<'a>,
-        is_in_attribute: bool,
+        _is_in_attribute: bool,
     ) -> Vec<Diagnostic> {
-        let mut diagnostics = Vec::new();
+        let diagnostics = Vec::new();
 
-        let Some(expr) = container.expression.as_expression() else {
+        let Some(_expr) = container.expression.as_expression() else {
             return diagnostics;
         };
 
@@ -169,7 +254,7 @@ impl Reactivity {
                             &jsx_attr.value
                         {
                             if let Some(Expression::CallExpression(_)) =
-                                container.expression.as_expression()
+                                container_expression(container)
                             {
                                 diagnostics.push(
                                     Diagnostic::warning(
@@ -188,23 +273,21 @@ impl Reactivity {
             // Check for event handlers that don't use functions
             if attr_name.starts_with("on") && attr_name.len() > 2 {
                 if let Some(JSXAttributeValue::ExpressionContainer(container)) = &jsx_attr.value {
-                    if let Some(expr) = container.expression.as_expression() {
-                        // Event handlers should be functions, not calls
-                        if let Expression::CallExpression(call) = expr {
-                            // Check if it's not creating a bound function
-                            if let Expression::Identifier(callee) = &call.callee {
-                                if callee.name != "bind" {
-                                    diagnostics.push(
-                                        Diagnostic::warning(
-                                            Self::NAME,
-                                            call.span,
-                                            format!(
-                                                "Event handler `{}` is calling a function. This will execute immediately. Wrap in an arrow function: `() => {}(...)`",
-                                                attr_name, callee.name
-                                            ),
+                    // Event handlers should be functions, not calls
+                    if let Some(Expression::CallExpression(call)) = container_expression(container) {
+                        // Check if it's not creating a bound function
+                        if let Expression::Identifier(callee) = &call.callee {
+                            if callee.name != "bind" {
+                                diagnostics.push(
+                                    Diagnostic::warning(
+                                        Self::NAME,
+                                        call.span,
+                                        format!(
+                                            "Event handler `{}` is calling a function. This will execute immediately. Wrap in an arrow function: `() => {}(...)`",
+                                            attr_name, callee.name
                                         ),
-                                    );
-                                }
+                                    ),
+                                );
                             }
                         }
                     }
@@ -1,9 +1,11 @@
 //! solid/self-closing-comp
 //!
-//! Disallow extra closing tags for components without children.
+//! Disallow extra closing tags for components without children, and any
+//! closing tag on void elements (`<br></br>`), which HTML does not have.
 
 use oxc_ast::ast::{JSXChild, JSXOpeningElement};
 use oxc_span::Span;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::diagnostic::{Diagnostic, Fix};
@@ -14,7 +16,7 @@ use crate::utils::{
 use crate::{RuleCategory, RuleMeta};
 
 /// Which elements should be self-closing
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum SelfClosingOption {
     /// All matching elements should self-close
@@ -25,7 +27,7 @@ pub enum SelfClosingOption {
 }
 
 /// Which HTML elements should be self-closing
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum HtmlSelfClosingOption {
     /// All HTML elements should self-close when empty
@@ -38,7 +40,7 @@ pub enum HtmlSelfClosingOption {
 }
 
 /// Configuration for self-closing-comp
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SelfClosingCompConfig {
     /// Which Solid components should be self-closing when possible
@@ -88,6 +90,13 @@ impl SelfClosingComp {
         }
     }
 
+    /// Fix replacing `>...</tagName>` with ` />`
+    fn self_closing_fix(opening: &JSXOpeningElement, closing: Span) -> Fix {
+        // From the opening tag's '>' to the end of the closing tag
+        Fix::new(Span::new(opening.span.end - 1, closing.end), " />")
+            .with_message("Make self-closing")
+    }
+
     /// Check a JSX element for self-closing issues
     ///
     /// Arguments:
@@ -105,6 +114,30 @@ impl SelfClosingComp {
         let can_self_close = children_is_empty_or_multiline_whitespace(children);
         let is_self_closing = closing_span.is_none();
 
+        // Void elements have no closing tag in HTML, whatever the config says
+        let void_name = get_element_name(opening)
+            .filter(|name| !is_component(opening) && is_void_element(name));
+        if let Some(name) = void_name {
+            if let Some(closing) = closing_span {
+                let diagnostic = if can_self_close {
+                    Diagnostic::warning(
+                        Self::NAME,
+                        opening.span,
+                        format!("`<{name}>` is a void element and must be self-closing."),
+                    )
+                    .with_fix(Self::self_closing_fix(opening, closing))
+                } else {
+                    Diagnostic::warning(
+                        Self::NAME,
+                        opening.span,
+                        format!("`<{name}>` is a void element and cannot have children."),
+                    )
+                };
+                diagnostics.push(diagnostic);
+            }
+            return diagnostics;
+        }
+
         if can_self_close {
             let should_self_close = self.should_be_self_closing(opening);
 
@@ -116,15 +149,8 @@ impl SelfClosingComp {
                     "Empty components are self-closing.",
                 );
 
-                // Add fix: replace `>...</tagName>` with ` />`
                 if let Some(closing) = closing_span {
-                    // Calculate the range from '>' to end of closing tag
-                    let fix_start = opening.span.end - 1; // The '>' character
-                    let fix_end = closing.end;
-                    diagnostic = diagnostic.with_fix(
-                        Fix::new(Span::new(fix_start, fix_end), " />")
-                            .with_message("Make self-closing"),
-                    );
+                    diagnostic = diagnostic.with_fix(Self::self_closing_fix(opening, closing));
                 }
 
                 diagnostics.push(diagnostic);
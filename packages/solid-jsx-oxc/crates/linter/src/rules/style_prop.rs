@@ -9,6 +9,7 @@ use oxc_ast::ast::{
 use oxc_span::{GetSpan, Span};
 
 use crate::diagnostic::{Diagnostic, Fix};
+use crate::utils::container_expression;
 use crate::{RuleCategory, RuleMeta};
 
 /// Common CSS length/percentage properties that shouldn't have numeric values
@@ -89,7 +90,7 @@ impl StyleProp {
                 // Get the style value
                 let style_expr = match &jsx_attr.value {
                     Some(JSXAttributeValue::ExpressionContainer(container)) => {
-                        container.expression.as_expression()
+                        container_expression(container)
                     }
                     Some(JSXAttributeValue::StringLiteral(lit)) if !self.allow_string => {
                         // String style prop - warn
@@ -4,48 +4,88 @@
 //! for proper scope resolution and symbol tracking.
 
 use oxc_ast::ast::{
-    Argument, ArrowFunctionExpression, CallExpression, Expression, Function,
-    ImportDeclaration, ImportDeclarationSpecifier, JSXElementName, JSXMemberExpressionObject,
-    JSXOpeningElement, Program, Statement,
+    Argument, ArrowFunctionExpression, BindingPattern, CallExpression, Expression,
+    FormalParameters, Function, IdentifierReference, ImportDeclarationSpecifier, JSXAttributeItem,
+    JSXAttributeName, JSXAttributeValue, JSXElement, JSXElementName, JSXMemberExpressionObject,
+    JSXOpeningElement, Program, Statement, TSTypeName, TSTypeReference, VariableDeclarator,
 };
 use oxc_ast_visit::{walk, Visit};
 use oxc_semantic::{ScopeId, Semantic, SymbolId};
 use oxc_span::{GetSpan, SourceType, Span};
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::diagnostic::Diagnostic;
+use crate::project::{exported_functions, ImportedBinding, ProjectFile};
 use crate::rules::jsx_no_undef::JsxNoUndef;
-use crate::rules::{ComponentsReturnOnce, NoDestructure, Reactivity};
-use crate::utils::is_dom_element;
+use crate::rules::migration::{
+    is_react_source, MigrationRulesConfig, NoReactApis, NoReactImports, NoReactTypes,
+};
+use crate::rules::reactivity::ITERATION_METHODS;
+use crate::rules::{
+    ComponentsReturnOnce, NoConditionalPrimitiveCreation, NoDestructure, Reactivity,
+};
+use crate::settings::LintSettings;
+use crate::timing::{self, RuleTimings};
+use crate::utils::{
+    container_expression, is_dom_element, is_pascal_case, is_probably_component, is_solid_source,
+    returns_jsx, solid_imports,
+};
 use crate::RuleMeta;
 
-/// Solid.js module sources
-const SOLID_SOURCES: &[&str] = &["solid-js", "solid-js/store", "solid-js/web"];
-
 /// Configuration for semantic-aware rules
 #[derive(Debug, Clone, Default)]
 pub struct SemanticRulesConfig {
     pub jsx_no_undef: bool,
     pub jsx_uses_vars: bool,
-    pub components_return_once: bool,
+    pub components_return_once: Option<ComponentsReturnOnce>,
     pub reactivity: bool,
     pub no_destructure: bool,
+    /// Nursery rule, not enabled by `all()`
+    pub no_conditional_primitive_creation: bool,
+    /// React-to-Solid migration rules, all disabled unless enabled explicitly
+    pub migration: MigrationRulesConfig,
+    /// Third-party modules exporting components and primitives
+    pub settings: LintSettings,
 }
 
 impl SemanticRulesConfig {
+    /// Every rule except the nursery ones
     pub fn all() -> Self {
         Self {
             jsx_no_undef: true,
             jsx_uses_vars: true,
-            components_return_once: true,
+            components_return_once: Some(ComponentsReturnOnce::new()),
             reactivity: true,
             no_destructure: true,
+            no_conditional_primitive_creation: false,
+            migration: MigrationRulesConfig::none(),
+            settings: LintSettings::default(),
         }
     }
 
     pub fn none() -> Self {
         Self::default()
     }
+
+    pub fn with_components_return_once(mut self, rule: Option<ComponentsReturnOnce>) -> Self {
+        self.components_return_once = rule;
+        self
+    }
+
+    pub fn with_no_conditional_primitive_creation(mut self, enabled: bool) -> Self {
+        self.no_conditional_primitive_creation = enabled;
+        self
+    }
+
+    pub fn with_migration(mut self, migration: MigrationRulesConfig) -> Self {
+        self.migration = migration;
+        self
+    }
+
+    pub fn with_settings(mut self, settings: LintSettings) -> Self {
+        self.settings = settings;
+        self
+    }
 }
 
 /// Result of semantic linting
@@ -54,6 +94,8 @@ pub struct SemanticLintResult {
     pub diagnostics: Vec<Diagnostic>,
     pub used_symbols: FxHashSet<SymbolId>,
     pub component_symbols: FxHashSet<SymbolId>,
+    /// Per-rule timings, present when the runner was created with timing enabled
+    pub timings: Option<RuleTimings>,
 }
 
 /// Semantic-aware lint runner that uses oxc_semantic for scope resolution
@@ -70,12 +112,35 @@ pub struct SemanticLintRunner<'a> {
     component_symbols: FxHashSet<SymbolId>,
     /// Solid imports (function names imported from solid-js)
     solid_imports: FxHashSet<String>,
+    /// Bindings imported from solid-js or a configured primitive module
+    solid_symbols: FxHashSet<SymbolId>,
+    /// Bindings imported from a configured component module
+    imported_components: FxHashSet<SymbolId>,
     /// Current scope stack for tracking nested scopes
     scope_stack: Vec<ScopeId>,
     /// Functions that contain JSX (potential components)
     functions_with_jsx: FxHashSet<Span>,
     /// Track if we're inside a JSX expression
     jsx_depth: usize,
+    /// Props parameters of components and variables aliasing values read
+    /// from them (for reactivity)
+    props_symbols: FxHashSet<SymbolId>,
+    /// Functions passed as call arguments, mapped to whether the callee makes
+    /// them components (wrappers, PascalCase factories) rather than callbacks
+    /// (for components-return-once)
+    call_argument_functions: FxHashMap<Span, bool>,
+    /// Per-rule timings, collected only when timing is enabled
+    timings: Option<RuleTimings>,
+    /// The file in its project, when linting with project analysis
+    project: Option<ProjectFile<'a>>,
+    /// Bindings imported by name from React, mapped to the imported name (for
+    /// the migration rules)
+    react_bindings: FxHashMap<SymbolId, String>,
+    /// `React` default and namespace imports (for the migration rules)
+    react_namespaces: FxHashSet<SymbolId>,
+    /// Imports of names their project module does not export, mapped to the
+    /// specifier and the imported name (for jsx-no-undef)
+    unexported_imports: FxHashMap<SymbolId, (String, String)>,
 }
 
 impl<'a> SemanticLintRunner<'a> {
@@ -94,16 +159,41 @@ impl<'a> SemanticLintRunner<'a> {
             used_symbols: FxHashSet::default(),
             component_symbols: FxHashSet::default(),
             solid_imports: FxHashSet::default(),
+            solid_symbols: FxHashSet::default(),
+            imported_components: FxHashSet::default(),
             scope_stack: vec![semantic.scoping().root_scope_id()],
             functions_with_jsx: FxHashSet::default(),
             jsx_depth: 0,
+            props_symbols: FxHashSet::default(),
+            call_argument_functions: FxHashMap::default(),
+            timings: None,
+            react_bindings: FxHashMap::default(),
+            react_namespaces: FxHashSet::default(),
+            project: None,
+            unexported_imports: FxHashMap::default(),
         }
     }
 
+    /// Measure how long each rule takes (reported in `SemanticLintResult::timings`)
+    pub fn with_timing(mut self, enabled: bool) -> Self {
+        self.timings = enabled.then(RuleTimings::new);
+        self
+    }
+
+    /// Resolve relative imports and props passed between files with the
+    /// project `file` belongs to
+    pub fn with_project(mut self, file: ProjectFile<'a>) -> Self {
+        self.project = Some(file);
+        self
+    }
+
     /// Run the semantic linter on the program
     pub fn run(mut self, program: &Program<'a>) -> SemanticLintResult {
         // Collect imports from solid-js
         self.collect_solid_imports(program);
+        self.collect_project_imports(program);
+        self.collect_react_imports(program);
+        self.collect_project_props(program);
 
         // Visit AST and run rules
         self.visit_program(program);
@@ -112,6 +202,7 @@ impl<'a> SemanticLintRunner<'a> {
             diagnostics: self.diagnostics,
             used_symbols: self.used_symbols,
             component_symbols: self.component_symbols,
+            timings: self.timings,
         }
     }
 
@@ -126,6 +217,22 @@ impl<'a> SemanticLintRunner<'a> {
         self.semantic.scoping().find_binding(self.current_scope(), name)
     }
 
+    /// Whether `ident` refers to an import `is_expected` accepts, or to no
+    /// binding at all (globals, auto-imports). A local declaration or an
+    /// import from elsewhere with the same name is something else.
+    fn refers_to_import(
+        &self,
+        ident: &IdentifierReference<'a>,
+        is_expected: impl Fn(&SymbolId) -> bool,
+    ) -> bool {
+        let scoping = self.semantic.scoping();
+        ident
+            .reference_id
+            .get()
+            .and_then(|reference_id| scoping.get_reference(reference_id).symbol_id())
+            .is_none_or(|symbol_id| is_expected(&symbol_id))
+    }
+
     /// Check if we're inside a JSX expression context
     fn is_inside_jsx(&self) -> bool {
         self.jsx_depth > 0
@@ -133,36 +240,224 @@ impl<'a> SemanticLintRunner<'a> {
 
     // ==================== Phase 1: Import and Type Inference ====================
 
-    /// Collect imports from solid-js modules
+    /// Collect imports from solid-js and the configured modules
     fn collect_solid_imports(&mut self, program: &Program<'a>) {
-        for stmt in &program.body {
-            if let Statement::ImportDeclaration(import) = stmt {
-                self.process_import(import);
+        for import in solid_imports(program, &self.config.settings) {
+            let symbol_id = import.local.symbol_id();
+            if import.is_primitive {
+                self.solid_imports.insert(import.local.name.to_string());
+                self.solid_symbols.insert(symbol_id);
+            }
+            if import.is_component {
+                self.imported_components.insert(symbol_id);
+                self.component_symbols.insert(symbol_id);
             }
         }
     }
 
-    fn process_import(&mut self, import: &ImportDeclaration<'a>) {
-        let source = import.source.value.as_str();
-        if !SOLID_SOURCES.iter().any(|s| source.starts_with(s)) {
+    /// Collect bindings imported from React and check the imports
+    fn collect_react_imports(&mut self, program: &Program<'a>) {
+        if !self.config.migration.is_enabled() {
             return;
         }
-
-        if let Some(specifiers) = &import.specifiers {
-            for spec in specifiers {
+        let scoping = self.semantic.scoping();
+        for stmt in &program.body {
+            let Statement::ImportDeclaration(import) = stmt else {
+                continue;
+            };
+            if !is_react_source(&import.source.value) {
+                continue;
+            }
+            for spec in import.specifiers.iter().flatten() {
                 match spec {
                     ImportDeclarationSpecifier::ImportSpecifier(named) => {
-                        let local_name = named.local.name.as_str();
-                        self.solid_imports.insert(local_name.to_string());
+                        self.react_bindings
+                            .insert(named.local.symbol_id(), named.imported.name().to_string());
                     }
                     ImportDeclarationSpecifier::ImportDefaultSpecifier(default) => {
-                        self.solid_imports.insert(default.local.name.to_string());
+                        self.react_namespaces.insert(default.local.symbol_id());
                     }
                     ImportDeclarationSpecifier::ImportNamespaceSpecifier(ns) => {
-                        self.solid_imports.insert(ns.local.name.to_string());
+                        self.react_namespaces.insert(ns.local.symbol_id());
                     }
                 }
             }
+            if self.config.migration.no_react_imports {
+                let start = timing::start(&self.timings, NoReactImports::NAME);
+                let diagnostic = NoReactImports::new().check(import, self.source_text, |local| {
+                    !scoping
+                        .get_resolved_reference_ids(local.symbol_id())
+                        .is_empty()
+                });
+                self.diagnostics.extend(diagnostic);
+                timing::record(&mut self.timings, start);
+            }
+        }
+    }
+
+    /// The symbol `ident` refers to, if it is bound
+    fn symbol_of(&self, ident: &IdentifierReference<'a>) -> Option<SymbolId> {
+        let reference_id = ident.reference_id.get()?;
+        let scoping = self.semantic.scoping();
+        scoping.get_reference(reference_id).symbol_id()
+    }
+
+    /// The React export `ident` (or `ident.member` when `member` is given)
+    /// refers to
+    fn react_export(
+        &self,
+        ident: &IdentifierReference<'a>,
+        member: Option<&str>,
+    ) -> Option<String> {
+        let symbol_id = self.symbol_of(ident)?;
+        match member {
+            Some(member) => self
+                .react_namespaces
+                .contains(&symbol_id)
+                .then(|| member.to_string()),
+            None => self.react_bindings.get(&symbol_id).cloned(),
+        }
+    }
+
+    /// migration/no-react-apis on a call
+    fn check_react_call(&mut self, call: &CallExpression<'a>) {
+        let name = match call.callee.get_inner_expression() {
+            Expression::Identifier(ident) => self.react_export(ident, None),
+            Expression::StaticMemberExpression(member) => match &member.object {
+                Expression::Identifier(object) => {
+                    self.react_export(object, Some(&member.property.name))
+                }
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(name) = name {
+            let diagnostic = NoReactApis::new().check_call(call, &name, self.source_text);
+            self.diagnostics.extend(diagnostic);
+        }
+    }
+
+    /// migration/no-react-apis on an element
+    fn check_react_element(&mut self, element: &JSXElement<'a>) {
+        let (name, is_member) = match &element.opening_element.name {
+            JSXElementName::IdentifierReference(ident) => (self.react_export(ident, None), false),
+            JSXElementName::MemberExpression(member) => match &member.object {
+                JSXMemberExpressionObject::IdentifierReference(object) => {
+                    (self.react_export(object, Some(&member.property.name)), true)
+                }
+                _ => (None, true),
+            },
+            _ => (None, false),
+        };
+        if let Some(name) = name {
+            let diagnostic =
+                NoReactApis::new().check_element(element, &name, is_member, self.source_text);
+            self.diagnostics.extend(diagnostic);
+        }
+    }
+
+    /// migration/no-react-types
+    fn check_react_type(&mut self, reference: &TSTypeReference<'a>) {
+        let (name, is_member) = match &reference.type_name {
+            TSTypeName::IdentifierReference(ident) => (self.react_export(ident, None), false),
+            TSTypeName::QualifiedName(qualified) => match &qualified.left {
+                TSTypeName::IdentifierReference(object) => {
+                    (self.react_export(object, Some(&qualified.right.name)), true)
+                }
+                _ => (None, true),
+            },
+            TSTypeName::ThisExpression(_) => (None, false),
+        };
+        if let Some(name) = name {
+            let diagnostic = NoReactTypes::new().check(
+                reference.type_name.span(),
+                &name,
+                is_member,
+                self.source_text,
+            );
+            self.diagnostics.extend(diagnostic);
+        }
+    }
+
+    /// Classify relative imports by what the project module exports
+    fn collect_project_imports(&mut self, program: &Program<'a>) {
+        let Some(project) = self.project else {
+            return;
+        };
+        for stmt in &program.body {
+            let Statement::ImportDeclaration(import) = stmt else {
+                continue;
+            };
+            if import.import_kind.is_type() {
+                continue;
+            }
+            let specifier = import.source.value.as_str();
+            for spec in import.specifiers.iter().flatten() {
+                let (local, imported) = match spec {
+                    ImportDeclarationSpecifier::ImportSpecifier(named) => {
+                        if named.import_kind.is_type() {
+                            continue;
+                        }
+                        (&named.local, named.imported.name())
+                    }
+                    ImportDeclarationSpecifier::ImportDefaultSpecifier(default) => {
+                        (&default.local, "default".into())
+                    }
+                    ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => continue,
+                };
+                let symbol_id = local.symbol_id();
+                match project.imported_binding(specifier, &imported) {
+                    ImportedBinding::Component => {
+                        self.imported_components.insert(symbol_id);
+                        self.component_symbols.insert(symbol_id);
+                    }
+                    ImportedBinding::Package { source, .. } => {
+                        let settings = &self.config.settings;
+                        if is_solid_source(&source) || settings.is_primitive_module(&source) {
+                            self.solid_imports.insert(local.name.to_string());
+                            self.solid_symbols.insert(symbol_id);
+                        }
+                        if settings.is_component_module(&source) {
+                            self.imported_components.insert(symbol_id);
+                            self.component_symbols.insert(symbol_id);
+                        }
+                    }
+                    ImportedBinding::Missing => {
+                        self.unexported_imports
+                            .insert(symbol_id, (specifier.to_string(), imported.to_string()));
+                    }
+                    ImportedBinding::Value | ImportedBinding::Unknown => {}
+                }
+            }
+        }
+    }
+
+    /// Track the parameters of exported functions that components of other
+    /// files pass their props to
+    fn collect_project_props(&mut self, program: &Program<'a>) {
+        let Some(project) = self.project else {
+            return;
+        };
+        let params: Vec<_> = project.props_params().collect();
+        if params.is_empty() {
+            return;
+        }
+        for (name, function) in exported_functions(program) {
+            for &(_, index) in params.iter().filter(|(export, _)| *export == name) {
+                let Some(param) = function.params.items.get(index) else {
+                    continue;
+                };
+                match &param.pattern {
+                    BindingPattern::BindingIdentifier(ident) => {
+                        self.props_symbols.insert(ident.symbol_id());
+                    }
+                    _ if self.config.no_destructure => {
+                        self.diagnostics
+                            .extend(NoDestructure::new().check_forwarded_props(param));
+                    }
+                    _ => {}
+                }
+            }
         }
     }
 
@@ -235,6 +530,16 @@ impl<'a> SemanticLintRunner<'a> {
             if is_component {
                 self.component_symbols.insert(symbol_id);
             }
+
+            if let Some((specifier, imported)) = self.unexported_imports.get(&symbol_id) {
+                if self.config.jsx_no_undef {
+                    self.diagnostics.push(Diagnostic::error(
+                        JsxNoUndef::NAME,
+                        span,
+                        format!("'{}' is not exported by '{}'.", imported, specifier),
+                    ));
+                }
+            }
         } else if self.config.jsx_no_undef {
             // Check if it's a Solid auto-import component
             let auto_components = ["Show", "For", "Index", "Switch", "Match"];
@@ -281,47 +586,61 @@ impl<'a> SemanticLintRunner<'a> {
 
     /// Check if a function is a component and run components-return-once
     fn check_function_component(&mut self, func: &Function<'a>) {
-        if !self.config.components_return_once {
+        let Some(rule) = &self.config.components_return_once else {
             return;
-        }
+        };
 
         // Skip if inside JSX expression (render props, callbacks)
         if self.is_inside_jsx() {
             return;
         }
 
-        // Heuristic 1: PascalCase name
-        let is_pascal_case = func.id.as_ref().is_some_and(|id| {
-            id.name.chars().next().is_some_and(|c| c.is_uppercase())
-        });
-
-        // Heuristic 2: Returns JSX
-        let returns_jsx = func.body.as_ref().is_some_and(|body| {
-            NoDestructure::body_has_jsx(body)
-        });
-
-        if !is_pascal_case && !returns_jsx {
-            return;
+        // Function arguments are callbacks unless the callee is a wrapper
+        match self.call_argument_functions.get(&func.span) {
+            Some(false) => return,
+            Some(true) => {
+                self.diagnostics.extend(rule.check_function(func, true, false));
+                return;
+            }
+            None => {}
         }
 
-        // If we have a symbol ID, check if it's marked as a component
-        let is_known_component = func.id.as_ref().map(|id| {
-            let sym = id.symbol_id();
-            self.component_symbols.contains(&sym)
-        }).unwrap_or(false);
+        // PascalCase name or returns JSX
+        if is_probably_component(func) {
+            self.diagnostics.extend(
+                rule.check_function(func, true, self.is_inside_jsx())
+            );
+        }
+    }
 
-        if is_pascal_case || returns_jsx || is_known_component {
-            let rule = ComponentsReturnOnce::new();
-            if func.body.is_some() {
-                self.diagnostics.extend(
-                    rule.check_function(func, true, self.is_inside_jsx())
-                );
-            }
+    /// Record the function arguments of a call before they are visited. As in
+    /// eslint-plugin-solid, they are components only when the callee looks
+    /// like a component (`Dynamic(...)`) or is a configured wrapper.
+    fn collect_call_argument_functions(&mut self, call: &CallExpression<'a>) {
+        let Some(rule) = &self.config.components_return_once else {
+            return;
+        };
+        let is_pascal_case = matches!(
+            call.callee.get_inner_expression(),
+            Expression::Identifier(ident) if is_pascal_case(&ident.name)
+        );
+        let is_component = is_pascal_case || rule.is_wrapper(&call.callee);
+
+        for arg in &call.arguments {
+            let Some(expr) = arg.as_expression() else {
+                continue;
+            };
+            let span = match expr.get_inner_expression() {
+                Expression::ArrowFunctionExpression(arrow) => arrow.span,
+                Expression::FunctionExpression(func) => func.span,
+                _ => continue,
+            };
+            self.call_argument_functions.insert(span, is_component);
         }
     }
 
     fn check_arrow_component(&mut self, arrow: &ArrowFunctionExpression<'a>) {
-        if !self.config.components_return_once && !self.config.no_destructure {
+        if self.config.components_return_once.is_none() && !self.config.no_destructure {
             return;
         }
 
@@ -330,29 +649,172 @@ impl<'a> SemanticLintRunner<'a> {
             return;
         }
 
-        // Check if returns JSX
-        let returns_jsx = NoDestructure::body_has_jsx(&arrow.body);
-        if !returns_jsx {
-            return;
+        let returns_jsx = returns_jsx(&arrow.body);
+
+        if let Some(rule) = &self.config.components_return_once {
+            let start = timing::start(&self.timings, ComponentsReturnOnce::NAME);
+            // Function arguments are callbacks unless the callee is a wrapper
+            let is_component = self
+                .call_argument_functions
+                .get(&arrow.span)
+                .copied()
+                .unwrap_or(returns_jsx);
+            self.diagnostics
+                .extend(rule.check_arrow(arrow, is_component, false));
+            timing::record(&mut self.timings, start);
         }
 
-        if self.config.components_return_once {
-            let rule = ComponentsReturnOnce::new();
-            self.diagnostics.extend(
-                rule.check_arrow(arrow, true, self.is_inside_jsx())
-            );
+        if !returns_jsx {
+            return;
         }
 
         if self.config.no_destructure {
+            let start = timing::start(&self.timings, NoDestructure::NAME);
             let rule = NoDestructure::new();
             self.diagnostics.extend(
                 rule.check_arrow(arrow, returns_jsx, self.is_inside_jsx())
             );
+            timing::record(&mut self.timings, start);
         }
     }
 
     // ==================== Phase 3: Reactivity Checks ====================
 
+    /// Whether `expr` reads from a component's props (`props`, `props.items`,
+    /// `props.items.filter(...)`, or a variable initialized from one of those)
+    fn is_props_derived(&self, expr: &Expression<'a>) -> bool {
+        match expr.get_inner_expression() {
+            Expression::Identifier(ident) => ident.reference_id.get().is_some_and(|reference_id| {
+                self.semantic
+                    .scoping()
+                    .get_reference(reference_id)
+                    .symbol_id()
+                    .is_some_and(|symbol_id| self.props_symbols.contains(&symbol_id))
+            }),
+            Expression::StaticMemberExpression(member) => self.is_props_derived(&member.object),
+            Expression::ComputedMemberExpression(member) => self.is_props_derived(&member.object),
+            Expression::CallExpression(call) => match &call.callee {
+                Expression::StaticMemberExpression(member) => self.is_props_derived(&member.object),
+                _ => false,
+            },
+            Expression::ChainExpression(chain) => chain
+                .expression
+                .as_member_expression()
+                .is_some_and(|member| self.is_props_derived(member.object())),
+            _ => false,
+        }
+    }
+
+    /// Start tracking the props of a component and check the parameters of
+    /// functions nested in one
+    fn check_function_params(&mut self, params: &FormalParameters<'a>, is_component: bool) {
+        if is_component {
+            if let Some(BindingPattern::BindingIdentifier(ident)) =
+                params.items.first().map(|param| &param.pattern)
+            {
+                self.props_symbols.insert(ident.symbol_id());
+            }
+            return;
+        }
+        if self.props_symbols.is_empty() {
+            return;
+        }
+        let diagnostics = Reactivity::new().check_destructured_defaults(
+            params,
+            self.source_text,
+            |expr| self.is_props_derived(expr),
+        );
+        self.diagnostics.extend(diagnostics);
+    }
+
+    /// Track variables holding values read from props
+    fn check_variable_declarator(&mut self, declarator: &VariableDeclarator<'a>) {
+        let (BindingPattern::BindingIdentifier(ident), Some(init)) =
+            (&declarator.id, &declarator.init)
+        else {
+            return;
+        };
+        if self.is_props_derived(init) {
+            self.props_symbols.insert(ident.symbol_id());
+        }
+    }
+
+    /// Check the render callback of `<For>`/`<Index>` iterating over props
+    fn check_jsx_element_callbacks(&mut self, element: &JSXElement<'a>) {
+        if self.props_symbols.is_empty() {
+            return;
+        }
+        let opening = &element.opening_element;
+        let is_list = matches!(&opening.name, JSXElementName::IdentifierReference(ident)
+        if matches!(ident.name.as_str(), "For" | "Index")
+            && self.refers_to_import(ident, |symbol_id| {
+                self.solid_symbols.contains(symbol_id)
+                    || self.imported_components.contains(symbol_id)
+            }));
+        let rule = Reactivity::new();
+
+        for attr in &opening.attributes {
+            let JSXAttributeItem::Attribute(attr) = attr else {
+                continue;
+            };
+            let JSXAttributeName::Identifier(name) = &attr.name else {
+                continue;
+            };
+            let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value else {
+                continue;
+            };
+            let Some(value) = container_expression(container) else {
+                continue;
+            };
+
+            if is_list && name.name == "each" && self.is_props_derived(value) {
+                if let Some(callback) = common::get_function_child(element) {
+                    let source = value.span().source_text(self.source_text);
+                    self.diagnostics
+                        .extend(rule.check_destructured_callback(callback, source));
+                }
+            }
+
+            // Bound event handlers receive their data first: `onClick={[handler, props.item]}`
+            if name.name.starts_with("on") {
+                if let Expression::ArrayExpression(array) = value.get_inner_expression() {
+                    if let [handler, data] = array.elements.as_slice() {
+                        if let (Some(handler), Some(data)) =
+                            (handler.as_expression(), data.as_expression())
+                        {
+                            if self.is_props_derived(data) {
+                                let source = data.span().source_text(self.source_text);
+                                self.diagnostics
+                                    .extend(rule.check_destructured_callback(handler, source));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check callbacks of array methods called on values read from props
+    fn check_iteration_callback(&mut self, call: &CallExpression<'a>) {
+        if self.props_symbols.is_empty() {
+            return;
+        }
+        let Expression::StaticMemberExpression(member) = &call.callee else {
+            return;
+        };
+        if !ITERATION_METHODS.contains(&member.property.name.as_str())
+            || !self.is_props_derived(&member.object)
+        {
+            return;
+        }
+        let Some(callback) = call.arguments.first().and_then(|arg| arg.as_expression()) else {
+            return;
+        };
+        let source = member.object.span().source_text(self.source_text);
+        self.diagnostics
+            .extend(Reactivity::new().check_destructured_callback(callback, source));
+    }
+
     fn check_call_expression(&mut self, call: &CallExpression<'a>) {
         if !self.config.reactivity {
             return;
@@ -361,9 +823,17 @@ impl<'a> SemanticLintRunner<'a> {
         // Check for signal getter called without parens (accessing as property)
         // This is a common mistake: signal.value instead of signal().value
 
-        // Check for reactive primitives receiving non-function arguments
+        // Check for reactive primitives receiving non-function arguments.
+        // Functions of the same name declared locally or imported from other
+        // modules are not Solid's.
         let callee_name = match &call.callee {
-            Expression::Identifier(ident) => Some(ident.name.as_str()),
+            Expression::Identifier(ident)
+                if self.refers_to_import(ident, |symbol_id| {
+                    self.solid_symbols.contains(symbol_id)
+                }) =>
+            {
+                Some(ident.name.as_str())
+            }
             _ => None,
         };
 
@@ -387,7 +857,7 @@ impl<'a> SemanticLintRunner<'a> {
                     arg => {
                         if let Some(expr) = arg.as_expression() {
                             if !matches!(
-                                expr,
+                                expr.get_inner_expression(),
                                 Expression::ArrowFunctionExpression(_)
                                     | Expression::FunctionExpression(_)
                                     | Expression::Identifier(_)
@@ -416,17 +886,40 @@ impl<'a> Visit<'a> for SemanticLintRunner<'a> {
 
     fn visit_function(&mut self, func: &Function<'a>, _flags: oxc_syntax::scope::ScopeFlags) {
         // Check function as component
-        self.check_function_component(func);
+        if self.config.components_return_once.is_some() {
+            let start = timing::start(&self.timings, ComponentsReturnOnce::NAME);
+            self.check_function_component(func);
+            timing::record(&mut self.timings, start);
+        }
 
         // Check for destructured props
         if self.config.no_destructure && !self.is_inside_jsx() {
-            let returns_jsx = func.body.as_ref().is_some_and(|b| NoDestructure::body_has_jsx(b));
+            let start = timing::start(&self.timings, NoDestructure::NAME);
+            let returns_jsx = func.body.as_ref().is_some_and(|b| returns_jsx(b));
             if returns_jsx {
                 let rule = NoDestructure::new();
                 self.diagnostics.extend(
                     rule.check_function(func, returns_jsx, self.is_inside_jsx())
                 );
             }
+            timing::record(&mut self.timings, start);
+        }
+
+        if self.config.reactivity {
+            let start = timing::start(&self.timings, Reactivity::NAME);
+            let is_component = !self.is_inside_jsx()
+                && func.body.as_ref().is_some_and(|b| returns_jsx(b));
+            self.check_function_params(&func.params, is_component);
+            timing::record(&mut self.timings, start);
+        }
+
+        if self.config.no_conditional_primitive_creation && !self.is_inside_jsx() {
+            if let Some(body) = func.body.as_ref().filter(|b| returns_jsx(b)) {
+                let start = timing::start(&self.timings, NoConditionalPrimitiveCreation::NAME);
+                self.diagnostics
+                    .extend(NoConditionalPrimitiveCreation::new().check_component_body(body));
+                timing::record(&mut self.timings, start);
+            }
         }
 
         // Push new scope (simplified - in full impl would track actual scope IDs)
@@ -435,15 +928,42 @@ impl<'a> Visit<'a> for SemanticLintRunner<'a> {
 
     fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
         self.check_arrow_component(arrow);
+        if self.config.reactivity {
+            let start = timing::start(&self.timings, Reactivity::NAME);
+            let is_component = !self.is_inside_jsx() && returns_jsx(&arrow.body);
+            self.check_function_params(&arrow.params, is_component);
+            timing::record(&mut self.timings, start);
+        }
+        if self.config.no_conditional_primitive_creation
+            && !self.is_inside_jsx()
+            && returns_jsx(&arrow.body)
+        {
+            let start = timing::start(&self.timings, NoConditionalPrimitiveCreation::NAME);
+            self.diagnostics
+                .extend(NoConditionalPrimitiveCreation::new().check_component_body(&arrow.body));
+            timing::record(&mut self.timings, start);
+        }
         walk::walk_arrow_function_expression(self, arrow);
     }
 
     fn visit_jsx_opening_element(&mut self, opening: &JSXOpeningElement<'a>) {
+        let start = timing::start(&self.timings, JsxNoUndef::NAME);
         self.check_jsx_opening_element(opening);
+        timing::record(&mut self.timings, start);
         walk::walk_jsx_opening_element(self, opening);
     }
 
-    fn visit_jsx_element(&mut self, element: &oxc_ast::ast::JSXElement<'a>) {
+    fn visit_jsx_element(&mut self, element: &JSXElement<'a>) {
+        if self.config.reactivity {
+            let start = timing::start(&self.timings, Reactivity::NAME);
+            self.check_jsx_element_callbacks(element);
+            timing::record(&mut self.timings, start);
+        }
+        if self.config.migration.no_react_apis {
+            let start = timing::start(&self.timings, NoReactApis::NAME);
+            self.check_react_element(element);
+            timing::record(&mut self.timings, start);
+        }
         self.jsx_depth += 1;
         walk::walk_jsx_element(self, element);
         self.jsx_depth -= 1;
@@ -456,9 +976,42 @@ impl<'a> Visit<'a> for SemanticLintRunner<'a> {
     }
 
     fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
-        self.check_call_expression(call);
+        if self.config.components_return_once.is_some() {
+            let start = timing::start(&self.timings, ComponentsReturnOnce::NAME);
+            self.collect_call_argument_functions(call);
+            timing::record(&mut self.timings, start);
+        }
+        if self.config.reactivity {
+            let start = timing::start(&self.timings, Reactivity::NAME);
+            self.check_call_expression(call);
+            self.check_iteration_callback(call);
+            timing::record(&mut self.timings, start);
+        }
+        if self.config.migration.no_react_apis {
+            let start = timing::start(&self.timings, NoReactApis::NAME);
+            self.check_react_call(call);
+            timing::record(&mut self.timings, start);
+        }
         walk::walk_call_expression(self, call);
     }
+
+    fn visit_ts_type_reference(&mut self, reference: &TSTypeReference<'a>) {
+        if self.config.migration.no_react_types {
+            let start = timing::start(&self.timings, NoReactTypes::NAME);
+            self.check_react_type(reference);
+            timing::record(&mut self.timings, start);
+        }
+        walk::walk_ts_type_reference(self, reference);
+    }
+
+    fn visit_variable_declarator(&mut self, declarator: &VariableDeclarator<'a>) {
+        if self.config.reactivity {
+            let start = timing::start(&self.timings, Reactivity::NAME);
+            self.check_variable_declarator(declarator);
+            timing::record(&mut self.timings, start);
+        }
+        walk::walk_variable_declarator(self, declarator);
+    }
 }
 
 /// Convenience function to run semantic linting
@@ -659,6 +1212,180 @@ mod tests {
         ));
     }
 
+    fn reactivity_messages(source: &str) -> Vec<String> {
+        parse_and_lint(source)
+            .diagnostics
+            .into_iter()
+            .filter(|d| d.rule == Reactivity::NAME)
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn test_reactivity_destructured_list_callback() {
+        let messages = reactivity_messages(
+            r#"
+            import { For } from 'solid-js';
+            function List(props) {
+                const items = props.items;
+                return (
+                    <ul>
+                        <For each={props.items}>{({ id }) => <li>{id}</li>}</For>
+                        <For each={items}>{(item) => <li>{item.id}</li>}</For>
+                        {props.items.map(({ name }) => <li>{name}</li>)}
+                        {items.filter(({ done }) => done).length}
+                    </ul>
+                );
+            }
+            "#,
+        );
+        assert_eq!(messages.len(), 3, "{messages:?}");
+        assert!(messages[0].contains("`props.items`"));
+        assert!(messages[2].contains("`items`"));
+    }
+
+    #[test]
+    fn test_reactivity_destructured_handler_data() {
+        let messages = reactivity_messages(
+            r#"
+            function Row(props) {
+                return <button onClick={[({ id }) => select(id), props.row]}>x</button>;
+            }
+            "#,
+        );
+        assert_eq!(messages.len(), 1, "{messages:?}");
+    }
+
+    #[test]
+    fn test_reactivity_destructured_default_param() {
+        let messages = reactivity_messages(
+            r#"
+            function Form(props) {
+                const submit = ({ name } = props.user) => save(name);
+                const reset = (value = props.value) => set(value);
+                return <form onSubmit={() => submit()} />;
+            }
+            "#,
+        );
+        assert_eq!(messages.len(), 1, "{messages:?}");
+        assert!(messages[0].contains("`props.user`"));
+    }
+
+    #[test]
+    fn test_reactivity_destructuring_unrelated_values() {
+        let messages = reactivity_messages(
+            r#"
+            function List(props) {
+                const rows = [{ id: 1 }];
+                return <ul>{rows.map(({ id }) => <li>{id}</li>)}</ul>;
+            }
+            data.map(({ id }) => id);
+            "#,
+        );
+        assert!(messages.is_empty(), "{messages:?}");
+    }
+
+    #[test]
+    fn test_no_conditional_primitive_creation() {
+        let source = r#"
+            import { createSignal, createEffect, createMemo } from 'solid-js';
+            function Counter(props) {
+                const [count, setCount] = createSignal(0);
+                if (props.tracked) {
+                    createEffect(() => console.log(count()));
+                }
+                for (const item of props.items) {
+                    createMemo(() => item.value);
+                }
+                const doubled = props.double ? createMemo(() => count() * 2) : count;
+                const later = () => createSignal(1);
+                return (
+                    <button onClick={() => { const [clicked] = createSignal(true); }}>
+                        <For each={props.items}>{(item) => createMemo(() => item)}</For>
+                    </button>
+                );
+            }
+        "#;
+        assert!(parse_and_lint(source)
+            .diagnostics
+            .iter()
+            .all(|d| d.rule != NoConditionalPrimitiveCreation::NAME));
+
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source, SourceType::jsx()).parse();
+        let semantic_ret = SemanticBuilder::new().build(&ret.program);
+        let config = SemanticRulesConfig::none().with_no_conditional_primitive_creation(true);
+        let result = lint_with_semantic_config(
+            &semantic_ret.semantic,
+            source,
+            SourceType::jsx(),
+            &ret.program,
+            config,
+        );
+        let messages: Vec<_> = result.diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages.len(), 4, "{messages:?}");
+        assert!(messages[0].contains("`createEffect` is called inside a conditional"));
+        assert!(messages[1].contains("`createMemo` is called inside a loop"));
+        assert!(messages[2].contains("`createMemo` is called inside a conditional"));
+        assert!(messages[3].contains("`createSignal` is called inside an event handler"));
+    }
+
+    fn lint_with_settings(source: &str, settings: LintSettings) -> SemanticLintResult {
+        let allocator = Allocator::default();
+        let source_type = SourceType::tsx();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        let semantic = SemanticBuilder::new().build(&ret.program).semantic;
+        let config = SemanticRulesConfig::all().with_settings(settings);
+        SemanticLintRunner::new(&semantic, source, source_type, config).run(&ret.program)
+    }
+
+    #[test]
+    fn test_reactivity_ignores_other_functions_named_like_primitives() {
+        let source = r#"
+            import { on } from 'event-lib';
+            import { createMemo } from '@solid-primitives/memo';
+            on('click');
+            createMemo(1);
+        "#;
+        let result = lint_with_settings(source, LintSettings::default());
+        assert!(result.diagnostics.iter().all(|d| d.rule != Reactivity::NAME));
+
+        let settings =
+            LintSettings::default().with_primitive_modules(vec!["@solid-primitives/memo".into()]);
+        let result = lint_with_settings(source, settings);
+        let messages: Vec<_> = result
+            .diagnostics
+            .iter()
+            .filter(|d| d.rule == Reactivity::NAME)
+            .map(|d| d.message.as_str())
+            .collect();
+        assert_eq!(messages.len(), 1, "{messages:?}");
+        assert!(messages[0].contains("createMemo"));
+    }
+
+    #[test]
+    fn test_component_modules() {
+        let source = r#"
+            import { Button, For } from '@kobalte/core';
+            function List(props) {
+                return <For each={props.items}>{({ id }) => <Button>{id}</Button>}</For>;
+            }
+        "#;
+        let result = lint_with_settings(source, LintSettings::default());
+        assert!(result.diagnostics.iter().all(|d| d.rule != Reactivity::NAME));
+        assert_eq!(result.component_symbols.len(), 2);
+
+        let settings = LintSettings::default().with_component_modules(vec!["@kobalte/core".into()]);
+        let result = lint_with_settings(source, settings);
+        assert!(result.diagnostics.iter().any(|d| d.rule == Reactivity::NAME));
+        assert!(LintSettings::default()
+            .with_component_modules(vec!["@kobalte/core".into()])
+            .is_component_module("@kobalte/core/dialog"));
+        assert!(!LintSettings::default()
+            .with_component_modules(vec!["@kobalte/core".into()])
+            .is_component_module("@kobalte/core-extra"));
+    }
+
     #[test]
     fn test_solid_imports_tracked() {
         let result = parse_and_lint(
@@ -670,4 +1397,139 @@ mod tests {
         // No diagnostics expected for just imports
         assert!(result.diagnostics.is_empty());
     }
+
+    #[test]
+    fn test_timing_collected() {
+        let allocator = Allocator::default();
+        let source = r#"
+            import { createEffect } from 'solid-js';
+            function App(props) {
+                createEffect(() => props.value);
+                return <div />;
+            }
+        "#;
+        let source_type = SourceType::jsx();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        let semantic_ret = SemanticBuilder::new().build(&ret.program);
+        let result = SemanticLintRunner::new(
+            &semantic_ret.semantic,
+            source,
+            source_type,
+            SemanticRulesConfig::all(),
+        )
+        .with_timing(true)
+        .run(&ret.program);
+
+        let timings = result.timings.expect("timings should be collected");
+        assert!(timings.get("reactivity").is_some());
+        assert!(timings.get("components-return-once").is_some());
+    }
+
+    fn return_once_count(source: &str, rule: ComponentsReturnOnce) -> usize {
+        let allocator = Allocator::default();
+        let source_type = SourceType::jsx();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        let semantic_ret = SemanticBuilder::new().build(&ret.program);
+        let config = SemanticRulesConfig::none().with_components_return_once(Some(rule));
+        lint_with_semantic_config(&semantic_ret.semantic, source, source_type, &ret.program, config)
+            .diagnostics
+            .iter()
+            .filter(|d| d.rule == ComponentsReturnOnce::NAME)
+            .count()
+    }
+
+    #[test]
+    fn test_components_return_once_skips_callbacks() {
+        let source = r#"
+            function List(props) {
+                const rows = props.items.map((item) => item.ok ? <A /> : <B />);
+                return <ul>{rows}</ul>;
+            }
+            const Lazy = lazy(() => import('./Page'));
+            const Row = Dynamic(function (props) {
+                return props.row ? <tr /> : null;
+            });
+        "#;
+        assert_eq!(return_once_count(source, ComponentsReturnOnce::new()), 1);
+    }
+
+    #[test]
+    fn test_components_return_once_wrappers() {
+        let source = r#"
+            const Admin = withAuth((props) => {
+                return props.user ? <div /> : null;
+            });
+            const Panel = observer(function Panel(props) {
+                return props.open && <section />;
+            });
+        "#;
+        assert_eq!(return_once_count(source, ComponentsReturnOnce::new()), 0);
+
+        let rule = ComponentsReturnOnce::with_config(
+            crate::rules::components_return_once::ComponentsReturnOnceConfig {
+                wrappers: vec!["withAuth".to_string(), "observer".to_string()],
+            },
+        );
+        assert_eq!(return_once_count(source, rule), 2);
+    }
+
+    fn migration_messages(source: &str) -> Vec<(String, String)> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::tsx();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+        let semantic = SemanticBuilder::new().build(&ret.program).semantic;
+        let config = SemanticRulesConfig::none().with_migration(MigrationRulesConfig::all());
+        let mut diagnostics = SemanticLintRunner::new(&semantic, source, source_type, config)
+            .run(&ret.program)
+            .diagnostics;
+        diagnostics.sort_by_key(|d| d.start);
+        diagnostics
+            .into_iter()
+            .map(|d| (d.rule, d.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_migration_is_opt_in() {
+        let source = "import { useState } from 'react';\nconst [a] = useState(0);";
+        let diagnostics = parse_and_lint(source).diagnostics;
+        assert!(!diagnostics.iter().any(|d| d.rule.starts_with("migration")));
+    }
+
+    #[test]
+    fn test_migration_react_apis_and_types() {
+        let messages = migration_messages(
+            r#"
+            import React, { useState, useContext, Fragment, type FC } from 'react';
+            const App: React.FC<{}> = () => {
+                const [count] = useState(0);
+                const theme = useContext(Theme);
+                const ref = React.useRef(null);
+                return <Fragment>{count}</Fragment>;
+            };
+            const Item: FC = () => <li />;
+            function useStateLike() { const useState = () => 0; return useState(); }
+            "#,
+        );
+        let rules: Vec<_> = messages.iter().map(|(rule, _)| rule.as_str()).collect();
+        assert_eq!(
+            rules,
+            [
+                NoReactImports::NAME,
+                NoReactTypes::NAME,
+                NoReactApis::NAME,
+                NoReactApis::NAME,
+                NoReactApis::NAME,
+            ],
+            "{messages:?}"
+        );
+        assert_eq!(
+            messages[1].1,
+            "`React.FC` is a React type; use `Component` from `solid-js` in Solid."
+        );
+        assert!(messages[2].1.starts_with("`useState` is a React API"));
+        assert!(messages[2].1.contains("`createSignal`"));
+        assert!(messages[3].1.starts_with("`React.useRef` is a React API"));
+        assert!(messages[4].1.starts_with("`Fragment` is a React API"));
+    }
 }
@@ -0,0 +1,65 @@
+//! Settings shared by rules
+//!
+//! Rules recognize Solid's primitives and components by what a file imports
+//! from `solid-js`. Libraries that wrap or re-export them (`@kobalte/core`,
+//! `@solid-primitives/*`) are declared here, like eslint's `settings.solid`,
+//! so their exports are treated the same way:
+//!
+//! ```json
+//! {
+//!   "settings": {
+//!     "componentModules": ["@kobalte/core"],
+//!     "primitiveModules": ["@solid-primitives/signal-builders"]
+//!   }
+//! }
+//! ```
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Modules the linter treats as sources of Solid components and primitives
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct LintSettings {
+    /// Modules exporting components; their subpaths (`@kobalte/core/dialog`)
+    /// are included
+    #[serde(default)]
+    pub component_modules: Vec<String>,
+    /// Modules exporting reactive primitives, checked like Solid's own
+    /// (`createEffect`, `createMemo`, `on`, ...)
+    #[serde(default)]
+    pub primitive_modules: Vec<String>,
+}
+
+impl LintSettings {
+    pub fn with_component_modules(mut self, modules: Vec<String>) -> Self {
+        self.component_modules = modules;
+        self
+    }
+
+    pub fn with_primitive_modules(mut self, modules: Vec<String>) -> Self {
+        self.primitive_modules = modules;
+        self
+    }
+
+    /// Whether imports from `source` are components
+    pub fn is_component_module(&self, source: &str) -> bool {
+        self.component_modules
+            .iter()
+            .any(|module| module_matches(module, source))
+    }
+
+    /// Whether imports from `source` are reactive primitives
+    pub fn is_primitive_module(&self, source: &str) -> bool {
+        self.primitive_modules
+            .iter()
+            .any(|module| module_matches(module, source))
+    }
+}
+
+/// Whether `source` is `module` or one of its subpaths
+fn module_matches(module: &str, source: &str) -> bool {
+    source
+        .strip_prefix(module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
@@ -0,0 +1,180 @@
+//! Per-rule execution timing
+//!
+//! Runners record how long each rule spends checking nodes when timing is
+//! enabled, so expensive rules can be identified across a whole run. With the
+//! `tracing` feature, every rule invocation and lint phase is also a span.
+
+use std::time::{Duration, Instant};
+
+use rustc_hash::FxHashMap;
+
+/// Accumulated timing for a single rule
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleTiming {
+    /// Total time spent in the rule
+    pub total: Duration,
+    /// Number of times the rule was invoked
+    pub calls: u64,
+}
+
+/// Timings for all rules that ran, keyed by rule name
+#[derive(Debug, Clone, Default)]
+pub struct RuleTimings {
+    rules: FxHashMap<&'static str, RuleTiming>,
+}
+
+impl RuleTimings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `elapsed` to the timing of `rule`
+    pub fn record(&mut self, rule: &'static str, elapsed: Duration) {
+        let timing = self.rules.entry(rule).or_default();
+        timing.total += elapsed;
+        timing.calls += 1;
+    }
+
+    /// Merge timings from another run (e.g. another file) into this one
+    pub fn merge(&mut self, other: &RuleTimings) {
+        for (rule, timing) in &other.rules {
+            let entry = self.rules.entry(rule).or_default();
+            entry.total += timing.total;
+            entry.calls += timing.calls;
+        }
+    }
+
+    pub fn get(&self, rule: &str) -> Option<RuleTiming> {
+        self.rules.get(rule).copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Total time spent across all rules
+    pub fn total(&self) -> Duration {
+        self.rules.values().map(|t| t.total).sum()
+    }
+
+    /// All rule timings, slowest first
+    pub fn sorted(&self) -> Vec<(&'static str, RuleTiming)> {
+        let mut entries: Vec<_> = self.rules.iter().map(|(name, t)| (*name, *t)).collect();
+        entries.sort_by(|a, b| b.1.total.cmp(&a.1.total).then(a.0.cmp(b.0)));
+        entries
+    }
+
+    /// The `n` slowest rules
+    pub fn slowest(&self, n: usize) -> Vec<(&'static str, RuleTiming)> {
+        let mut entries = self.sorted();
+        entries.truncate(n);
+        entries
+    }
+
+    /// Render the `n` slowest rules as a table, like eslint's `TIMING` output
+    pub fn format_table(&self, n: usize) -> String {
+        let total = self.total().as_secs_f64();
+        let rows = self.slowest(n);
+        let width = rows
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0)
+            .max("Rule".len());
+
+        let mut out = format!(
+            "{:<width$} | {:>10} | {:>8} | {:>8}\n",
+            "Rule", "Time (ms)", "Calls", "Relative"
+        );
+        out.push_str(&format!("{:-<width$}-|-{:->10}-|-{:->8}-|-{:->8}\n", "", "", "", ""));
+        for (name, timing) in rows {
+            let secs = timing.total.as_secs_f64();
+            let relative = if total > 0.0 { secs / total * 100.0 } else { 0.0 };
+            out.push_str(&format!(
+                "{:<width$} | {:>10.3} | {:>8} | {:>7.1}%\n",
+                name,
+                secs * 1000.0,
+                timing.calls,
+                relative
+            ));
+        }
+        out
+    }
+}
+
+/// One running invocation of a rule
+pub(crate) struct RuleTimer {
+    rule: &'static str,
+    start: Option<Instant>,
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+/// Start timing `rule`; the clock only runs if timing is enabled
+pub(crate) fn start(timings: &Option<RuleTimings>, rule: &'static str) -> RuleTimer {
+    RuleTimer {
+        rule,
+        start: timings.as_ref().map(|_| Instant::now()),
+        #[cfg(feature = "tracing")]
+        _span: tracing::trace_span!("lint_rule", rule).entered(),
+    }
+}
+
+/// Record the time elapsed since `timer` started, if timing is enabled
+pub(crate) fn record(timings: &mut Option<RuleTimings>, timer: RuleTimer) {
+    if let (Some(timings), Some(start)) = (timings.as_mut(), timer.start) {
+        timings.record(timer.rule, start.elapsed());
+    }
+}
+
+/// Run `f` in a span for the lint phase `name` when the `tracing` feature is on
+#[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+pub(crate) fn phase<T>(name: &'static str, f: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("lint_phase", phase = name).entered();
+    f()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_sort() {
+        let mut timings = RuleTimings::new();
+        timings.record("fast", Duration::from_millis(1));
+        timings.record("slow", Duration::from_millis(5));
+        timings.record("slow", Duration::from_millis(5));
+
+        let sorted = timings.sorted();
+        assert_eq!(sorted[0].0, "slow");
+        assert_eq!(sorted[0].1.calls, 2);
+        assert_eq!(sorted[0].1.total, Duration::from_millis(10));
+        assert_eq!(timings.total(), Duration::from_millis(11));
+        assert_eq!(timings.slowest(1).len(), 1);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut a = RuleTimings::new();
+        a.record("rule", Duration::from_millis(2));
+        let mut b = RuleTimings::new();
+        b.record("rule", Duration::from_millis(3));
+        b.record("other", Duration::from_millis(1));
+
+        a.merge(&b);
+        assert_eq!(a.get("rule").unwrap().total, Duration::from_millis(5));
+        assert_eq!(a.get("rule").unwrap().calls, 2);
+        assert!(a.get("other").is_some());
+    }
+
+    #[test]
+    fn test_format_table() {
+        let mut timings = RuleTimings::new();
+        timings.record("reactivity", Duration::from_millis(3));
+        let table = timings.format_table(10);
+        assert!(table.starts_with("Rule"));
+        assert!(table.contains("reactivity"));
+        assert!(table.contains("100.0%"));
+    }
+}
@@ -1,11 +1,29 @@
 //! Utility functions for Solid linting rules
+//!
+//! Besides the JSX helpers, this module holds the heuristics rules use to
+//! tell what in a program is Solid's: which imports come from Solid, which
+//! functions are components and which are custom hooks. Tools that want to
+//! agree with the linter (codemods, editor integrations) can use them
+//! directly:
+//!
+//! ```
+//! use solid_linter::utils::{is_custom_hook, is_pascal_case, is_solid_source};
+//!
+//! assert!(is_solid_source("solid-js/store"));
+//! assert!(is_pascal_case("Counter"));
+//! assert!(is_custom_hook("useCounter") && !is_custom_hook("user"));
+//! ```
 
 use oxc_ast::ast::{
-    JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXChild,
-    JSXElementName, JSXMemberExpressionObject, JSXOpeningElement,
+    BindingIdentifier, Expression, Function, FunctionBody, ImportDeclarationSpecifier,
+    JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXChild, JSXElementName,
+    JSXExpressionContainer, JSXMemberExpressionObject, JSXOpeningElement, ModuleExportName,
+    Program, Statement,
 };
 use oxc_span::Span;
 
+use crate::settings::LintSettings;
+
 /// Check if an element name is a DOM element (lowercase)
 pub fn is_dom_element(name: &str) -> bool {
     name.chars().next().is_some_and(|c| c.is_lowercase())
@@ -22,6 +40,145 @@ pub fn is_component(opening: &JSXOpeningElement) -> bool {
     }
 }
 
+/// Check if a name follows the component naming convention (starts with an
+/// uppercase letter)
+pub fn is_pascal_case(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_uppercase())
+}
+
+/// Check if a name follows the custom hook naming convention: `use` or
+/// `create` followed by an uppercase letter (`useTheme`, `createCounter`)
+pub fn is_custom_hook(name: &str) -> bool {
+    ["use", "create"].iter().any(|prefix| {
+        name.strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()))
+    })
+}
+
+/// Modules Solid's components and primitives are imported from
+pub const SOLID_SOURCES: &[&str] = &["solid-js", "solid-js/web", "solid-js/store"];
+
+/// Check if a module is `solid-js` or one of its subpaths
+pub fn is_solid_source(source: &str) -> bool {
+    source
+        .strip_prefix("solid-js")
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// An import binding from Solid or one of the modules in [`LintSettings`]
+#[derive(Debug, Clone, Copy)]
+pub struct SolidImport<'a> {
+    /// The local binding
+    pub local: &'a BindingIdentifier<'a>,
+    /// Exported name, `default` for default imports and `None` for namespace
+    /// imports
+    pub imported: Option<&'a str>,
+    /// Module imported from
+    pub source: &'a str,
+    /// Imported from Solid or a primitive module
+    pub is_primitive: bool,
+    /// Imported from a component module
+    pub is_component: bool,
+}
+
+/// Collect the bindings `program` imports from Solid and the modules in
+/// `settings`
+pub fn solid_imports<'a>(
+    program: &'a Program<'a>,
+    settings: &LintSettings,
+) -> Vec<SolidImport<'a>> {
+    let mut imports = Vec::new();
+    for stmt in &program.body {
+        let Statement::ImportDeclaration(import) = stmt else {
+            continue;
+        };
+        let source = import.source.value.as_str();
+        let is_primitive = is_solid_source(source) || settings.is_primitive_module(source);
+        let is_component = settings.is_component_module(source);
+        if !is_primitive && !is_component {
+            continue;
+        }
+        for spec in import.specifiers.iter().flatten() {
+            let (local, imported) = match spec {
+                ImportDeclarationSpecifier::ImportSpecifier(named) => {
+                    let imported = match &named.imported {
+                        ModuleExportName::IdentifierName(name) => name.name.as_str(),
+                        ModuleExportName::IdentifierReference(name) => name.name.as_str(),
+                        ModuleExportName::StringLiteral(name) => name.value.as_str(),
+                    };
+                    (&named.local, Some(imported))
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(default) => {
+                    (&default.local, Some("default"))
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(ns) => (&ns.local, None),
+            };
+            imports.push(SolidImport {
+                local,
+                imported,
+                source,
+                is_primitive,
+                is_component,
+            });
+        }
+    }
+    imports
+}
+
+/// Check if a function body returns or evaluates to JSX
+pub fn returns_jsx(body: &FunctionBody) -> bool {
+    body.statements.iter().any(statement_has_jsx)
+}
+
+fn statement_has_jsx(stmt: &Statement) -> bool {
+    match stmt {
+        Statement::ExpressionStatement(expr_stmt) => is_jsx_expression(&expr_stmt.expression),
+        Statement::ReturnStatement(ret) => ret.argument.as_ref().is_some_and(is_jsx_expression),
+        Statement::BlockStatement(block) => block.body.iter().any(statement_has_jsx),
+        Statement::IfStatement(if_stmt) => {
+            statement_has_jsx(&if_stmt.consequent)
+                || if_stmt.alternate.as_ref().is_some_and(statement_has_jsx)
+        }
+        _ => false,
+    }
+}
+
+/// Check if an expression evaluates to JSX: an element or fragment, possibly
+/// behind a conditional, a logical operator, an arrow function or TypeScript
+/// type syntax
+pub fn is_jsx_expression(expr: &Expression) -> bool {
+    match expr.get_inner_expression() {
+        Expression::JSXElement(_) | Expression::JSXFragment(_) => true,
+        Expression::ConditionalExpression(cond) => {
+            is_jsx_expression(&cond.consequent) || is_jsx_expression(&cond.alternate)
+        }
+        Expression::LogicalExpression(logical) => {
+            is_jsx_expression(&logical.left) || is_jsx_expression(&logical.right)
+        }
+        Expression::ArrowFunctionExpression(arrow) => returns_jsx(&arrow.body),
+        _ => false,
+    }
+}
+
+/// The expression of a JSX expression container, looking through
+/// parentheses and TypeScript type syntax (`as`, `satisfies`, `!`)
+pub fn container_expression<'a, 'b>(
+    container: &'b JSXExpressionContainer<'a>,
+) -> Option<&'b Expression<'a>> {
+    container
+        .expression
+        .as_expression()
+        .map(Expression::get_inner_expression)
+}
+
+/// Check if a function declaration or expression is probably a component:
+/// it has a PascalCase name or returns JSX
+pub fn is_probably_component(func: &Function) -> bool {
+    func.body.is_some()
+        && (func.id.as_ref().is_some_and(|id| is_pascal_case(&id.name))
+            || func.body.as_ref().is_some_and(|body| returns_jsx(body)))
+}
+
 /// Void HTML elements that don't have closing tags
 pub const VOID_ELEMENTS: &[&str] = &[
     "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
@@ -197,3 +354,120 @@ pub fn get_solid_prop_replacement(react_prop: &str) -> Option<&'static str> {
         .find(|(from, _)| *from == react_prop)
         .map(|(_, to)| *to)
 }
+
+/// Convert a byte offset into a 1-based (line, column) pair.
+///
+/// Columns are counted in characters, not bytes.
+pub fn offset_to_line_col(source_text: &str, offset: u32) -> (usize, usize) {
+    let mut offset = (offset as usize).min(source_text.len());
+    while !source_text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &source_text[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    use super::*;
+
+    #[test]
+    fn test_naming_conventions() {
+        assert!(is_pascal_case("Counter"));
+        assert!(!is_pascal_case("counter"));
+        assert!(is_custom_hook("useTheme"));
+        assert!(is_custom_hook("createCounter"));
+        assert!(!is_custom_hook("user"));
+        assert!(!is_custom_hook("created"));
+        assert!(!is_custom_hook("use"));
+    }
+
+    #[test]
+    fn test_solid_sources() {
+        assert!(is_solid_source("solid-js"));
+        assert!(is_solid_source("solid-js/store"));
+        assert!(is_solid_source("solid-js/html"));
+        assert!(!is_solid_source("solid-jsx"));
+        assert!(!is_solid_source("@solid-primitives/memo"));
+    }
+
+    #[test]
+    fn test_solid_imports() {
+        let allocator = Allocator::default();
+        let source = r#"
+            import { createSignal, Show as When } from "solid-js";
+            import * as store from "solid-js/store";
+            import { Dialog } from "@kobalte/core/dialog";
+            import { debounce } from "@solid-primitives/scheduled";
+            import { format } from "date-fns";
+        "#;
+        let program = Parser::new(&allocator, source, SourceType::tsx())
+            .parse()
+            .program;
+        let settings = LintSettings::default()
+            .with_component_modules(vec!["@kobalte/core".to_string()])
+            .with_primitive_modules(vec!["@solid-primitives/scheduled".to_string()]);
+        let imports = solid_imports(&program, &settings);
+        let summary: Vec<_> = imports
+            .iter()
+            .map(|import| {
+                (
+                    import.local.name.as_str(),
+                    import.imported,
+                    import.is_primitive,
+                    import.is_component,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("createSignal", Some("createSignal"), true, false),
+                ("When", Some("Show"), true, false),
+                ("store", None, true, false),
+                ("Dialog", Some("Dialog"), false, true),
+                ("debounce", Some("debounce"), true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_probable_components() {
+        let allocator = Allocator::default();
+        let source = r#"
+            function Counter() { return null; }
+            function list(props) { if (props.empty) return <p />; return <ul />; }
+            function total(items) { return items.length; }
+            const row = () => (props) => <tr />;
+        "#;
+        let program = Parser::new(&allocator, source, SourceType::tsx())
+            .parse()
+            .program;
+        let components: Vec<_> = program
+            .body
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::FunctionDeclaration(func) => Some(func),
+                _ => None,
+            })
+            .filter(|func| is_probably_component(func))
+            .map(|func| func.id.as_ref().unwrap().name.as_str())
+            .collect();
+        assert_eq!(components, ["Counter", "list"]);
+
+        let Statement::VariableDeclaration(decl) = &program.body[3] else {
+            panic!("expected a declaration");
+        };
+        let Some(Expression::ArrowFunctionExpression(arrow)) = &decl.declarations[0].init else {
+            panic!("expected an arrow function");
+        };
+        assert!(returns_jsx(&arrow.body));
+    }
+}
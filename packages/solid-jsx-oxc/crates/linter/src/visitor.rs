@@ -3,20 +3,27 @@
 //! This module provides a `LintRunner` that traverses the AST once and runs
 //! all enabled rules during the traversal, collecting diagnostics efficiently.
 
-use oxc_ast::ast::{JSXElement, JSXFragment, JSXOpeningElement, Program};
+use oxc_ast::ast::{
+    CallExpression, ImportDeclaration, JSXElement, JSXFragment, JSXOpeningElement,
+    JSXSpreadAttribute, NewExpression, Program,
+};
 use oxc_ast_visit::{walk, Visit};
 use oxc_semantic::Semantic;
 use oxc_span::SourceType;
 
 use crate::diagnostic::Diagnostic;
 use crate::rules::{
-    JsxNoDuplicateProps, JsxNoScriptUrl, JsxUsesVars, NoInnerhtml, NoReactSpecificProps,
-    NoUnknownNamespaces, PreferClasslist, PreferFor, PreferShow, SelfClosingComp, StyleProp,
+    A11yRulesConfig, AltText, AnchorHasContent, AriaProps, AriaRole, Imports, JsxNoDuplicateProps, JsxNoScriptUrl, JsxUsesVars, NoInnerhtml, NoReactSpecificProps,
+    NoAutofocus, NoUnknownNamespaces, PreferClasslist, PreferFor, PreferShow, SelfClosingComp,
+    NoProxyApis, StyleProp,
 };
+use crate::timing::{self, RuleTimings};
+use crate::RuleMeta;
 
 /// Configuration for which rules are enabled
 #[derive(Debug, Clone)]
 pub struct RulesConfig {
+    pub imports: bool,
     pub jsx_no_duplicate_props: Option<JsxNoDuplicateProps>,
     pub jsx_no_script_url: Option<JsxNoScriptUrl>,
     pub jsx_uses_vars: bool,
@@ -28,11 +35,16 @@ pub struct RulesConfig {
     pub prefer_show: bool,
     pub self_closing_comp: Option<SelfClosingComp>,
     pub style_prop: Option<StyleProp>,
+    /// Off by default, for targets without Proxy support only
+    pub no_proxy_apis: Option<NoProxyApis>,
+    /// Accessibility rules, all disabled unless enabled explicitly
+    pub a11y: A11yRulesConfig,
 }
 
 impl Default for RulesConfig {
     fn default() -> Self {
         Self {
+            imports: true,
             jsx_no_duplicate_props: Some(JsxNoDuplicateProps::new()),
             jsx_no_script_url: Some(JsxNoScriptUrl::new()),
             jsx_uses_vars: true,
@@ -44,6 +56,8 @@ impl Default for RulesConfig {
             prefer_show: true,
             self_closing_comp: Some(SelfClosingComp::new()),
             style_prop: Some(StyleProp::new()),
+            no_proxy_apis: None,
+            a11y: A11yRulesConfig::none(),
         }
     }
 }
@@ -55,6 +69,7 @@ impl RulesConfig {
 
     pub fn none() -> Self {
         Self {
+            imports: false,
             jsx_no_duplicate_props: None,
             jsx_no_script_url: None,
             jsx_uses_vars: false,
@@ -66,9 +81,16 @@ impl RulesConfig {
             prefer_show: false,
             self_closing_comp: None,
             style_prop: None,
+            no_proxy_apis: None,
+            a11y: A11yRulesConfig::none(),
         }
     }
 
+    pub fn with_imports(mut self, enabled: bool) -> Self {
+        self.imports = enabled;
+        self
+    }
+
     pub fn with_jsx_no_duplicate_props(mut self, rule: JsxNoDuplicateProps) -> Self {
         self.jsx_no_duplicate_props = Some(rule);
         self
@@ -123,6 +145,16 @@ impl RulesConfig {
         self.style_prop = Some(rule);
         self
     }
+
+    pub fn with_no_proxy_apis(mut self, rule: NoProxyApis) -> Self {
+        self.no_proxy_apis = Some(rule);
+        self
+    }
+
+    pub fn with_a11y(mut self, a11y: A11yRulesConfig) -> Self {
+        self.a11y = a11y;
+        self
+    }
 }
 
 /// Context for lint execution
@@ -165,6 +197,8 @@ pub struct LintRunner<'a> {
     config: RulesConfig,
     diagnostics: Vec<Diagnostic>,
     used_vars: Vec<String>,
+    /// Per-rule timings, collected only when timing is enabled
+    timings: Option<RuleTimings>,
 }
 
 impl<'a> LintRunner<'a> {
@@ -174,15 +208,35 @@ impl<'a> LintRunner<'a> {
             config,
             diagnostics: Vec::new(),
             used_vars: Vec::new(),
+            timings: None,
         }
     }
 
+    /// Measure how long each rule takes (reported in `LintResult::timings`)
+    pub fn with_timing(mut self, enabled: bool) -> Self {
+        self.timings = enabled.then(RuleTimings::new);
+        self
+    }
+
     /// Run all enabled rules on the given program
     pub fn run(mut self, program: &Program<'a>) -> LintResult {
+        self.check_program(program);
         self.visit_program(program);
         LintResult {
             diagnostics: self.diagnostics,
             used_vars: self.used_vars,
+            timings: self.timings,
+        }
+    }
+
+    /// Run the rules that look at the whole program at once
+    fn check_program(&mut self, program: &Program<'a>) {
+        // imports: Solid sources imported by several declarations
+        if self.config.imports {
+            let start = timing::start(&self.timings, Imports::NAME);
+            self.diagnostics
+                .extend(Imports::new().check_duplicates(program, self.ctx.source_text()));
+            timing::record(&mut self.timings, start);
         }
     }
 
@@ -194,31 +248,56 @@ impl<'a> LintRunner<'a> {
 
         // jsx-no-duplicate-props
         if let Some(rule) = &self.config.jsx_no_duplicate_props {
+            let start = timing::start(&self.timings, JsxNoDuplicateProps::NAME);
             self.diagnostics.extend(rule.check(opening, children));
+            timing::record(&mut self.timings, start);
         }
 
         // no-innerhtml (needs full element for children check)
         if let Some(rule) = &self.config.no_innerhtml {
+            let start = timing::start(&self.timings, NoInnerhtml::NAME);
             self.diagnostics.extend(rule.check(element));
+            timing::record(&mut self.timings, start);
         }
 
         // self-closing-comp
         if let Some(rule) = &self.config.self_closing_comp {
+            let start = timing::start(&self.timings, SelfClosingComp::NAME);
             self.diagnostics
                 .extend(rule.check(opening, children, closing_span));
+            timing::record(&mut self.timings, start);
         }
 
         // prefer-for: check children for map() calls
         if self.config.prefer_for {
+            let start = timing::start(&self.timings, PreferFor::NAME);
             let rule = PreferFor::new();
             self.diagnostics.extend(rule.check_element_children(element));
+            timing::record(&mut self.timings, start);
         }
 
         // prefer-show: check children for conditionals
         if self.config.prefer_show {
+            let start = timing::start(&self.timings, PreferShow::NAME);
             let rule = PreferShow::new();
             self.diagnostics
                 .extend(rule.check_element_children(element, self.ctx.source_text()));
+            timing::record(&mut self.timings, start);
+        }
+
+        // a11y/alt-text
+        if self.config.a11y.alt_text {
+            let start = timing::start(&self.timings, AltText::NAME);
+            self.diagnostics.extend(AltText::new().check(opening, children));
+            timing::record(&mut self.timings, start);
+        }
+
+        // a11y/anchor-has-content
+        if self.config.a11y.anchor_has_content {
+            let start = timing::start(&self.timings, AnchorHasContent::NAME);
+            self.diagnostics
+                .extend(AnchorHasContent::new().check(opening, children));
+            timing::record(&mut self.timings, start);
         }
     }
 
@@ -226,35 +305,68 @@ impl<'a> LintRunner<'a> {
     fn check_jsx_opening_element(&mut self, opening: &JSXOpeningElement<'a>) {
         // jsx-no-script-url
         if let Some(rule) = &self.config.jsx_no_script_url {
+            let start = timing::start(&self.timings, JsxNoScriptUrl::NAME);
             self.diagnostics.extend(rule.check(opening));
+            timing::record(&mut self.timings, start);
         }
 
         // no-react-specific-props
         if self.config.no_react_specific_props {
+            let start = timing::start(&self.timings, NoReactSpecificProps::NAME);
             let rule = NoReactSpecificProps::new();
             self.diagnostics.extend(rule.check(opening));
+            timing::record(&mut self.timings, start);
         }
 
         // no-unknown-namespaces
         if let Some(rule) = &self.config.no_unknown_namespaces {
+            let start = timing::start(&self.timings, NoUnknownNamespaces::NAME);
             self.diagnostics.extend(rule.check(opening));
+            timing::record(&mut self.timings, start);
         }
 
         // style-prop
         if let Some(rule) = &self.config.style_prop {
+            let start = timing::start(&self.timings, StyleProp::NAME);
             self.diagnostics.extend(rule.check(opening));
+            timing::record(&mut self.timings, start);
         }
 
         // prefer-classlist
         if self.config.prefer_classlist {
+            let start = timing::start(&self.timings, PreferClasslist::NAME);
             let rule = PreferClasslist::new();
             self.diagnostics.extend(rule.check(opening));
+            timing::record(&mut self.timings, start);
+        }
+
+        // a11y/aria-props
+        if self.config.a11y.aria_props {
+            let start = timing::start(&self.timings, AriaProps::NAME);
+            self.diagnostics.extend(AriaProps::new().check(opening));
+            timing::record(&mut self.timings, start);
+        }
+
+        // a11y/aria-role
+        if self.config.a11y.aria_role {
+            let start = timing::start(&self.timings, AriaRole::NAME);
+            self.diagnostics.extend(AriaRole::new().check(opening));
+            timing::record(&mut self.timings, start);
+        }
+
+        // a11y/no-autofocus
+        if self.config.a11y.no_autofocus {
+            let start = timing::start(&self.timings, NoAutofocus::NAME);
+            self.diagnostics.extend(NoAutofocus::new().check(opening));
+            timing::record(&mut self.timings, start);
         }
 
         // jsx-uses-vars (collects used vars, doesn't produce diagnostics)
         if self.config.jsx_uses_vars {
+            let start = timing::start(&self.timings, JsxUsesVars::NAME);
             let rule = JsxUsesVars::new();
             self.used_vars.extend(rule.collect_used_vars(opening));
+            timing::record(&mut self.timings, start);
         }
     }
 
@@ -262,16 +374,20 @@ impl<'a> LintRunner<'a> {
     fn check_jsx_fragment(&mut self, fragment: &JSXFragment<'a>) {
         // prefer-for: check children for map() calls
         if self.config.prefer_for {
+            let start = timing::start(&self.timings, PreferFor::NAME);
             let rule = PreferFor::new();
             self.diagnostics
                 .extend(rule.check_fragment_children(fragment));
+            timing::record(&mut self.timings, start);
         }
 
         // prefer-show: check children for conditionals
         if self.config.prefer_show {
+            let start = timing::start(&self.timings, PreferShow::NAME);
             let rule = PreferShow::new();
             self.diagnostics
                 .extend(rule.check_fragment_children(fragment, self.ctx.source_text()));
+            timing::record(&mut self.timings, start);
         }
     }
 }
@@ -291,13 +407,62 @@ impl<'a> Visit<'a> for LintRunner<'a> {
         self.check_jsx_fragment(fragment);
         walk::walk_jsx_fragment(self, fragment);
     }
+
+    fn visit_import_declaration(&mut self, import: &ImportDeclaration<'a>) {
+        // imports
+        if self.config.imports {
+            let start = timing::start(&self.timings, Imports::NAME);
+            self.diagnostics.extend(Imports::new().check(import));
+            timing::record(&mut self.timings, start);
+        }
+
+        // no-proxy-apis
+        if let Some(rule) = &self.config.no_proxy_apis {
+            let start = timing::start(&self.timings, NoProxyApis::NAME);
+            self.diagnostics.extend(rule.check_import(import));
+            timing::record(&mut self.timings, start);
+        }
+        walk::walk_import_declaration(self, import);
+    }
+
+    fn visit_jsx_spread_attribute(&mut self, spread: &JSXSpreadAttribute<'a>) {
+        // no-proxy-apis
+        if let Some(rule) = &self.config.no_proxy_apis {
+            let start = timing::start(&self.timings, NoProxyApis::NAME);
+            self.diagnostics.extend(rule.check_spread(spread));
+            timing::record(&mut self.timings, start);
+        }
+        walk::walk_jsx_spread_attribute(self, spread);
+    }
+
+    fn visit_new_expression(&mut self, new_expr: &NewExpression<'a>) {
+        // no-proxy-apis
+        if let Some(rule) = &self.config.no_proxy_apis {
+            let start = timing::start(&self.timings, NoProxyApis::NAME);
+            self.diagnostics.extend(rule.check_new_expression(new_expr));
+            timing::record(&mut self.timings, start);
+        }
+        walk::walk_new_expression(self, new_expr);
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        // no-proxy-apis
+        if let Some(rule) = &self.config.no_proxy_apis {
+            let start = timing::start(&self.timings, NoProxyApis::NAME);
+            self.diagnostics.extend(rule.check_call(call));
+            timing::record(&mut self.timings, start);
+        }
+        walk::walk_call_expression(self, call);
+    }
 }
 
 /// Result of running the linter
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LintResult {
     pub diagnostics: Vec<Diagnostic>,
     pub used_vars: Vec<String>,
+    /// Per-rule timings, present when the runner was created with timing enabled
+    pub timings: Option<RuleTimings>,
 }
 
 impl LintResult {
@@ -324,11 +489,25 @@ impl LintResult {
             .filter(|d| matches!(d.severity, crate::DiagnosticSeverity::Warning))
             .count()
     }
+
+    /// Number of info and hint diagnostics
+    pub fn note_count(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| {
+                matches!(
+                    d.severity,
+                    crate::DiagnosticSeverity::Info | crate::DiagnosticSeverity::Hint
+                )
+            })
+            .count()
+    }
 }
 
-/// Convenience function to lint a program with default configuration
+/// Convenience function to lint a program with default configuration, using
+/// the source type it was parsed with
 pub fn lint<'a>(source_text: &'a str, program: &Program<'a>) -> LintResult {
-    let ctx = VisitorLintContext::new(source_text, SourceType::jsx());
+    let ctx = VisitorLintContext::new(source_text, program.source_type);
     let config = RulesConfig::default();
     LintRunner::new(ctx, config).run(program)
 }
@@ -413,6 +592,17 @@ mod tests {
         assert!(result.diagnostics[0].message.contains("className"));
     }
 
+    #[test]
+    fn test_lint_a11y_is_opt_in() {
+        let source = r#"<img src="a.png" />"#;
+        assert!(parse_and_lint(source).diagnostics.is_empty());
+
+        let config = RulesConfig::default().with_a11y(A11yRulesConfig::all());
+        let result = parse_and_lint_with_config(source, config);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].rule, AltText::NAME);
+    }
+
     #[test]
     fn test_lint_nested_elements() {
         let result = parse_and_lint(
@@ -437,4 +627,25 @@ mod tests {
         assert_eq!(result.error_count(), 0);
         assert!(result.warning_count() > 0);
     }
+
+    #[test]
+    fn test_lint_with_timing() {
+        let allocator = Allocator::default();
+        let source = r#"<div className="foo" style={{ fontSize: "12px" }} />"#;
+        let ret = Parser::new(&allocator, source, SourceType::jsx()).parse();
+        let ctx = VisitorLintContext::new(source, SourceType::jsx());
+        let result = LintRunner::new(ctx, RulesConfig::default())
+            .with_timing(true)
+            .run(&ret.program);
+
+        let timings = result.timings.expect("timings should be collected");
+        assert_eq!(timings.get("style-prop").unwrap().calls, 1);
+        assert!(timings.get("no-react-specific-props").is_some());
+    }
+
+    #[test]
+    fn test_lint_without_timing() {
+        let result = parse_and_lint(r#"<div />"#);
+        assert!(result.timings.is_none());
+    }
 }
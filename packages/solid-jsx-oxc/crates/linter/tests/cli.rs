@@ -0,0 +1,82 @@
+//! Tests running the `solid-lint` binary
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// An empty directory under the system temp dir, unique to this test run
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("solid-lint-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Run `solid-lint` with `dir` as the working directory
+fn solid_lint(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_solid-lint"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("solid-lint should run")
+}
+
+#[test]
+fn test_fix_merges_duplicate_solid_imports() {
+    let dir = scratch_dir("fix-imports");
+    let file = dir.join("App.jsx");
+    fs::write(
+        &file,
+        r#"import { createSignal } from "solid-js";
+import { Show } from "solid-js";
+
+const [count] = createSignal(0);
+export const App = () => <Show when={count()}><p /></Show>;
+"#,
+    )
+    .unwrap();
+
+    let output = solid_lint(&dir, &["--fix", "App.jsx"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        r#"import { createSignal, Show } from "solid-js";
+
+const [count] = createSignal(0);
+export const App = () => <Show when={count()}><p /></Show>;
+"#
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_fix_dry_run_diff_paths() {
+    let dir = scratch_dir("dry-run-paths");
+    let file = dir.join("App.jsx");
+    fs::write(&file, "export const App = () => <div className=\"a\" />;\n").unwrap();
+
+    // Absolute paths under the working directory become relative
+    let output = solid_lint(&dir, &["--fix-dry-run", file.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with("--- a/App.jsx\n+++ b/App.jsx\n"),
+        "{stdout}"
+    );
+
+    // Paths outside it are kept as they are, without prefixes
+    let elsewhere = scratch_dir("dry-run-paths-cwd");
+    let output = solid_lint(&elsewhere, &["--fix-dry-run", file.to_str().unwrap()]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let path = file.display();
+    assert!(
+        stdout.starts_with(&format!("--- {path}\n+++ {path}\n")),
+        "{stdout}"
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+    fs::remove_dir_all(&elsewhere).unwrap();
+}
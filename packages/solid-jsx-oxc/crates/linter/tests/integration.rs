@@ -6,9 +6,13 @@ use oxc_parser::Parser;
 use oxc_span::SourceType;
 
 use solid_linter::rules::{
-    JsxNoDuplicateProps, NoInnerhtml, NoReactDeps, NoReactSpecificProps, NoUnknownNamespaces,
-    PreferClasslist, SelfClosingComp, StyleProp,
+    AltText, AnchorHasContent, AriaProps, AriaRole, JsxNoDuplicateProps, JsxNoScriptUrl,
+    NoAutofocus, NoInnerhtml,
+    NoProxyApis, NoReactDeps, NoReactSpecificProps, NoUnknownNamespaces, PreferClasslist,
+    SelfClosingComp, StyleProp,
 };
+use solid_linter::rules::no_proxy_apis::NoProxyApisConfig;
+use solid_linter::rules::self_closing_comp::{HtmlSelfClosingOption, SelfClosingCompConfig};
 
 fn parse_jsx_element<'a>(allocator: &'a Allocator, source: &'a str) -> Option<oxc_ast::ast::Program<'a>> {
     let source_type = SourceType::jsx();
@@ -90,7 +94,7 @@ fn test_no_react_specific_props_class_name() {
     
     assert_eq!(diagnostics.len(), 1, "should have one diagnostic");
     assert!(diagnostics[0].message.contains("class"));
-    assert!(diagnostics[0].fixes.len() > 0, "should have a fix");
+    assert!(!diagnostics[0].fixes.is_empty(), "should have a fix");
 }
 
 #[test]
@@ -154,6 +158,80 @@ fn test_self_closing_comp_with_children() {
     assert!(diagnostics.is_empty(), "should have no diagnostics - has children");
 }
 
+fn self_closing_diagnostics(source: &str, rule: &SelfClosingComp) -> Vec<solid_linter::Diagnostic> {
+    let allocator = Allocator::default();
+    let program = parse_jsx_element(&allocator, source).expect("should parse");
+    let element = find_jsx_element(&program).expect("should find element");
+    let closing_span = element.closing_element.as_ref().map(|c| c.span);
+    rule.check(&element.opening_element, &element.children, closing_span)
+}
+
+#[test]
+fn test_self_closing_comp_void_with_closing_tag() {
+    let source = r#"<br class="a"></br>"#;
+    let none = SelfClosingComp::with_config(SelfClosingCompConfig {
+        html: HtmlSelfClosingOption::None,
+        ..Default::default()
+    });
+    for rule in [SelfClosingComp::new(), none] {
+        let diagnostics = self_closing_diagnostics(source, &rule);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("void element"));
+        let fixed = solid_linter::apply_fixes(source, &diagnostics);
+        assert_eq!(fixed.output, r#"<br class="a" />"#);
+    }
+}
+
+#[test]
+fn test_self_closing_comp_void_with_children() {
+    let diagnostics = self_closing_diagnostics("<input>text</input>", &SelfClosingComp::new());
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("cannot have children"));
+    assert!(diagnostics[0].fixes.is_empty());
+}
+
+#[test]
+fn test_self_closing_comp_void_never_gets_closing_tag() {
+    let rule = SelfClosingComp::with_config(SelfClosingCompConfig {
+        html: HtmlSelfClosingOption::None,
+        ..Default::default()
+    });
+    assert!(self_closing_diagnostics("<img src=\"a.png\" />", &rule).is_empty());
+    assert_eq!(self_closing_diagnostics("<div />", &rule).len(), 1);
+}
+
+// ============ jsx-no-script-url tests ============
+
+fn script_url_count(source: &str) -> usize {
+    let allocator = Allocator::default();
+    let program = parse_jsx_element(&allocator, source).expect("should parse");
+    let element = find_jsx_element(&program).expect("should find element");
+    JsxNoScriptUrl::new().check(&element.opening_element).len()
+}
+
+#[test]
+fn test_jsx_no_script_url_literals() {
+    assert_eq!(script_url_count(r#"<a href="javascript:void(0)" />"#), 1);
+    assert_eq!(script_url_count(r#"<a href={" JavaScript:alert(1)"} />"#), 1);
+    assert_eq!(script_url_count(r#"<a href="/javascript:docs" />"#), 0);
+}
+
+#[test]
+fn test_jsx_no_script_url_template_literals() {
+    assert_eq!(script_url_count(r#"<a href={`javascript:${code}`} />"#), 1);
+    assert_eq!(script_url_count(r#"<iframe src={`java\tscript:alert(1)`} />"#), 1);
+    assert_eq!(script_url_count(r#"<a href={`${base}/javascript:`} />"#), 0);
+    assert_eq!(script_url_count(r#"<a href={`https://${host}`} />"#), 0);
+}
+
+#[test]
+fn test_jsx_no_script_url_concatenation() {
+    assert_eq!(script_url_count(r#"<a href={"java" + "script:" + code} />"#), 1);
+    assert_eq!(script_url_count(r#"<form action={("javascript" + `:`) + run()} />"#), 1);
+    assert_eq!(script_url_count(r#"<a href={"java" + scheme + ":"} />"#), 0);
+    assert_eq!(script_url_count(r#"<a href={url + "javascript:"} />"#), 0);
+}
+
 // ============ no-innerhtml tests ============
 
 #[test]
@@ -454,3 +532,145 @@ fn test_no_react_deps_valid_other_function() {
     
     assert!(diagnostics.is_empty(), "should not warn about other functions");
 }
+
+// ============ a11y tests ============
+
+#[test]
+fn test_a11y_alt_text() {
+    let cases = [
+        (r#"<img src="a.png" />"#, 1),
+        (r#"<img src="a.png" alt={undefined} />"#, 1),
+        (r#"<img src="a.png" role="presentation" />"#, 1),
+        (r#"<img src="a.png" alt="" />"#, 0),
+        (r#"<img src="a.png" alt={props.alt} />"#, 0),
+        (r#"<img src="a.png" aria-label="Logo" />"#, 0),
+        (r#"<img {...props} />"#, 0),
+        (r#"<area href="/" />"#, 1),
+        (r#"<input type="image" src="go.png" />"#, 1),
+        (r#"<input type="text" />"#, 0),
+        (r#"<object data="movie.swf" />"#, 1),
+        (r#"<object data="movie.swf">Movie</object>"#, 0),
+    ];
+    for (source, expected) in cases {
+        let allocator = Allocator::default();
+        let program = parse_jsx_element(&allocator, source).expect("should parse");
+        let element = find_jsx_element(&program).expect("should find element");
+
+        let diagnostics = AltText::new().check(&element.opening_element, &element.children);
+        assert_eq!(diagnostics.len(), expected, "{}", source);
+    }
+}
+
+#[test]
+fn test_a11y_anchor_has_content() {
+    let cases = [
+        (r#"<a href="/" />"#, 1),
+        (r#"<a href="/"><Icon aria-hidden /></a>"#, 1),
+        (r#"<a href="/">Home</a>"#, 0),
+        (r#"<a href="/">{props.label}</a>"#, 0),
+        (r#"<a href="/" aria-label="Home"><Icon /></a>"#, 0),
+        (r#"<a href="/" innerHTML={html} />"#, 0),
+        (r#"<a {...props} />"#, 0),
+    ];
+    for (source, expected) in cases {
+        let allocator = Allocator::default();
+        let program = parse_jsx_element(&allocator, source).expect("should parse");
+        let element = find_jsx_element(&program).expect("should find element");
+
+        let diagnostics =
+            AnchorHasContent::new().check(&element.opening_element, &element.children);
+        assert_eq!(diagnostics.len(), expected, "{}", source);
+    }
+}
+
+#[test]
+fn test_a11y_no_autofocus() {
+    let cases = [
+        (r#"<input autofocus />"#, 1),
+        (r#"<input autoFocus={true} />"#, 1),
+        (r#"<input autofocus={false} />"#, 0),
+        (r#"<Input autofocus />"#, 0),
+    ];
+    for (source, expected) in cases {
+        let allocator = Allocator::default();
+        let program = parse_jsx_element(&allocator, source).expect("should parse");
+        let element = find_jsx_element(&program).expect("should find element");
+
+        let diagnostics = NoAutofocus::new().check(&element.opening_element);
+        assert_eq!(diagnostics.len(), expected, "{}", source);
+    }
+}
+
+#[test]
+fn test_a11y_aria_props() {
+    let allocator = Allocator::default();
+    let source = r#"<div aria-label="a" aria-labeledby="b" aria-foo="c" data-x="d" />"#;
+
+    let program = parse_jsx_element(&allocator, source).expect("should parse");
+    let element = find_jsx_element(&program).expect("should find element");
+
+    let diagnostics = AriaProps::new().check(&element.opening_element);
+    assert_eq!(diagnostics.len(), 2);
+    assert!(diagnostics[0].message.contains("aria-labeledby"));
+    assert_eq!(
+        diagnostics[0].help.as_deref(),
+        Some("Did you mean `aria-labelledby`?")
+    );
+    assert!(diagnostics[1].help.is_none());
+}
+
+#[test]
+fn test_a11y_aria_role() {
+    let cases = [
+        (r#"<div role="button" />"#, 0),
+        (r#"<div role="switch checkbox" />"#, 0),
+        (r#"<div role="doc-toc" />"#, 0),
+        (r#"<div role={props.role} />"#, 0),
+        (r#"<Foo role="anything" />"#, 0),
+        (r#"<div role="range" />"#, 1),
+        (r#"<div role="datepicker" />"#, 1),
+        (r#"<div role="" />"#, 1),
+    ];
+    for (source, expected) in cases {
+        let allocator = Allocator::default();
+        let program = parse_jsx_element(&allocator, source).expect("should parse");
+        let element = find_jsx_element(&program).expect("should find element");
+
+        let diagnostics = AriaRole::new().check(&element.opening_element);
+        assert_eq!(diagnostics.len(), expected, "{}", source);
+    }
+}
+
+// ============ no-proxy-apis tests ============
+
+#[test]
+fn test_no_proxy_apis_merge_props_function() {
+    let allocator = Allocator::default();
+    let source = r#"mergeProps(props, () => ({ a: 1 }));"#;
+
+    let program = parse_jsx_element(&allocator, source).expect("should parse");
+    let call = find_call_expression(&program).expect("should find call");
+
+    let diagnostics = NoProxyApis::new().check_call(call);
+    assert_eq!(diagnostics.len(), 2, "identifier and function arguments create Proxies");
+
+    let rule = NoProxyApis::with_config(NoProxyApisConfig {
+        allow_merge_props_functions: true,
+        ..NoProxyApisConfig::default()
+    });
+    assert!(rule.check_call(call).is_empty());
+}
+
+#[test]
+fn test_no_proxy_apis_allow_proxy() {
+    let allocator = Allocator::default();
+    let source = r#"Proxy.revocable(target, handler);"#;
+
+    let program = parse_jsx_element(&allocator, source).expect("should parse");
+    let call = find_call_expression(&program).expect("should find call");
+
+    assert_eq!(NoProxyApis::new().check_call(call).len(), 1);
+
+    let config: NoProxyApisConfig = serde_json::from_str(r#"{"allowProxy": true}"#).unwrap();
+    assert!(NoProxyApis::with_config(config).check_call(call).is_empty());
+}
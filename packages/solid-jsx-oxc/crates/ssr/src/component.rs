@@ -13,7 +13,10 @@ use oxc_ast::AstBuilder;
 use oxc_ast::NONE;
 use oxc_span::SPAN;
 
-use common::{is_dynamic, TransformOptions};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use common::{helper_ident, is_dynamic, Island, TransformOptions};
 
 use crate::ir::{SSRChildTransformer, SSRContext, SSRResult};
 
@@ -112,6 +115,7 @@ fn get_children_ssr<'a, 'b>(
     element: &JSXElement<'a>,
     context: &SSRContext<'a>,
     transform_child: SSRChildTransformer<'a, 'b>,
+    element_only: bool,
 ) -> Expression<'a> {
     let ast = context.ast();
     let mut children: Vec<Expression<'a>> = Vec::new();
@@ -119,12 +123,12 @@ fn get_children_ssr<'a, 'b>(
     for child in &element.children {
         match child {
             JSXChild::Text(text) => {
-                let content = common::expression::trim_whitespace(&text.value);
+                let content = common::expression::decode_jsx_text(&text.value);
                 if !content.is_empty() {
-                    let escaped = common::expression::escape_html(&content, false);
+                    // Text, escaped when the component renders it
                     children.push(ast.expression_string_literal(
                         SPAN,
-                        ast.allocator.alloc_str(&escaped),
+                        ast.allocator.alloc_str(&content),
                         None,
                     ));
                 }
@@ -137,7 +141,17 @@ fn get_children_ssr<'a, 'b>(
             JSXChild::Element(_) | JSXChild::Fragment(_) => {
                 // Transform the child JSX element/fragment
                 if let Some(result) = transform_child(child) {
-                    children.push(result.to_ssr_expression(ast, false));
+                    if element_only {
+                        children.push(result.to_element_expression(ast, false));
+                        continue;
+                    }
+                    if result.uses_ssr_tag(true) {
+                        context.register_helper("ssr");
+                    }
+                    if result.uses_escape() {
+                        context.register_helper("escape");
+                    }
+                    children.push(result.to_html_expression(ast, false));
                 }
             }
             JSXChild::Spread(spread) => {
@@ -164,7 +178,69 @@ fn get_children_ssr<'a, 'b>(
 /// Transform a component for SSR
 pub fn transform_component<'a, 'b>(
     element: &JSXElement<'a>,
-    _tag_name: &str,
+    tag_name: &str,
+    context: &SSRContext<'a>,
+    options: &TransformOptions<'a>,
+    transform_child: SSRChildTransformer<'a, 'b>,
+) -> SSRResult<'a> {
+    // Nothing under <NoHydration> is hydrated, so its subtree renders
+    // without hydration keys and markers
+    if tag_name == "NoHydration" {
+        return context.without_hydration(|| {
+            build_component(element, tag_name, context, options, transform_child)
+        });
+    }
+    if let Some((source, export)) = context.island_import(tag_name) {
+        let island = register_island(element, tag_name, source, export, options);
+        let component = context.within_island(|| {
+            build_component(element, tag_name, context, options, transform_child)
+        });
+        return island_boundary(&island, component);
+    }
+    build_component(element, tag_name, context, options, transform_child)
+}
+
+/// Add `component` to the island manifest, keyed by a hash of the filename
+/// and its position in the module
+fn register_island<'a>(
+    element: &JSXElement<'a>,
+    component: &str,
+    source: &str,
+    export: &str,
+    options: &TransformOptions<'a>,
+) -> Island {
+    let mut hasher = DefaultHasher::new();
+    options.filename.hash(&mut hasher);
+    let mut manifest = options.island_manifest.borrow_mut();
+    let island = Island {
+        id: format!("{:08x}:{}", hasher.finish() as u32, manifest.len()),
+        component: component.to_string(),
+        source: source.to_string(),
+        export: export.to_string(),
+        span: element.span,
+    };
+    manifest.push(island.clone());
+    island
+}
+
+/// Wrap the output of an island component in its boundary markers. The
+/// markers delimit what the island hydrates, so the component goes without
+/// the hydration markers of an inserted value.
+fn island_boundary<'a>(island: &Island, mut component: SSRResult<'a>) -> SSRResult<'a> {
+    let mut result = SSRResult::new();
+    result.span = component.span;
+    result.push_static(&format!("<!--island:{}-->", island.id));
+    for value in &mut component.template_values {
+        value.needs_hydration_marker = false;
+    }
+    result.merge(component);
+    result.push_static("<!--/island-->");
+    result
+}
+
+fn build_component<'a, 'b>(
+    element: &JSXElement<'a>,
+    tag_name: &str,
     context: &SSRContext<'a>,
     options: &TransformOptions<'a>,
     transform_child: SSRChildTransformer<'a, 'b>,
@@ -174,27 +250,24 @@ pub fn transform_component<'a, 'b>(
     result.span = element.span;
 
     context.register_helper("createComponent");
-    context.register_helper("escape");
 
     // Build props
-    let props = build_props(element, context, options, transform_child);
+    let props = build_props(element, tag_name, context, options, transform_child);
 
     // Generate createComponent call - will be escaped by parent
     let component = jsx_element_name_to_expression(ast, &element.opening_element.name);
-    let callee = ast.expression_identifier(SPAN, "createComponent");
+    let callee = helper_ident(ast, "createComponent");
     let mut args = ast.vec_with_capacity(2);
     args.push(Argument::from(component));
     args.push(Argument::from(props));
     let call = ast.expression_call(
-        SPAN,
+        element.span,
         callee,
         None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
         args,
         false,
     );
-    result.push_dynamic(
-        call, false, false, // Components return escaped content
-    );
+    result.push_html(call);
 
     result
 }
@@ -202,8 +275,9 @@ pub fn transform_component<'a, 'b>(
 /// Build props object for a component
 fn build_props<'a, 'b>(
     element: &JSXElement<'a>,
+    tag_name: &str,
     context: &SSRContext<'a>,
-    _options: &TransformOptions<'a>,
+    options: &TransformOptions<'a>,
     transform_child: SSRChildTransformer<'a, 'b>,
 ) -> Expression<'a> {
     let ast = context.ast();
@@ -212,6 +286,9 @@ fn build_props<'a, 'b>(
     let mut static_props: Vec<ObjectPropertyKind<'a>> = Vec::new();
     let mut dynamic_props: Vec<ObjectPropertyKind<'a>> = Vec::new();
     let mut spreads: Vec<Expression<'a>> = Vec::new();
+    // `<Dynamic>` hands its props to whatever it renders, which may be a
+    // component that uses its handlers or ref even on the server
+    let forwards_all_props = tag_name == "Dynamic";
 
     for attr in &element.opening_element.attributes {
         match attr {
@@ -224,21 +301,27 @@ fn build_props<'a, 'b>(
                 };
                 let key = make_prop_key(ast, span, &raw_key);
 
-                // Skip event handlers and refs in SSR
-                if raw_key.starts_with("on") || raw_key == "ref" || raw_key.starts_with("use:") {
+                // Skip event handlers and refs in SSR. Namespaced props
+                // (`on:custom`, `use:dir`, `attr:x`) mean something to the
+                // wrapper components that receive them, so they are kept
+                let namespaced = matches!(attr.name, JSXAttributeName::NamespacedName(_));
+                if !forwards_all_props
+                    && !namespaced
+                    && (raw_key.starts_with("on") || raw_key == "ref")
+                {
                     continue;
                 }
 
-                // Ignore explicit `children` prop when actual JSX children exist.
-                // JSX children win over `children={...}`.
-                if raw_key == "children" && !element.children.is_empty() {
+                // JSX children win over `children={...}`, and a repeated
+                // `children` attribute only keeps its last value
+                if common::is_shadowed_children_attr(element, attr) {
                     continue;
                 }
 
                 match &attr.value {
                     Some(JSXAttributeValue::StringLiteral(lit)) => {
                         static_props.push(ast.object_property_kind_object_property(
-                            span,
+                            attr.span,
                             PropertyKind::Init,
                             key,
                             ast.expression_string_literal(
@@ -255,7 +338,7 @@ fn build_props<'a, 'b>(
                         if let Some(expr) = container.expression.as_expression() {
                             if is_dynamic(expr) {
                                 dynamic_props.push(ast.object_property_kind_object_property(
-                                    span,
+                                    attr.span,
                                     PropertyKind::Get,
                                     key,
                                     getter_return_expr(ast, span, context.clone_expr(expr)),
@@ -265,7 +348,7 @@ fn build_props<'a, 'b>(
                                 ));
                             } else {
                                 static_props.push(ast.object_property_kind_object_property(
-                                    span,
+                                    attr.span,
                                     PropertyKind::Init,
                                     key,
                                     context.clone_expr(expr),
@@ -278,7 +361,7 @@ fn build_props<'a, 'b>(
                     }
                     None => {
                         static_props.push(ast.object_property_kind_object_property(
-                            span,
+                            attr.span,
                             PropertyKind::Init,
                             key,
                             ast.expression_boolean_literal(span, true),
@@ -297,8 +380,21 @@ fn build_props<'a, 'b>(
     }
 
     // Handle children
-    if !element.children.is_empty() {
-        let children = get_children_ssr(element, context, transform_child);
+    if let Some(callback) = common::get_function_child(element) {
+        // Callback children (`<Show>{u => ...}</Show>`) are called by the
+        // component itself, so they skip get_children_ssr
+        static_props.push(ast.object_property_kind_object_property(
+            span,
+            PropertyKind::Init,
+            make_prop_key(ast, span, "children"),
+            context.clone_expr(callback),
+            false,
+            false,
+            false,
+        ));
+    } else if common::has_children(element) {
+        let children =
+            get_children_ssr(element, context, transform_child, options.ssr_element_only);
         let key = make_prop_key(ast, span, "children");
         if is_dynamic(&children) {
             let getter = getter_return_expr(ast, span, children);
@@ -336,7 +432,7 @@ fn build_props<'a, 'b>(
     // Combine props
     if !spreads.is_empty() {
         context.register_helper("mergeProps");
-        let callee = ast.expression_identifier(span, "mergeProps");
+        let callee = helper_ident(ast, "mergeProps");
         let mut args = ast.vec_with_capacity(spreads.len() + if has_inline_props { 1 } else { 0 });
         for spread in spreads {
             args.push(Argument::from(spread));
@@ -4,23 +4,29 @@
 //! Unlike DOM, we don't create DOM nodes - we build strings.
 
 use oxc_ast::ast::{
-    Argument, ArrayExpressionElement, Expression, JSXAttribute, JSXAttributeItem, JSXAttributeName,
-    JSXAttributeValue, JSXElement, PropertyKey, PropertyKind,
+    Argument, Expression, JSXAttribute, JSXAttributeItem, JSXAttributeName, JSXAttributeValue,
+    JSXChild, JSXElement, PropertyKey, PropertyKind,
 };
 use oxc_span::SPAN;
 
 use common::{
-    constants::{ALIASES, CHILD_PROPERTIES, PROPERTIES, VOID_ELEMENTS},
+    attribute_name,
+    constants::{BOOLEAN_ATTRIBUTES, CHILD_PROPERTIES, PROPERTIES, VOID_ELEMENTS},
     expression::escape_html,
-    get_attr_name, is_svg_element, TransformOptions,
+    get_attr_name, helper_ident, is_custom_element, is_single_dynamic_child, is_svg_element,
+    prerender_element, static_attribute_markup, static_attribute_text, static_child_text,
+    static_text_content, static_truthiness, TransformOptions,
 };
 
 use crate::ir::{SSRContext, SSRResult};
 
-/// Transform a native HTML/SVG element for SSR
+/// Transform a native HTML/SVG element for SSR. `top_level` is set for the
+/// root element of a template, the only one that gets a hydration key: the
+/// client claims it with `getNextElement` and walks to the others.
 pub fn transform_element<'a>(
     element: &JSXElement<'a>,
     tag_name: &str,
+    top_level: bool,
     context: &SSRContext<'a>,
     options: &TransformOptions<'a>,
 ) -> SSRResult<'a> {
@@ -33,6 +39,26 @@ pub fn transform_element<'a>(
     result.tag_name = Some(tag_name.to_string());
     result.skip_escape = is_script_or_style;
 
+    if options.ssr_element_only {
+        return transform_ssr_element(element, tag_name, top_level, context, options);
+    }
+
+    let hydratable = context.is_hydratable() && options.hydratable;
+    let needs_hydration_key = top_level && hydratable;
+    let injects_hydration_script = tag_name == "head"
+        && options.hydration_script
+        && hydratable
+        && !has_hydration_script(element);
+
+    // Fully static subtrees become a single precomputed string; an element
+    // with a hydration key or script always takes the slow path
+    if !needs_hydration_key && !injects_hydration_script {
+        if let Some(html) = prerender_element(element, options) {
+            result.push_static(&html);
+            return result;
+        }
+    }
+
     // Check for spread attributes - need different handling
     let has_spread = element
         .opening_element
@@ -41,24 +67,25 @@ pub fn transform_element<'a>(
         .any(|a| matches!(a, JSXAttributeItem::SpreadAttribute(_)));
 
     if has_spread {
-        return transform_element_with_spread(element, tag_name, context, options);
+        return transform_ssr_element(element, tag_name, top_level, context, options);
     }
 
     // Start the tag
     result.push_static(&format!("<{}", tag_name));
 
     // Add hydration key if needed
-    if context.hydratable && options.hydratable {
+    if needs_hydration_key {
         context.register_helper("ssrHydrationKey");
-        let callee = ast.expression_identifier(SPAN, "ssrHydrationKey");
+        let callee = helper_ident(ast, "ssrHydrationKey");
         let expr = ast.expression_call(
-            SPAN,
+            element.span,
             callee,
             None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
             ast.vec(),
             false,
         );
-        result.push_dynamic(expr, false, true);
+        // Inside the opening tag, where a marker comment would be markup
+        result.push_dynamic_with_marker(expr, false, true, false);
     }
 
     // Transform attributes
@@ -70,32 +97,81 @@ pub fn transform_element<'a>(
     // Transform children (if not void element)
     if !is_void {
         transform_children(element, &mut result, context, options);
+        if injects_hydration_script {
+            push_hydration_script(&mut result, context);
+        }
         result.push_static(&format!("</{}>", tag_name));
     }
 
     result
 }
 
-/// Transform element with spread attributes using ssrElement()
-fn transform_element_with_spread<'a>(
+/// Whether a `<head>` already renders the hydration bootstrap itself
+fn has_hydration_script(element: &JSXElement<'_>) -> bool {
+    element.children.iter().any(|child| match child {
+        JSXChild::Element(child) => common::get_tag_name(child) == "HydrationScript",
+        JSXChild::ExpressionContainer(container) => container
+            .expression
+            .as_expression()
+            .and_then(|expr| match expr.without_parentheses() {
+                Expression::CallExpression(call) => call.callee_name(),
+                _ => None,
+            })
+            .is_some_and(|name| name == "generateHydrationScript"),
+        _ => false,
+    })
+}
+
+/// Append the unescaped `generateHydrationScript()` bootstrap
+fn push_hydration_script<'a>(result: &mut SSRResult<'a>, context: &SSRContext<'a>) {
+    let ast = context.ast();
+    context.register_helper("generateHydrationScript");
+    let callee = helper_ident(ast, "generateHydrationScript");
+    let call = ast.expression_call(
+        SPAN,
+        callee,
+        None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+        ast.vec(),
+        false,
+    );
+    result.push_dynamic_with_marker(call, false, true, false);
+}
+
+/// Transform an element into an `ssrElement()` call, which spreads need to
+/// merge their props at runtime and `ssr_element_only` uses for every element
+fn transform_ssr_element<'a>(
     element: &JSXElement<'a>,
     tag_name: &str,
+    top_level: bool,
     context: &SSRContext<'a>,
     options: &TransformOptions<'a>,
 ) -> SSRResult<'a> {
     context.register_helper("ssrElement");
-    context.register_helper("escape");
     let ast = context.ast();
     let span = SPAN;
 
     let mut result = SSRResult::new();
     result.span = element.span;
-    result.has_spread = true;
+    result.has_spread = element
+        .opening_element
+        .attributes
+        .iter()
+        .any(|a| matches!(a, JSXAttributeItem::SpreadAttribute(_)));
 
     // Build props object - merge spreads with regular attributes
     let is_svg = is_svg_element(tag_name);
+    let is_custom_element = is_custom_element(tag_name, options);
     let mut props = ast.vec();
 
+    // `class:name` toggles go into the classList object ssrElement renders,
+    // along with the properties of a classList attribute
+    let has_class_toggle = element.opening_element.attributes.iter().any(|attr| {
+        matches!(attr, JSXAttributeItem::Attribute(attr)
+            if get_attr_name(&attr.name).starts_with("class:"))
+    });
+    let mut class_list = ast.vec();
+    let mut class_list_index = None;
+
     for attr in &element.opening_element.attributes {
         match attr {
             JSXAttributeItem::SpreadAttribute(spread) => {
@@ -114,15 +190,94 @@ fn transform_element_with_spread<'a>(
                 {
                     continue;
                 }
+                // Rendered as the children
+                if CHILD_PROPERTIES.contains(key.as_str())
+                    || (key == "value" && tag_name == "textarea")
+                {
+                    continue;
+                }
 
-                let attr_name = if is_svg {
-                    key.clone()
-                } else {
-                    ALIASES
-                        .get(key.as_str())
-                        .copied()
-                        .unwrap_or(&key)
-                        .to_string()
+                if has_class_toggle && (key == "classList" || key.starts_with("class:")) {
+                    let value = match &attr.value {
+                        Some(JSXAttributeValue::ExpressionContainer(container)) => container
+                            .expression
+                            .as_expression()
+                            .map(|e| context.clone_expr(e)),
+                        Some(JSXAttributeValue::StringLiteral(lit)) => {
+                            Some(ast.expression_boolean_literal(span, !lit.value.is_empty()))
+                        }
+                        None => Some(ast.expression_boolean_literal(span, true)),
+                        _ => None,
+                    };
+                    let Some(value) = value else {
+                        continue;
+                    };
+                    class_list_index.get_or_insert(props.len());
+                    let property = match key.strip_prefix("class:") {
+                        Some(name) => {
+                            let key = PropertyKey::StringLiteral(ast.alloc_string_literal(
+                                span,
+                                ast.allocator.alloc_str(name),
+                                None,
+                            ));
+                            ast.object_property_kind_object_property(
+                                span,
+                                PropertyKind::Init,
+                                key,
+                                value,
+                                false,
+                                false,
+                                false,
+                            )
+                        }
+                        None => ast.object_property_kind_spread_property(span, value),
+                    };
+                    class_list.push(property);
+                    continue;
+                }
+
+                // bool:name is present as `name=""` while the value is
+                // truthy; ssrElement skips the undefined otherwise
+                if let Some(name) = key.strip_prefix("bool:") {
+                    let empty = ast.expression_string_literal(span, "", None);
+                    let value = match &attr.value {
+                        Some(JSXAttributeValue::StringLiteral(lit)) if lit.value.is_empty() => {
+                            continue
+                        }
+                        Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                            let Some(expr) = container.expression.as_expression() else {
+                                continue;
+                            };
+                            ast.expression_conditional(
+                                span,
+                                context.clone_expr(expr),
+                                empty,
+                                ast.expression_identifier(span, "undefined"),
+                            )
+                        }
+                        _ => empty,
+                    };
+                    let key = PropertyKey::StringLiteral(ast.alloc_string_literal(
+                        span,
+                        ast.allocator.alloc_str(name),
+                        None,
+                    ));
+                    props.push(ast.object_property_kind_object_property(
+                        span,
+                        PropertyKind::Init,
+                        key,
+                        value,
+                        false,
+                        false,
+                        false,
+                    ));
+                    continue;
+                }
+
+                // ssrElement renders classList itself, under that name
+                let attr_name = match key.as_str() {
+                    "classList" => "classList".into(),
+                    _ => attribute_name(&key, is_svg, is_custom_element),
                 };
 
                 match &attr.value {
@@ -132,11 +287,8 @@ fn transform_element_with_spread<'a>(
                             ast.allocator.alloc_str(&attr_name),
                             None,
                         ));
-                        let value = ast.expression_string_literal(
-                            span,
-                            ast.allocator.alloc_str(&escape_html(&lit.value, true)),
-                            None,
-                        );
+                        // ssrElement escapes attribute values itself
+                        let value = ast.expression_string_literal(span, lit.value, None);
                         props.push(ast.object_property_kind_object_property(
                             span,
                             PropertyKind::Init,
@@ -188,119 +340,58 @@ fn transform_element_with_spread<'a>(
         }
     }
 
+    if let Some(index) = class_list_index {
+        let key = PropertyKey::StringLiteral(ast.alloc_string_literal(span, "classList", None));
+        let value = ast.expression_object(span, class_list);
+        props.insert(
+            index,
+            ast.object_property_kind_object_property(
+                span,
+                PropertyKind::Init,
+                key,
+                value,
+                false,
+                false,
+                false,
+            ),
+        );
+    }
+
     let props_expr = ast.expression_object(span, props);
 
+    // Void elements render no children, so there is nothing to pass
+    let is_void = VOID_ELEMENTS.contains(tag_name);
+
     // Build children
-    let children_expr = if element.children.is_empty() {
+    let hydratable = context.is_hydratable() && options.hydratable;
+    let needs_hydration_key = top_level && hydratable;
+    let children_expr = if is_void {
         ast.expression_null_literal(span)
     } else {
-        let mut children: Vec<Expression<'a>> = Vec::new();
-        for child in &element.children {
-            match child {
-                oxc_ast::ast::JSXChild::Text(text) => {
-                    let content = common::expression::trim_whitespace(&text.value);
-                    if !content.is_empty() {
-                        children.push(ast.expression_string_literal(
-                            span,
-                            ast.allocator.alloc_str(&escape_html(&content, false)),
-                            None,
-                        ));
-                    }
-                }
-                oxc_ast::ast::JSXChild::ExpressionContainer(container) => {
-                    if let Some(expr) = container.expression.as_expression() {
-                        let callee = ast.expression_identifier(span, "escape");
-                        let mut args = ast.vec();
-                        args.push(Argument::from(context.clone_expr(expr)));
-                        children.push(ast.expression_call(
-                            span,
-                            callee,
-                            None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
-                            args,
-                            false,
-                        ));
-                    }
-                }
-                oxc_ast::ast::JSXChild::Element(child_elem) => {
-                    // Recursively transform child element - check if component or native
-                    let child_tag = common::get_tag_name(child_elem);
-                    let child_result = if common::is_component(&child_tag) {
-                        // Component - use component transformer
-                        let child_transformer =
-                            |child: &oxc_ast::ast::JSXChild<'a>| -> Option<SSRResult<'a>> {
-                                match child {
-                                    oxc_ast::ast::JSXChild::Element(el) => {
-                                        let tag = common::get_tag_name(el);
-                                        Some(if common::is_component(&tag) {
-                                            // For deeply nested components, use simple fallback
-                                            context.register_helper("createComponent");
-                                            context.register_helper("escape");
-
-                                            let mut r = SSRResult::new();
-                                            r.span = el.span;
-                                            let callee =
-                                                ast.expression_identifier(span, "createComponent");
-                                            let mut args = ast.vec();
-                                            let tag_expr = ast.expression_identifier(
-                                                span,
-                                                ast.allocator.alloc_str(&tag),
-                                            );
-                                            args.push(Argument::from(tag_expr));
-                                            args.push(Argument::from(
-                                                ast.expression_object(span, ast.vec()),
-                                            ));
-                                            let call = ast.expression_call(
-                                                span,
-                                                callee,
-                                                None::<
-                                                    oxc_ast::ast::TSTypeParameterInstantiation<'a>,
-                                                >,
-                                                args,
-                                                false,
-                                            );
-                                            r.push_dynamic(call, false, false);
-                                            r
-                                        } else {
-                                            transform_element(el, &tag, context, options)
-                                        })
-                                    }
-                                    _ => None,
-                                }
-                            };
-                        crate::component::transform_component(
-                            child_elem,
-                            &child_tag,
-                            context,
-                            options,
-                            &child_transformer,
-                        )
-                    } else {
-                        transform_element(child_elem, &child_tag, context, options)
-                    };
-
-                    children.push(child_result.to_ssr_expression(ast, context.hydratable));
-                }
-                _ => {}
-            }
+        let mut children = SSRResult::new();
+        children.tag_name = Some(tag_name.to_string());
+        children.skip_escape = tag_name == "script" || tag_name == "style";
+        transform_children(element, &mut children, context, options);
+        if tag_name == "head"
+            && options.hydration_script
+            && hydratable
+            && !has_hydration_script(element)
+        {
+            push_hydration_script(&mut children, context);
         }
-
-        if children.len() == 1 {
-            children
-                .pop()
-                .unwrap_or_else(|| ast.expression_null_literal(span))
-        } else if children.is_empty() {
+        if children.template_values.is_empty() && children.template_parts.concat().is_empty() {
             ast.expression_null_literal(span)
         } else {
-            let mut elements = ast.vec_with_capacity(children.len());
-            for expr in children {
-                elements.push(ArrayExpressionElement::from(expr));
+            if children.uses_escape() {
+                context.register_helper("escape");
             }
-            ast.expression_array(span, elements)
+            children.to_children_expression(ast, hydratable)
         }
     };
 
-    // For spread, we generate: ssrElement("tag", props, children, needsHydrationKey)
-    let callee = ast.expression_identifier(span, "ssrElement");
+    // ssrElement("tag", props, children, needsHydrationKey); void elements
+    // drop the children argument: ssrElement("input", props)
+    let callee = helper_ident(ast, "ssrElement");
     let mut args = ast.vec();
     args.push(Argument::from(ast.expression_string_literal(
         span,
@@ -308,19 +399,25 @@ fn transform_element_with_spread<'a>(
         None,
     )));
     args.push(Argument::from(props_expr));
-    args.push(Argument::from(children_expr));
-    args.push(Argument::from(ast.expression_boolean_literal(
-        span,
-        context.hydratable && options.hydratable,
-    )));
+    if !is_void {
+        args.push(Argument::from(children_expr));
+    } else if needs_hydration_key {
+        args.push(Argument::from(ast.expression_identifier(span, "undefined")));
+    }
+    if !is_void || needs_hydration_key {
+        args.push(Argument::from(ast.expression_boolean_literal(
+            span,
+            needs_hydration_key,
+        )));
+    }
     let call = ast.expression_call(
-        span,
+        element.span,
         callee,
         None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
         args,
         false,
     );
-    result.push_dynamic(call, false, true);
+    result.push_element(call);
 
     result
 }
@@ -334,12 +431,143 @@ fn transform_attributes<'a>(
 ) {
     let tag_name = result.tag_name.as_deref().unwrap_or("");
     let is_svg = is_svg_element(tag_name);
+    let is_custom_element = is_custom_element(tag_name, options);
+
+    // `class:name` toggles, and several class attributes, render as one
+    // class attribute where the first of them was written
+    let class_attrs: Vec<&JSXAttribute<'a>> = element
+        .opening_element
+        .attributes
+        .iter()
+        .filter_map(|attr| match attr {
+            JSXAttributeItem::Attribute(attr) if is_class_key(&get_attr_name(&attr.name)) => {
+                Some(&**attr)
+            }
+            _ => None,
+        })
+        .collect();
+    let merge_classes = class_attrs.len() > 1
+        || class_attrs
+            .iter()
+            .any(|attr| get_attr_name(&attr.name).starts_with("class:"));
+    let mut classes_pushed = false;
 
     for attr in &element.opening_element.attributes {
         if let JSXAttributeItem::Attribute(attr) = attr {
-            transform_attribute(attr, result, context, options, is_svg);
+            if merge_classes && is_class_key(&get_attr_name(&attr.name)) {
+                if !classes_pushed {
+                    transform_class_attributes(&class_attrs, result, context);
+                    classes_pushed = true;
+                }
+                continue;
+            }
+            transform_attribute(attr, result, context, is_svg, is_custom_element);
+        }
+    }
+}
+
+/// Whether the attribute `key` adds to the element's class attribute
+fn is_class_key(key: &str) -> bool {
+    matches!(key, "class" | "className" | "classList") || key.starts_with("class:")
+}
+
+/// A piece of a merged class attribute
+enum ClassPart<'a> {
+    Static(String),
+    /// A value to escape
+    Escaped(Expression<'a>),
+    /// A value that is already class text
+    Raw(Expression<'a>),
+}
+
+/// Render `class`, `className`, `classList` and `class:name` attributes as a
+/// single class attribute, their classes separated by spaces:
+/// `class="base ${on() ? "on" : ""}"`
+fn transform_class_attributes<'a>(
+    attrs: &[&JSXAttribute<'a>],
+    result: &mut SSRResult<'a>,
+    context: &SSRContext<'a>,
+) {
+    let ast = context.ast();
+    let mut parts = Vec::new();
+    for attr in attrs {
+        let key = get_attr_name(&attr.name);
+        let toggled = key.strip_prefix("class:");
+        let expr = match &attr.value {
+            Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                let Some(expr) = container.expression.as_expression() else {
+                    continue;
+                };
+                expr
+            }
+            Some(JSXAttributeValue::StringLiteral(lit)) => {
+                let text = match toggled {
+                    Some(name) if !lit.value.is_empty() => name,
+                    Some(_) => continue,
+                    None => lit.value.as_str(),
+                };
+                parts.push(ClassPart::Static(text.to_string()));
+                continue;
+            }
+            None => {
+                if let Some(name) = toggled {
+                    parts.push(ClassPart::Static(name.to_string()));
+                }
+                continue;
+            }
+            _ => continue,
+        };
+        let part = if let Some(name) = toggled {
+            match static_truthiness(expr) {
+                Some(true) => ClassPart::Static(name.to_string()),
+                Some(false) => continue,
+                None => {
+                    // on() ? "name" : ""
+                    let name = escape_html(name, true);
+                    let class =
+                        ast.expression_string_literal(SPAN, ast.allocator.alloc_str(&name), None);
+                    ClassPart::Raw(ast.expression_conditional(
+                        attr.span,
+                        context.clone_expr(expr),
+                        class,
+                        ast.expression_string_literal(SPAN, "", None),
+                    ))
+                }
+            }
+        } else if key == "classList" {
+            context.register_helper("ssrClassList");
+            let callee = helper_ident(ast, "ssrClassList");
+            ClassPart::Raw(ast.expression_call(
+                attr.span,
+                callee,
+                None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+                ast.vec1(Argument::from(context.clone_expr(expr))),
+                false,
+            ))
+        } else if let Some(text) = static_attribute_text(expr) {
+            ClassPart::Static(text)
+        } else {
+            context.register_helper("escape");
+            ClassPart::Escaped(context.clone_expr(expr))
+        };
+        parts.push(part);
+    }
+
+    result.push_static(" class=\"");
+    for (i, part) in parts.into_iter().enumerate() {
+        if i > 0 {
+            result.push_static(" ");
+        }
+        match part {
+            ClassPart::Static(text) => {
+                result.push_static(&escape_html(&text, true));
+            }
+            ClassPart::Escaped(expr) => result.push_dynamic(expr, true, false),
+            // Inside the opening tag, where a marker comment would be markup
+            ClassPart::Raw(expr) => result.push_dynamic_with_marker(expr, false, true, false),
         }
     }
+    result.push_static("\"");
 }
 
 /// Transform a single attribute for SSR
@@ -347,8 +575,8 @@ fn transform_attribute<'a>(
     attr: &JSXAttribute<'a>,
     result: &mut SSRResult<'a>,
     context: &SSRContext<'a>,
-    _options: &TransformOptions<'a>,
     is_svg: bool,
+    is_custom_element: bool,
 ) {
     let ast = context.ast();
     let key = get_attr_name(&attr.name);
@@ -365,16 +593,19 @@ fn transform_attribute<'a>(
         return;
     }
 
+    // A textarea's value is its text content, rendered with the children
+    if key == "value" && result.tag_name.as_deref() == Some("textarea") {
+        return;
+    }
+
+    // bool:name renders the bare attribute while the value is truthy
+    if let Some(name) = key.strip_prefix("bool:") {
+        transform_bool_attribute(attr, name, result, context);
+        return;
+    }
+
     // Get the attribute name (handle aliases like className -> class)
-    let attr_name = if is_svg {
-        key.clone()
-    } else {
-        ALIASES
-            .get(key.as_str())
-            .copied()
-            .unwrap_or(&key)
-            .to_string()
-    };
+    let attr_name = attribute_name(&key, is_svg, is_custom_element);
 
     match &attr.value {
         // Static string value
@@ -386,18 +617,25 @@ fn transform_attribute<'a>(
         // Dynamic value
         Some(JSXAttributeValue::ExpressionContainer(container)) => {
             if let Some(expr) = container.expression.as_expression() {
+                // Literal strings, numbers and booleans are rendered as
+                // static markup
+                if let Some(markup) = static_attribute_markup(&attr_name, expr) {
+                    result.push_static(&markup);
+                    return;
+                }
+
                 let expr = context.clone_expr(expr);
 
                 // Handle special attributes
                 if key == "style" {
                     context.register_helper("ssrStyle");
                     result.push_static(&format!(" {}=\"", attr_name));
-                    let callee = ast.expression_identifier(SPAN, "ssrStyle");
+                    let callee = helper_ident(ast, "ssrStyle");
                     let mut args = ast.vec();
                     args.push(Argument::from(expr));
-                    result.push_dynamic(
+                    result.push_dynamic_with_marker(
                         ast.expression_call(
-                            SPAN,
+                            attr.span,
                             callee,
                             None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
                             args,
@@ -405,6 +643,7 @@ fn transform_attribute<'a>(
                         ),
                         false,
                         true,
+                        false,
                     );
                     result.push_static("\"");
                 } else if key == "class" || key == "className" {
@@ -415,12 +654,12 @@ fn transform_attribute<'a>(
                 } else if key == "classList" {
                     context.register_helper("ssrClassList");
                     result.push_static(" class=\"");
-                    let callee = ast.expression_identifier(SPAN, "ssrClassList");
+                    let callee = helper_ident(ast, "ssrClassList");
                     let mut args = ast.vec();
                     args.push(Argument::from(expr));
-                    result.push_dynamic(
+                    result.push_dynamic_with_marker(
                         ast.expression_call(
-                            SPAN,
+                            attr.span,
                             callee,
                             None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
                             args,
@@ -428,12 +667,33 @@ fn transform_attribute<'a>(
                         ),
                         false,
                         true,
+                        false,
                     );
                     result.push_static("\"");
-                } else if PROPERTIES.contains(key.as_str()) {
-                    // Boolean attributes
+                } else {
+                    // Boolean attributes render only when truthy; every other
+                    // attribute is escaped and dropped when nullish instead of
+                    // printing "undefined" (`value` is the one string property)
+                    let is_boolean = BOOLEAN_ATTRIBUTES.contains(key.as_str())
+                        || (PROPERTIES.contains(key.as_str()) && key != "value");
+                    let expr = if is_boolean {
+                        expr
+                    } else {
+                        context.register_helper("escape");
+                        let escape = helper_ident(ast, "escape");
+                        ast.expression_call(
+                            attr.span,
+                            escape,
+                            None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+                            ast.vec_from_array([
+                                Argument::from(expr),
+                                Argument::from(ast.expression_boolean_literal(SPAN, true)),
+                            ]),
+                            false,
+                        )
+                    };
                     context.register_helper("ssrAttribute");
-                    let callee = ast.expression_identifier(SPAN, "ssrAttribute");
+                    let callee = helper_ident(ast, "ssrAttribute");
                     let mut args = ast.vec();
                     args.push(Argument::from(ast.expression_string_literal(
                         SPAN,
@@ -441,10 +701,12 @@ fn transform_attribute<'a>(
                         None,
                     )));
                     args.push(Argument::from(expr));
-                    args.push(Argument::from(ast.expression_boolean_literal(SPAN, true)));
-                    result.push_dynamic(
+                    args.push(Argument::from(ast.expression_boolean_literal(
+                        SPAN, is_boolean,
+                    )));
+                    result.push_dynamic_with_marker(
                         ast.expression_call(
-                            SPAN,
+                            attr.span,
                             callee,
                             None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
                             args,
@@ -452,13 +714,8 @@ fn transform_attribute<'a>(
                         ),
                         false,
                         true,
+                        false,
                     );
-                } else {
-                    // Regular attribute
-                    context.register_helper("escape");
-                    result.push_static(&format!(" {}=\"", attr_name));
-                    result.push_dynamic(expr, true, false);
-                    result.push_static("\"");
                 }
             }
         }
@@ -472,6 +729,58 @@ fn transform_attribute<'a>(
     }
 }
 
+/// Transform a `bool:` attribute for SSR
+fn transform_bool_attribute<'a>(
+    attr: &JSXAttribute<'a>,
+    name: &str,
+    result: &mut SSRResult<'a>,
+    context: &SSRContext<'a>,
+) {
+    let ast = context.ast();
+    let present = match &attr.value {
+        Some(JSXAttributeValue::StringLiteral(lit)) => !lit.value.is_empty(),
+        Some(JSXAttributeValue::ExpressionContainer(container)) => {
+            let Some(expr) = container.expression.as_expression() else {
+                return;
+            };
+            match static_truthiness(expr) {
+                Some(present) => present,
+                None => {
+                    context.register_helper("ssrAttribute");
+                    let callee = helper_ident(ast, "ssrAttribute");
+                    let args = ast.vec_from_array([
+                        Argument::from(ast.expression_string_literal(
+                            SPAN,
+                            ast.allocator.alloc_str(name),
+                            None,
+                        )),
+                        Argument::from(context.clone_expr(expr)),
+                        Argument::from(ast.expression_boolean_literal(SPAN, true)),
+                    ]);
+                    result.push_dynamic_with_marker(
+                        ast.expression_call(
+                            attr.span,
+                            callee,
+                            None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+                            args,
+                            false,
+                        ),
+                        false,
+                        true,
+                        false,
+                    );
+                    return;
+                }
+            }
+        }
+        None => true,
+        _ => return,
+    };
+    if present {
+        result.push_static(&format!(" {}", name));
+    }
+}
+
 /// Transform element children for SSR
 fn transform_children<'a>(
     element: &JSXElement<'a>,
@@ -479,6 +788,12 @@ fn transform_children<'a>(
     context: &SSRContext<'a>,
     options: &TransformOptions<'a>,
 ) {
+    // A literal textContent on a childless element is static text
+    if let Some(text) = static_text_content(element) {
+        result.push_static(&escape_html(&text, false));
+        return;
+    }
+
     // Check for innerHTML/textContent in attributes first
     for attr in &element.opening_element.attributes {
         if let JSXAttributeItem::Attribute(attr) = attr {
@@ -491,25 +806,54 @@ fn transform_children<'a>(
                 if let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value {
                     if let Some(expr) = container.expression.as_expression() {
                         // innerHTML - don't escape
-                        result.push_dynamic(context.clone_expr(expr), false, true);
+                        result.push_dynamic_with_marker(
+                            context.clone_expr(expr),
+                            false,
+                            true,
+                            false,
+                        );
                         return;
                     }
                 }
-            } else if key == "textContent" || key == "innerText" {
-                if let Some(JSXAttributeValue::ExpressionContainer(container)) = &attr.value {
-                    if let Some(expr) = container.expression.as_expression() {
-                        context.register_helper("escape");
-                        result.push_dynamic(context.clone_expr(expr), false, false);
+            } else if key == "textContent"
+                || key == "innerText"
+                || (key == "value" && result.tag_name.as_deref() == Some("textarea"))
+            {
+                match &attr.value {
+                    Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                        if let Some(expr) = container.expression.as_expression() {
+                            context.register_helper("escape");
+                            result.push_dynamic_with_marker(
+                                context.clone_expr(expr),
+                                false,
+                                false,
+                                false,
+                            );
+                            return;
+                        }
+                    }
+                    Some(JSXAttributeValue::StringLiteral(lit)) => {
+                        result.push_static(&escape_html(&lit.value, false));
                         return;
                     }
+                    _ => {}
                 }
             }
         }
     }
 
-    // Process children
+    // Process children; like the DOM transform, a lone dynamic child is
+    // inserted without markers around it
     let skip_escape = result.skip_escape;
-    process_jsx_children(&element.children, result, skip_escape, context, options);
+    let markers = !is_single_dynamic_child(&element.children);
+    process_jsx_children(
+        &element.children,
+        result,
+        skip_escape,
+        markers,
+        context,
+        options,
+    );
 }
 
 /// Process a list of JSX children, appending to the result.
@@ -518,22 +862,32 @@ fn process_jsx_children<'a>(
     children: &oxc_allocator::Vec<'a, oxc_ast::ast::JSXChild<'a>>,
     result: &mut SSRResult<'a>,
     skip_escape: bool,
+    markers: bool,
     context: &SSRContext<'a>,
     options: &TransformOptions<'a>,
 ) {
     let ast = context.ast();
     for child in children {
-        match child {
+        // Literal children are plain text, merged with the text around them
+        let text = match child {
             oxc_ast::ast::JSXChild::Text(text) => {
-                let content = common::expression::trim_whitespace(&text.value);
-                if !content.is_empty() {
-                    if skip_escape {
-                        result.push_static(&content);
-                    } else {
-                        result.push_static(&escape_html(&content, false));
-                    }
-                }
+                Some(common::expression::decode_jsx_text(&text.value))
+            }
+            oxc_ast::ast::JSXChild::ExpressionContainer(container) => {
+                container.expression.as_expression().and_then(static_child_text)
+            }
+            _ => None,
+        };
+        if let Some(content) = text {
+            if skip_escape {
+                result.push_static(&content);
+            } else {
+                result.push_static(&escape_html(&content, false));
             }
+            continue;
+        }
+
+        match child {
 
             oxc_ast::ast::JSXChild::Element(child_elem) => {
                 let child_tag = common::get_tag_name(child_elem);
@@ -550,8 +904,7 @@ fn process_jsx_children<'a>(
                                         context.register_helper("escape");
                                         let mut r = SSRResult::new();
                                         r.span = el.span;
-                                        let callee =
-                                            ast.expression_identifier(SPAN, "createComponent");
+                                        let callee = helper_ident(ast, "createComponent");
                                         let mut args = ast.vec();
                                         let tag_expr = ast.expression_identifier(
                                             SPAN,
@@ -562,16 +915,16 @@ fn process_jsx_children<'a>(
                                             ast.expression_object(SPAN, ast.vec()),
                                         ));
                                         let call = ast.expression_call(
-                                            SPAN,
+                                            el.span,
                                             callee,
                                             None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
                                             args,
                                             false,
                                         );
-                                        r.push_dynamic(call, false, false);
+                                        r.push_html(call);
                                         r
                                     } else {
-                                        transform_element(el, &tag, context, options)
+                                        transform_element(el, &tag, true, context, options)
                                     })
                                 }
                                 _ => None,
@@ -585,7 +938,7 @@ fn process_jsx_children<'a>(
                         &child_transformer,
                     )
                 } else {
-                    transform_element(child_elem, &child_tag, context, options)
+                    transform_element(child_elem, &child_tag, false, context, options)
                 };
                 result.merge(child_result);
             }
@@ -596,18 +949,32 @@ fn process_jsx_children<'a>(
 
                     if skip_escape {
                         // Inside script/style - don't escape
-                        result.push_dynamic(expr, false, true);
+                        result.push_dynamic_with_marker(expr, false, true, markers);
                     } else {
                         // Normal content - escape
                         context.register_helper("escape");
-                        result.push_dynamic(expr, false, false);
+                        result.push_dynamic_with_marker(expr, false, false, markers);
                     }
                 }
             }
 
             oxc_ast::ast::JSXChild::Fragment(fragment) => {
                 // Recursively process fragment children with same escape settings
-                process_jsx_children(&fragment.children, result, skip_escape, context, options);
+                process_jsx_children(
+                    &fragment.children,
+                    result,
+                    skip_escape,
+                    markers,
+                    context,
+                    options,
+                );
+            }
+
+            oxc_ast::ast::JSXChild::Spread(spread) => {
+                // escape() renders each item of the spread array
+                context.register_helper("escape");
+                let expr = context.clone_expr(&spread.expression);
+                result.push_dynamic_with_marker(expr, false, false, markers);
             }
 
             _ => {}
@@ -5,12 +5,15 @@
 use indexmap::IndexSet;
 use oxc_allocator::{Allocator, CloneIn};
 use oxc_ast::ast::JSXChild;
-use oxc_ast::ast::{Argument, Expression, TemplateElementValue};
+use oxc_ast::ast::{
+    Argument, ArrayExpressionElement, Expression, PropertyKind, TemplateElementValue,
+};
 use oxc_ast::AstBuilder;
-use oxc_span::{Span, SPAN};
-use std::cell::RefCell;
+use oxc_span::{GetSpan, Span, SPAN};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 
-use common::expr_to_string;
+use common::{escape_template_raw, expr_to_string, helper_ident};
 
 /// Function type for transforming child JSX elements
 pub type SSRChildTransformer<'a, 'b> = &'b dyn Fn(&JSXChild<'a>) -> Option<SSRResult<'a>>;
@@ -52,6 +55,10 @@ pub struct TemplateValue<'a> {
 
     /// Whether this needs hydration markers (for dynamic children)
     pub needs_hydration_marker: bool,
+
+    /// Whether the value is already HTML (a component's output), which
+    /// `escape()` resolves without encoding it again
+    pub is_html: bool,
 }
 
 impl<'a> Default for SSRResult<'a> {
@@ -106,11 +113,49 @@ impl<'a> SSRResult<'a> {
             is_attr,
             skip_escape,
             needs_hydration_marker: needs_marker,
+            is_html: false,
         });
         // Add empty part for after this value
         self.template_parts.push(String::new());
     }
 
+    /// Append a value that is already HTML, like a component's output
+    pub fn push_html(&mut self, expr: Expression<'a>) {
+        self.push_dynamic(expr, false, false);
+        self.template_values.last_mut().unwrap().is_html = true;
+    }
+
+    /// Append an `ssrElement()` call, which renders its own markup and
+    /// hydration key, so it needs neither escaping nor hydration markers
+    pub fn push_element(&mut self, expr: Expression<'a>) {
+        self.push_dynamic_with_marker(expr, false, true, false);
+        self.template_values.last_mut().unwrap().is_html = true;
+    }
+
+    /// The value this result consists of, when it is a lone HTML value that
+    /// needs no template around it
+    fn lone_html_value(&self) -> Option<&TemplateValue<'a>> {
+        match self.template_values.as_slice() {
+            [value] if value.is_html && self.template_parts.iter().all(String::is_empty) => {
+                Some(value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether [`to_ssr_expression`](Self::to_ssr_expression) (or, with
+    /// `as_html`, [`to_html_expression`](Self::to_html_expression)) emits an
+    /// `ssr` template
+    pub fn uses_ssr_tag(&self, as_html: bool) -> bool {
+        self.lone_html_value().is_none() && (as_html || !self.template_values.is_empty())
+    }
+
+    /// Whether the expression built for this result wraps a value in
+    /// `escape()`; a lone component is returned as it is
+    pub fn uses_escape(&self) -> bool {
+        self.lone_html_value().is_none() && self.template_values.iter().any(|v| !v.skip_escape)
+    }
+
     /// Merge another SSR result into this one
     pub fn merge(&mut self, other: SSRResult<'a>) {
         for (i, part) in other.template_parts.into_iter().enumerate() {
@@ -145,7 +190,7 @@ impl<'a> SSRResult<'a> {
 
                     // Add hydration marker before dynamic content (not for attributes)
                     if hydratable && !val.is_attr && val.needs_hydration_marker {
-                        result.push_str("<!--#-->");
+                        result.push_str("<!--$-->");
                     }
 
                     result.push_str("${");
@@ -170,17 +215,129 @@ impl<'a> SSRResult<'a> {
         }
     }
 
+    /// Build the expression for a top-level result: static markup is a plain
+    /// string and a lone component is its `createComponent` call
     pub fn to_ssr_expression(&self, ast: AstBuilder<'a>, hydratable: bool) -> Expression<'a> {
-        let gen_span = SPAN;
-
         if self.template_values.is_empty() {
             let content = self.template_parts.join("");
             let allocated_str = ast.allocator.alloc_str(&content);
-            return ast.expression_string_literal(gen_span, allocated_str, None);
+            return ast.expression_string_literal(SPAN, allocated_str, None);
+        }
+        self.to_html_expression(ast, hydratable)
+    }
+
+    /// Build the `children` argument of an `ssrElement()` call, which renders
+    /// strings as they are: markup stays a string, dynamic values are
+    /// escaped and more than one part becomes an array
+    pub fn to_children_expression(&self, ast: AstBuilder<'a>, hydratable: bool) -> Expression<'a> {
+        self.to_parts_expression(ast, hydratable, true)
+    }
+
+    /// Build the expression for a result without an `ssr` template, as the
+    /// `ssr_element_only` output does. The value is escaped when it's
+    /// rendered, so markup becomes a `{ t }` node, which escaping leaves
+    /// alone, and dynamic values are left to that escaping.
+    pub fn to_element_expression(&self, ast: AstBuilder<'a>, hydratable: bool) -> Expression<'a> {
+        self.to_parts_expression(ast, hydratable, false)
+    }
+
+    fn to_parts_expression(
+        &self,
+        ast: AstBuilder<'a>,
+        hydratable: bool,
+        in_element: bool,
+    ) -> Expression<'a> {
+        if let Some(value) = self.lone_html_value() {
+            return value.expr.clone_in(ast.allocator);
+        }
+
+        let markup = |text: &str| {
+            let text = ast.expression_string_literal(SPAN, ast.allocator.alloc_str(text), None);
+            if in_element {
+                return text;
+            }
+            let key = ast.property_key_static_identifier(SPAN, "t");
+            let property = ast.object_property_kind_object_property(
+                SPAN,
+                PropertyKind::Init,
+                key,
+                text,
+                false,
+                false,
+                false,
+            );
+            ast.expression_object(SPAN, ast.vec1(property))
+        };
+
+        let mut items = Vec::new();
+        let mut closing_marker = "";
+        for (i, part) in self.template_parts.iter().enumerate() {
+            let value = self.template_values.get(i);
+            let marked =
+                hydratable && value.is_some_and(|v| !v.is_attr && v.needs_hydration_marker);
+            let opening_marker = if marked { "<!--$-->" } else { "" };
+            let text = format!("{closing_marker}{part}{opening_marker}");
+            closing_marker = if marked { "<!--/-->" } else { "" };
+            if !text.is_empty() {
+                items.push(markup(&text));
+            }
+            let Some(value) = value else {
+                continue;
+            };
+            let expr = value.expr.clone_in(ast.allocator);
+            if value.skip_escape || !in_element {
+                items.push(expr);
+            } else {
+                let callee = helper_ident(ast, "escape");
+                let span = expr.span();
+                let mut args = ast.vec1(Argument::from(expr));
+                if value.is_attr {
+                    args.push(Argument::from(ast.expression_boolean_literal(SPAN, true)));
+                }
+                items.push(ast.expression_call(
+                    span,
+                    callee,
+                    None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
+                    args,
+                    false,
+                ));
+            }
+        }
+        if !closing_marker.is_empty() {
+            items.push(markup(closing_marker));
+        }
+
+        match items.len() {
+            0 => markup(""),
+            1 => items.pop().unwrap(),
+            _ => ast.expression_array(
+                SPAN,
+                ast.vec_from_iter(items.into_iter().map(ArrayExpressionElement::from)),
+            ),
+        }
+    }
+
+    /// Build the expression for a result used as a value (component children,
+    /// JSX nested in an expression), which is escaped when it's rendered.
+    /// Static markup becomes an `ssr` template too, so it is not encoded as
+    /// text, and a lone component is not wrapped in a template to be escaped
+    /// twice.
+    pub fn to_html_expression(&self, ast: AstBuilder<'a>, hydratable: bool) -> Expression<'a> {
+        let gen_span = SPAN;
+
+        if let Some(value) = self.lone_html_value() {
+            return value.expr.clone_in(ast.allocator);
         }
 
         // Build quasis (static template parts)
         let mut quasis = ast.vec();
+        if self.template_parts.is_empty() {
+            let value = TemplateElementValue {
+                raw: ast.atom(""),
+                cooked: Some(ast.atom("")),
+            };
+            quasis.push(ast.template_element(gen_span, value, true));
+        }
         let mut closing_marker_prefix = String::new();
         for (i, part) in self.template_parts.iter().enumerate() {
             let mut raw = String::new();
@@ -191,15 +348,16 @@ impl<'a> SSRResult<'a> {
             if i < self.template_values.len() {
                 let val = &self.template_values[i];
                 if hydratable && !val.is_attr && val.needs_hydration_marker {
-                    raw.push_str("<!--#-->");
+                    raw.push_str("<!--$-->");
                     closing_marker_prefix.push_str("<!--/-->");
                 }
             }
 
             let is_tail = i == self.template_parts.len() - 1;
             let part_str = ast.allocator.alloc_str(&raw);
+            let raw_str = ast.allocator.alloc_str(&escape_template_raw(&raw));
             let value = TemplateElementValue {
-                raw: ast.atom(part_str),
+                raw: ast.atom(raw_str),
                 cooked: Some(ast.atom(part_str)),
             };
             let element = ast.template_element(gen_span, value, is_tail);
@@ -213,7 +371,8 @@ impl<'a> SSRResult<'a> {
             let wrapped = if val.skip_escape {
                 expr
             } else {
-                let callee = ast.expression_identifier(gen_span, "escape");
+                let callee = helper_ident(ast, "escape");
+                let span = expr.span();
                 let mut args = ast.vec();
                 args.push(Argument::from(expr));
                 if val.is_attr {
@@ -221,7 +380,7 @@ impl<'a> SSRResult<'a> {
                     args.push(Argument::from(true_lit));
                 }
                 ast.expression_call(
-                    gen_span,
+                    span,
                     callee,
                     None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
                     args,
@@ -235,10 +394,10 @@ impl<'a> SSRResult<'a> {
         let template = ast.template_literal(gen_span, quasis, expressions);
 
         // Build the tag (ssr identifier)
-        let tag = ast.expression_identifier(gen_span, "ssr");
+        let tag = helper_ident(ast, "ssr");
 
         ast.expression_tagged_template(
-            gen_span,
+            self.span,
             tag,
             None::<oxc_ast::ast::TSTypeParameterInstantiation<'a>>,
             template,
@@ -251,12 +410,20 @@ pub struct SSRContext<'a> {
     /// Helper imports needed
     pub helpers: RefCell<IndexSet<String>>,
 
-    /// Variable counter for unique names
-    pub var_counter: RefCell<usize>,
-
     /// Whether we're in hydratable mode
     pub hydratable: bool,
 
+    /// Number of `<NoHydration>` components being transformed around the
+    /// current node
+    no_hydration_depth: Cell<usize>,
+
+    /// Imported bindings that can be rendered as islands, by local name:
+    /// the module and the name it exports them under
+    pub island_imports: HashMap<String, (String, String)>,
+
+    /// Number of islands being transformed around the current node
+    island_depth: Cell<usize>,
+
     allocator: &'a Allocator,
 }
 
@@ -264,17 +431,47 @@ impl<'a> SSRContext<'a> {
     pub fn new(allocator: &'a Allocator, hydratable: bool) -> Self {
         Self {
             helpers: RefCell::new(IndexSet::new()),
-            var_counter: RefCell::new(0),
             hydratable,
+            no_hydration_depth: Cell::new(0),
+            island_imports: HashMap::new(),
+            island_depth: Cell::new(0),
             allocator,
         }
     }
 
-    /// Generate a unique variable name
-    pub fn generate_uid(&self, prefix: &str) -> String {
-        let mut counter = self.var_counter.borrow_mut();
-        *counter += 1;
-        format!("_{}{}", prefix, *counter)
+    /// Whether the current node gets hydration keys and markers: in
+    /// hydratable mode, outside of any `<NoHydration>` subtree
+    pub fn is_hydratable(&self) -> bool {
+        self.hydratable && self.no_hydration_depth.get() == 0
+    }
+
+    /// Run `f` for the subtree of a `<NoHydration>`, which renders without
+    /// hydration keys and markers
+    pub fn without_hydration<T>(&self, f: impl FnOnce() -> T) -> T {
+        let depth = &self.no_hydration_depth;
+        depth.set(depth.get() + 1);
+        let result = f();
+        depth.set(depth.get() - 1);
+        result
+    }
+
+    /// The module and export of `component` if it renders as an island:
+    /// it's imported, and neither the current node nor one of its ancestors
+    /// is an island or `<NoHydration>`
+    pub fn island_import(&self, component: &str) -> Option<&(String, String)> {
+        if !self.is_hydratable() || self.island_depth.get() > 0 {
+            return None;
+        }
+        self.island_imports.get(component)
+    }
+
+    /// Run `f` for the subtree of an island, which hydrates as part of it
+    pub fn within_island<T>(&self, f: impl FnOnce() -> T) -> T {
+        let depth = &self.island_depth;
+        depth.set(depth.get() + 1);
+        let result = f();
+        depth.set(depth.get() - 1);
+        result
     }
 
     /// Register a helper import
@@ -10,7 +10,7 @@
 //! <div class={style()}>{count()}</div>
 //!
 //! // SSR Output
-//! ssr`<div${ssrHydrationKey()} class="${escape(style(), true)}">${escape(count())}</div>`
+//! _$ssr`<div${_$ssrHydrationKey()} class="${_$escape(style(), true)}">${_$escape(count())}</div>`
 //! ```
 
 pub mod component;
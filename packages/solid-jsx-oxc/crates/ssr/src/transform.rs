@@ -5,13 +5,19 @@
 use oxc_allocator::Allocator;
 use oxc_ast::ast::{
     Expression, ImportDeclarationSpecifier, ImportOrExportKind, JSXChild, JSXElement,
-    JSXExpressionContainer, JSXFragment, JSXText, ModuleExportName, Program, Statement,
+    JSXElementName, JSXExpressionContainer, JSXFragment, JSXText, ModuleExportName, Program,
+    Statement,
 };
-use oxc_semantic::SemanticBuilder;
+use oxc_semantic::{Scoping, SemanticBuilder};
 use oxc_span::SPAN;
-use oxc_traverse::{traverse_mut, Traverse, TraverseCtx};
+use oxc_traverse::{traverse_mut, Ancestor, Traverse, TraverseCtx};
 
-use common::{get_tag_name, is_component, TransformOptions};
+use std::collections::HashMap;
+
+use common::{
+    build_require_declaration, get_tag_name, helper_local_name, is_component, ModuleFormat,
+    TransformOptions,
+};
 
 use crate::component::transform_component;
 use crate::element::transform_element;
@@ -34,7 +40,21 @@ impl<'a> SSRTransform<'a> {
     }
 
     /// Run the transform on a program
-    pub fn transform(mut self, program: &mut Program<'a>) {
+    pub fn transform(self, program: &mut Program<'a>) {
+        let scoping = SemanticBuilder::new()
+            .build(program)
+            .semantic
+            .into_scoping();
+        self.transform_with_scoping(program, scoping);
+    }
+
+    /// Run the transform with scoping the caller already built for `program`
+    /// (e.g. from the `Semantic` a linter ran on), instead of building it again
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "ssr_transform", skip_all)
+    )]
+    pub fn transform_with_scoping(mut self, program: &mut Program<'a>, scoping: Scoping) {
         // SAFETY: We convert the allocator reference to a raw pointer and back to a reference
         // to satisfy oxc_traverse's API which requires `&Allocator` while we hold `&mut self`.
         // This is safe because:
@@ -43,16 +63,7 @@ impl<'a> SSRTransform<'a> {
         // 3. We don't mutate the allocator through any path during traversal
         // 4. The pointer is never escaped or stored beyond this call
         let allocator = self.allocator as *const Allocator;
-        traverse_mut(
-            &mut self,
-            unsafe { &*allocator },
-            program,
-            SemanticBuilder::new()
-                .build(program)
-                .semantic
-                .into_scoping(),
-            (),
-        );
+        traverse_mut(&mut self, unsafe { &*allocator }, program, scoping, ());
     }
 
     /// Transform a JSX node and return the SSR result
@@ -69,7 +80,8 @@ impl<'a> SSRTransform<'a> {
                 let mut result = SSRResult::new();
                 result.span = spread.span;
                 self.context.register_helper("escape");
-                result.push_dynamic(self.context.clone_expr(&spread.expression), false, false);
+                let expr = self.context.clone_expr(&spread.expression);
+                result.push_dynamic_with_marker(expr, false, false, false);
                 Some(result)
             }
         }
@@ -91,7 +103,7 @@ impl<'a> SSRTransform<'a> {
                 &child_transformer,
             )
         } else {
-            transform_element(element, &tag_name, &self.context, self.options)
+            transform_element(element, &tag_name, true, &self.context, self.options)
         }
     }
 
@@ -111,7 +123,7 @@ impl<'a> SSRTransform<'a> {
 
     /// Transform JSX text
     fn transform_text(&self, text: &JSXText<'a>) -> Option<SSRResult<'a>> {
-        let content = common::expression::trim_whitespace(&text.value);
+        let content = common::expression::decode_jsx_text(&text.value);
         if content.is_empty() {
             return None;
         }
@@ -128,10 +140,17 @@ impl<'a> SSRTransform<'a> {
         container: &JSXExpressionContainer<'a>,
     ) -> Option<SSRResult<'a>> {
         if let Some(expr) = container.expression.as_expression() {
-            self.context.register_helper("escape");
             let mut result = SSRResult::new();
             result.span = container.span;
-            result.push_dynamic(self.context.clone_expr(expr), false, false);
+            // Literal children are static text
+            if let Some(text) = common::static_child_text(expr) {
+                result.push_static(&common::expression::escape_html(&text, false));
+                return Some(result);
+            }
+            // Outside an element there is no insert to bracket with markers
+            self.context.register_helper("escape");
+            let expr = self.context.clone_expr(expr);
+            result.push_dynamic_with_marker(expr, false, false, false);
             Some(result)
         } else {
             None
@@ -143,20 +162,47 @@ impl<'a> Traverse<'a, ()> for SSRTransform<'a> {
     // Use exit_expression instead of enter_expression to avoid
     // oxc_traverse walking into our newly created nodes (which lack scope info)
     fn exit_expression(&mut self, node: &mut Expression<'a>, ctx: &mut TraverseCtx<'a, ()>) {
-        let new_expr = match node {
-            Expression::JSXElement(element) => {
-                let result = self.transform_jsx_element(element);
-                Some(self.build_ssr_expression(&result, ctx))
-            }
-            Expression::JSXFragment(fragment) => {
-                let result = self.transform_fragment(fragment);
-                Some(self.build_ssr_expression(&result, ctx))
-            }
-            _ => None,
+        if !matches!(node, Expression::JSXElement(_) | Expression::JSXFragment(_)) {
+            return;
+        }
+        // JSX in an expression child of <NoHydration> is transformed before
+        // the component, so it checks for it among its ancestors
+        let in_no_hydration = ctx.ancestors().any(|ancestor| match ancestor {
+            Ancestor::JSXElementChildren(element) => matches!(
+                &element.opening_element().name,
+                JSXElementName::IdentifierReference(ident) if ident.name == "NoHydration"
+            ),
+            _ => false,
+        });
+        // The same goes for JSX passed to an island, which hydrates with it
+        let in_island = !self.context.island_imports.is_empty()
+            && ctx.ancestors().any(|ancestor| {
+                let name = match ancestor {
+                    Ancestor::JSXElementChildren(element) => &element.opening_element().name,
+                    Ancestor::JSXOpeningElementAttributes(opening) => opening.name(),
+                    _ => return false,
+                };
+                matches!(
+                    name,
+                    JSXElementName::IdentifierReference(ident)
+                        if self.context.island_imports.contains_key(ident.name.as_str())
+                )
+            });
+        let new_expr = if in_no_hydration {
+            self.context
+                .without_hydration(|| self.transform_jsx_expression(node, ctx))
+        } else if in_island {
+            self.context
+                .within_island(|| self.transform_jsx_expression(node, ctx))
+        } else {
+            self.transform_jsx_expression(node, ctx)
         };
+        *node = new_expr;
+    }
 
-        if let Some(expr) = new_expr {
-            *node = expr;
+    fn enter_program(&mut self, program: &mut Program<'a>, _ctx: &mut TraverseCtx<'a, ()>) {
+        if self.options.islands {
+            self.context.island_imports = collect_island_imports(program, self.options);
         }
     }
 
@@ -168,7 +214,7 @@ impl<'a> Traverse<'a, ()> for SSRTransform<'a> {
             return;
         }
 
-        // Build import statement: import { ssr, escape, ... } from 'solid-js/web';
+        // Build import statement: import { ssr as _$ssr, ... } from 'solid-js/web';
         // NOTE: This import building logic is duplicated with DOM transform.
         // Extraction is non-trivial due to OXC's lifetime requirements.
         let ast = ctx.ast;
@@ -216,12 +262,28 @@ impl<'a> Traverse<'a, ()> for SSRTransform<'a> {
             }
         }
 
+        let missing_helpers = helpers
+            .iter()
+            .filter(|h| !existing_helper_locals.contains(&helper_local_name(h)))
+            .map(String::as_str);
+
+        // CommonJS output: const { ssr: _$ssr, ... } = require('solid-js/web');
+        if self.options.module_format == ModuleFormat::Cjs {
+            let mut missing_helpers = missing_helpers.peekable();
+            if missing_helpers.peek().is_some() {
+                let require_stmt = build_require_declaration(ast, missing_helpers, module_name);
+                program.body.insert(0, require_stmt);
+            }
+            return;
+        }
+
         // Build specifiers
         let mut specifiers = ast.vec();
-        for helper in helpers.iter().filter(|h| !existing_helper_locals.contains(*h)) {
+        for helper in missing_helpers {
             let helper_str = ast.allocator.alloc_str(helper);
             let imported = ModuleExportName::IdentifierName(ast.identifier_name(span, helper_str));
-            let local = ast.binding_identifier(span, helper_str);
+            let local =
+                ast.binding_identifier(span, ast.allocator.alloc_str(&helper_local_name(helper)));
             let specifier = ast.import_specifier(span, imported, local, ImportOrExportKind::Value);
             specifiers.push(ImportDeclarationSpecifier::ImportSpecifier(
                 ast.alloc(specifier),
@@ -263,6 +325,20 @@ impl<'a> Traverse<'a, ()> for SSRTransform<'a> {
 }
 
 impl<'a> SSRTransform<'a> {
+    /// Transform a JSX element or fragment expression
+    fn transform_jsx_expression(
+        &self,
+        node: &Expression<'a>,
+        ctx: &mut TraverseCtx<'a, ()>,
+    ) -> Expression<'a> {
+        let result = match node {
+            Expression::JSXElement(element) => self.transform_jsx_element(element),
+            Expression::JSXFragment(fragment) => self.transform_fragment(fragment),
+            _ => unreachable!("only called for JSX"),
+        };
+        self.build_ssr_expression(&result, ctx)
+    }
+
     /// Build the SSR expression from the transform result
     fn build_ssr_expression(
         &self,
@@ -270,12 +346,67 @@ impl<'a> SSRTransform<'a> {
         ctx: &mut TraverseCtx<'a, ()>,
     ) -> Expression<'a> {
         let ast = ctx.ast;
-        let hydratable = self.context.hydratable && self.options.hydratable;
+        let hydratable = self.context.is_hydratable() && self.options.hydratable;
+
+        if self.options.ssr_element_only {
+            return result.to_element_expression(ast, hydratable);
+        }
 
-        if !result.template_values.is_empty() {
+        // JSX inside an expression of other JSX is interpolated and escaped
+        // by the outer template, so it has to stay HTML
+        let nested = ctx.ancestors().any(|ancestor| {
+            ancestor.is_jsx_expression_container()
+                || ancestor.is_jsx_spread_child()
+                || ancestor.is_jsx_spread_attribute()
+        });
+        if result.uses_ssr_tag(nested) {
             self.context.register_helper("ssr");
         }
+        if result.uses_escape() {
+            self.context.register_helper("escape");
+        }
+
+        if nested {
+            result.to_html_expression(ast, hydratable)
+        } else {
+            result.to_ssr_expression(ast, hydratable)
+        }
+    }
+}
 
-        result.to_ssr_expression(ast, hydratable)
+/// The imported components of `program` that can be rendered as islands.
+/// Solid's own components and the configured built-ins are part of the
+/// page, not islands.
+fn collect_island_imports(
+    program: &Program<'_>,
+    options: &TransformOptions<'_>,
+) -> HashMap<String, (String, String)> {
+    let mut imports = HashMap::new();
+    for stmt in &program.body {
+        let Statement::ImportDeclaration(import_decl) = stmt else {
+            continue;
+        };
+        let source = import_decl.source.value.as_str();
+        if import_decl.import_kind != ImportOrExportKind::Value
+            || source == "solid-js"
+            || source.starts_with("solid-js/")
+        {
+            continue;
+        }
+        for spec in import_decl.specifiers.iter().flatten() {
+            let (local, export) = match spec {
+                ImportDeclarationSpecifier::ImportSpecifier(s) if s.import_kind.is_value() => {
+                    (s.local.name.as_str(), s.imported.name().to_string())
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => {
+                    (s.local.name.as_str(), "default".to_string())
+                }
+                _ => continue,
+            };
+            if is_component(local) && !options.built_ins.contains(&local) {
+                imports.insert(local.to_string(), (source.to_string(), export));
+            }
+        }
     }
+    imports
 }
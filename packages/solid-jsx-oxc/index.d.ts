@@ -10,17 +10,45 @@ export interface TransformOptions {
   moduleName?: string;
 
   /**
-   * Generate mode: "dom", "ssr", or "universal"
+   * Generate mode: "dom", "ssr", or "universal". "universal" creates nodes
+   * with the `createElement`/`insertNode`/`setProp` helpers of the custom
+   * renderer named by `moduleName`
    * @default "dom"
    */
   generate?: 'dom' | 'ssr' | 'universal';
 
   /**
-   * Whether to enable hydration support
+   * How runtime helpers are imported: "esm" emits `import { ... } from`,
+   * "cjs" emits `const { ... } = require(...)` (e.g. for Jest without ESM)
+   * @default "esm"
+   */
+  moduleFormat?: 'esm' | 'cjs';
+
+  /**
+   * Whether to enable hydration support: SSR output carries hydration keys
+   * and markers, and DOM output claims the server-rendered nodes instead of
+   * cloning templates
    * @default false
    */
   hydratable?: boolean;
 
+  /**
+   * Inject `generateHydrationScript()` at the end of `<head>` in hydratable
+   * SSR output, so entry modules rendering the document don't need
+   * `<HydrationScript />`
+   * @default false
+   */
+  hydrationScript?: boolean;
+
+  /**
+   * Experimental: render the imported components of hydratable SSR output
+   * as islands, between `<!--island:id-->` and `<!--/island-->` markers,
+   * and list them in the result's `islands` manifest so the client can
+   * hydrate each one on its own. Requires `generate: 'ssr'` and `hydratable`.
+   * @default false
+   */
+  islands?: boolean;
+
   /**
    * Whether to delegate events
    * @default true
@@ -28,17 +56,56 @@ export interface TransformOptions {
   delegateEvents?: boolean;
 
   /**
-   * Whether to wrap conditionals
+   * How delegated events are registered: `'call'` emits
+   * `delegateEvents([...])` in every module, `'export'` emits
+   * `export const _$delegatedEvents = [...]` so a bundler plugin can hoist a
+   * single deduplicated `delegateEvents` call per app
+   * @default 'call'
+   */
+  delegateEventsStrategy?: 'call' | 'export';
+
+  /**
+   * Memoize the test of conditional children (`{cond() ? <A /> : <B />}`,
+   * `{cond() && <A />}`) so a branch is only re-created when the condition
+   * flips
    * @default true
    */
   wrapConditionals?: boolean;
 
+  /**
+   * Keep JSX text as written. By default indentation and line breaks are
+   * trimmed and runs of whitespace collapse to one space, as in
+   * babel-plugin-jsx-dom-expressions
+   * @default false
+   */
+  preserveWhitespace?: boolean;
+
+  /**
+   * Helper that reactive bindings run in, imported from `moduleName`. Lets
+   * the output target another reactive library.
+   * @default 'effect'
+   */
+  effectWrapper?: string;
+
+  /**
+   * Helper that derived values are memoized with, imported from `moduleName`
+   * @default 'memo'
+   */
+  memoWrapper?: string;
+
   /**
    * Whether to pass context to custom elements
    * @default true
    */
   contextToCustomElements?: boolean;
 
+  /**
+   * Tags treated as custom elements even without a dash; an entry ending
+   * in `*` matches every tag with that prefix
+   * @default []
+   */
+  customElements?: string[];
+
   /**
    * Source filename
    * @default "input.jsx"
@@ -51,10 +118,84 @@ export interface TransformOptions {
    */
   sourceMap?: boolean;
 
+  /**
+   * Strip TypeScript syntax (type annotations, interfaces, type-only imports)
+   * and lower enums/namespaces before the JSX transform, so `.ts`/`.tsx`
+   * input comes out as plain JavaScript
+   * @default false
+   */
+  stripTypes?: boolean;
+
+  /**
+   * Minify the output: shorten the locals the compiler generates
+   * (`_el$`, `_tmpl$`) and drop whitespace and comments
+   * @default false
+   */
+  minify?: boolean;
+
+  /**
+   * Build templates with `document.createElement`/`appendChild` calls
+   * instead of `template()`, for Content Security Policies that block
+   * `innerHTML`
+   * @default false
+   */
+  cspSafe?: boolean;
+
+  /**
+   * Emit the runtime helpers that don't need Solid's reactive runtime
+   * (`template`, `setAttribute`, `style`, `classList`, `addEventListener`,
+   * `delegateEvents`) into the DOM output as plain functions instead of
+   * importing them from `solid-js/web`, so snippets that only use those run
+   * without any dependency
+   * @default false
+   */
+  inlineHelpers?: boolean;
+
+  /**
+   * Render every SSR element with an `ssrElement()` call instead of `ssr`
+   * tagged templates, so the output is built from props objects a server
+   * can post-process or stream
+   * @default false
+   */
+  ssrElementOnly?: boolean;
+
+  /**
+   * Resolve `isServer` (and `isDev`/`DEV` when `dev` is set) at compile time
+   * and drop the branches they make unreachable
+   * @default true
+   */
+  foldEnvironment?: boolean;
+
+  /**
+   * Whether the output targets Solid's development build, used to fold
+   * `isDev` and `DEV`; leave unset to keep those checks at runtime
+   */
+  dev?: boolean;
+
+  /**
+   * Constant replacements applied before the transform. Keys are global
+   * identifiers or member chains, values are JavaScript expressions:
+   * `{ __DEV__: 'false', 'import.meta.env.SSR': 'true' }`
+   */
+  define?: Record<string, string>;
+
+  /**
+   * Only transform files whose `@jsxImportSource` pragma names this source
+   * (e.g. `'solid-js'`), returning the others unchanged for another JSX
+   * transform
+   */
+  requireImportSource?: string;
+
   /**
    * Built-in components that receive special handling
    */
   builtIns?: string[];
+
+  /**
+   * Encoding of the source passed to `transformBuffer`
+   * @default "utf8"
+   */
+  encoding?: 'utf8' | 'latin1';
 }
 
 export interface TransformResult {
@@ -62,6 +203,59 @@ export interface TransformResult {
   code: string;
   /** Source map (if enabled) */
   map?: string;
+  /**
+   * `false` when the file has no JSX and `code` is the source unchanged, so
+   * bundler plugins can keep the module and skip its source map
+   */
+  transformed: boolean;
+  /**
+   * Parse and unsupported-syntax errors reported by the native binding.
+   * `transform` throws a `TransformParseError` instead of returning them.
+   */
+  errors?: ParseErrorInfo[];
+  /** The components rendered as islands, with the `islands` option */
+  islands?: IslandInfo[];
+}
+
+export interface IslandInfo {
+  /** Key in the `<!--island:id-->` boundary markers */
+  id: string;
+  /** Local name of the component */
+  component: string;
+  /** Module the component is imported from */
+  source: string;
+  /** Name the module exports it under, `default` for a default import */
+  export: string;
+  /** Byte offset of the JSX element in the source */
+  start: number;
+  /** Byte offset of the end of the JSX element */
+  end: number;
+}
+
+export interface ParseErrorInfo {
+  /** Source filename */
+  filename: string;
+  /** Error message from the parser */
+  message: string;
+  /** 1-based line of the error */
+  line: number;
+  /** 1-based column of the error */
+  column: number;
+  /** Excerpt of the source around the error */
+  codeFrame: string;
+}
+
+/**
+ * Error thrown by `transform` when the source fails to parse
+ */
+export interface TransformParseError extends SyntaxError {
+  filename: string;
+  line: number;
+  column: number;
+  loc: { line: number; column: number };
+  codeFrame: string;
+  /** All parse errors, including the first one */
+  errors: ParseErrorInfo[];
 }
 
 /**
@@ -69,9 +263,113 @@ export interface TransformResult {
  * @param source - The source code to transform
  * @param options - Transform options
  * @returns The transformed code and optional source map
+ * @throws {TransformParseError} When the source fails to parse
  */
 export function transform(source: string, options?: TransformOptions): TransformResult;
 
+export interface BufferTransformResult {
+  /** The transformed code, UTF-8 encoded */
+  code: Buffer;
+  /** Source map (if enabled) */
+  map?: string;
+  /** `false` when the file has no JSX and `code` is the source unchanged */
+  transformed: boolean;
+  /** The components rendered as islands, with the `islands` option */
+  islands?: IslandInfo[];
+}
+
+/**
+ * Transform JSX source code held in a buffer (e.g. from `fs.readFile`).
+ * UTF-8 input is read without a copy and the output is handed back as a
+ * buffer, which saves two string conversions per file in large builds.
+ * @param source - The source code, UTF-8 unless `options.encoding` is `'latin1'`
+ * @param options - Transform options
+ * @returns The transformed code as a buffer and optional source map
+ * @throws {TransformParseError} When the source fails to parse
+ */
+export function transformBuffer(source: Uint8Array, options?: TransformOptions): BufferTransformResult;
+
+export interface BatchFile {
+  /** Source filename, used in place of `options.filename` */
+  filename: string;
+  /** The source code to transform */
+  source: string;
+}
+
+/**
+ * Transform many files in parallel, off the main thread. A file that fails
+ * to parse doesn't reject the batch; its result carries the `errors`.
+ * @param files - Files to transform
+ * @param options - Transform options shared by every file
+ * @param onProgress - Called as each file finishes (in completion order),
+ *   with its position in `files` and the size of the batch
+ * @returns The results, in the order of `files`
+ * @throws When the options contradict each other
+ */
+export function transformBatch(
+  files: BatchFile[],
+  options?: TransformOptions,
+  onProgress?: (filename: string, index: number, total: number) => void
+): Promise<TransformResult[]>;
+
+/**
+ * Transform cache for watch mode and dev servers. Outputs are keyed by the
+ * source and the options (filename included); each file and configuration
+ * keeps its latest output, and failed transforms are not cached.
+ */
+export class TransformCache {
+  constructor();
+  /**
+   * Same as `transform`, reusing the cached output when the source and
+   * options are unchanged since the last call
+   * @throws {TransformParseError} When the source fails to parse
+   */
+  transform(source: string, options?: TransformOptions): TransformResult;
+  /** Number of cached outputs */
+  readonly size: number;
+  /** Drop every cached output */
+  clear(): void;
+}
+
+export interface TemplateAttribute {
+  /** Attribute name as written, `...` for spreads */
+  name: string;
+  /** Value when it is a static string, number or boolean */
+  value?: string;
+  /** Whether the value is only known at runtime */
+  dynamic: boolean;
+}
+
+export interface TemplateElement {
+  tag: string;
+  attributes: TemplateAttribute[];
+  /** Statically known class names (`class`, `classList` keys, `class:name`) */
+  classes: string[];
+  /** Static text children */
+  text: string[];
+  /** Native element children */
+  children: TemplateElement[];
+  /** Whether the element has expression, component or fragment children */
+  hasDynamicChildren: boolean;
+}
+
+export interface ExtractedTemplate {
+  /** 1-based line of the root element */
+  line: number;
+  /** 1-based column of the root element */
+  column: number;
+  root: TemplateElement;
+}
+
+/**
+ * Collect the static templates in a file without generating code
+ * @param source - The source code to analyze
+ * @param options - Transform options (only `filename` is used)
+ * @returns Template roots in source order
+ * @throws {TransformParseError} When the source fails to parse
+ */
+export function extractTemplates(source: string, options?: TransformOptions): ExtractedTemplate[];
+
 /**
  * Low-level transform function from the native binding.
  */
@@ -79,11 +377,27 @@ export function transformJsx(source: string, options?: {
   moduleName?: string;
   generate?: 'dom' | 'ssr' | 'universal' | string;
   hydratable?: boolean;
+  hydrationScript?: boolean;
+  islands?: boolean;
   delegateEvents?: boolean;
+  delegateEventsStrategy?: 'call' | 'export' | string;
   wrapConditionals?: boolean;
+  preserveWhitespace?: boolean;
+  effectWrapper?: string;
+  memoWrapper?: string;
   contextToCustomElements?: boolean;
+  customElements?: string[];
   filename?: string;
   sourceMap?: boolean;
+  stripTypes?: boolean;
+  minify?: boolean;
+  cspSafe?: boolean;
+  inlineHelpers?: boolean;
+  ssrElementOnly?: boolean;
+  foldEnvironment?: boolean;
+  dev?: boolean;
+  define?: Record<string, string>;
+  requireImportSource?: string;
 } | null): TransformResult;
 
 export interface PresetResult {
@@ -106,6 +420,10 @@ export const defaultOptions: Required<Omit<TransformOptions, 'filename'>>;
 
 declare const _default: {
   transform: typeof transform;
+  transformBuffer: typeof transformBuffer;
+  transformBatch: typeof transformBatch;
+  TransformCache: typeof TransformCache;
+  extractTemplates: typeof extractTemplates;
   preset: typeof preset;
   defaultOptions: typeof defaultOptions;
   transformJsx: typeof transformJsx;
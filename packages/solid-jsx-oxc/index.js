@@ -61,17 +61,30 @@ export const defaultOptions = {
   ],
   contextToCustomElements: true,
   wrapConditionals: true,
+  preserveWhitespace: false,
+  effectWrapper: 'effect',
+  memoWrapper: 'memo',
   generate: 'dom', // 'dom' | 'ssr' | 'universal'
+  moduleFormat: 'esm', // 'esm' | 'cjs'
   hydratable: false,
+  hydrationScript: false,
+  islands: false,
   delegateEvents: true,
+  delegateEventsStrategy: 'call', // 'call' | 'export'
   sourceMap: false,
+  stripTypes: false,
+  minify: false,
+  cspSafe: false,
+  inlineHelpers: false,
+  ssrElementOnly: false,
+  foldEnvironment: true,
 };
 
 /**
  * Transform JSX source code
  * @param {string} source - The source code to transform
  * @param {object} options - Transform options
- * @returns {{ code: string, map?: string }}
+ * @returns {{ code: string, map?: string, transformed: boolean }}
  */
 export function transform(source, options = {}) {
   if (!nativeBinding) {
@@ -82,7 +95,134 @@ export function transform(source, options = {}) {
 
   // NAPI-RS automatically converts camelCase (JS) to snake_case (Rust)
   // so we can pass options directly without manual conversion
-  return nativeBinding.transformJsx(source, mergedOptions);
+  const result = nativeBinding.transformJsx(source, mergedOptions);
+
+  if (result.errors && result.errors.length > 0) {
+    throw createParseError(result.errors);
+  }
+
+  return result;
+}
+
+/**
+ * Transform JSX source code held in a buffer, e.g. straight from
+ * `fs.readFile`, without decoding it to a string first
+ * @param {Uint8Array} source - The source code, UTF-8 (or `encoding: 'latin1'`)
+ * @param {object} options - Transform options
+ * @returns {{ code: Buffer, map?: string, transformed: boolean }}
+ */
+export function transformBuffer(source, options = {}) {
+  if (!nativeBinding) {
+    throw new Error('solid-jsx-oxc: Native module not loaded. Ensure it is built for your platform.');
+  }
+
+  const mergedOptions = { ...defaultOptions, ...options };
+  const result = nativeBinding.transformJsxBuffer(source, mergedOptions);
+
+  if (result.errors && result.errors.length > 0) {
+    throw createParseError(result.errors);
+  }
+
+  return result;
+}
+
+/**
+ * Transform many files in parallel, off the main thread. Failed files don't
+ * reject the batch: their result has `errors` instead of code.
+ * @param {Array<{ filename: string, source: string }>} files - Files to transform
+ * @param {object} options - Transform options shared by every file; `filename` comes from each file
+ * @param {(filename: string, index: number, total: number) => void} [onProgress] - Called as
+ *   each file finishes, with its position in `files`
+ * @returns {Promise<Array<{ code: string, map?: string, transformed: boolean, errors?: Array<object> }>>}
+ *   Results in the order of `files`
+ */
+export function transformBatch(files, options = {}, onProgress) {
+  if (!nativeBinding) {
+    throw new Error('solid-jsx-oxc: Native module not loaded. Ensure it is built for your platform.');
+  }
+
+  return nativeBinding.transformJsxBatch(files, { ...defaultOptions, ...options }, onProgress);
+}
+
+/**
+ * Transform cache for watch mode and dev servers: a file whose source and
+ * options are unchanged since its last transform is not compiled again
+ */
+export class TransformCache {
+  #native;
+
+  constructor() {
+    if (!nativeBinding) {
+      throw new Error('solid-jsx-oxc: Native module not loaded. Ensure it is built for your platform.');
+    }
+    this.#native = new nativeBinding.TransformCache();
+  }
+
+  /**
+   * Same as `transform`, reusing the cached output when possible
+   * @param {string} source - The source code to transform
+   * @param {object} options - Transform options
+   * @returns {{ code: string, map?: string, transformed: boolean }}
+   */
+  transform(source, options = {}) {
+    const result = this.#native.transform(source, { ...defaultOptions, ...options });
+
+    if (result.errors && result.errors.length > 0) {
+      throw createParseError(result.errors);
+    }
+
+    return result;
+  }
+
+  /** Number of cached outputs */
+  get size() {
+    return this.#native.size;
+  }
+
+  /** Drop every cached output */
+  clear() {
+    this.#native.clear();
+  }
+}
+
+/**
+ * Collect the static templates (tags, attributes, classes, text) in a file
+ * without generating code
+ * @param {string} source - The source code to analyze
+ * @param {object} options - Transform options, validated as for `transform`; only `filename` affects the result
+ * @returns {Array<object>} Template roots in source order
+ */
+export function extractTemplates(source, options = {}) {
+  if (!nativeBinding) {
+    throw new Error('solid-jsx-oxc: Native module not loaded. Ensure it is built for your platform.');
+  }
+
+  const result = nativeBinding.extractJsxTemplates(source, options);
+
+  if (result.errors && result.errors.length > 0) {
+    throw createParseError(result.errors);
+  }
+
+  return result.templates;
+}
+
+/**
+ * Build a SyntaxError from the parse errors reported by the native binding
+ * @param {Array<{ filename: string, message: string, line: number, column: number, codeFrame: string }>} errors
+ * @returns {SyntaxError}
+ */
+function createParseError(errors) {
+  const [first] = errors;
+  const error = new SyntaxError(
+    `${first.filename}:${first.line}:${first.column}: ${first.message}\n\n${first.codeFrame}`
+  );
+  error.filename = first.filename;
+  error.line = first.line;
+  error.column = first.column;
+  error.loc = { line: first.line, column: first.column };
+  error.codeFrame = first.codeFrame;
+  error.errors = errors;
+  return error;
 }
 
 /**
@@ -111,6 +251,10 @@ export const transformJsx = nativeBinding ? nativeBinding.transformJsx : null;
 // Default export for convenience
 export default {
   transform,
+  transformBuffer,
+  transformBatch,
+  TransformCache,
+  extractTemplates,
   preset,
   defaultOptions,
   transformJsx,
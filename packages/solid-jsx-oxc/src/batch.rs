@@ -0,0 +1,107 @@
+//! Transforming many files at once
+//!
+//! Production builds and prebundling passes know the whole set of modules up
+//! front. [`transform_files`] spreads them over threads and reports each file
+//! as soon as it is done, so a build tool can render progress and start
+//! downstream work (writing, bundling) before the whole batch finishes.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use common::TransformOptions;
+
+use crate::{transform, TransformError, TransformOutput};
+
+/// Transform `files`, given as `(filename, source)` pairs, on all available
+/// threads. `options` builds the options of each file from its filename.
+///
+/// `on_progress(filename, index, total)` is called from the worker threads
+/// as each file finishes, in completion order, with `index` the position of
+/// the file in `files`. The results are returned in input order. A file that
+/// fails, including one whose options are rejected or that makes the
+/// compiler panic, gets its own error and doesn't stop the others.
+pub fn transform_files<'f, N, S, O, P>(
+    files: &'f [(N, S)],
+    options: O,
+    on_progress: P,
+) -> Vec<Result<TransformOutput, TransformError>>
+where
+    N: AsRef<str> + Sync,
+    S: AsRef<str> + Sync,
+    O: Fn(&'f str) -> Result<TransformOptions<'f>, TransformError> + Sync,
+    P: Fn(&str, usize, usize) + Sync,
+{
+    let total = files.len();
+    let run = |index: usize| {
+        let (filename, source) = &files[index];
+        let filename = filename.as_ref();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            transform(source.as_ref(), Some(options(filename)?))
+        }))
+        .unwrap_or_else(|payload| Err(internal_error(filename, payload.as_ref())));
+        on_progress(filename, index, total);
+        result
+    };
+
+    let threads = thread::available_parallelism()
+        .map_or(1, |threads| threads.get())
+        .min(total);
+    if threads <= 1 {
+        return (0..total).map(run).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<Option<Result<TransformOutput, TransformError>>> =
+        (0..total).map(|_| None).collect();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= total {
+                            break;
+                        }
+                        results.push((index, run(index)));
+                    }
+                    results
+                })
+            })
+            .collect();
+        // A worker only dies if `on_progress` panics; the files it had
+        // finished are lost with it and reported below
+        for finished in workers.into_iter().filter_map(|worker| worker.join().ok()) {
+            for (index, result) in finished {
+                results[index] = Some(result);
+            }
+        }
+    });
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(index, result)| {
+            result.unwrap_or_else(|| {
+                Err(TransformError::Internal {
+                    filename: files[index].0.as_ref().to_string(),
+                    message: "the worker transforming this file panicked".to_string(),
+                })
+            })
+        })
+        .collect()
+}
+
+/// The error for a file whose transform panicked with `payload`
+fn internal_error(filename: &str, payload: &(dyn Any + Send)) -> TransformError {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "the transform panicked".to_string());
+    TransformError::Internal {
+        filename: filename.to_string(),
+        message,
+    }
+}
@@ -0,0 +1,96 @@
+//! Transform result cache
+//!
+//! Watch-mode rebuilds and dev servers transform the same files over and
+//! over. `TransformCache` keeps the last output of every file and
+//! configuration, keyed by a hash of the source and
+//! [`TransformOptions::cache_key`], so a file is only compiled again when its
+//! content or the options change.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use common::TransformOptions;
+
+use crate::{transform, TransformError, TransformOutput};
+
+#[derive(Debug)]
+struct CacheEntry {
+    source_hash: u64,
+    output: Arc<TransformOutput>,
+}
+
+/// Caches transform outputs across rebuilds.
+///
+/// Each combination of options (filename included) keeps only its latest
+/// output, so editing a file replaces its entry instead of adding one.
+/// Failed transforms are not cached.
+#[derive(Debug, Default)]
+pub struct TransformCache {
+    entries: Mutex<HashMap<u64, CacheEntry>>,
+}
+
+impl TransformCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Like [`transform`], reusing the cached output when `source` and
+    /// `options` are unchanged since the last call
+    pub fn transform(
+        &self,
+        source: &str,
+        options: Option<TransformOptions>,
+    ) -> Result<Arc<TransformOutput>, TransformError> {
+        let options = options.unwrap_or_else(TransformOptions::solid_defaults);
+        let options_key = options.cache_key();
+        let source_hash = hash_source(source);
+        if let Some(output) = self.lookup(options_key, source_hash) {
+            return Ok(output);
+        }
+
+        let output = Arc::new(transform(source, Some(options))?);
+        self.entries.lock().unwrap().insert(
+            options_key,
+            CacheEntry {
+                source_hash,
+                output: Arc::clone(&output),
+            },
+        );
+        Ok(output)
+    }
+
+    /// The cached output for `source` and `options`, if there is one
+    pub fn get(&self, source: &str, options: &TransformOptions) -> Option<Arc<TransformOutput>> {
+        self.lookup(options.cache_key(), hash_source(source))
+    }
+
+    fn lookup(&self, options_key: u64, source_hash: u64) -> Option<Arc<TransformOutput>> {
+        self.entries
+            .lock()
+            .unwrap()
+            .get(&options_key)
+            .filter(|entry| entry.source_hash == source_hash)
+            .map(|entry| Arc::clone(&entry.output))
+    }
+
+    /// Number of cached outputs
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+fn hash_source(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
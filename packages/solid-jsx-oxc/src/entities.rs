@@ -0,0 +1,43 @@
+//! Character references in JSX attribute strings
+//!
+//! JSX attribute strings may spell characters as HTML references,
+//! `title="a &amp; b"`. Babel decodes them while parsing, oxc keeps the
+//! string as written, so they are decoded here before the JSX transform,
+//! which then escapes each value exactly once. Text children are decoded
+//! where they are trimmed instead, since trimming works on the raw text.
+//! Only strings are replaced, so the scoping of the program stays valid.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::*;
+use oxc_ast::AstBuilder;
+use oxc_ast_visit::{walk_mut, VisitMut};
+
+use common::decode_html_entities;
+
+/// Decode the character references of every JSX attribute string in
+/// `program`
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "decode_entities", skip_all)
+)]
+pub(crate) fn decode_attribute_entities<'a>(allocator: &'a Allocator, program: &mut Program<'a>) {
+    let mut decoder = EntityDecoder {
+        ast: AstBuilder::new(allocator),
+    };
+    decoder.visit_program(program);
+}
+
+struct EntityDecoder<'a> {
+    ast: AstBuilder<'a>,
+}
+
+impl<'a> VisitMut<'a> for EntityDecoder<'a> {
+    fn visit_jsx_attribute(&mut self, attr: &mut JSXAttribute<'a>) {
+        if let Some(JSXAttributeValue::StringLiteral(lit)) = &mut attr.value {
+            if lit.value.contains('&') {
+                lit.value = self.ast.atom(&decode_html_entities(&lit.value));
+            }
+        }
+        walk_mut::walk_jsx_attribute(self, attr);
+    }
+}
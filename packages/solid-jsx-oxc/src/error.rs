@@ -0,0 +1,214 @@
+//! Structured transform errors with rendered code frames
+
+use std::fmt;
+
+use common::OptionsError;
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::Span;
+
+/// Number of source lines shown above the error line in a code frame
+const LINES_ABOVE: usize = 2;
+/// Number of source lines shown below the error line in a code frame
+const LINES_BELOW: usize = 3;
+
+/// An error at a location of the transformed source: a syntax error, or
+/// syntax the transform doesn't support
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Source filename (as given in the options)
+    pub filename: String,
+    /// Error message
+    pub message: String,
+    /// 1-based line of the error
+    pub line: usize,
+    /// 1-based column of the error (in characters)
+    pub column: usize,
+    /// Byte offset where the error starts
+    pub start: usize,
+    /// Byte offset where the error ends
+    pub end: usize,
+    /// Excerpt of the source around the error, babel-style
+    pub code_frame: String,
+}
+
+impl ParseError {
+    pub(crate) fn from_diagnostic(
+        diagnostic: &OxcDiagnostic,
+        source: &str,
+        filename: &str,
+    ) -> Self {
+        let (start, end) = diagnostic
+            .labels
+            .as_ref()
+            .and_then(|labels| labels.first())
+            .map(|label| (label.offset(), label.offset() + label.len()))
+            .unwrap_or((0, 0));
+        Self::new(diagnostic.message.to_string(), start, end, source, filename)
+    }
+
+    /// An error about `span` of `source`
+    pub(crate) fn at(message: impl Into<String>, span: Span, source: &str, filename: &str) -> Self {
+        Self::new(
+            message.into(),
+            span.start as usize,
+            span.end as usize,
+            source,
+            filename,
+        )
+    }
+
+    fn new(message: String, start: usize, end: usize, source: &str, filename: &str) -> Self {
+        let (line, column) = line_col(source, start);
+        Self {
+            filename: filename.to_string(),
+            message,
+            line,
+            column,
+            start,
+            end,
+            code_frame: code_frame(source, start, end),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}\n\n{}",
+            self.filename, self.line, self.column, self.message, self.code_frame
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why a transform produced no output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransformError {
+    /// The source doesn't parse
+    Parse(Vec<ParseError>),
+    /// The source parses, but uses JSX the compiler can't translate without
+    /// changing its meaning
+    UnsupportedSyntax(Vec<ParseError>),
+    /// The options contradict each other
+    InvalidOptions(OptionsError),
+    /// The compiler panicked on the file; a bug in the transform, reported
+    /// for the one file instead of aborting the others in a batch
+    Internal {
+        /// Source filename
+        filename: String,
+        /// The panic message
+        message: String,
+    },
+}
+
+impl TransformError {
+    /// The located errors, empty for [`TransformError::InvalidOptions`] and
+    /// [`TransformError::Internal`]
+    pub fn errors(&self) -> &[ParseError] {
+        match self {
+            Self::Parse(errors) | Self::UnsupportedSyntax(errors) => errors,
+            Self::InvalidOptions(_) | Self::Internal { .. } => &[],
+        }
+    }
+}
+
+impl fmt::Display for TransformError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(errors) | Self::UnsupportedSyntax(errors) => {
+                for (i, error) in errors.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str("\n\n")?;
+                    }
+                    write!(f, "{error}")?;
+                }
+                Ok(())
+            }
+            Self::InvalidOptions(error) => write!(f, "invalid options: {error}"),
+            Self::Internal { filename, message } => {
+                write!(f, "{filename}: internal compiler error: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TransformError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidOptions(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<OptionsError> for TransformError {
+    fn from(error: OptionsError) -> Self {
+        Self::InvalidOptions(error)
+    }
+}
+
+/// Clamp `offset` to the source and back it up to a char boundary
+fn clamp_offset(source: &str, offset: usize) -> usize {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// 1-based (line, column) of a byte offset
+pub(crate) fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..clamp_offset(source, offset)];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+/// Render the lines around `start..end` with a `>` gutter marker on the
+/// error line and carets under the error span.
+pub fn code_frame(source: &str, start: usize, end: usize) -> String {
+    let start = clamp_offset(source, start);
+    let end = clamp_offset(source, end).max(start);
+    let (line, column) = line_col(source, start);
+
+    let lines: Vec<&str> = source.split('\n').map(|l| l.trim_end_matches('\r')).collect();
+    let first = line.saturating_sub(LINES_ABOVE).max(1);
+    let last = (line + LINES_BELOW).min(lines.len());
+    let gutter_width = last.to_string().len();
+
+    // Underline up to the end of the error line
+    let line_text = lines[line - 1];
+    let line_start_col = column - 1;
+    let span_chars = source[start..end].split('\n').next().unwrap_or("").chars().count();
+    let max_chars = line_text.chars().count().saturating_sub(line_start_col);
+    let carets = span_chars.min(max_chars).max(1);
+
+    let mut frame = String::new();
+    for number in first..=last {
+        let text = lines[number - 1];
+        let marker = if number == line { '>' } else { ' ' };
+        if text.is_empty() {
+            frame.push_str(&format!("{} {:>gutter_width$} |\n", marker, number));
+        } else {
+            frame.push_str(&format!("{} {:>gutter_width$} | {}\n", marker, number, text));
+        }
+
+        if number == line {
+            let indent: String = line_text
+                .chars()
+                .take(line_start_col)
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            frame.push_str(&format!(
+                "  {:>gutter_width$} | {}{}\n",
+                "",
+                indent,
+                "^".repeat(carets)
+            ));
+        }
+    }
+    frame.pop();
+    frame
+}
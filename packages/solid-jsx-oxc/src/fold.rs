@@ -0,0 +1,417 @@
+//! Compile-time constant folding
+//!
+//! Resolves checks on Solid's environment flags at compile time, the way the
+//! Solid Vite plugin does with esbuild's `define`. When the generate mode is
+//! known, `isServer` from `solid-js/web` is a constant; with `dev` set,
+//! `isDev` from `solid-js/web` and `DEV` from `solid-js` are too. The
+//! user's `define` replacements (`__DEV__`, `import.meta.env.SSR`) are
+//! substituted in the same pass. Branches made unreachable are removed before
+//! the JSX transform, so their templates are never generated, and defined
+//! literals reach the static analysis of the JSX transform.
+//!
+//! Only expressions built from the flags, `!`, `&&`, `||` and boolean
+//! literals are folded, so the pass never drops code with side effects.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use oxc_allocator::{Allocator, CloneIn, TakeIn, Vec as ArenaVec};
+use oxc_ast::ast::*;
+use oxc_ast::AstBuilder;
+use oxc_ast_visit::{walk_mut, VisitMut};
+use oxc_parser::Parser;
+use oxc_semantic::{Scoping, SemanticBuilder, SymbolId};
+use oxc_span::{SourceType, SPAN};
+use oxc_syntax::identifier::is_identifier_name;
+use oxc_syntax::operator::{LogicalOperator, UnaryOperator};
+
+use common::{GenerateMode, TransformOptions};
+
+/// What is known about a flag at compile time
+#[derive(Clone, Copy)]
+struct Flag {
+    truthy: bool,
+    /// Exact value when it is a boolean (`DEV` is an object or `undefined`)
+    value: Option<bool>,
+}
+
+impl Flag {
+    fn boolean(value: bool) -> Self {
+        Self {
+            truthy: value,
+            value: Some(value),
+        }
+    }
+}
+
+/// A `define` replacement: a global identifier or member chain and the
+/// expression it is replaced with
+struct Define<'a> {
+    path: Vec<&'a str>,
+    value: Expression<'a>,
+}
+
+/// Fold environment flag checks and apply `define` replacements in `program`
+pub fn fold_constants<'a>(
+    allocator: &'a Allocator,
+    program: &mut Program<'a>,
+    options: &TransformOptions<'a>,
+) {
+    fold_constants_with_scoping(allocator, program, options, &mut None);
+}
+
+/// [`fold_constants`] taking the scoping of `program` from `scoping` instead
+/// of building it. The scoping is left in place when nothing gets folded and
+/// taken otherwise, as folding makes it stale.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "fold_constants", skip_all)
+)]
+pub(crate) fn fold_constants_with_scoping<'a>(
+    allocator: &'a Allocator,
+    program: &mut Program<'a>,
+    options: &TransformOptions<'a>,
+    scoping: &mut Option<Scoping>,
+) {
+    let defines = parse_defines(allocator, options);
+    let imports_solid = options.fold_environment
+        && program.body.iter().any(|statement| {
+            matches!(statement, Statement::ImportDeclaration(import)
+                if matches!(import.source.value.as_str(), "solid-js" | "solid-js/web"))
+        });
+    if !imports_solid && defines.is_empty() {
+        return;
+    }
+    // Binds the import specifiers to symbols and resolves references
+    let scoping = scoping.take().unwrap_or_else(|| {
+        SemanticBuilder::new()
+            .build(program)
+            .semantic
+            .into_scoping()
+    });
+    let flags = if imports_solid {
+        collect_flags(program, options)
+    } else {
+        HashMap::new()
+    };
+    if flags.is_empty() && defines.is_empty() {
+        return;
+    }
+    let mut folder = ConstantFolder {
+        allocator,
+        ast: AstBuilder::new(allocator),
+        scoping,
+        flags,
+        defines,
+    };
+    folder.visit_program(program);
+}
+
+/// Parse the `define` option; keys that aren't identifier paths and values
+/// that aren't expressions are ignored
+fn parse_defines<'a>(allocator: &'a Allocator, options: &TransformOptions<'a>) -> Vec<Define<'a>> {
+    let mut defines: Vec<Define<'a>> = options
+        .define
+        .iter()
+        .filter_map(|(key, value)| {
+            let path: Vec<&'a str> = allocator.alloc_str(key).split('.').collect();
+            if !path.iter().all(|part| is_identifier_name(part)) {
+                return None;
+            }
+            let source = allocator.alloc_str(value);
+            let value = Parser::new(allocator, source, SourceType::mjs())
+                .parse_expression()
+                .ok()?;
+            Some(Define { path, value })
+        })
+        .collect();
+    // Longer paths first, so `process.env.NODE_ENV` wins over `process.env`
+    defines.sort_by_key(|define| Reverse(define.path.len()));
+    defines
+}
+
+/// Map the local bindings of imported environment flags to their values
+fn collect_flags(program: &Program<'_>, options: &TransformOptions<'_>) -> HashMap<SymbolId, Flag> {
+    let is_server = match options.generate {
+        GenerateMode::Dom => Some(false),
+        GenerateMode::Ssr => Some(true),
+        GenerateMode::Universal => None,
+    };
+
+    let mut flags = HashMap::new();
+    for statement in &program.body {
+        let Statement::ImportDeclaration(import) = statement else {
+            continue;
+        };
+        if import.import_kind.is_type() {
+            continue;
+        }
+        for specifier in import.specifiers.iter().flatten() {
+            let ImportDeclarationSpecifier::ImportSpecifier(specifier) = specifier else {
+                continue;
+            };
+            let flag = match (
+                import.source.value.as_str(),
+                specifier.imported.name().as_str(),
+            ) {
+                ("solid-js/web", "isServer") => is_server.map(Flag::boolean),
+                ("solid-js/web", "isDev") => options.dev.map(Flag::boolean),
+                ("solid-js", "DEV") => options.dev.map(|dev| Flag {
+                    truthy: dev,
+                    value: None,
+                }),
+                _ => None,
+            };
+            if let (Some(flag), Some(symbol_id)) = (flag, specifier.local.symbol_id.get()) {
+                flags.insert(symbol_id, flag);
+            }
+        }
+    }
+    flags
+}
+
+struct ConstantFolder<'a> {
+    allocator: &'a Allocator,
+    ast: AstBuilder<'a>,
+    scoping: Scoping,
+    flags: HashMap<SymbolId, Flag>,
+    defines: Vec<Define<'a>>,
+}
+
+impl<'a> ConstantFolder<'a> {
+    fn symbol(&self, ident: &IdentifierReference<'a>) -> Option<SymbolId> {
+        self.scoping
+            .get_reference(ident.reference_id.get()?)
+            .symbol_id()
+    }
+
+    /// Whether `expr` is the global identifier or member chain `path`
+    fn matches_path(&self, expr: &Expression<'a>, path: &[&str]) -> bool {
+        match expr {
+            Expression::Identifier(ident) => {
+                path == [ident.name.as_str()] && self.symbol(ident).is_none()
+            }
+            Expression::MetaProperty(meta) => {
+                path == [meta.meta.name.as_str(), meta.property.name.as_str()]
+            }
+            Expression::StaticMemberExpression(member) => {
+                path.split_last().is_some_and(|(last, object)| {
+                    *last == member.property.name.as_str()
+                        && self.matches_path(&member.object, object)
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// The `define` replacement for `expr`
+    fn define(&self, expr: &Expression<'a>) -> Option<&Expression<'a>> {
+        if !matches!(
+            expr,
+            Expression::Identifier(_) | Expression::StaticMemberExpression(_)
+        ) {
+            return None;
+        }
+        self.defines
+            .iter()
+            .find(|define| self.matches_path(expr, &define.path))
+            .map(|define| &define.value)
+    }
+
+    /// The known value of an environment flag or boolean `define`
+    fn flag(&self, expr: &Expression<'a>) -> Option<Flag> {
+        if let Expression::Identifier(ident) = expr {
+            if let Some(flag) = self
+                .symbol(ident)
+                .and_then(|symbol_id| self.flags.get(&symbol_id))
+            {
+                return Some(*flag);
+            }
+        }
+        match self.define(expr)? {
+            Expression::BooleanLiteral(lit) => Some(Flag::boolean(lit.value)),
+            _ => None,
+        }
+    }
+
+    /// Whether `expr` reads one of the flags
+    fn mentions_flag(&self, expr: &Expression<'a>) -> bool {
+        match expr {
+            Expression::ParenthesizedExpression(paren) => self.mentions_flag(&paren.expression),
+            Expression::Identifier(_) | Expression::StaticMemberExpression(_) => {
+                self.flag(expr).is_some()
+            }
+            Expression::UnaryExpression(unary) => self.mentions_flag(&unary.argument),
+            Expression::LogicalExpression(logical) => {
+                self.mentions_flag(&logical.left) || self.mentions_flag(&logical.right)
+            }
+            _ => false,
+        }
+    }
+
+    /// Evaluate a side-effect free condition built from flags and literals
+    fn evaluate(&self, expr: &Expression<'a>) -> Option<Flag> {
+        match expr {
+            Expression::ParenthesizedExpression(paren) => self.evaluate(&paren.expression),
+            Expression::Identifier(_) | Expression::StaticMemberExpression(_) => self.flag(expr),
+            Expression::BooleanLiteral(lit) => Some(Flag::boolean(lit.value)),
+            Expression::UnaryExpression(unary) if unary.operator == UnaryOperator::LogicalNot => {
+                let argument = self.evaluate(&unary.argument)?;
+                Some(Flag::boolean(!argument.truthy))
+            }
+            Expression::LogicalExpression(logical) => {
+                let left = self.evaluate(&logical.left)?;
+                match logical.operator {
+                    LogicalOperator::And if !left.truthy => Some(left),
+                    LogicalOperator::Or if left.truthy => Some(left),
+                    LogicalOperator::And | LogicalOperator::Or => self.evaluate(&logical.right),
+                    LogicalOperator::Coalesce => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Known truthiness of a condition that reads a flag
+    fn condition(&self, expr: &Expression<'a>) -> Option<bool> {
+        if !self.mentions_flag(expr) {
+            return None;
+        }
+        self.evaluate(expr).map(|flag| flag.truthy)
+    }
+
+    /// Replacement for an expression that can be folded
+    fn fold_expression(&self, expr: &mut Expression<'a>) -> Option<Expression<'a>> {
+        match expr {
+            Expression::Identifier(_) => {
+                let value = self.flag(expr)?.value?;
+                Some(self.ast.expression_boolean_literal(SPAN, value))
+            }
+            Expression::UnaryExpression(unary) if unary.operator == UnaryOperator::LogicalNot => {
+                let truthy = self.condition(&unary.argument)?;
+                Some(self.ast.expression_boolean_literal(SPAN, !truthy))
+            }
+            Expression::ConditionalExpression(conditional) => {
+                Some(if self.condition(&conditional.test)? {
+                    conditional.consequent.take_in(self.ast)
+                } else {
+                    conditional.alternate.take_in(self.ast)
+                })
+            }
+            Expression::LogicalExpression(logical) => {
+                if !self.mentions_flag(&logical.left) {
+                    return None;
+                }
+                let left = self.evaluate(&logical.left)?;
+                let short_circuits = match logical.operator {
+                    LogicalOperator::And => !left.truthy,
+                    LogicalOperator::Or => left.truthy,
+                    LogicalOperator::Coalesce => return None,
+                };
+                if short_circuits {
+                    // The result is the left value, which is only known for booleans
+                    Some(self.ast.expression_boolean_literal(SPAN, left.value?))
+                } else {
+                    Some(logical.right.take_in(self.ast))
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// The statements an `if` on a flag reduces to, or `None` when the
+    /// statement can't be folded
+    fn fold_if(&self, statement: &mut Statement<'a>) -> Option<Option<Statement<'a>>> {
+        let Statement::IfStatement(if_statement) = statement else {
+            return None;
+        };
+        Some(if self.condition(&if_statement.test)? {
+            Some(if_statement.consequent.take_in(self.ast))
+        } else {
+            if_statement.alternate.take()
+        })
+    }
+}
+
+/// Whether the statements of a block can be moved into the enclosing list
+/// without changing scoping
+fn can_unwrap_block(block: &BlockStatement<'_>) -> bool {
+    block.body.iter().all(|statement| match statement {
+        Statement::VariableDeclaration(decl) => decl.kind == VariableDeclarationKind::Var,
+        Statement::FunctionDeclaration(_) | Statement::ClassDeclaration(_) => false,
+        _ => true,
+    })
+}
+
+impl<'a> VisitMut<'a> for ConstantFolder<'a> {
+    fn visit_statements(&mut self, statements: &mut ArenaVec<'a, Statement<'a>>) {
+        let mut folded = self.ast.vec_with_capacity(statements.len());
+        let mut pending: Vec<Statement<'a>> = statements.drain(..).rev().collect();
+        while let Some(mut statement) = pending.pop() {
+            match self.fold_if(&mut statement) {
+                Some(Some(Statement::BlockStatement(block))) if can_unwrap_block(&block) => {
+                    pending.extend(block.unbox().body.into_iter().rev());
+                }
+                Some(Some(branch)) => pending.push(branch),
+                Some(None) => {}
+                None => folded.push(statement),
+            }
+        }
+        *statements = folded;
+        walk_mut::walk_statements(self, statements);
+    }
+
+    fn visit_statement(&mut self, statement: &mut Statement<'a>) {
+        while let Some(branch) = self.fold_if(statement) {
+            *statement = branch.unwrap_or_else(|| self.ast.statement_empty(SPAN));
+        }
+        walk_mut::walk_statement(self, statement);
+    }
+
+    fn visit_object_property(&mut self, property: &mut ObjectProperty<'a>) {
+        // `{ isServer }` keeps the key when the value is replaced
+        if property.shorthand {
+            property.shorthand = self.define(&property.value).is_none()
+                && self
+                    .flag(&property.value)
+                    .is_none_or(|flag| flag.value.is_none());
+        }
+        walk_mut::walk_object_property(self, property);
+    }
+
+    fn visit_jsx_attribute_value(&mut self, value: &mut JSXAttributeValue<'a>) {
+        // A defined string becomes a plain attribute string, which the JSX
+        // transform inlines into the template
+        if let JSXAttributeValue::ExpressionContainer(container) = value {
+            if let Some(Expression::StringLiteral(lit)) = container
+                .expression
+                .as_expression()
+                .and_then(|expr| self.define(expr))
+            {
+                if !lit.value.contains('"') {
+                    *value = JSXAttributeValue::StringLiteral(
+                        self.ast.alloc_string_literal(SPAN, lit.value, None),
+                    );
+                    return;
+                }
+            }
+        }
+        walk_mut::walk_jsx_attribute_value(self, value);
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        loop {
+            if let Some(folded) = self.fold_expression(expr) {
+                *expr = folded;
+            } else if let Some(value) = self.define(expr) {
+                // Replacements are not visited again, so a value may refer
+                // to its own key
+                *expr = value.clone_in(self.allocator);
+                return;
+            } else {
+                break;
+            }
+        }
+        walk_mut::walk_expression(self, expr);
+    }
+}
@@ -9,18 +9,70 @@
 //! use solid_jsx_oxc::{transform, TransformOptions};
 //!
 //! let source = r#"<div class="hello">{count()}</div>"#;
-//! let result = transform(source, None);
+//! let result = transform(source, None).expect("valid JSX");
 //! println!("{}", result.code);
+//!
+//! let options = TransformOptions::builder()
+//!     .generate(common::GenerateMode::Ssr)
+//!     .hydratable(true)
+//!     .build()
+//!     .expect("valid options");
+//! match transform(source, Some(options)) {
+//!     Ok(result) => println!("{}", result.code),
+//!     Err(error) => eprintln!("{error}"),
+//! }
 //! ```
+//!
+//! Callers that already parsed the file with oxc can run the transform on
+//! their `Program` with [`transform_program`] and print it themselves.
+
+mod batch;
+mod cache;
+mod entities;
+mod error;
+mod fold;
+mod minify;
+mod pragma;
+mod prescan;
+mod spread;
+mod templates;
+mod typescript;
+mod unsupported;
+mod whitespace;
+
+pub use batch::transform_files;
+pub use cache::TransformCache;
+pub use common::{GenerateMode, Island, OptionsError, TransformOptions, TransformOptionsBuilder};
+pub use error::{code_frame, ParseError, TransformError};
+pub use fold::fold_constants;
+pub use minify::mangle_generated_locals;
+pub use templates::{extract_templates, ExtractedTemplate, TemplateAttribute, TemplateElement};
+pub use typescript::strip_typescript;
 
-pub use common::TransformOptions;
+use entities::decode_attribute_entities;
+use fold::fold_constants_with_scoping;
+use pragma::{lacks_required_pragma, targets_import_source};
+use prescan::needs_transform;
+use spread::split_object_spreads;
+use typescript::strip_typescript_with_scoping;
+use unsupported::find_unsupported_syntax;
+use whitespace::preserve_jsx_text;
 
+#[cfg(feature = "napi")]
+use napi::bindgen_prelude::{AsyncTask, Buffer, FnArgs, Uint8Array, Unknown};
+#[cfg(feature = "napi")]
+use napi::threadsafe_function::{ThreadsafeFunction, ThreadsafeFunctionCallMode};
 #[cfg(feature = "napi")]
 use napi_derive::napi;
+#[cfg(feature = "napi")]
+use std::borrow::Cow;
 
 use oxc_allocator::Allocator;
-use oxc_codegen::{Codegen, CodegenOptions, CodegenReturn, IndentChar};
-use oxc_parser::Parser;
+use oxc_ast::ast::Program;
+use oxc_codegen::{Codegen, CodegenOptions, CommentOptions, IndentChar};
+use oxc_parser::{Parser, ParserReturn};
+use oxc_semantic::{Scoping, SemanticBuilder};
+use oxc_sourcemap::SourceMap;
 use oxc_span::SourceType;
 
 use std::path::PathBuf;
@@ -36,6 +88,84 @@ pub struct TransformResult {
     pub code: String,
     /// Source map (if enabled)
     pub map: Option<String>,
+    /// `false` when the file has no JSX and `code` is the source as given,
+    /// so a bundler can keep the module and skip its source map
+    pub transformed: bool,
+    /// Parse and unsupported-syntax errors; when present, `code` is empty and
+    /// `map` is unset
+    pub errors: Option<Vec<JsParseError>>,
+    /// The components rendered as islands, with the `islands` option
+    pub islands: Option<Vec<JsIsland>>,
+}
+
+/// An island of the `islands` option exposed to JavaScript
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct JsIsland {
+    /// Key in the `<!--island:id-->` boundary markers
+    pub id: String,
+    /// Local name of the component
+    pub component: String,
+    /// Module the component is imported from
+    pub source: String,
+    /// Name the module exports it under, `default` for a default import
+    pub export: String,
+    /// Byte offset of the JSX element in the source
+    pub start: u32,
+    /// Byte offset of the end of the JSX element
+    pub end: u32,
+}
+
+#[cfg(feature = "napi")]
+impl JsIsland {
+    /// The manifest of a transform, unset when it has no islands
+    fn manifest(islands: &[Island]) -> Option<Vec<Self>> {
+        if islands.is_empty() {
+            return None;
+        }
+        Some(
+            islands
+                .iter()
+                .map(|island| Self {
+                    id: island.id.clone(),
+                    component: island.component.clone(),
+                    source: island.source.clone(),
+                    export: island.export.clone(),
+                    start: island.span.start,
+                    end: island.span.end,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// A parse error exposed to JavaScript
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct JsParseError {
+    /// Source filename
+    pub filename: String,
+    /// Error message from the parser
+    pub message: String,
+    /// 1-based line of the error
+    pub line: u32,
+    /// 1-based column of the error
+    pub column: u32,
+    /// Excerpt of the source around the error
+    pub code_frame: String,
+}
+
+#[cfg(feature = "napi")]
+impl From<ParseError> for JsParseError {
+    fn from(error: ParseError) -> Self {
+        Self {
+            filename: error.filename,
+            message: error.message,
+            line: error.line as u32,
+            column: error.column as u32,
+            code_frame: error.code_frame,
+        }
+    }
 }
 
 /// Transform options exposed to JavaScript
@@ -48,26 +178,60 @@ pub struct JsTransformOptions {
     pub module_name: Option<String>,
 
     /// Generate mode: "dom", "ssr", or "universal"
-    /// Note: "universal" is currently treated as "dom" (not a separate universal renderer output).
+    /// "universal" calls the `createRenderer` helpers of the custom renderer in `module_name`
     /// @default "dom"
     pub generate: Option<String>,
 
+    /// How runtime helpers are imported: "esm" (`import`) or "cjs" (`require`)
+    /// @default "esm"
+    pub module_format: Option<String>,
+
     /// Whether to enable hydration support
     /// @default false
     pub hydratable: Option<bool>,
 
+    /// Inject `generateHydrationScript()` at the end of `<head>` in hydratable SSR output
+    /// @default false
+    pub hydration_script: Option<bool>,
+
+    /// Experimental: render imported components of hydratable SSR output as
+    /// islands and list them in the result's `islands` manifest
+    /// @default false
+    pub islands: Option<bool>,
+
     /// Whether to delegate events
     /// @default true
     pub delegate_events: Option<bool>,
 
-    /// Whether to wrap conditionals
+    /// How delegated events are registered: "call" (`delegateEvents([...])` per module)
+    /// or "export" (`export const _$delegatedEvents = [...]` for a bundler to collect)
+    /// @default "call"
+    pub delegate_events_strategy: Option<String>,
+
+    /// Whether to memoize the test of conditional children
     /// @default true
     pub wrap_conditionals: Option<bool>,
 
+    /// Keep JSX text as written instead of trimming and collapsing its whitespace
+    /// @default false
+    pub preserve_whitespace: Option<bool>,
+
+    /// Helper reactive bindings are wrapped in, imported from `module_name`
+    /// @default "effect"
+    pub effect_wrapper: Option<String>,
+
+    /// Helper derived values are memoized with, imported from `module_name`
+    /// @default "memo"
+    pub memo_wrapper: Option<String>,
+
     /// Whether to pass context to custom elements
     /// @default true
     pub context_to_custom_elements: Option<bool>,
 
+    /// Tags treated as custom elements even without a dash; an entry ending in `*` matches by prefix
+    /// @default []
+    pub custom_elements: Option<Vec<String>>,
+
     /// Source filename
     /// @default "input.jsx"
     pub filename: Option<String>,
@@ -75,52 +239,635 @@ pub struct JsTransformOptions {
     /// Whether to generate source maps
     /// @default false
     pub source_map: Option<bool>,
+
+    /// Strip TypeScript syntax (and lower enums/namespaces) before the JSX transform
+    /// @default false
+    pub strip_types: Option<bool>,
+
+    /// Minify the output: shorten generated locals and drop whitespace and comments
+    /// @default false
+    pub minify: Option<bool>,
+
+    /// Build templates with `document.createElement` calls instead of `template()`,
+    /// for Content Security Policies that block `innerHTML`
+    /// @default false
+    pub csp_safe: Option<bool>,
+
+    /// Emit the runtime helpers that don't need Solid's reactive runtime into
+    /// the DOM output instead of importing them from `solid-js/web`
+    /// @default false
+    pub inline_helpers: Option<bool>,
+
+    /// Render SSR elements with `ssrElement()` calls instead of `ssr` tagged templates
+    /// @default false
+    pub ssr_element_only: Option<bool>,
+
+    /// Resolve `isServer`, `isDev` and `DEV` checks at compile time
+    /// @default true
+    pub fold_environment: Option<bool>,
+
+    /// Whether the output targets Solid's development build (folds `isDev` and `DEV`)
+    pub dev: Option<bool>,
+
+    /// Constant replacements applied before the transform, e.g.
+    /// `{ "import.meta.env.SSR": "true" }`
+    pub define: Option<std::collections::HashMap<String, String>>,
+
+    /// Only transform files with a `/** @jsxImportSource <name> */` pragma
+    /// naming this source; other files are returned unchanged
+    pub require_import_source: Option<String>,
+
+    /// Encoding of the source passed to `transformJsxBuffer`: "utf8" or "latin1"
+    /// @default "utf8"
+    pub encoding: Option<String>,
 }
 
 /// Transform JSX source code
 #[cfg(feature = "napi")]
 #[napi]
-pub fn transform_jsx(source: String, options: Option<JsTransformOptions>) -> TransformResult {
+pub fn transform_jsx(
+    source: String,
+    options: Option<JsTransformOptions>,
+) -> napi::Result<TransformResult> {
     let js_options = options.unwrap_or_default();
+    let options = js_options.build()?;
 
-    // Convert JS options to internal options
-    let generate = match js_options.generate.as_deref() {
-        Some("ssr") => common::GenerateMode::Ssr,
-        Some("universal") => common::GenerateMode::Universal,
-        _ => common::GenerateMode::Dom,
-    };
+    Ok(TransformResult::from_output(transform_for_js(&source, options)?))
+}
 
-    let options = TransformOptions {
-        generate,
-        hydratable: js_options.hydratable.unwrap_or(false),
-        delegate_events: js_options.delegate_events.unwrap_or(true),
-        wrap_conditionals: js_options.wrap_conditionals.unwrap_or(true),
-        context_to_custom_elements: js_options.context_to_custom_elements.unwrap_or(true),
-        filename: js_options.filename.as_deref().unwrap_or("input.jsx"),
-        source_map: js_options.source_map.unwrap_or(false),
-        ..TransformOptions::solid_defaults()
-    };
+#[cfg(feature = "napi")]
+impl TransformResult {
+    fn from_output(output: Result<TransformOutput, Vec<JsParseError>>) -> Self {
+        match output {
+            Ok(output) => Self {
+                code: output.code,
+                map: output.map.map(|m| m.to_json_string()),
+                transformed: output.transformed,
+                errors: None,
+                islands: JsIsland::manifest(&output.islands),
+            },
+            Err(errors) => Self {
+                code: String::new(),
+                map: None,
+                transformed: false,
+                errors: Some(errors),
+                islands: None,
+            },
+        }
+    }
+}
 
-    let result = transform_internal(&source, &options);
+/// A file of a batch transform
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct JsBatchFile {
+    /// Source filename, used in place of the `filename` option
+    pub filename: String,
+    /// The source code to transform
+    pub source: String,
+}
+
+/// `(filename, index, total)`, called as each file of a batch finishes
+#[cfg(feature = "napi")]
+type ProgressCallback = ThreadsafeFunction<
+    FnArgs<(String, u32, u32)>,
+    Unknown<'static>,
+    FnArgs<(String, u32, u32)>,
+    napi::Status,
+    false,
+>;
 
-    TransformResult {
-        code: result.code,
-        map: result.map.map(|m| m.to_json_string()),
+/// Transform many files in parallel, off the JS thread. `on_progress` is
+/// called with `(filename, index, total)` as each file finishes, `index`
+/// being its position in `files`; the promise resolves with the results in
+/// input order.
+#[cfg(feature = "napi")]
+#[napi]
+pub fn transform_jsx_batch(
+    files: Vec<JsBatchFile>,
+    options: Option<JsTransformOptions>,
+    on_progress: Option<ProgressCallback>,
+) -> napi::Result<AsyncTask<BatchTransformTask>> {
+    let options = options.unwrap_or_default();
+    // Reject invalid options up front instead of once per file
+    options.build()?;
+    Ok(AsyncTask::new(BatchTransformTask {
+        files,
+        options,
+        on_progress,
+    }))
+}
+
+#[cfg(feature = "napi")]
+pub struct BatchTransformTask {
+    files: Vec<JsBatchFile>,
+    options: JsTransformOptions,
+    on_progress: Option<ProgressCallback>,
+}
+
+#[cfg(feature = "napi")]
+impl napi::Task for BatchTransformTask {
+    type Output = Vec<TransformResult>;
+    type JsValue = Vec<TransformResult>;
+
+    fn compute(&mut self) -> napi::Result<Self::Output> {
+        let files: Vec<(&str, &str)> = self
+            .files
+            .iter()
+            .map(|file| (file.filename.as_str(), file.source.as_str()))
+            .collect();
+        let options = &self.options;
+        let on_progress = self.on_progress.as_ref();
+        let results = transform_files(
+            &files,
+            |filename| options.build_for(filename).map_err(TransformError::from),
+            |filename, index, total| {
+                if let Some(on_progress) = on_progress {
+                    on_progress.call(
+                        FnArgs::from((filename.to_string(), index as u32, total as u32)),
+                        ThreadsafeFunctionCallMode::NonBlocking,
+                    );
+                }
+            },
+        );
+        results
+            .into_iter()
+            .map(|result| split_js_errors(result).map(TransformResult::from_output))
+            .collect()
+    }
+
+    fn resolve(&mut self, _env: napi::Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+        Ok(output)
     }
 }
 
-/// Internal transform function
-pub fn transform(source: &str, options: Option<TransformOptions>) -> CodegenReturn {
-    let options = options.unwrap_or_else(TransformOptions::solid_defaults);
-    transform_internal(source, &options)
+/// Result of a buffer transform
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct BufferTransformResult {
+    /// The transformed code, UTF-8 encoded
+    pub code: Buffer,
+    /// Source map (if enabled)
+    pub map: Option<String>,
+    /// `false` when the file has no JSX and `code` is the source as given
+    pub transformed: bool,
+    /// Parse and unsupported-syntax errors; when present, `code` is empty and
+    /// `map` is unset
+    pub errors: Option<Vec<JsParseError>>,
+    /// The components rendered as islands, with the `islands` option
+    pub islands: Option<Vec<JsIsland>>,
+}
+
+/// Transform JSX source code passed as bytes (a `Buffer` or `Uint8Array`
+/// straight from `fs.readFile`), returning the code as a `Buffer`.
+///
+/// UTF-8 sources are read in place and the output buffer takes over the
+/// generated code, so neither side is copied into or out of a JS string.
+#[cfg(feature = "napi")]
+#[napi]
+pub fn transform_jsx_buffer(
+    source: Uint8Array,
+    options: Option<JsTransformOptions>,
+) -> napi::Result<BufferTransformResult> {
+    let js_options = options.unwrap_or_default();
+    let source = decode_source(&source, js_options.encoding.as_deref())?;
+    let options = js_options.build()?;
+
+    Ok(match transform_for_js(&source, options)? {
+        Ok(output) => BufferTransformResult {
+            code: output.code.into_bytes().into(),
+            map: output.map.map(|m| m.to_json_string()),
+            transformed: output.transformed,
+            errors: None,
+            islands: JsIsland::manifest(&output.islands),
+        },
+        Err(errors) => BufferTransformResult {
+            code: Vec::new().into(),
+            map: None,
+            transformed: false,
+            errors: Some(errors),
+            islands: None,
+        },
+    })
+}
+
+/// Source text of a buffer in `encoding` (`"utf8"` by default). UTF-8 is
+/// borrowed once validated; latin1 is borrowed when it is plain ASCII and
+/// decoded otherwise.
+#[cfg(feature = "napi")]
+fn decode_source<'s>(bytes: &'s [u8], encoding: Option<&str>) -> napi::Result<Cow<'s, str>> {
+    match encoding {
+        None | Some("utf8" | "utf-8") => std::str::from_utf8(bytes)
+            .map(Cow::Borrowed)
+            .map_err(|error| {
+                napi::Error::new(
+                    napi::Status::InvalidArg,
+                    format!("source is not valid UTF-8: {}", error),
+                )
+            }),
+        Some("latin1") if bytes.is_ascii() => Ok(Cow::Borrowed(
+            std::str::from_utf8(bytes).expect("ASCII is valid UTF-8"),
+        )),
+        Some("latin1") => Ok(Cow::Owned(bytes.iter().map(|&byte| char::from(byte)).collect())),
+        Some(other) => Err(napi::Error::new(
+            napi::Status::InvalidArg,
+            format!("unsupported source encoding `{}`", other),
+        )),
+    }
+}
+
+/// Run `transform`, throwing on invalid options and returning parse and
+/// unsupported-syntax errors for the result object
+#[cfg(feature = "napi")]
+fn transform_for_js(
+    source: &str,
+    options: TransformOptions,
+) -> napi::Result<Result<TransformOutput, Vec<JsParseError>>> {
+    split_js_errors(transform(source, Some(options)))
+}
+
+/// Throw on invalid options, keep parse, unsupported-syntax and internal
+/// errors for the result object
+#[cfg(feature = "napi")]
+fn split_js_errors<T>(
+    result: Result<T, TransformError>,
+) -> napi::Result<Result<T, Vec<JsParseError>>> {
+    match result {
+        Ok(output) => Ok(Ok(output)),
+        Err(TransformError::InvalidOptions(error)) => Err(napi::Error::new(
+            napi::Status::InvalidArg,
+            error.to_string(),
+        )),
+        Err(TransformError::Parse(errors) | TransformError::UnsupportedSyntax(errors)) => {
+            Ok(Err(errors.into_iter().map(JsParseError::from).collect()))
+        }
+        Err(TransformError::Internal { filename, message }) => Ok(Err(vec![JsParseError {
+            filename,
+            message: format!("internal compiler error: {message}"),
+            line: 1,
+            column: 1,
+            code_frame: String::new(),
+        }])),
+    }
+}
+
+/// Transform cache for watch mode: unchanged files with unchanged options
+/// return the previous output without being compiled again
+#[cfg(feature = "napi")]
+#[napi(js_name = "TransformCache")]
+#[derive(Default)]
+pub struct JsTransformCache {
+    cache: TransformCache,
 }
 
-fn transform_internal(source: &str, options: &TransformOptions) -> CodegenReturn {
+#[cfg(feature = "napi")]
+#[napi]
+impl JsTransformCache {
+    #[napi(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Same as `transformJsx`, reusing the cached output when possible
+    #[napi]
+    pub fn transform(
+        &self,
+        source: String,
+        options: Option<JsTransformOptions>,
+    ) -> napi::Result<TransformResult> {
+        let js_options = options.unwrap_or_default();
+        let options = js_options.build()?;
+
+        Ok(
+            match split_js_errors(self.cache.transform(&source, Some(options)))? {
+                Ok(output) => TransformResult {
+                    code: output.code.clone(),
+                    map: output.map.as_ref().map(|m| m.to_json_string()),
+                    transformed: output.transformed,
+                    errors: None,
+                    islands: JsIsland::manifest(&output.islands),
+                },
+                Err(errors) => TransformResult {
+                    code: String::new(),
+                    map: None,
+                    transformed: false,
+                    errors: Some(errors),
+                    islands: None,
+                },
+            },
+        )
+    }
+
+    /// Number of cached outputs
+    #[napi(getter)]
+    pub fn size(&self) -> u32 {
+        self.cache.len() as u32
+    }
+
+    /// Drop every cached output
+    #[napi]
+    pub fn clear(&self) {
+        self.cache.clear();
+    }
+}
+
+#[cfg(feature = "napi")]
+impl JsTransformOptions {
+    /// Internal options for these JS options
+    fn build(&self) -> napi::Result<TransformOptions<'_>> {
+        self.build_for(self.filename.as_deref().unwrap_or("input.jsx"))
+            .map_err(|error| napi::Error::new(napi::Status::InvalidArg, error.to_string()))
+    }
+
+    /// Internal options for these JS options, transforming `filename`
+    fn build_for<'s>(&'s self, filename: &'s str) -> Result<TransformOptions<'s>, OptionsError> {
+        let generate = match self.generate.as_deref() {
+            Some("ssr") => common::GenerateMode::Ssr,
+            Some("universal") => common::GenerateMode::Universal,
+            _ => common::GenerateMode::Dom,
+        };
+
+        let module_format = match self.module_format.as_deref() {
+            Some("cjs") => common::ModuleFormat::Cjs,
+            _ => common::ModuleFormat::Esm,
+        };
+
+        let delegate_events_strategy = match self.delegate_events_strategy.as_deref() {
+            None | Some("call") => common::DelegateEventsStrategy::Call,
+            Some("export") => common::DelegateEventsStrategy::Export,
+            Some(value) => {
+                return Err(OptionsError::UnknownValue {
+                    option: "delegate_events_strategy",
+                    value: value.to_string(),
+                    expected: &["call", "export"],
+                })
+            }
+        };
+
+        let define = self.define.clone().unwrap_or_default();
+        TransformOptions::builder()
+            .module_name(
+                self.module_name
+                    .as_deref()
+                    .unwrap_or(common::DEFAULT_MODULE_NAME),
+            )
+            .generate(generate)
+            .module_format(module_format)
+            .hydratable(self.hydratable.unwrap_or(false))
+            .hydration_script(self.hydration_script.unwrap_or(false))
+            .islands(self.islands.unwrap_or(false))
+            .delegate_events(self.delegate_events.unwrap_or(true))
+            .delegate_events_strategy(delegate_events_strategy)
+            .wrap_conditionals(self.wrap_conditionals.unwrap_or(true))
+            .preserve_whitespace(self.preserve_whitespace.unwrap_or(false))
+            .effect_wrapper(self.effect_wrapper.as_deref().unwrap_or("effect"))
+            .memo_wrapper(self.memo_wrapper.as_deref().unwrap_or("memo"))
+            .context_to_custom_elements(self.context_to_custom_elements.unwrap_or(true))
+            .custom_elements(self.custom_elements.iter().flatten().map(String::as_str))
+            .filename(filename)
+            .source_map(self.source_map.unwrap_or(false))
+            .strip_types(self.strip_types.unwrap_or(false))
+            .minify(self.minify.unwrap_or(false))
+            .csp_safe(self.csp_safe.unwrap_or(false))
+            .inline_helpers(self.inline_helpers.unwrap_or(false))
+            .ssr_element_only(self.ssr_element_only.unwrap_or(false))
+            .fold_environment(self.fold_environment.unwrap_or(true))
+            .dev(self.dev)
+            .define(define)
+            .require_import_source(self.require_import_source.as_deref())
+            .build()
+    }
+}
+
+/// Result of a template extraction
+#[cfg(feature = "napi")]
+#[napi(object)]
+pub struct ExtractTemplatesResult {
+    /// Template roots in source order
+    pub templates: Vec<ExtractedTemplate>,
+    /// Parse errors; when present, `templates` is empty
+    pub errors: Option<Vec<JsParseError>>,
+}
+
+/// Collect the static templates in a file without generating code
+#[cfg(feature = "napi")]
+#[napi]
+pub fn extract_jsx_templates(
+    source: String,
+    options: Option<JsTransformOptions>,
+) -> napi::Result<ExtractTemplatesResult> {
+    let js_options = options.unwrap_or_default();
+    let options = js_options.build()?;
+
+    Ok(match extract_templates(&source, Some(options)) {
+        Ok(templates) => ExtractTemplatesResult {
+            templates,
+            errors: None,
+        },
+        Err(error) => ExtractTemplatesResult {
+            templates: Vec::new(),
+            errors: Some(
+                error
+                    .errors()
+                    .iter()
+                    .cloned()
+                    .map(JsParseError::from)
+                    .collect(),
+            ),
+        },
+    })
+}
+
+/// Install a global subscriber that prints each phase span (parse, semantic,
+/// transform, codegen) to stderr with its duration when it closes. `filter`
+/// uses `RUST_LOG` syntax, e.g. `"info"` or `"solid_jsx_oxc=info,dom=info"`.
+///
+/// Returns `false` if another global subscriber was installed first.
+#[cfg(feature = "tracing")]
+pub fn init_tracing(filter: &str) -> bool {
+    use tracing_subscriber::fmt::format::FmtSpan;
+    use tracing_subscriber::EnvFilter;
+
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::new(filter))
+        .with_span_events(FmtSpan::CLOSE)
+        .with_writer(std::io::stderr)
+        .try_init()
+        .is_ok()
+}
+
+/// Print the duration of every transform phase to stderr, for profiling
+/// builds that go through the addon. `filter` defaults to `"info"`
+#[cfg(all(feature = "napi", feature = "tracing"))]
+#[napi(js_name = "initTracing")]
+pub fn init_tracing_js(filter: Option<String>) -> bool {
+    init_tracing(filter.as_deref().unwrap_or("info"))
+}
+
+/// Output of a successful transform
+#[derive(Debug)]
+pub struct TransformOutput {
+    /// The transformed code
+    pub code: String,
+    /// Source map, when `source_map` is set in the options
+    pub map: Option<SourceMap>,
+    /// `false` when the source has no JSX and nothing else to transform, or
+    /// lacks the pragma `require_import_source` asks for, in which case
+    /// `code` is the source unchanged and there is no map
+    pub transformed: bool,
+    /// The components rendered as islands, with the `islands` option
+    pub islands: Vec<Island>,
+}
+
+/// Transform JSX source code.
+///
+/// Fails without output when the options contradict each other, the source
+/// doesn't parse, or it uses JSX the transform can't translate faithfully.
+/// Sources without JSX are returned as they are, without being parsed, unless
+/// the options change plain code too (`strip_types`, `minify`, `define`, or
+/// environment folding in a file importing Solid). With
+/// `require_import_source`, sources without a matching `@jsxImportSource`
+/// pragma are returned as they are too.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip_all, fields(filename = options.as_ref().map(|o| o.filename)))
+)]
+pub fn transform(
+    source: &str,
+    options: Option<TransformOptions>,
+) -> Result<TransformOutput, TransformError> {
+    let options = options.unwrap_or_else(TransformOptions::solid_defaults);
+    options.validate()?;
+    let unchanged = || TransformOutput {
+        code: source.to_string(),
+        map: None,
+        transformed: false,
+        islands: Vec::new(),
+    };
+    if lacks_required_pragma(source, &options) || !needs_transform(source, &options) {
+        return Ok(unchanged());
+    }
     let allocator = Allocator::default();
+    let parsed = parse_source(&allocator, source, &options);
+    check_parsed(&parsed, source, options.filename)?;
+    let mut program = parsed.program;
+    if !targets_import_source(&program, &options) {
+        return Ok(unchanged());
+    }
+    transform_program(&mut program, &allocator, &options)?;
+    Ok(TransformOutput {
+        islands: options.island_manifest.take(),
+        ..print(&program, &options)
+    })
+}
+
+/// The parse errors of `parsed`, if any
+pub(crate) fn check_parsed(
+    parsed: &ParserReturn<'_>,
+    source: &str,
+    filename: &str,
+) -> Result<(), TransformError> {
+    if parsed.panicked || !parsed.errors.is_empty() {
+        return Err(TransformError::Parse(
+            parsed
+                .errors
+                .iter()
+                .map(|error| ParseError::from_diagnostic(error, source, filename))
+                .collect(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(name = "parse", skip_all))]
+fn parse_source<'a>(
+    allocator: &'a Allocator,
+    source: &'a str,
+    options: &TransformOptions,
+) -> ParserReturn<'a> {
     let source_type = SourceType::from_path(options.filename).unwrap_or(SourceType::tsx());
+    Parser::new(allocator, source, source_type).parse()
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(name = "codegen", skip_all))]
+fn print(program: &Program<'_>, options: &TransformOptions) -> TransformOutput {
+    let printed = Codegen::new()
+        .with_options(CodegenOptions {
+            source_map_path: if options.source_map {
+                Some(PathBuf::from(options.filename))
+            } else {
+                None
+            },
+            minify: options.minify,
+            comments: if options.minify {
+                CommentOptions::disabled()
+            } else {
+                CommentOptions::default()
+            },
+            indent_width: 2,
+            indent_char: IndentChar::Space,
+            ..CodegenOptions::default()
+        })
+        .build(program);
+    TransformOutput {
+        code: printed.code,
+        map: printed.map,
+        transformed: true,
+        islands: Vec::new(),
+    }
+}
 
-    // Parse the source
-    let mut program = Parser::new(&allocator, source, source_type).parse().program;
+/// Run the Solid transform on an already parsed program, in place.
+///
+/// This is what [`transform`] does between parsing and printing: TypeScript
+/// stripping, constant folding, the DOM or SSR transform and minification, as
+/// set by `options`. `program` must have been parsed into `allocator`; print it
+/// with `oxc_codegen` afterwards. `options.filename`, `source_map` and the
+/// codegen side of `minify` are up to the caller, and with `islands` the
+/// manifest is left in `options.island_manifest`.
+///
+/// Invalid options and unsupported syntax are reported before anything is
+/// changed, so `program` is left as parsed on error.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+pub fn transform_program<'a>(
+    program: &mut Program<'a>,
+    allocator: &'a Allocator,
+    options: &TransformOptions<'a>,
+) -> Result<(), TransformError> {
+    run_passes(program, allocator, options, None)
+}
+
+/// [`transform_program`] reusing scoping the caller already built for
+/// `program`, e.g. `semantic.into_scoping()` after linting the same parse, so
+/// semantic analysis isn't run twice.
+///
+/// `scoping` must come from `SemanticBuilder` on `program` as it is now. It is
+/// handed to the first pass that needs it; passes after one that changed the
+/// AST build fresh scoping.
+#[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+pub fn transform_program_with_scoping<'a>(
+    program: &mut Program<'a>,
+    allocator: &'a Allocator,
+    options: &TransformOptions<'a>,
+    scoping: Scoping,
+) -> Result<(), TransformError> {
+    run_passes(program, allocator, options, Some(scoping))
+}
+
+fn run_passes<'a>(
+    program: &mut Program<'a>,
+    allocator: &'a Allocator,
+    options: &TransformOptions<'a>,
+    mut scoping: Option<Scoping>,
+) -> Result<(), TransformError> {
+    options.validate()?;
+    if !targets_import_source(program, options) {
+        return Ok(());
+    }
+    let unsupported = find_unsupported_syntax(program, options.filename);
+    if !unsupported.is_empty() {
+        return Err(TransformError::UnsupportedSyntax(unsupported));
+    }
 
     // Run the appropriate transform based on generate mode
     // SAFETY: We create a raw pointer to `options` and dereference it to get a reference
@@ -132,45 +879,96 @@ fn transform_internal(source: &str, options: &TransformOptions) -> CodegenReturn
     // multiple mutable borrows needed during AST traversal.
     let options_ref = unsafe { &*(options as *const TransformOptions) };
 
+    if options.strip_types && program.source_type.is_typescript() {
+        let scoping = scoping.take().unwrap_or_else(|| build_scoping(program));
+        let stripped = strip_typescript_with_scoping(allocator, program, scoping);
+        if !stripped.errors.is_empty() {
+            return Err(TransformError::UnsupportedSyntax(
+                stripped
+                    .errors
+                    .iter()
+                    .map(|error| {
+                        ParseError::from_diagnostic(error, program.source_text, options.filename)
+                    })
+                    .collect(),
+            ));
+        }
+    }
+
+    if options.fold_environment || !options.define.is_empty() {
+        fold_constants_with_scoping(allocator, program, options, &mut scoping);
+    }
+
+    // Only moves expressions around and replaces strings, so the scoping
+    // stays valid
+    decode_attribute_entities(allocator, program);
+    split_object_spreads(allocator, program);
+    if options.preserve_whitespace {
+        preserve_jsx_text(allocator, program);
+    }
+
+    let scoping = scoping.unwrap_or_else(|| build_scoping(program));
     match options.generate {
-        common::GenerateMode::Dom => {
-            let transformer = SolidTransform::new(&allocator, options_ref);
-            transformer.transform(&mut program);
+        // Universal output shares the DOM transform; elements are created
+        // with the custom renderer's helpers instead of cloned templates
+        common::GenerateMode::Dom | common::GenerateMode::Universal => {
+            let transformer = SolidTransform::new(allocator, options_ref);
+            transformer.transform_with_scoping(program, scoping);
         }
         common::GenerateMode::Ssr => {
-            let transformer = SSRTransform::new(&allocator, options_ref);
-            transformer.transform(&mut program);
-        }
-        common::GenerateMode::Universal => {
-            // Universal mode is not implemented yet; treat as DOM for now.
-            let transformer = SolidTransform::new(&allocator, options_ref);
-            transformer.transform(&mut program);
+            let transformer = SSRTransform::new(allocator, options_ref);
+            transformer.transform_with_scoping(program, scoping);
         }
     }
 
-    // Generate code
-    Codegen::new()
-        .with_options(CodegenOptions {
-            source_map_path: if options.source_map {
-                Some(PathBuf::from(options.filename))
-            } else {
-                None
-            },
-            indent_width: 2,
-            indent_char: IndentChar::Space,
-            ..CodegenOptions::default()
-        })
-        .build(&program)
+    if options.minify {
+        mangle_generated_locals(allocator, program);
+    }
+    Ok(())
+}
+
+#[cfg_attr(feature = "tracing", tracing::instrument(name = "semantic", skip_all))]
+fn build_scoping(program: &Program<'_>) -> Scoping {
+    SemanticBuilder::new()
+        .build(program)
+        .semantic
+        .into_scoping()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[cfg(feature = "napi")]
+    #[test]
+    fn test_unknown_delegate_events_strategy_rejected() {
+        let options = JsTransformOptions {
+            delegate_events_strategy: Some("exports".to_string()),
+            ..Default::default()
+        };
+        let Err(error) = options.build_for("input.jsx") else {
+            panic!("an unknown strategy should be rejected");
+        };
+        assert_eq!(
+            error.to_string(),
+            r#"unknown `delegate_events_strategy` "exports"; expected one of "call", "export""#
+        );
+        assert_eq!(
+            options.build().err().map(|error| error.status),
+            Some(napi::Status::InvalidArg)
+        );
+        assert_eq!(
+            extract_jsx_templates("<div />".to_string(), Some(options))
+                .err()
+                .map(|error| error.status),
+            Some(napi::Status::InvalidArg)
+        );
+    }
+
     #[test]
     fn test_basic_element() {
         let source = r#"<div class="hello">world</div>"#;
-        let result = transform(source, None);
+        let result = transform(source, None).unwrap();
         // The transform should produce valid code
         assert!(!result.code.is_empty());
     }
@@ -178,54 +976,54 @@ mod tests {
     #[test]
     fn test_dynamic_attribute() {
         let source = r#"<div class={style()}>content</div>"#;
-        let result = transform(source, None);
+        let result = transform(source, None).unwrap();
         assert!(!result.code.is_empty());
     }
 
     #[test]
     fn test_component() {
         let source = r#"<Button onClick={handler}>Click me</Button>"#;
-        let result = transform(source, None);
+        let result = transform(source, None).unwrap();
         assert!(!result.code.is_empty());
     }
 
     #[test]
     fn test_for_loop() {
         let source = r#"<For each={items}>{item => <div>{item}</div>}</For>"#;
-        let result = transform(source, None);
+        let result = transform(source, None).unwrap();
         assert!(!result.code.is_empty());
     }
 
     #[test]
     fn test_ssr_basic_element() {
         let source = r#"<div class="hello">world</div>"#;
-        let options = TransformOptions {
-            generate: common::GenerateMode::Ssr,
-            ..TransformOptions::solid_defaults()
-        };
-        let result = transform(source, Some(options));
+        let options = TransformOptions::builder()
+            .generate(common::GenerateMode::Ssr)
+            .build()
+            .unwrap();
+        let result = transform(source, Some(options)).unwrap();
         assert!(!result.code.is_empty());
     }
 
     #[test]
     fn test_ssr_dynamic_attribute() {
         let source = r#"<div class={style()}>content</div>"#;
-        let options = TransformOptions {
-            generate: common::GenerateMode::Ssr,
-            ..TransformOptions::solid_defaults()
-        };
-        let result = transform(source, Some(options));
+        let options = TransformOptions::builder()
+            .generate(common::GenerateMode::Ssr)
+            .build()
+            .unwrap();
+        let result = transform(source, Some(options)).unwrap();
         assert!(!result.code.is_empty());
     }
 
     #[test]
     fn test_ssr_component() {
         let source = r#"<Button onClick={handler}>Click me</Button>"#;
-        let options = TransformOptions {
-            generate: common::GenerateMode::Ssr,
-            ..TransformOptions::solid_defaults()
-        };
-        let result = transform(source, Some(options));
+        let options = TransformOptions::builder()
+            .generate(common::GenerateMode::Ssr)
+            .build()
+            .unwrap();
+        let result = transform(source, Some(options)).unwrap();
         assert!(!result.code.is_empty());
     }
 
@@ -255,11 +1053,11 @@ mod tests {
         ];
 
         for (source, label) in cases {
-            let options = TransformOptions {
-                generate: common::GenerateMode::Ssr,
-                ..TransformOptions::solid_defaults()
-            };
-            let result = transform(source, Some(options));
+            let options = TransformOptions::builder()
+                .generate(common::GenerateMode::Ssr)
+                .build()
+                .unwrap();
+            let result = transform(source, Some(options)).unwrap();
             println!(
                 "\n=== {} ===\nInput:  {}\nOutput: {}",
                 label, source, result.code
@@ -308,7 +1106,7 @@ mod tests {
 
         for (source, label) in cases {
             // DOM mode is the default
-            let result = transform(source, None);
+            let result = transform(source, None).unwrap();
             println!(
                 "\n=== DOM: {} ===\nInput:  {}\nOutput: {}",
                 label, source, result.code
@@ -0,0 +1,138 @@
+//! Minification stage
+//!
+//! Shortens the locals the transform generates (`_el$1`, `_tmpl$1`, `_ref$`)
+//! for production output; whitespace and comments are removed by the
+//! minified codegen. User-written names are left alone, so this is not a
+//! replacement for a full minifier.
+
+use std::collections::{HashMap, HashSet};
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{BindingIdentifier, IdentifierReference, Program};
+use oxc_ast_visit::{Visit, VisitMut};
+use oxc_span::Atom;
+
+const FIRST_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$";
+const REST_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$0123456789";
+
+/// Short names that are keywords
+const RESERVED: &[&str] = &[
+    "do", "if", "in", "as", "of", "for", "let", "new", "try", "var", "int", "byte", "case", "char",
+    "else", "enum", "eval", "goto", "long", "null", "this", "true", "void", "with",
+];
+
+/// Whether `name` is a local generated by the transform
+fn is_generated(name: &str) -> bool {
+    if name == "_ref$" {
+        return true;
+    }
+    ["_el$", "_tmpl$"].iter().any(|prefix| {
+        name.strip_prefix(prefix)
+            .is_some_and(|rest| !rest.is_empty() && rest.bytes().all(|b| b.is_ascii_digit()))
+    })
+}
+
+/// Short name number `index` (`a`, `b`, ..., `$`, `aa`, `ab`, ...)
+fn short_name(mut index: usize) -> String {
+    let mut name = String::new();
+    name.push(FIRST_CHARS[index % FIRST_CHARS.len()] as char);
+    index /= FIRST_CHARS.len();
+    while index > 0 {
+        index -= 1;
+        name.push(REST_CHARS[index % REST_CHARS.len()] as char);
+        index /= REST_CHARS.len();
+    }
+    name
+}
+
+/// Collects every identifier name used in the program
+#[derive(Default)]
+struct NameCollector {
+    names: HashSet<String>,
+    generated: Vec<String>,
+}
+
+impl<'a> Visit<'a> for NameCollector {
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        self.record(ident.name.as_str());
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        self.record(ident.name.as_str());
+    }
+}
+
+impl NameCollector {
+    fn record(&mut self, name: &str) {
+        if self.names.insert(name.to_string()) && is_generated(name) {
+            self.generated.push(name.to_string());
+        }
+    }
+}
+
+struct Renamer<'a> {
+    renames: HashMap<String, Atom<'a>>,
+}
+
+impl<'a> VisitMut<'a> for Renamer<'a> {
+    fn visit_binding_identifier(&mut self, ident: &mut BindingIdentifier<'a>) {
+        if let Some(name) = self.renames.get(ident.name.as_str()) {
+            ident.name = *name;
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &mut IdentifierReference<'a>) {
+        if let Some(name) = self.renames.get(ident.name.as_str()) {
+            ident.name = *name;
+        }
+    }
+}
+
+/// Rename the locals generated by the transform to the shortest names not
+/// already used anywhere in the program
+#[cfg_attr(feature = "tracing", tracing::instrument(name = "minify", skip_all))]
+pub fn mangle_generated_locals<'a>(allocator: &'a Allocator, program: &mut Program<'a>) {
+    let mut collector = NameCollector::default();
+    collector.visit_program(program);
+    if collector.generated.is_empty() {
+        return;
+    }
+
+    let mut index = 0;
+    let mut renames = HashMap::with_capacity(collector.generated.len());
+    for generated in collector.generated {
+        let name = loop {
+            let candidate = short_name(index);
+            index += 1;
+            if !collector.names.contains(&candidate) && !RESERVED.contains(&candidate.as_str()) {
+                break candidate;
+            }
+        };
+        renames.insert(generated, Atom::from(allocator.alloc_str(&name)));
+    }
+
+    Renamer { renames }.visit_program(program);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_generated() {
+        assert!(is_generated("_el$1"));
+        assert!(is_generated("_tmpl$12"));
+        assert!(is_generated("_ref$"));
+        assert!(!is_generated("_el$"));
+        assert!(!is_generated("_el$x"));
+        assert!(!is_generated("el$1"));
+    }
+
+    #[test]
+    fn test_short_names() {
+        assert_eq!(short_name(0), "a");
+        assert_eq!(short_name(53), "$");
+        assert_eq!(short_name(54), "aa");
+        assert_eq!(short_name(55), "ba");
+    }
+}
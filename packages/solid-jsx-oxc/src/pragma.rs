@@ -0,0 +1,36 @@
+//! `@jsxImportSource` pragmas
+//!
+//! A project can compile some files' JSX with Solid and leave others to a
+//! different JSX consumer in the same build. With `require_import_source`,
+//! only files that opt in with `/** @jsxImportSource <source> */` are
+//! transformed; the rest pass through untouched.
+
+use oxc_ast::ast::Program;
+
+use common::TransformOptions;
+
+const PRAGMA: &str = "@jsxImportSource";
+
+/// The import source named by the first `@jsxImportSource` pragma in the
+/// comments of `program`
+pub(crate) fn jsx_import_source<'a>(program: &Program<'a>) -> Option<&'a str> {
+    program.comments.iter().find_map(|comment| {
+        let text = comment.content_span().source_text(program.source_text);
+        let (_, rest) = text.split_once(PRAGMA)?;
+        rest.split_whitespace().next()
+    })
+}
+
+/// Whether `options.require_import_source` lets `program` be transformed
+pub(crate) fn targets_import_source(program: &Program<'_>, options: &TransformOptions) -> bool {
+    match options.require_import_source {
+        Some(required) => jsx_import_source(program) == Some(required),
+        None => true,
+    }
+}
+
+/// Whether `source` can't have the pragma `options` require, so it can be
+/// returned without parsing
+pub(crate) fn lacks_required_pragma(source: &str, options: &TransformOptions) -> bool {
+    options.require_import_source.is_some() && !source.contains(PRAGMA)
+}
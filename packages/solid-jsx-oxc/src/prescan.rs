@@ -0,0 +1,47 @@
+//! Pre-scan for files the transform would leave unchanged
+//!
+//! Bundler plugins hand every module that passes their filter to the
+//! transform, and most of them are plain `.js`/`.ts` without any JSX.
+//! Parsing, analyzing and printing those only to return the same program is
+//! the whole cost of the call, so [`needs_transform`] looks at the raw text
+//! first. It may answer yes for a file without JSX (a `<` in a string or a
+//! comparison), but not no for a file with JSX, unless a tag name is
+//! separated from its `<` by whitespace or a comment, which JSX allows and
+//! formatters never write.
+
+use oxc_span::SourceType;
+
+use common::TransformOptions;
+
+/// Whether any pass could change `source`: it may contain JSX, or the
+/// options ask for work on plain code (type stripping, constant folding,
+/// `define`, minification)
+pub(crate) fn needs_transform(source: &str, options: &TransformOptions) -> bool {
+    let source_type = SourceType::from_path(options.filename).unwrap_or(SourceType::tsx());
+    if options.minify || (options.strip_types && source_type.is_typescript()) {
+        return true;
+    }
+    // Environment flags are only folded in files importing them from Solid
+    if options.fold_environment && source.contains("solid-js") {
+        return true;
+    }
+    // A defined member chain always spells out its last segment
+    let defines_apply = options
+        .define
+        .keys()
+        .any(|key| source.contains(key.rsplit('.').next().unwrap_or(key)));
+    defines_apply || (source_type.is_jsx() && may_contain_jsx(source))
+}
+
+/// Whether `source` has a `<` that could open a JSX element or fragment
+fn may_contain_jsx(source: &str) -> bool {
+    source
+        .as_bytes()
+        .windows(2)
+        .any(|pair| pair[0] == b'<' && is_tag_start(pair[1]))
+}
+
+/// `<div`, `<Foo`, `<_el`, `<$x`, `<>` and non-ASCII tag names
+fn is_tag_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || matches!(byte, b'_' | b'$' | b'>') || !byte.is_ascii()
+}
@@ -0,0 +1,136 @@
+//! Compile-time splitting of object-literal spreads
+//!
+//! `<div {...{ class: "a", onClick: fn }} />` spells out its props, so it
+//! doesn't need the runtime `spread()` (DOM) or `ssrElement()` (SSR) a spread
+//! normally compiles to. The object is rewritten into the attributes it
+//! stands for, `<div class="a" onClick={fn} />`, before the JSX transform,
+//! which then handles them like any other attribute.
+//!
+//! Only native elements are rewritten, and only objects whose properties
+//! all map to plain attributes: no nested spreads (their precedence over the
+//! other keys would change), computed or duplicate keys, accessors, methods,
+//! `ref` or `children`. Values are moved as they are, so the scoping of the
+//! program stays valid.
+
+use oxc_allocator::{Allocator, TakeIn};
+use oxc_ast::ast::*;
+use oxc_ast::AstBuilder;
+use oxc_ast_visit::{walk_mut, VisitMut};
+use oxc_span::GetSpan;
+
+use common::{get_tag_name, is_component};
+
+/// Split the object-literal spreads of native elements in `program`
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "split_spreads", skip_all)
+)]
+pub(crate) fn split_object_spreads<'a>(allocator: &'a Allocator, program: &mut Program<'a>) {
+    let mut splitter = SpreadSplitter {
+        ast: AstBuilder::new(allocator),
+    };
+    splitter.visit_program(program);
+}
+
+struct SpreadSplitter<'a> {
+    ast: AstBuilder<'a>,
+}
+
+impl<'a> VisitMut<'a> for SpreadSplitter<'a> {
+    fn visit_jsx_element(&mut self, element: &mut JSXElement<'a>) {
+        let attributes = &element.opening_element.attributes;
+        if !is_component(&get_tag_name(element)) && attributes.iter().any(is_splittable) {
+            self.split(&mut element.opening_element);
+        }
+        walk_mut::walk_jsx_element(self, element);
+    }
+}
+
+impl<'a> SpreadSplitter<'a> {
+    fn split(&self, opening: &mut JSXOpeningElement<'a>) {
+        let ast = self.ast;
+        let mut attributes = ast.vec_with_capacity(opening.attributes.len());
+        for mut item in opening.attributes.take_in(ast) {
+            let splittable = is_splittable(&item);
+            let object = match &mut item {
+                JSXAttributeItem::SpreadAttribute(spread) if splittable => {
+                    object_literal(&mut spread.argument)
+                }
+                _ => None,
+            };
+            let Some(object) = object else {
+                attributes.push(item);
+                continue;
+            };
+            // is_splittable checked every property, so none is skipped
+            for property in object.properties.take_in(ast) {
+                let ObjectPropertyKind::ObjectProperty(property) = property else {
+                    continue;
+                };
+                let property = property.unbox();
+                let Some(name) = static_key(&property.key) else {
+                    continue;
+                };
+                attributes.push(ast.jsx_attribute_item_attribute(
+                    property.span,
+                    ast.jsx_attribute_name_identifier(property.key.span(), name),
+                    Some(ast.jsx_attribute_value_expression_container(
+                        property.span,
+                        JSXExpression::from(property.value),
+                    )),
+                ));
+            }
+        }
+        opening.attributes = attributes;
+    }
+}
+
+/// Whether `item` is a spread of an object literal that maps onto attributes
+fn is_splittable(item: &JSXAttributeItem<'_>) -> bool {
+    let JSXAttributeItem::SpreadAttribute(spread) = item else {
+        return false;
+    };
+    let Expression::ObjectExpression(object) = spread.argument.without_parentheses() else {
+        return false;
+    };
+    let mut names = Vec::with_capacity(object.properties.len());
+    for property in &object.properties {
+        let ObjectPropertyKind::ObjectProperty(property) = property else {
+            return false;
+        };
+        if property.kind != PropertyKind::Init || property.method || property.computed {
+            return false;
+        }
+        let Some(name) = static_key(&property.key) else {
+            return false;
+        };
+        if matches!(name, "ref" | "children" | "__proto__") || names.contains(&name) {
+            return false;
+        }
+        names.push(name);
+    }
+    true
+}
+
+/// The object literal `expr` is, looking through parentheses
+fn object_literal<'a, 'b>(expr: &'b mut Expression<'a>) -> Option<&'b mut ObjectExpression<'a>> {
+    match expr {
+        Expression::ObjectExpression(object) => Some(object),
+        Expression::ParenthesizedExpression(paren) => object_literal(&mut paren.expression),
+        _ => None,
+    }
+}
+
+/// Key of a property when it is a name a JSX attribute can have
+fn static_key<'a>(key: &PropertyKey<'a>) -> Option<&'a str> {
+    let name = match key {
+        PropertyKey::StaticIdentifier(ident) => ident.name.as_str(),
+        PropertyKey::StringLiteral(lit) => lit.value.as_str(),
+        _ => return None,
+    };
+    let mut chars = name.chars();
+    let first = chars.next()?;
+    let valid = (first.is_ascii_alphabetic() || first == '_' || first == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '-'));
+    valid.then_some(name)
+}
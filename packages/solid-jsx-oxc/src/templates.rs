@@ -0,0 +1,237 @@
+//! Static template extraction
+//!
+//! Walks the JSX in a file and reports the native element trees the
+//! compiler would turn into templates: tags, attribute names, static
+//! attribute values, classes and static text. No code is generated, so this
+//! is cheap enough for tooling like critical-CSS extraction or scoped-CSS
+//! plugins.
+
+#[cfg(feature = "napi")]
+use napi_derive::napi;
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    Expression, JSXAttributeItem, JSXAttributeName, JSXAttributeValue, JSXChild, JSXElement,
+    JSXExpression, ObjectPropertyKind, PropertyKey,
+};
+use oxc_ast_visit::{walk, Visit};
+
+use common::{decode_html_entities, decode_jsx_text, get_tag_name, is_component, TransformOptions};
+
+use crate::error::{line_col, TransformError};
+use crate::{check_parsed, parse_source};
+
+/// A template root found in the source
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedTemplate {
+    /// 1-based line of the root element
+    pub line: u32,
+    /// 1-based column of the root element
+    pub column: u32,
+    pub root: TemplateElement,
+}
+
+/// A native element inside a template
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateElement {
+    pub tag: String,
+    pub attributes: Vec<TemplateAttribute>,
+    /// Statically known class names (`class`, `classList` keys, `class:name`)
+    pub classes: Vec<String>,
+    /// Static text children
+    pub text: Vec<String>,
+    /// Native element children
+    pub children: Vec<TemplateElement>,
+    /// Whether the element has expression, component or fragment children
+    pub has_dynamic_children: bool,
+}
+
+/// An attribute of a template element
+#[cfg_attr(feature = "napi", napi(object))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateAttribute {
+    /// Attribute name as written, `...` for spreads
+    pub name: String,
+    /// Value when it is a static string, number or boolean
+    pub value: Option<String>,
+    /// Whether the value is only known at runtime
+    pub dynamic: bool,
+}
+
+/// Collect the static templates in `source` without generating code
+pub fn extract_templates(
+    source: &str,
+    options: Option<TransformOptions>,
+) -> Result<Vec<ExtractedTemplate>, TransformError> {
+    let options = options.unwrap_or_else(TransformOptions::solid_defaults);
+    let allocator = Allocator::default();
+    let parsed = parse_source(&allocator, source, &options);
+    check_parsed(&parsed, source, options.filename)?;
+
+    let mut extractor = TemplateExtractor {
+        source,
+        templates: Vec::new(),
+    };
+    extractor.visit_program(&parsed.program);
+    Ok(extractor.templates)
+}
+
+struct TemplateExtractor<'s> {
+    source: &'s str,
+    templates: Vec<ExtractedTemplate>,
+}
+
+impl<'a> Visit<'a> for TemplateExtractor<'_> {
+    fn visit_jsx_element(&mut self, element: &JSXElement<'a>) {
+        if is_component(&get_tag_name(element)) {
+            walk::walk_jsx_element(self, element);
+            return;
+        }
+        let (line, column) = line_col(self.source, element.span.start as usize);
+        // Nested templates are pushed while building; keep source order
+        let index = self.templates.len();
+        let root = self.build_element(element);
+        self.templates.insert(
+            index,
+            ExtractedTemplate {
+                line: line as u32,
+                column: column as u32,
+                root,
+            },
+        );
+    }
+}
+
+impl<'a> TemplateExtractor<'_> {
+    /// Describe a native element; nested templates (in attribute
+    /// expressions or non-element children) are collected separately
+    fn build_element(&mut self, element: &JSXElement<'a>) -> TemplateElement {
+        let mut template = TemplateElement {
+            tag: get_tag_name(element),
+            attributes: Vec::new(),
+            classes: Vec::new(),
+            text: Vec::new(),
+            children: Vec::new(),
+            has_dynamic_children: false,
+        };
+
+        for item in &element.opening_element.attributes {
+            match item {
+                JSXAttributeItem::Attribute(attr) => {
+                    let name = match &attr.name {
+                        JSXAttributeName::Identifier(id) => id.name.to_string(),
+                        JSXAttributeName::NamespacedName(ns) => {
+                            format!("{}:{}", ns.namespace.name, ns.name.name)
+                        }
+                    };
+                    let value = match &attr.value {
+                        None => Some("true".to_string()),
+                        Some(JSXAttributeValue::StringLiteral(lit)) => {
+                            Some(decode_html_entities(&lit.value))
+                        }
+                        Some(JSXAttributeValue::ExpressionContainer(container)) => {
+                            match &container.expression {
+                                JSXExpression::EmptyExpression(_) => None,
+                                expr => {
+                                    let expr = expr.to_expression();
+                                    self.collect_classes(&name, expr, &mut template.classes);
+                                    self.visit_expression(expr);
+                                    static_value(expr)
+                                }
+                            }
+                        }
+                        Some(value) => {
+                            self.visit_jsx_attribute_value(value);
+                            None
+                        }
+                    };
+
+                    if let Some(class_name) = name.strip_prefix("class:") {
+                        template.classes.push(class_name.to_string());
+                    } else if name == "class" || name == "className" {
+                        if let Some(value) = &value {
+                            template
+                                .classes
+                                .extend(value.split_whitespace().map(str::to_string));
+                        }
+                    }
+
+                    template.attributes.push(TemplateAttribute {
+                        dynamic: value.is_none(),
+                        name,
+                        value,
+                    });
+                }
+                JSXAttributeItem::SpreadAttribute(spread) => {
+                    self.visit_expression(&spread.argument);
+                    template.attributes.push(TemplateAttribute {
+                        name: "...".to_string(),
+                        value: None,
+                        dynamic: true,
+                    });
+                }
+            }
+        }
+
+        for child in &element.children {
+            match child {
+                JSXChild::Text(text) => {
+                    let text = decode_jsx_text(&text.value);
+                    if !text.trim().is_empty() {
+                        template.text.push(text);
+                    }
+                }
+                JSXChild::Element(child) if !is_component(&get_tag_name(child)) => {
+                    let child = self.build_element(child);
+                    template.children.push(child);
+                }
+                JSXChild::ExpressionContainer(container)
+                    if matches!(container.expression, JSXExpression::EmptyExpression(_)) => {}
+                child => {
+                    template.has_dynamic_children = true;
+                    self.visit_jsx_child(child);
+                }
+            }
+        }
+
+        template
+    }
+
+    /// Static class names from the keys of `classList={{ a: x }}`
+    fn collect_classes(&self, name: &str, expr: &Expression<'a>, classes: &mut Vec<String>) {
+        if name != "classList" {
+            return;
+        }
+        let Expression::ObjectExpression(object) = expr.without_parentheses() else {
+            return;
+        };
+        for property in &object.properties {
+            let ObjectPropertyKind::ObjectProperty(property) = property else {
+                continue;
+            };
+            let key = match &property.key {
+                PropertyKey::StaticIdentifier(id) => id.name.as_str(),
+                PropertyKey::StringLiteral(lit) => lit.value.as_str(),
+                _ => continue,
+            };
+            classes.extend(key.split_whitespace().map(str::to_string));
+        }
+    }
+}
+
+/// String form of a literal attribute value
+fn static_value(expr: &Expression<'_>) -> Option<String> {
+    match expr.without_parentheses() {
+        Expression::StringLiteral(lit) => Some(lit.value.to_string()),
+        Expression::NumericLiteral(lit) => Some(lit.value.to_string()),
+        Expression::BooleanLiteral(lit) => Some(lit.value.to_string()),
+        Expression::TemplateLiteral(lit) if lit.expressions.is_empty() => lit
+            .quasis
+            .first()
+            .and_then(|quasi| quasi.value.cooked)
+            .map(|cooked| cooked.to_string()),
+        _ => None,
+    }
+}
@@ -0,0 +1,45 @@
+//! TypeScript stripping
+//!
+//! Runs oxc's TypeScript transform on the parsed program before the JSX
+//! transform, in the same allocator, so `.ts`/`.tsx` input comes out as plain
+//! JavaScript without a second tool. Only the TypeScript pass is enabled: JSX
+//! is left for the Solid transform and no syntax is lowered.
+
+use std::path::Path;
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::Program;
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_semantic::{Scoping, SemanticBuilder};
+use oxc_transformer::{JsxOptions, TransformOptions, Transformer, TransformerReturn};
+
+/// Strip TypeScript syntax from `program` in place, returning the errors
+/// for syntax that can't be stripped
+pub fn strip_typescript<'a>(
+    allocator: &'a Allocator,
+    program: &mut Program<'a>,
+) -> Vec<OxcDiagnostic> {
+    let scoping = SemanticBuilder::new()
+        .build(program)
+        .semantic
+        .into_scoping();
+    strip_typescript_with_scoping(allocator, program, scoping).errors
+}
+
+/// [`strip_typescript`] with scoping already built for `program`, returning
+/// the scoping updated for the stripped program
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "strip_typescript", skip_all)
+)]
+pub(crate) fn strip_typescript_with_scoping<'a>(
+    allocator: &'a Allocator,
+    program: &mut Program<'a>,
+    scoping: Scoping,
+) -> TransformerReturn {
+    let options = TransformOptions {
+        jsx: JsxOptions::disable(),
+        ..TransformOptions::default()
+    };
+    Transformer::new(allocator, Path::new(""), &options).build_with_scoping(scoping, program)
+}
@@ -0,0 +1,52 @@
+//! Unsupported syntax detection
+//!
+//! Finds JSX that parses but that the transform would otherwise drop or
+//! mistranslate without a word, so it can be reported as an error instead.
+
+use oxc_ast::ast::{JSXAttribute, JSXAttributeValue, Program};
+use oxc_ast_visit::{walk, Visit};
+use oxc_span::GetSpan;
+
+use common::get_attr_name;
+
+use crate::error::ParseError;
+
+/// Collect the unsupported constructs in `program`, in source order
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "check_syntax", skip_all)
+)]
+pub(crate) fn find_unsupported_syntax(program: &Program<'_>, filename: &str) -> Vec<ParseError> {
+    let mut finder = UnsupportedSyntaxFinder {
+        source: program.source_text,
+        filename,
+        errors: Vec::new(),
+    };
+    finder.visit_program(program);
+    finder.errors
+}
+
+struct UnsupportedSyntaxFinder<'s> {
+    source: &'s str,
+    filename: &'s str,
+    errors: Vec<ParseError>,
+}
+
+impl<'a> Visit<'a> for UnsupportedSyntaxFinder<'_> {
+    fn visit_jsx_attribute(&mut self, attr: &JSXAttribute<'a>) {
+        // `title=<span />` is valid JSX, but neither transform reads element
+        // values, so the attribute would vanish from the output
+        if let Some(value @ (JSXAttributeValue::Element(_) | JSXAttributeValue::Fragment(_))) =
+            &attr.value
+        {
+            let name = get_attr_name(&attr.name);
+            self.errors.push(ParseError::at(
+                format!("JSX as an attribute value is not supported; wrap it in braces: `{name}={{...}}`"),
+                value.span(),
+                self.source,
+                self.filename,
+            ));
+        }
+        walk::walk_jsx_attribute(self, attr);
+    }
+}
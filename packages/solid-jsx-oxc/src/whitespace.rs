@@ -0,0 +1,49 @@
+//! `preserve_whitespace`
+//!
+//! JSX text is normally trimmed the way dom-expressions does it: lines are
+//! stripped of their indentation, whitespace-only lines dropped and runs of
+//! whitespace collapsed to one space. With `preserve_whitespace`, each text
+//! child is rewritten into the string it stands for, `<pre>  a\n  b</pre>`
+//! into `<pre>{"  a\n  b"}</pre>`, before the JSX transform, which keeps
+//! string children as they are. Only strings are created, so the scoping of
+//! the program stays valid.
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::*;
+use oxc_ast::AstBuilder;
+use oxc_ast_visit::{walk_mut, VisitMut};
+
+use common::decode_html_entities;
+
+/// Turn the text children of every element and fragment in `program` into
+/// string literals
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "preserve_whitespace", skip_all)
+)]
+pub(crate) fn preserve_jsx_text<'a>(allocator: &'a Allocator, program: &mut Program<'a>) {
+    let mut preserver = TextPreserver {
+        ast: AstBuilder::new(allocator),
+    };
+    preserver.visit_program(program);
+}
+
+struct TextPreserver<'a> {
+    ast: AstBuilder<'a>,
+}
+
+impl<'a> VisitMut<'a> for TextPreserver<'a> {
+    fn visit_jsx_child(&mut self, child: &mut JSXChild<'a>) {
+        if let JSXChild::Text(text) = child {
+            if text.value.is_empty() {
+                return;
+            }
+            let ast = self.ast;
+            let value = ast.atom(&decode_html_entities(&text.value));
+            let string = ast.expression_string_literal(text.span, value, None);
+            *child = ast.jsx_child_expression_container(text.span, JSXExpression::from(string));
+            return;
+        }
+        walk_mut::walk_jsx_child(self, child);
+    }
+}
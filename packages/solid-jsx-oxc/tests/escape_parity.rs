@@ -0,0 +1,189 @@
+//! Parity of text escaping and whitespace trimming with dom-expressions
+//!
+//! The markup we emit at compile time is compared with what the runtime and
+//! babel-plugin-jsx-dom-expressions produce during hydration, so these check
+//! our implementations against literal ports of the JavaScript originals over
+//! randomly generated inputs.
+
+use common::{decode_html_entities, decode_jsx_text, escape_html, trim_whitespace};
+use proptest::prelude::*;
+use solid_jsx_oxc::{transform, GenerateMode, TransformOptions};
+
+const CASES: u32 = 5_000;
+
+/// Characters the functions treat specially, plus some that they must not
+const ALPHABET: &[char] = &[
+    'a', 'b', 'Z', '0', ' ', ' ', '\t', '\n', '\n', '\r', '\u{b}', '\u{c}', '&', '<', '>', '"',
+    '\'', ';', '#', '`', '$', '{', '}', '\u{a0}', '\u{85}', '\u{1680}', '\u{2003}', '\u{2028}',
+    '\u{202f}', '\u{3000}', '\u{feff}', '\u{200b}', 'é', '😀',
+];
+
+/// Fragments that show up in real JSX text
+const FRAGMENTS: &[&str] = &[
+    "&amp;", "&lt;", "&#39;", "\n    ", "\r\n", "  ", "<b>", "\"x\"",
+];
+
+/// Text built from the alphabet and fragments above, or any string at all
+fn text() -> impl Strategy<Value = String> {
+    let piece = prop_oneof![
+        4 => prop::sample::select(ALPHABET).prop_map(String::from),
+        1 => prop::sample::select(FRAGMENTS).prop_map(String::from),
+    ];
+    prop_oneof![
+        3 => prop::collection::vec(piece, 0..24).prop_map(|pieces| pieces.concat()),
+        1 => any::<String>(),
+    ]
+}
+
+/// `\s` of JavaScript regular expressions
+fn js_is_space(c: char) -> bool {
+    const SPACES: &[char] = &[
+        '\t', '\n', '\u{b}', '\u{c}', '\r', ' ', '\u{a0}', '\u{1680}', '\u{2028}', '\u{2029}',
+        '\u{202f}', '\u{205f}', '\u{3000}', '\u{feff}',
+    ];
+    SPACES.contains(&c) || ('\u{2000}'..='\u{200a}').contains(&c)
+}
+
+/// Port of `escape(s, attr)` from dom-expressions' server runtime, which
+/// babel-plugin-jsx-dom-expressions' `escapeHTML` mirrors
+fn reference_escape(s: &str, attr: bool) -> String {
+    let delim = if attr { "\"" } else { "<" };
+    let esc_delim = if attr { "&quot;" } else { "&lt;" };
+    let index_of = |needle: &str, from: usize| s[from..].find(needle).map(|i| i + from);
+    let mut i_delim = index_of(delim, 0);
+    let mut i_amp = index_of("&", 0);
+    if i_delim.is_none() && i_amp.is_none() {
+        return s.to_string();
+    }
+
+    let mut left = 0;
+    let mut out = String::new();
+    while let (Some(d), Some(a)) = (i_delim, i_amp) {
+        if d < a {
+            out.push_str(&s[left..d]);
+            out.push_str(esc_delim);
+            left = d + 1;
+            i_delim = index_of(delim, left);
+        } else {
+            out.push_str(&s[left..a]);
+            out.push_str("&amp;");
+            left = a + 1;
+            i_amp = index_of("&", left);
+        }
+    }
+    while let Some(d) = i_delim {
+        out.push_str(&s[left..d]);
+        out.push_str(esc_delim);
+        left = d + 1;
+        i_delim = index_of(delim, left);
+    }
+    while let Some(a) = i_amp {
+        out.push_str(&s[left..a]);
+        out.push_str("&amp;");
+        left = a + 1;
+        i_amp = index_of("&", left);
+    }
+    out.push_str(&s[left..]);
+    out
+}
+
+/// Port of babel-plugin-jsx-dom-expressions' `trimWhitespace`:
+///
+/// ```js
+/// text = text.replace(/\r/g, "");
+/// if (/\n/g.test(text)) {
+///   text = text
+///     .split("\n")
+///     .map((t, i) => (i ? t.replace(/^\s*/g, "") : t))
+///     .filter(s => !/^\s*$/.test(s))
+///     .join(" ");
+/// }
+/// return text.replace(/\s+/g, " ");
+/// ```
+fn reference_trim_whitespace(text: &str) -> String {
+    let mut text = text.replace('\r', "");
+    if text.contains('\n') {
+        text = text
+            .split('\n')
+            .enumerate()
+            .map(|(i, t)| {
+                if i > 0 {
+                    t.trim_start_matches(js_is_space)
+                } else {
+                    t
+                }
+            })
+            .filter(|s| !s.chars().all(js_is_space))
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+    let mut out = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if js_is_space(c) {
+            while chars.next_if(|&c| js_is_space(c)).is_some() {}
+            out.push(' ');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(CASES))]
+
+    #[test]
+    fn test_escape_html_matches_dom_expressions(text in text()) {
+        prop_assert_eq!(escape_html(&text, false), reference_escape(&text, false));
+    }
+
+    #[test]
+    fn test_attribute_escaping_matches_dom_expressions(text in text()) {
+        prop_assert_eq!(escape_html(&text, true), reference_escape(&text, true));
+    }
+
+    #[test]
+    fn test_jsx_text_escaped_once(text in text()) {
+        // Babel keeps JSX text as written in templates; the text we decode and
+        // escape must stand for the same characters
+        prop_assert_eq!(
+            decode_html_entities(&escape_html(&decode_jsx_text(&text), false)),
+            decode_html_entities(&reference_trim_whitespace(&text))
+        );
+    }
+
+    #[test]
+    fn test_trim_whitespace_matches_dom_expressions(text in text()) {
+        prop_assert_eq!(trim_whitespace(&text), reference_trim_whitespace(&text));
+    }
+}
+
+#[test]
+fn test_parity_known_cases() {
+    assert_eq!(escape_html("a < b > c & d", false), "a &lt; b > c &amp; d");
+    assert_eq!(
+        escape_html(r#"say "hi" & 'bye' <x>"#, true),
+        r#"say &quot;hi&quot; &amp; 'bye' <x>"#
+    );
+    assert_eq!(trim_whitespace("\n  Hello "), "Hello ");
+    assert_eq!(trim_whitespace("  Hello\n  world\n  "), " Hello world");
+    assert_eq!(trim_whitespace("a\r\n\r\n  b"), "a b");
+    assert_eq!(trim_whitespace("\n   \n"), "");
+    assert_eq!(trim_whitespace(". "), ". ");
+
+    let source = r#"<div title="a &amp; b">x &amp; y</div>"#;
+    for generate in [GenerateMode::Dom, GenerateMode::Ssr] {
+        let options = TransformOptions::builder()
+            .generate(generate)
+            .build()
+            .unwrap();
+        let code = transform(source, Some(options)).unwrap().code;
+        assert!(
+            code.contains(r#"title="a &amp; b">x &amp; y</div>"#)
+                || code.contains(r#"title=\"a &amp; b\">x &amp; y</div>"#),
+            "Output was:\n{code}"
+        );
+        assert!(!code.contains("&amp;amp;"), "Output was:\n{code}");
+    }
+}
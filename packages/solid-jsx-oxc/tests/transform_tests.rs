@@ -2,8 +2,12 @@
 //!
 //! These tests verify the OXC compiler output matches expected SolidJS patterns.
 
-use common::GenerateMode;
-use solid_jsx_oxc::{transform, TransformOptions};
+use common::{GenerateMode, ModuleFormat};
+use solid_jsx_oxc::{
+    code_frame, extract_templates, transform, transform_files, transform_program,
+    transform_program_with_scoping, OptionsError, TemplateAttribute, TransformCache,
+    TransformError, TransformOptions,
+};
 
 /// Helper to normalize whitespace for comparison
 fn normalize(s: &str) -> String {
@@ -16,16 +20,22 @@ fn normalize(s: &str) -> String {
 
 /// Test helper that transforms and returns normalized code
 fn transform_dom(source: &str) -> String {
-    let result = transform(source, None);
+    let result = transform(source, None).unwrap();
     normalize(&result.code)
 }
 
 fn transform_ssr(source: &str) -> String {
-    let options = TransformOptions {
-        generate: GenerateMode::Ssr,
-        ..TransformOptions::solid_defaults()
-    };
-    let result = transform(source, Some(options));
+    let options = TransformOptions::builder()
+        .generate(GenerateMode::Ssr)
+        .build()
+        .unwrap();
+    let result = transform(source, Some(options)).unwrap();
+    normalize(&result.code)
+}
+
+/// Test helper that transforms with `options` and returns normalized code
+fn transform_with(source: &str, options: TransformOptions<'_>) -> String {
+    let result = transform(source, Some(options)).unwrap();
     normalize(&result.code)
 }
 
@@ -60,6 +70,21 @@ fn test_dom_self_closing() {
     assert!(code.contains("template(`<div></div>`)"));
 }
 
+#[test]
+fn test_dom_identical_templates_are_shared() {
+    let code = transform_dom(
+        r#"
+const a = <i class="icon" />;
+const b = <p>{x()}<i class="icon" /></p>;
+const c = <i class="icon" />;
+"#,
+    );
+    let icon = "`<i class=\"icon\"></i>`";
+    assert_eq!(code.matches(icon).count(), 1, "{code}");
+    assert!(code.contains("c = _tmpl$1.cloneNode(true);"), "{code}");
+    assert!(!code.contains("_tmpl$3"), "{code}");
+}
+
 // ============================================================================
 // DOM: Dynamic Attributes
 // ============================================================================
@@ -68,8 +93,7 @@ fn test_dom_self_closing() {
 fn test_dom_dynamic_class() {
     let code = transform_dom(r#"<div class={style()}>content</div>"#);
     assert!(code.contains("effect"));
-    assert!(code.contains("setAttribute"));
-    assert!(code.contains("style()"));
+    assert!(code.contains("_el$1.className = style()"));
 }
 
 #[test]
@@ -94,6 +118,64 @@ fn test_dom_boolean_attribute() {
     assert!(code.contains("disabled"));
 }
 
+#[test]
+fn test_dom_bool_namespace() {
+    let code = transform_dom(
+        r#"<input bool:checked={on()} bool:hidden={true} bool:open={false} bool:y="1" />"#,
+    );
+    assert!(code.contains("_$template(`<input hidden y>`)"), "Output was:\n{code}");
+    assert!(
+        code.contains(r#"_$effect(() => _$setBoolAttribute(_el$1, "checked", on()));"#),
+        "Output was:\n{code}"
+    );
+
+    // Resolved in the template, so the span isn't walked to
+    let code = transform_dom(r#"<div><span bool:hidden={false} />{y()}</div>"#);
+    assert!(
+        code.contains("const _el$2 = _el$1.firstChild.nextSibling;"),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("_el$1.firstChild;"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_class_namespace() {
+    let code = transform_dom(
+        r#"<div class="base" class:on={on()} class:big={true} class:off class:no={false} />"#,
+    );
+    // Literal toggles are decided in the template
+    assert!(
+        code.contains(r#"_$template(`<div class="base big off"></div>`)"#),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains(r#"_$effect(() => _el$1.classList.toggle("on", !!on()));"#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains(r#"toggle("big""#), "Output was:\n{code}");
+
+    let code = transform_dom(r#"<div><span class:big />{y()}</div>"#);
+    assert!(
+        code.contains(r#"_$template(`<div><span class="big"></span><!></div>`)"#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("_el$1.firstChild;"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_class_namespace_with_dynamic_class() {
+    // Setting className would drop toggled classes, so they are merged
+    let code =
+        transform_dom(r#"<div class={c()} class:on={on()} class:big={true} class:no={0} />"#);
+    assert!(
+        code.contains(
+            r#"_$effect(() => _el$1.className = `${c() || ""} ${on() ? "on" : ""} big`);"#
+        ),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("classList.toggle"), "Output was:\n{code}");
+}
+
 // ============================================================================
 // DOM: Event Handlers
 // ============================================================================
@@ -113,6 +195,54 @@ fn test_dom_oncapture_not_delegated() {
     assert!(code.contains("addEventListener"));
 }
 
+#[test]
+fn test_dom_on_namespace_custom_event() {
+    let code = transform_dom(r#"<div on:custom-event={handler} on:click={other} />"#);
+    // on: keeps the event name as written and is never delegated
+    assert!(
+        code.contains(r#"_$addEventListener(_el$1, "custom-event", handler, false);"#),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains(r#"_$addEventListener(_el$1, "click", other, false);"#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("delegateEvents"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_oncapture_namespace() {
+    let code = transform_dom(r#"<div oncapture:click={handler} />"#);
+    assert!(
+        code.contains(r#"_$addEventListener(_el$1, "click", handler, true);"#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("delegateEvents"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_array_event_handler() {
+    let code = transform_dom(r#"<button onClick={[select, item.id]} onInput={[update]} />"#);
+    assert!(
+        code.contains("_el$1.$$click = select;\n_el$1.$$clickData = item.id;"),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains("_el$1.$$input = update;"),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("$$inputData"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_array_event_handler_not_delegated() {
+    let code = transform_dom(r#"<div onScroll={[track, "list"]} />"#);
+    assert!(
+        code.contains(r#"_$addEventListener(_el$1, "scroll", (e) => track("list", e), false);"#),
+        "Output was:\n{code}"
+    );
+}
+
 #[test]
 fn test_dom_onscroll_not_delegated() {
     let code = transform_dom(r#"<div onScroll={handler}>scroll</div>"#);
@@ -184,7 +314,7 @@ fn test_dom_nested_element_after_text_walks_next_sibling() {
 fn test_dom_component_between_elements_inserts_before_marker() {
     let code = transform_dom(r#"<div><span>text</span><Counter /><p>more</p></div>"#);
     assert!(code.contains("<span>text</span><!><p>more</p>"));
-    assert!(code.contains("insert(_el$1, createComponent(Counter, {}), _el$2)"));
+    assert!(code.contains("_$insert(_el$1, _$createComponent(Counter, {}), _el$2)"));
 }
 
 // ============================================================================
@@ -215,6 +345,36 @@ fn test_dom_ref_const_identifier_no_assignment_fallback() {
     assert!(!code.contains("setHeader=_el$"), "Output was:\n{code}");
 }
 
+#[test]
+fn test_dom_ref_member_expression() {
+    let code = transform_dom(r#"const el = <div ref={props.ref}>content</div>;"#);
+    for expected in [
+        "const _ref$1 = props.ref;",
+        "typeof _ref$1 === \"function\" ? _$use(_ref$1, _el$1) : props.ref = _el$1;",
+    ] {
+        assert!(code.contains(expected), "Missing `{expected}`, output was:\n{code}");
+    }
+    assert!(code.contains("import { use"), "Output was:\n{code}");
+
+    let code = transform_dom(r#"const el = <div ref={refs["main"]} />;"#);
+    assert!(
+        code.contains("? _$use(_ref$1, _el$1) : refs[\"main\"] = _el$1;"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_dom_ref_call_expression_not_assigned() {
+    // A computed ref value is read once and only used when it is a function
+    let code = transform_dom(r#"const el = <div ref={getRef()} />;"#);
+    assert!(code.contains("const _ref$1 = getRef();"), "Output was:\n{code}");
+    assert!(
+        code.contains("typeof _ref$1 === \"function\" && _$use(_ref$1, _el$1);"),
+        "Output was:\n{code}"
+    );
+    assert_eq!(code.matches("getRef()").count(), 1, "Output was:\n{code}");
+}
+
 #[test]
 fn test_component_ref_const_identifier_passed_directly() {
     // Component refs for const bindings (signal setters) should be passed directly
@@ -288,9 +448,9 @@ fn test_dom_does_not_duplicate_existing_solid_web_imports() {
 }
 
 #[test]
-fn test_dom_does_not_duplicate_mergeprops_from_solid_js() {
-    // mergeProps can be imported from "solid-js" (re-export) instead of "solid-js/web"
-    // The transformer should not add a duplicate import
+fn test_dom_user_mergeprops_import_kept_apart() {
+    // The user's own mergeProps binding stays untouched; generated code
+    // imports its helper under the `_$` name
     let code = transform_dom(
         r#"
         import { mergeProps } from "solid-js";
@@ -299,11 +459,11 @@ fn test_dom_does_not_duplicate_mergeprops_from_solid_js() {
         <Comp {...props} a={1} />
         "#,
     );
-    // Should use the existing mergeProps import, not add a new one
     assert!(
-        !code.contains("mergeProps } from \"solid-js/web\""),
-        "Should not add duplicate mergeProps import from solid-js/web. Output was:\n{code}"
+        code.contains("mergeProps as _$mergeProps } from \"solid-js/web\""),
+        "Should import the helper under its own name. Output was:\n{code}"
     );
+    assert!(code.contains("_$mergeProps(props, {"), "Output was:\n{code}");
     // The existing import should be preserved
     assert!(
         code.contains("mergeProps } from \"solid-js\""),
@@ -311,6 +471,26 @@ fn test_dom_does_not_duplicate_mergeprops_from_solid_js() {
     );
 }
 
+#[test]
+fn test_dom_helpers_join_existing_solid_web_import() {
+    let code = transform_dom(
+        r#"
+        import { createSignal } from "solid-js";
+        import { render } from "solid-js/web";
+        import "./app.css";
+        const a = <div>{x()}</div>;
+        "#,
+    );
+    let expected = [
+        r#"import { createSignal } from "solid-js";"#,
+        r#"import { render, insert as _$insert, template as _$template } from "solid-js/web";"#,
+        r#"import "./app.css";"#,
+        "const _tmpl$1 = _$template(`<div></div>`);",
+    ]
+    .join("\n");
+    assert!(code.starts_with(&expected), "Output was:\n{code}");
+}
+
 // ============================================================================
 // DOM: Style
 // ============================================================================
@@ -336,6 +516,19 @@ fn test_dom_style_object_dynamic() {
     assert!(code.contains("styles()"));
 }
 
+#[test]
+fn test_dom_style_and_class_list_get_previous_value() {
+    let code = transform_dom(r#"<div style={{ color: color() }} classList={classes()} />"#);
+    assert!(
+        code.contains("_$effect((_p$) => _$style(_el$1, { color: color() }, _p$));"),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains("_$effect((_p$) => _$classList(_el$1, classes(), _p$));"),
+        "Output was:\n{code}"
+    );
+}
+
 // ============================================================================
 // DOM: innerHTML/textContent
 // ============================================================================
@@ -365,6 +558,151 @@ fn test_dom_spread() {
     assert!(code.contains("props"));
 }
 
+#[test]
+fn test_dom_spread_merges_later_attributes() {
+    let code = transform_dom(r#"<div class="a" {...p} onClick={h} title="t" />"#);
+    assert!(code.contains(r#"<div class="a">"#), "Output was:\n{code}");
+    assert!(
+        code.contains(
+            "_$spread(_el$1, _$mergeProps(p, {\nonClick: h,\ntitle: \"t\"\n}), false, false);"
+        ),
+        "Output was:\n{code}"
+    );
+    // The handler goes through the spread, not delegation
+    assert!(!code.contains("$$click"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_spread_dynamic_attributes_keep_order() {
+    // Dynamic attributes before a spread can be overridden by it
+    let code = transform_dom(r#"<div title={t()} {...p} {...q} hidden />"#);
+    assert!(
+        code.contains("_$mergeProps({ get title() {\nreturn t();\n} }, p, q, { hidden: true })"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_dom_dynamic_spread_wrapped() {
+    let code = transform_dom(r#"<div {...props()} {...getProps("x")} {...local.rest} />"#);
+    assert!(
+        code.contains(r#"_$mergeProps(props, () => getProps("x"), () => local.rest)"#),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_dom_spread_keeps_ref_and_directives() {
+    let code = transform_dom(r#"<div ref={el} {...p} use:tip={1} />"#);
+    let spread = code.find("_$spread(_el$1, p, false, false);").expect(&code);
+    let directive = code.find("_$use(tip, _el$1").expect(&code);
+    assert!(spread < directive, "Output was:\n{code}");
+    assert!(code.contains("el = _el$1"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// DOM: Conditional Children
+// ============================================================================
+
+#[test]
+fn test_dom_wrap_conditionals_ternary() {
+    let code = transform_dom(r#"<div>{cond() ? <A /> : <B />}</div>"#);
+    assert!(
+        code.contains("const _c$ = _$memo(() => !!cond());\nreturn () => _c$() ? _$createComponent(A, {}) : _$createComponent(B, {});"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_dom_wrap_conditionals_logical() {
+    let code = transform_dom(r#"<div>{count() > 1 && <X />}</div>"#);
+    // Comparisons are memoized as they are
+    assert!(
+        code.contains("const _c$ = _$memo(() => count() > 1);\nreturn () => _c$() && _$createComponent(X, {});"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_dom_wrap_conditionals_nested() {
+    let code = transform_dom(r#"<div>{a() ? x() : b() ? y() : z()}</div>"#);
+    assert!(
+        code.contains("return () => _c$() ? x() : _$memo(() => !!b())() ? y() : z();"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_dom_wrap_conditionals_static_branches() {
+    // Nothing to re-create when the branches are plain values
+    let code = transform_dom(r#"<div>{cond() ? "yes" : "no"}</div>"#);
+    assert!(!code.contains("memo"), "Output was:\n{code}");
+    assert!(
+        code.contains(r#"() => cond() ? "yes" : "no""#),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_dom_wrap_conditionals_component_props_and_children() {
+    // Getters re-run anyway, so the memo is read inline
+    let code = transform_dom(r#"<Comp when={c() ? <A /> : <B />}>{ok() && <A />}</Comp>"#);
+    for expected in [
+        "return _$memo(() => !!c())() ? _$createComponent(A, {}) : _$createComponent(B, {});",
+        "return _$memo(() => !!ok())() && _$createComponent(A, {});",
+    ] {
+        assert!(code.contains(expected), "Output was:\n{code}");
+    }
+}
+
+#[test]
+fn test_dom_wrap_conditionals_fragment_child() {
+    let code = transform_dom(r#"<>{c() && <A />}</>"#);
+    assert!(
+        code.contains("_$memo(() => _$memo(() => !!c())() && _$createComponent(A, {}))"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_dom_wrap_conditionals_disabled() {
+    let options = TransformOptions::builder()
+        .wrap_conditionals(false)
+        .build()
+        .unwrap();
+    let result = transform(r#"<div>{cond() ? <A /> : <B />}</div>"#, Some(options)).unwrap();
+    let code = normalize(&result.code);
+    assert!(!code.contains("memo"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_custom_effect_and_memo_wrappers() {
+    let options = TransformOptions::builder()
+        .effect_wrapper("createRenderEffect")
+        .memo_wrapper("createMemo")
+        .build()
+        .unwrap();
+    let source = r#"<div title={title()}>{cond() ? <A /> : <B />}</div>"#;
+    let code = normalize(&transform(source, Some(options)).unwrap().code);
+    assert!(
+        code.contains("createRenderEffect as _$createRenderEffect"),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains("createMemo as _$createMemo"),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains(r#"_$createRenderEffect(() => _$setAttribute(_el$1, "title", title()));"#),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains("const _c$ = _$createMemo(() => !!cond());"),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("_$effect"), "Output was:\n{code}");
+}
+
 // ============================================================================
 // DOM: Nested Dynamic Elements
 // ============================================================================
@@ -632,6 +970,38 @@ fn test_dom_error_boundary() {
     assert!(code.contains("ErrorBoundary"));
 }
 
+#[test]
+fn test_dom_dynamic_forwards_handlers_and_ref() {
+    let code = transform_dom(r#"<Dynamic component={Comp} onClick={fn} ref={r} />"#);
+    assert!(code.contains("createComponent(Dynamic"), "Output was:\n{code}");
+    assert!(code.contains("get onClick()"), "Output was:\n{code}");
+    assert!(code.contains("ref(r$)"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_dynamic_forwards_handlers_and_ref() {
+    let code = transform_ssr(r#"<Dynamic component={Comp} onClick={fn} ref={r} title="t" />"#);
+    assert!(code.contains("createComponent(Dynamic"), "Output was:\n{code}");
+    assert!(code.contains("get onClick()"), "Output was:\n{code}");
+    assert!(code.contains("get ref()"), "Output was:\n{code}");
+    assert!(code.contains(r#"title: "t""#), "Output was:\n{code}");
+
+    // Other components still drop them on the server
+    let code = transform_ssr(r#"<Comp onClick={fn} ref={r} />"#);
+    assert!(!code.contains("onClick"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_component_namespaced_props_passed_through() {
+    let source = r#"<MyComp on:custom={fn} attr:x={1} use:dir={v} prop:y="y" onClick={h} />"#;
+    for code in [transform_dom(source), transform_ssr(source)] {
+        assert!(code.contains(r#"get "on:custom"()"#), "Output was:\n{code}");
+        assert!(code.contains(r#""attr:x": 1"#), "Output was:\n{code}");
+        assert!(code.contains(r#"get "use:dir"()"#), "Output was:\n{code}");
+        assert!(code.contains(r#""prop:y": "y""#), "Output was:\n{code}");
+    }
+}
+
 // ============================================================================
 // SSR: Basic Elements
 // ============================================================================
@@ -673,6 +1043,99 @@ fn test_ssr_for() {
     assert!(code.contains("get each()"));
 }
 
+#[test]
+fn test_ssr_component_escaped_once() {
+    let code = transform_ssr(r#"const a = <Comp />; const b = <div><Comp /></div>;"#);
+    assert!(
+        code.contains("const a = _$createComponent(Comp, {});"),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains("_$ssr`<div>${_$escape(_$createComponent(Comp, {}))}</div>`"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_ssr_lone_component_imports_no_escape() {
+    let code = transform_ssr(
+        r#"const a = <Comp />; const b = <Show when={x()}>{(v) => <B v={v} />}</Show>;"#,
+    );
+    assert!(
+        code.contains("import { createComponent as _$createComponent } from"),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("escape"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_nested_static_markup_stays_html() {
+    // Static JSX used as a value must not be encoded as text when rendered
+    let code = transform_ssr(r#"<div>{cond() ? <p>x</p> : <Comp />}</div>"#);
+    assert!(
+        code.contains("_$escape(cond() ? _$ssr`<p>x</p>` : _$createComponent(Comp, {}))"),
+        "Output was:\n{code}"
+    );
+
+    let code = transform_ssr(r#"<div>{<p>x</p>}</div>"#);
+    assert!(!code.contains("&lt;p&gt;"), "Output was:\n{code}");
+
+    let code = transform_ssr(r#"<Comp><p>x</p></Comp>"#);
+    assert!(code.contains("return _$ssr`<p>x</p>`;"), "Output was:\n{code}");
+    assert!(code.contains("_$createComponent, ssr as _$ssr }"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_bool_namespace() {
+    let code =
+        transform_ssr(r#"<input bool:checked={on()} bool:hidden={true} bool:open={false} />"#);
+    assert!(
+        code.contains(r#"_$ssr`<input${_$ssrAttribute("checked", on(), true)} hidden>`"#),
+        "Output was:\n{code}"
+    );
+
+    let code = transform_ssr(r#"<div {...props} bool:open={open()} />"#);
+    assert!(code.contains(r#""open": open() ? "" : undefined"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_class_namespace() {
+    let code =
+        transform_ssr(r#"<div class="base" class:on={on()} class:big={true} class:x={false} />"#);
+    assert!(
+        code.contains(r#"_$ssr`<div class="base ${on() ? "on" : ""} big"></div>`"#),
+        "Output was:\n{code}"
+    );
+
+    let code = transform_ssr(r#"<div class="a" classList={{ b: b() }} />"#);
+    assert!(
+        code.contains(r#"<div class="a ${_$ssrClassList({ b: b() })}"></div>"#),
+        "Output was:\n{code}"
+    );
+
+    let code = transform_ssr(r#"<div {...props} classList={list()} class:on={on()} />"#);
+    assert!(
+        code.contains(r#""classList": {"#) && code.contains(r#"...list(),"#),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains(r#""on": on()"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_component_text_children_not_preescaped() {
+    let code = transform_ssr(r#"<Comp>a & b</Comp>"#);
+    assert!(code.contains(r#"children: "a & b""#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_component_entities_decoded() {
+    let source = r#"<Comp title="a &amp; b">x &amp; y</Comp>"#;
+    for code in [transform_dom(source), transform_ssr(source)] {
+        assert!(code.contains(r#"title: "a & b""#), "Output was:\n{code}");
+        assert!(code.contains(r#"children: "x & y""#), "Output was:\n{code}");
+    }
+}
+
 // ============================================================================
 // Edge Cases
 // ============================================================================
@@ -690,6 +1153,70 @@ fn test_fragment_with_children() {
     assert!(code.contains("template"));
 }
 
+#[test]
+fn test_generated_names_skip_user_bindings() {
+    let code = transform_dom(
+        r#"const _tmpl$1 = 1, _el$1 = 2;
+const a = <div class={_el$1}>{x()}</div>;"#,
+    );
+    for expected in [
+        "const _tmpl$1 = 1, _el$1 = 2;",
+        "const _tmpl$2 = _$template(`<div></div>`);",
+        "const _el$2 = _tmpl$2.cloneNode(true);",
+        "_el$2.className = _el$1",
+    ] {
+        assert!(code.contains(expected), "Output was:\n{code}");
+    }
+
+    // Bindings of nested scopes are skipped too, wherever the JSX is
+    let code = transform_dom(
+        r#"function f(_el$1) {
+  { let _tmpl$1; }
+  return <p>{_el$1}</p>;
+}
+const b = <b>{y()}</b>;"#,
+    );
+    for expected in [
+        "const _tmpl$2 = _$template(`<p></p>`);",
+        "const _el$2 = _tmpl$2.cloneNode(true);",
+        "_$insert(_el$2, () => _el$1);",
+        "const _el$3 = _tmpl$3.cloneNode(true);",
+    ] {
+        assert!(code.contains(expected), "Output was:\n{code}");
+    }
+}
+
+#[test]
+fn test_generated_locals_skip_user_bindings() {
+    let code = transform_dom(
+        r#"let r$;
+const a = <Comp ref={r$} />;"#,
+    );
+    for expected in ["ref(r$2) {", "var _ref$ = r$;", ": r$ = r$2;"] {
+        assert!(code.contains(expected), "Output was:\n{code}");
+    }
+}
+
+#[test]
+fn test_helpers_dont_collide_with_user_bindings() {
+    let code = transform_dom(
+        r#"import { insert } from "./db";
+function template(row) { return row; }
+const a = <div>{insert(template(x))}</div>;"#,
+    );
+    for expected in [
+        r#"import { insert } from "./db";"#,
+        r#"import { insert as _$insert, template as _$template } from "solid-js/web";"#,
+        "const _tmpl$1 = _$template(`<div></div>`);",
+        "_$insert(_el$1, () => insert(template(x)));",
+    ] {
+        assert!(code.contains(expected), "Missing `{expected}`, output was:\n{code}");
+    }
+
+    let code = transform_ssr(r#"const escape = (s) => s; const a = <p>{escape(x)}</p>;"#);
+    assert!(code.contains("_$ssr`<p>${_$escape(escape(x))}</p>`"), "Output was:\n{code}");
+}
+
 #[test]
 fn test_fragment_multiple_root_elements_declare_el_bindings() {
     // Regression: multi-root fragments must not merge into a single template output
@@ -734,6 +1261,29 @@ fn test_svg_element() {
     assert!(code.contains("circle"));
 }
 
+#[test]
+fn test_svg_namespace_follows_parent() {
+    // `<a>` is an SVG link inside <svg>, and class is an attribute there
+    let code = transform_dom(r#"<svg><a href={u()} className={c()}><text>x</text></a></svg>"#);
+    let class = r#"_$setAttribute(_el$2, "class", c())"#;
+    assert!(code.contains(class), "{code}");
+    assert!(!code.contains("className ="), "{code}");
+
+    // Inserted elements follow the elements around them
+    let code = transform_dom(r#"<svg>{y() && <a href="/"><circle /></a>}</svg>"#);
+    let template = r#"_$template(`<a href="/"><circle></circle></a>`, true)"#;
+    assert!(code.contains(template), "{code}");
+}
+
+#[test]
+fn test_foreign_object_children_are_html() {
+    let code = transform_dom(
+        r#"<svg><foreignObject><div className={c()}><title>{t()}</title></div>{x() && <title>hi</title>}</foreignObject></svg>"#,
+    );
+    assert!(code.contains("_el$4.className = c()"), "{code}");
+    assert!(code.contains("_$template(`<title>hi</title>`);"), "{code}");
+}
+
 #[test]
 fn test_custom_element() {
     let code = transform_dom(r#"<my-element attr="value">content</my-element>"#);
@@ -760,6 +1310,41 @@ fn test_whitespace_handling() {
     assert!(code.contains("hello"));
 }
 
+#[test]
+fn test_whitespace_between_text_and_expressions() {
+    let code = transform_dom(
+        r#"<p>
+    Hello {name()}!
+    {a()} {b()}
+</p>"#,
+    );
+    assert!(code.contains("`<p>Hello <!>!<!> <!></p>`"), "{code}");
+}
+
+#[test]
+fn test_preserve_whitespace() {
+    let source = "<pre>\n  Hello   {name()}!\n    &amp; more</pre>";
+    for generate in [GenerateMode::Dom, GenerateMode::Ssr] {
+        let options = TransformOptions::builder()
+            .generate(generate)
+            .preserve_whitespace(true)
+            .build()
+            .unwrap();
+        let code = transform(source, Some(options)).unwrap().code;
+        assert!(code.contains("<pre>\n  Hello   "), "{code}");
+        assert!(code.contains("!\n    &amp; more</pre>`"), "{code}");
+    }
+
+    let options = TransformOptions::builder()
+        .preserve_whitespace(true)
+        .build()
+        .unwrap();
+    let source = "<Comp>  Hi {name()}  </Comp>";
+    let code = transform(source, Some(options)).unwrap().code;
+    assert!(code.contains(r#""  Hi ""#), "{code}");
+    assert!(code.contains(r#""  ""#), "{code}");
+}
+
 #[test]
 fn test_special_characters() {
     let code = transform_dom(r#"<div>&amp; &lt; &gt;</div>"#);
@@ -807,27 +1392,85 @@ fn test_ssr_imports() {
 
 #[test]
 fn test_dom_source_map_generation() {
-    let options = TransformOptions {
-        filename: "input.jsx",
-        source_map: true,
-        ..TransformOptions::solid_defaults()
-    };
-    let result = transform(r#"<div>{x()}</div>"#, Some(options));
+    let options = TransformOptions::builder()
+        .filename("input.jsx")
+        .source_map(true)
+        .build()
+        .unwrap();
+    let result = transform(r#"<div>{x()}</div>"#, Some(options)).unwrap();
     assert!(result.map.is_some(), "expected source map to be generated");
 }
 
 #[test]
 fn test_ssr_source_map_generation() {
-    let options = TransformOptions {
-        generate: GenerateMode::Ssr,
-        filename: "input.jsx",
-        source_map: true,
-        ..TransformOptions::solid_defaults()
-    };
-    let result = transform(r#"<div>{x()}</div>"#, Some(options));
+    let options = TransformOptions::builder()
+        .generate(GenerateMode::Ssr)
+        .filename("input.jsx")
+        .source_map(true)
+        .build()
+        .unwrap();
+    let result = transform(r#"<div>{x()}</div>"#, Some(options)).unwrap();
     assert!(result.map.is_some(), "expected source map to be generated");
 }
 
+/// Source position (0-based line and column) that the generated code at the
+/// first occurrence of `needle` maps back to
+fn original_position(output: &solid_jsx_oxc::TransformOutput, needle: &str) -> (u32, u32) {
+    let offset = output.code.find(needle).expect("needle not in output");
+    let before = &output.code[..offset];
+    let line = before.matches('\n').count() as u32;
+    let col = (offset - before.rfind('\n').map_or(0, |i| i + 1)) as u32;
+    let map = output.map.as_ref().expect("no source map");
+    let token = map
+        .get_tokens()
+        .filter(|t| (t.get_dst_line(), t.get_dst_col()) <= (line, col))
+        .max_by_key(|t| (t.get_dst_line(), t.get_dst_col()))
+        .expect("no mapping before needle");
+    (token.get_src_line(), token.get_src_col())
+}
+
+const MAPPED_VIEW: &str = r#"import Comp from "./Comp";
+const view = (
+  <div class={cls()}>
+    <span>{label()}</span>
+    <Comp value={v()} />
+  </div>
+);"#;
+
+#[test]
+fn test_dom_source_map_points_at_jsx() {
+    let options = TransformOptions::builder()
+        .filename("view.jsx")
+        .source_map(true)
+        .build()
+        .unwrap();
+    let output = transform(MAPPED_VIEW, Some(options)).unwrap();
+    assert_eq!(original_position(&output, "const _el$1 ="), (2, 2));
+    assert_eq!(original_position(&output, "_$insert(_el$2"), (3, 10));
+    assert_eq!(original_position(&output, "_$createComponent(Comp"), (4, 4));
+    assert_eq!(original_position(&output, "get value()"), (4, 10));
+    assert_eq!(original_position(&output, "_$effect("), (2, 7));
+    // Generated names aren't recorded as renamings of the JSX they map to
+    let map = output.map.as_ref().unwrap();
+    assert_eq!(map.get_names().count(), 0, "Output was:\n{}", output.code);
+}
+
+#[test]
+fn test_ssr_source_map_points_at_jsx() {
+    let options = TransformOptions::builder()
+        .generate(GenerateMode::Ssr)
+        .filename("view.jsx")
+        .source_map(true)
+        .build()
+        .unwrap();
+    let output = transform(MAPPED_VIEW, Some(options)).unwrap();
+    assert_eq!(original_position(&output, "_$ssr`<div"), (2, 2));
+    assert_eq!(original_position(&output, "label()"), (3, 11));
+    assert_eq!(original_position(&output, "get value()"), (4, 10));
+    let map = output.map.as_ref().unwrap();
+    assert_eq!(map.get_names().count(), 0, "Output was:\n{}", output.code);
+}
+
 // ============================================================================
 // Regression Tests for Nested Dynamic Content
 // ============================================================================
@@ -880,3 +1523,2162 @@ fn test_dom_two_siblings_with_events() {
         code
     );
 }
+
+// ============================================================================
+// CommonJS Output
+// ============================================================================
+
+#[test]
+fn test_dom_cjs_requires_helpers() {
+    let code = transform_with(
+        r#"<div onClick={handler}>{count()}</div>"#,
+        TransformOptions::builder()
+            .module_format(ModuleFormat::Cjs)
+            .build()
+            .unwrap(),
+    );
+    assert!(!code.contains("import "), "Output was:\n{code}");
+    assert!(
+        code.starts_with(
+            "const { insert: _$insert, template: _$template, delegateEvents: _$delegateEvents } = require(\"solid-js/web\");"
+        ),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains("const _tmpl$1 = _$template("), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_cjs_keeps_user_bindings() {
+    let code = transform_with(
+        r#"
+        import { mergeProps } from "solid-js";
+        const Comp = (p) => p;
+        <Comp {...props} a={1} />
+        "#,
+        TransformOptions::builder()
+            .module_format(ModuleFormat::Cjs)
+            .build()
+            .unwrap(),
+    );
+    assert!(
+        code.contains(
+            "const { createComponent: _$createComponent, mergeProps: _$mergeProps } = require(\"solid-js/web\");"
+        ),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains(r#"import { mergeProps } from "solid-js";"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_cjs_requires_helpers() {
+    let code = transform_with(
+        r#"<div class={style()}>content</div>"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .module_format(ModuleFormat::Cjs)
+            .build()
+            .unwrap(),
+    );
+    assert!(!code.contains("import "), "Output was:\n{code}");
+    assert!(code.contains("} = require(\"solid-js/web\");"), "Output was:\n{code}");
+    assert!(code.contains("ssr"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// TypeScript Stripping
+// ============================================================================
+
+#[test]
+fn test_ts_strips_types() {
+    let code = transform_with(
+        r#"
+        import type { JSX } from "solid-js";
+        import { Component, createSignal } from "solid-js";
+        interface Props { name: string }
+        type Name = string;
+        declare const injected: number;
+        const Greeting: Component<Props> = (props: Props): JSX.Element => (
+          <div class={props.name as Name}>{props.name!}</div>
+        );
+        const [count, setCount] = createSignal<number>(0);
+        "#,
+        TransformOptions::builder()
+            .filename("input.tsx")
+            .strip_types(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains("import { createSignal } from \"solid-js\";"), "Output was:\n{code}");
+    assert!(!code.contains("Component"), "Output was:\n{code}");
+    assert!(!code.contains("interface") && !code.contains("type "), "Output was:\n{code}");
+    assert!(!code.contains("injected"), "Output was:\n{code}");
+    assert!(code.contains("const Greeting = (props) =>"), "Output was:\n{code}");
+    assert!(code.contains("createSignal(0)"), "Output was:\n{code}");
+    assert!(!code.contains(" as Name") && !code.contains("!"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ts_lowers_enum() {
+    let code = transform_with(
+        "export enum Color { Red, Green = 5, Blue, Name = \"n\" }",
+        TransformOptions::builder()
+            .filename("input.tsx")
+            .strip_types(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains("export let Color = /* @__PURE__ */ function(Color) {"), "Output was:\n{code}");
+    assert!(code.contains("Color[Color[\"Red\"] = 0] = \"Red\";"), "Output was:\n{code}");
+    assert!(code.contains("Color[Color[\"Blue\"] = 6] = \"Blue\";"), "Output was:\n{code}");
+    assert!(code.contains("Color[\"Name\"] = \"n\";"), "Output was:\n{code}");
+    assert!(
+        code.contains("return Color;\n}({});"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_ts_lowers_namespace() {
+    let code = transform_with(
+        r#"
+        namespace Utils {
+          export const size = 1;
+          export function double(n: number) { return n * 2; }
+          export type Size = number;
+        }
+        namespace Types { export interface Only {} }
+        "#,
+        TransformOptions::builder()
+            .filename("input.tsx")
+            .strip_types(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains("let Utils;"), "Output was:\n{code}");
+    assert!(code.contains("const size = _Utils.size = 1;"), "Output was:\n{code}");
+    assert!(code.contains("_Utils.double = double;"), "Output was:\n{code}");
+    assert!(code.contains("})(Utils || (Utils = {}));"), "Output was:\n{code}");
+    assert!(!code.contains("Types"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ts_class_members() {
+    let code = transform_with(
+        r#"
+        abstract class Base implements Named {
+          declare injected: number;
+          private readonly label?: string = "x";
+          abstract render(): void;
+        }
+        class Child extends Base {
+          constructor(private name: string) { super(); }
+          render(): void {}
+        }
+        "#,
+        TransformOptions::builder()
+            .filename("input.tsx")
+            .strip_types(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains("class Base {"), "Output was:\n{code}");
+    assert!(code.contains("label = \"x\";"), "Output was:\n{code}");
+    assert!(!code.contains("injected") && !code.contains("abstract"), "Output was:\n{code}");
+    assert!(code.contains("constructor(name) {\nsuper();\nthis.name = name;"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ts_not_stripped_by_default() {
+    let options = TransformOptions::builder()
+        .filename("input.tsx")
+        .build()
+        .unwrap();
+    let code = transform("const a: number = 1;", Some(options))
+        .unwrap()
+        .code;
+    assert!(code.contains("const a: number = 1;"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Minification
+// ============================================================================
+
+#[test]
+fn test_minify_output() {
+    let options = TransformOptions::builder().minify(true).build().unwrap();
+    let code = transform(
+        r#"const a = 1; const view = <div><span class={style()}>{count()}</span></div>;"#,
+        Some(options),
+    )
+    .unwrap()
+    .code;
+    assert!(!code.contains("_el$") && !code.contains("_tmpl$"), "Output was:\n{code}");
+    assert!(!code.contains("\n  "), "Output was:\n{code}");
+    // Short names skip names already used in the program
+    assert!(!code.contains("const a=template"), "Output was:\n{code}");
+    assert!(code.contains("const a=1"), "Output was:\n{code}");
+    assert!(code.contains("template(`<div><span></span></div>`)"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Static Subtree Prerendering
+// ============================================================================
+
+#[test]
+fn test_dom_prerenders_literal_children() {
+    let code = transform_dom(r#"<div>{props.x}<p>{"hello"} {42}</p></div>"#);
+    assert!(
+        code.contains("template(`<div><!><p>hello 42</p></div>`)"),
+        "Output was:\n{code}"
+    );
+    assert_eq!(code.matches("insert(").count(), 1, "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_prerenders_static_subtree() {
+    let code = transform_ssr(r#"<section><p>{"a < b"}</p><br /><i>{`tpl`}</i></section>"#);
+    assert!(
+        code.contains(r#""<section><p>a &lt; b</p><br><i>tpl</i></section>""#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("escape("), "Output was:\n{code}");
+}
+
+#[test]
+fn test_prerender_escapes_template_literal_syntax() {
+    let code = transform_dom(r#"<p>{"a`b ${c}"}</p>"#);
+    assert!(
+        code.contains(r#"template(`<p>a\`b \${c}</p>`)"#),
+        "Output was:\n{code}"
+    );
+}
+
+// ============================================================================
+// Constant Folding
+// ============================================================================
+
+#[test]
+fn test_dom_constant_attributes_inlined() {
+    let code = transform_dom(
+        r#"<div class={"a" + " b"} title={`hello`} tabIndex={1 + 1} id={`n${2 * 3}`} hidden={!0} />"#,
+    );
+    assert!(
+        code.contains(r#"<div class="a b" title="hello" tabindex="2" id="n6" hidden></div>"#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("effect"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_constant_children_inlined() {
+    let code = transform_dom(r#"<p>{"a" + 1}{true ? "b" : c}{null ?? "d"}</p>"#);
+    assert!(
+        code.contains("_$template(`<p>a1bd</p>`)"),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("insert"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_partially_constant_stays_dynamic() {
+    let code = transform_dom(r#"<div title={"a" + name()} tabIndex={1e21 + 1} />"#);
+    assert!(
+        code.contains(r#"_$setAttribute(_el$1, "title", "a" + name())"#),
+        "Output was:\n{code}"
+    );
+    // Numbers JS prints with an exponent are left to the runtime
+    assert!(code.contains("1e21 + 1"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_constant_expressions_inlined() {
+    let code = transform_ssr(r#"<div class={"a" + " b"} tabIndex={1 + 1}>{`x${1}`}</div>"#);
+    assert!(
+        code.contains(r#"("<div class=\"a b\" tabindex=\"2\">x1</div>")"#),
+        "Output was:\n{code}"
+    );
+}
+
+// ============================================================================
+// Environment Branch Folding
+// ============================================================================
+
+#[test]
+fn test_fold_is_server_in_dom() {
+    let code = transform_dom(
+        r#"import { isServer } from "solid-js/web";
+const view = isServer ? <div>server</div> : <span>client</span>;
+if (isServer) { renderOnServer(); } else { hydrate(); }"#,
+    );
+    assert!(!code.contains("<div>server</div>"), "Output was:\n{code}");
+    assert!(code.contains("template(`<span>client</span>`)"), "Output was:\n{code}");
+    assert!(!code.contains("renderOnServer"), "Output was:\n{code}");
+    assert!(code.contains("hydrate();"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_fold_is_server_in_ssr() {
+    let code = transform_ssr(
+        r#"import { isServer } from "solid-js/web";
+const flags = { isServer, lazy: !isServer && load() };"#,
+    );
+    assert!(code.contains("isServer: true"), "Output was:\n{code}");
+    assert!(code.contains("lazy: false"), "Output was:\n{code}");
+    assert!(!code.contains("load()"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_fold_dev_only_when_known() {
+    let source = r#"import { DEV } from "solid-js";
+function f(isServer) { return isServer ? 1 : 2; }
+if (DEV) { warn(); }"#;
+    let code = transform_dom(source);
+    assert!(code.contains("if (DEV)"), "Output was:\n{code}");
+    // Unrelated bindings named like a flag are left alone
+    assert!(code.contains("isServer ? 1 : 2"), "Output was:\n{code}");
+
+    let options = TransformOptions::builder().dev(false).build().unwrap();
+    let code = transform(source, Some(options)).unwrap().code;
+    assert!(!code.contains("warn()"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_fold_environment_disabled() {
+    let options = TransformOptions::builder()
+        .fold_environment(false)
+        .build()
+        .unwrap();
+    let code = transform(
+        r#"import { isServer } from "solid-js/web"; const a = isServer ? 1 : 2;"#,
+        Some(options),
+    )
+    .unwrap()
+    .code;
+    assert!(code.contains("isServer ? 1 : 2"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Define Replacements
+// ============================================================================
+
+#[test]
+fn test_define_inlines_into_template() {
+    let code = transform_with(
+        r#"<div class={__THEME__} title={import.meta.env.TITLE}>{__VERSION__}</div>"#,
+        TransformOptions::builder()
+            .define_constant("__THEME__", r#""dark""#)
+            .define_constant("import.meta.env.TITLE", "'App'")
+            .define_constant("__VERSION__", r#""1.2.0""#)
+            .build()
+            .unwrap(),
+    );
+    assert!(
+        code.contains(r#"template(`<div class="dark" title="App">1.2.0</div>`)"#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("effect("), "Output was:\n{code}");
+}
+
+#[test]
+fn test_define_folds_branches() {
+    let code = transform_with(
+        r#"if (__DEV__) { validate(); }
+const view = import.meta.env.SSR ? <div>server</div> : <span>client</span>;
+const mode = process.env.NODE_ENV;"#,
+        TransformOptions::builder()
+            .define_constant("__DEV__", "false")
+            .define_constant("import.meta.env.SSR", "false")
+            .define_constant("process.env.NODE_ENV", r#""production""#)
+            .build()
+            .unwrap(),
+    );
+    assert!(!code.contains("validate"), "Output was:\n{code}");
+    assert!(!code.contains("server"), "Output was:\n{code}");
+    assert!(code.contains(r#"const mode = "production";"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_define_skips_local_bindings() {
+    let code = transform_with(
+        r#"function check(__DEV__) { return __DEV__; }"#,
+        TransformOptions::builder()
+            .define_constant("__DEV__", "false")
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains("return __DEV__;"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Custom Elements Registry
+// ============================================================================
+
+#[test]
+fn test_custom_elements_option() {
+    let source = r#"<section><x className="a">{"text"}</x><ui-card /></section>"#;
+    let options = TransformOptions::builder()
+        .generate(GenerateMode::Ssr)
+        .custom_elements(["x"])
+        .build()
+        .unwrap();
+    // Listed tags are not prerendered and keep their attribute names
+    let code = transform(source, Some(options)).unwrap().code;
+    assert!(code.contains(r#"<x className=\"a\">text</x>"#), "Output was:\n{code}");
+
+    let code = transform_ssr(source);
+    assert!(code.contains(r#"<x class=\"a\">text</x>"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_custom_elements_prefix_pattern() {
+    let options = TransformOptions::builder()
+        .generate(GenerateMode::Ssr)
+        .custom_elements(["ion*"])
+        .build()
+        .unwrap();
+    let code = transform(r#"<ionbutton htmlFor="a" />"#, Some(options))
+        .unwrap()
+        .code;
+    assert!(code.contains("htmlFor="), "Output was:\n{code}");
+}
+
+#[test]
+fn test_custom_element_properties() {
+    let code = transform_dom(r#"<my-el items={list()} data-id={id()} slot={s()} mode="dark" />"#);
+    for expected in [
+        "_el$1.items = list()",
+        r#"_$setAttribute(_el$1, "data-id", id())"#,
+        r#"_$setAttribute(_el$1, "slot", s())"#,
+        r#"<my-el mode="dark">"#,
+    ] {
+        assert!(code.contains(expected), "Output was:\n{code}");
+    }
+}
+
+#[test]
+fn test_context_to_custom_elements() {
+    let source = r#"<div><my-el /><slot /><span /></div>"#;
+    let code = transform_dom(source);
+    assert!(
+        code.contains("_el$2._$owner = _$getOwner();"),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains("_el$3._$owner = _$getOwner();"),
+        "Output was:\n{code}"
+    );
+    assert_eq!(code.matches("_$owner =").count(), 2, "Output was:\n{code}");
+
+    let options = TransformOptions::builder()
+        .context_to_custom_elements(false)
+        .build()
+        .unwrap();
+    let code = transform(source, Some(options)).unwrap().code;
+    assert!(!code.contains("getOwner"), "Output was:\n{code}");
+    assert!(
+        code.contains("<div><my-el></my-el><slot></slot><span></span></div>"),
+        "{code}"
+    );
+}
+
+// ============================================================================
+// CSP-safe Templates
+// ============================================================================
+
+#[test]
+fn test_csp_safe_builds_nodes() {
+    let code = transform_with(
+        r#"<div class="card" hidden>{count()}<p>a &amp; b</p><br /></div>"#,
+        TransformOptions::builder().csp_safe(true).build().unwrap(),
+    );
+    assert!(!code.contains("template("), "Output was:\n{code}");
+    assert!(!code.contains("template as _$template"), "Output was:\n{code}");
+    assert!(
+        code.contains(r#"const _n$1 = document.createElement("div");"#),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains(r#"_n$1.setAttribute("class", "card");"#), "Output was:\n{code}");
+    assert!(code.contains(r#"_n$1.setAttribute("hidden", "");"#), "Output was:\n{code}");
+    assert!(
+        code.contains(r#"_n$1.appendChild(document.createComment(""));"#),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains("return _n$1;"), "Output was:\n{code}");
+    // Usage is unchanged: the node is cloned per render
+    assert!(code.contains("_tmpl$1.cloneNode(true)"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_csp_safe_svg_namespace() {
+    let code = transform_with(
+        r#"<svg><foreignObject><div /></foreignObject></svg>"#,
+        TransformOptions::builder().csp_safe(true).build().unwrap(),
+    );
+    assert!(
+        code.contains(r#"document.createElementNS("http://www.w3.org/2000/svg", "foreignObject")"#),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains(r#"document.createElement("div")"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_csp_safe_namespaced_attributes() {
+    let code = transform_with(
+        r##"<svg xmlns:xlink="http://www.w3.org/1999/xlink"><use xlink:href="#icon" /></svg>"##,
+        TransformOptions::builder().csp_safe(true).build().unwrap(),
+    );
+    assert!(
+        code.contains(
+            r##"_n$2.setAttributeNS("http://www.w3.org/1999/xlink", "xlink:href", "#icon");"##
+        ),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains(r#"_n$1.setAttributeNS("http://www.w3.org/2000/xmlns/", "xmlns:xlink""#),
+        "Output was:\n{code}"
+    );
+}
+
+// ============================================================================
+// Inlined Runtime Helpers
+// ============================================================================
+
+#[test]
+fn test_inline_helpers_without_imports() {
+    let code = transform_with(
+        r#"<div on:focus={focus} onClick={go}><p>Hi</p></div>"#,
+        TransformOptions::builder()
+            .inline_helpers(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(!code.contains("solid-js/web"), "Output was:\n{code}");
+    for helper in ["template", "addEventListener", "delegateEvents"] {
+        assert!(
+            code.contains(&format!("function _${helper}(")),
+            "Output was:\n{code}"
+        );
+    }
+    assert!(code.contains("_el$1.$$click = go;"), "Output was:\n{code}");
+    assert!(code.contains(r#"_$delegateEvents(["click"]);"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_inline_helpers_keep_reactive_imports() {
+    let code = transform_with(
+        r#"<div style={style()}>{count()}</div>"#,
+        TransformOptions::builder()
+            .inline_helpers(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(
+        code.contains(r#"import { effect as _$effect, insert as _$insert } from "solid-js/web";"#),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains("function _$style(node, value, prev) {"), "Output was:\n{code}");
+    assert!(
+        code.contains(r#"v.slice(0, important.index), "important")"#),
+        "{code}"
+    );
+    assert!(code.contains("function _$template(html, isSVG) {"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_inline_helpers_ignore_user_bindings() {
+    let code = transform_with(
+        r#"import { template } from "solid-js/web";
+const a = <div />;"#,
+        TransformOptions::builder()
+            .inline_helpers(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains(r#"import { template } from "solid-js/web";"#), "Output was:\n{code}");
+    assert!(code.contains("function _$template("), "Output was:\n{code}");
+}
+
+// ============================================================================
+// SSR: ssrElement-only Output
+// ============================================================================
+
+#[test]
+fn test_ssr_element_only_output() {
+    let code = transform_with(
+        r#"const a = <ul class="list" id={id()}><li>salt & pepper</li><li>{item()}</li></ul>;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .ssr_element_only(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(!code.contains("_$ssr`"), "Output was:\n{code}");
+    assert!(!code.contains(" ssr,") && !code.contains(" ssr "), "Output was:\n{code}");
+    assert!(code.contains(r#"const a = _$ssrElement("ul", {"#), "Output was:\n{code}");
+    assert!(code.contains(r#""class": "list""#), "Output was:\n{code}");
+    assert!(code.contains(r#""id": id()"#), "Output was:\n{code}");
+    assert!(code.contains(r#"_$ssrElement("li", {}, "salt &amp; pepper", false)"#), "Output was:\n{code}");
+    assert!(code.contains(r#"_$ssrElement("li", {}, _$escape(item()), false)"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_element_only_child_properties() {
+    let code = transform_with(
+        r#"const a = <div innerHTML={html} onClick={fn} />; const b = <p textContent={text} />;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .ssr_element_only(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains(r#"_$ssrElement("div", {}, html, false)"#), "Output was:\n{code}");
+    assert!(code.contains(r#"_$ssrElement("p", {}, _$escape(text), false)"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_element_only_fragment() {
+    let code = transform_with(
+        r#"const a = <><b>x</b> and {value}</>;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .ssr_element_only(true)
+            .hydratable(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(!code.contains("ssr`"), "Output was:\n{code}");
+    // Markup is a { t } node so the escaping the value gets leaves it alone
+    assert!(code.contains(r#"ssrElement("b", {}, "x", true)"#), "Output was:\n{code}");
+    assert!(code.contains(r#"{ t: " and " }"#), "Output was:\n{code}");
+    assert!(code.contains("value"), "Output was:\n{code}");
+    assert!(!code.contains("escape(value)"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_spread_is_not_wrapped_in_template() {
+    let code = transform_ssr(r#"const a = <div {...props} />;"#);
+    assert!(code.contains(r#"const a = _$ssrElement("div""#), "Output was:\n{code}");
+    assert!(!code.contains("_$ssr`"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Delegated Events Strategy
+// ============================================================================
+
+#[test]
+fn test_delegate_events_export_strategy() {
+    let options = TransformOptions::builder()
+        .delegate_events_strategy(common::DelegateEventsStrategy::Export)
+        .build()
+        .unwrap();
+    let code = normalize(
+        &transform(
+            r#"<button onClick={inc} onInput={log}>+</button>"#,
+            Some(options),
+        )
+        .unwrap()
+        .code,
+    );
+    assert!(
+        code.contains(r#"export const _$delegatedEvents = ["click", "input"];"#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("delegateEvents("), "Output was:\n{code}");
+}
+
+#[test]
+fn test_delegate_events_export_strategy_cjs() {
+    let options = TransformOptions::builder()
+        .delegate_events_strategy(common::DelegateEventsStrategy::Export)
+        .module_format(ModuleFormat::Cjs)
+        .build()
+        .unwrap();
+    let code = normalize(
+        &transform(r#"<button onClick={inc}>+</button>"#, Some(options))
+            .unwrap()
+            .code,
+    );
+    assert!(
+        code.contains(r#"exports._$delegatedEvents = ["click"];"#),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_delegate_events_call_strategy_default() {
+    let code = transform_dom(r#"<button onClick={inc}>+</button>"#);
+    assert!(code.contains(r#"delegateEvents(["click"]);"#), "Output was:\n{code}");
+    assert!(!code.contains("_$delegatedEvents"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// DOM: Hydration
+// ============================================================================
+
+#[test]
+fn test_dom_hydratable_claims_nodes() {
+    let code = transform_with(
+        r#"const a = <div onClick={go}>Hello {name()} <span class={cls()}>x</span></div>;"#,
+        TransformOptions::builder()
+            .hydratable(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(
+        code.contains("_$template(`<div>Hello <!$><!/> <span>x</span></div>`)"),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains("const _el$1 = _$getNextElement(_tmpl$1);"), "Output was:\n{code}");
+    assert!(
+        code.contains(
+            "const [_el$2, _co$1] = _$getNextMarker(_el$1.firstChild.nextSibling.nextSibling);"
+        ),
+        "Output was:\n{code}"
+    );
+    // Server-rendered content of any length sits before the marker end, so
+    // later siblings are reached from it
+    assert!(code.contains("const _el$3 = _el$2.nextSibling.nextSibling;"), "Output was:\n{code}");
+    assert!(code.contains("_$insert(_el$1, () => name(), _el$2, _co$1);"), "Output was:\n{code}");
+    assert!(code.contains("_$runHydrationEvents();\nreturn _el$1;"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_hydratable_static_and_single_child() {
+    let code = transform_with(
+        r#"const a = <p>static</p>; const b = <ul>{items()}</ul>; const c = <Comp><i>x</i></Comp>;"#,
+        TransformOptions::builder()
+            .hydratable(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains("const a = _$getNextElement(_tmpl$1);"), "Output was:\n{code}");
+    assert!(code.contains("_$insert(_el$2, () => items());"), "Output was:\n{code}");
+    assert!(code.contains("return _$getNextElement(_tmpl$3);"), "Output was:\n{code}");
+    assert!(!code.contains("cloneNode"), "Output was:\n{code}");
+    assert!(!code.contains("runHydrationEvents"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// SSR: Hydration
+// ============================================================================
+
+#[test]
+fn test_ssr_hydratable_keys_and_markers() {
+    let code = transform_with(
+        r#"const a = <div>Hello {name()} <span>{x()}</span></div>; const b = <ul>{items()}</ul>;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .hydratable(true)
+            .build()
+            .unwrap(),
+    );
+    // Only template roots get a key; the client walks to the elements inside
+    assert!(
+        code.contains(
+            "_$ssr`<div${_$ssrHydrationKey()}>Hello <!--$-->${_$escape(name())}<!--/--> <span>${_$escape(x())}</span></div>`"
+        ),
+        "Output was:\n{code}"
+    );
+    // A lone child is inserted without markers, as on the client
+    assert!(
+        code.contains("_$ssr`<ul${_$ssrHydrationKey()}>${_$escape(items())}</ul>`"),
+        "Output was:\n{code}"
+    );
+}
+
+/// The markup of each `ssr` template with its dynamic values left out, in the
+/// notation of DOM templates
+fn ssr_skeletons(code: &str) -> Vec<String> {
+    code.split("_$ssr`")
+        .skip(1)
+        .map(|rest| {
+            let template = &rest[..rest.find('`').unwrap()];
+            let template = template
+                .replace("${_$ssrHydrationKey()}", "")
+                .replace("<!--$-->", "<!$>")
+                .replace("<!--/-->", "<!/>");
+            let mut skeleton = String::new();
+            let mut depth = 0;
+            let mut chars = template.chars().peekable();
+            while let Some(c) = chars.next() {
+                if c == '$' && depth == 0 && chars.peek() == Some(&'{') {
+                    chars.next();
+                    depth = 1;
+                } else if depth > 0 {
+                    match c {
+                        '{' => depth += 1,
+                        '}' => depth -= 1,
+                        _ => {}
+                    }
+                } else {
+                    skeleton.push(c);
+                }
+            }
+            skeleton
+        })
+        .collect()
+}
+
+#[test]
+fn test_ssr_hydration_matches_dom_templates() {
+    let source = r#"
+const a = <div id="a">Hello {name()} <span title={t()}>{x()}</span><b>bold</b>{y()}</div>;
+const b = <ul><li>one</li>{items()}</ul>;
+const c = <p>{z()}</p>;
+const d = <section><h1>Title</h1><>{a()}{b()}</></section>;
+"#;
+    let options = |generate| {
+        TransformOptions::builder()
+            .generate(generate)
+            .hydratable(true)
+            .build()
+            .unwrap()
+    };
+    let ssr = transform_with(source, options(GenerateMode::Ssr));
+    let dom = transform_with(source, options(GenerateMode::Dom));
+
+    // The server renders what the client templates expect to claim, with
+    // markers where the client inserts
+    let templates: Vec<&str> = dom
+        .split("_$template(`")
+        .skip(1)
+        .map(|rest| &rest[..rest.find('`').unwrap()])
+        .collect();
+    assert_eq!(ssr_skeletons(&ssr), templates, "SSR:\n{ssr}\nDOM:\n{dom}");
+    // One hydration key for every node the client claims
+    assert_eq!(
+        ssr.matches("_$ssrHydrationKey()").count(),
+        dom.matches("_$getNextElement(").count(),
+        "SSR:\n{ssr}\nDOM:\n{dom}"
+    );
+}
+
+// ============================================================================
+// Universal Output
+// ============================================================================
+
+#[test]
+fn test_universal_creates_nodes_with_renderer() {
+    let code = transform_with(
+        r#"const a = <box id="main" title={title()}>Hi &amp; {name()} <text>x</text>{rest()}</box>;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Universal)
+            .module_name("my-renderer")
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains(r#"from "my-renderer";"#), "Output was:\n{code}");
+    assert!(!code.contains("template"), "Output was:\n{code}");
+    assert!(code.contains(r#"const _el$1 = _$createElement("box");"#), "Output was:\n{code}");
+    assert!(code.contains(r#"const _el$2 = _$createTextNode("Hi & ");"#), "Output was:\n{code}");
+    assert!(code.contains(r#"_$setProp(_el$1, "id", "main");"#), "Output was:\n{code}");
+    assert!(
+        code.contains(r#"_$effect((_p$) => _$setProp(_el$1, "title", title(), _p$));"#),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains("_$insertNode(_el$4, _el$5);"), "Output was:\n{code}");
+    assert!(code.contains("_$insertNode(_el$1, _el$4);"), "Output was:\n{code}");
+    // Dynamic children go before the next created node, or are appended
+    assert!(code.contains("_$insert(_el$1, () => name(), _el$3);"), "Output was:\n{code}");
+    assert!(code.contains("_$insert(_el$1, () => rest(), null);"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_universal_events_spread_and_components() {
+    let code = transform_with(
+        r#"const a = <box onClick={go} {...props}>{items()}</box>; const b = <Comp><text /></Comp>;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Universal)
+            .module_name("my-renderer")
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains(r#"_$setProp(_el$1, "onClick", go);"#), "Output was:\n{code}");
+    assert!(code.contains("_$spread(_el$1, props, true);"), "Output was:\n{code}");
+    assert!(code.contains("_$insert(_el$1, () => items());"), "Output was:\n{code}");
+    assert!(!code.contains("delegateEvents"), "Output was:\n{code}");
+    assert!(code.contains("_$createComponent(Comp, {"), "Output was:\n{code}");
+    assert!(code.contains(r#"const _el$2 = _$createElement("text");"#), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Hydration Script Injection
+// ============================================================================
+
+#[test]
+fn test_hydration_script_injected_into_head() {
+    let code = transform_with(
+        r#"const Doc = () => <html><head><title>App</title></head><body><App /></body></html>;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .hydratable(true)
+            .hydration_script(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains("generateHydrationScript"), "Output was:\n{code}");
+    assert!(
+        code.contains("App</title>${_$generateHydrationScript()}</head>"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_hydration_script_not_duplicated() {
+    let code = transform_with(
+        r#"const Doc = () => <head><HydrationScript /></head>;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .hydratable(true)
+            .hydration_script(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(!code.contains("generateHydrationScript"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_hydration_script_requires_hydratable() {
+    let options = TransformOptions::builder()
+        .generate(GenerateMode::Ssr)
+        .hydration_script(true)
+        .build()
+        .unwrap();
+    let code = normalize(
+        &transform(r#"<head><title>App</title></head>"#, Some(options))
+            .unwrap()
+            .code,
+    );
+    assert!(
+        !code.contains("generateHydrationScript"),
+        "Output was:\n{code}"
+    );
+}
+
+// ============================================================================
+// NoHydration
+// ============================================================================
+
+#[test]
+fn test_no_hydration_children_have_no_hydration_keys() {
+    let code = transform_with(
+        r#"const a = <main><NoHydration><div id={id()}>{y()}</div></NoHydration><i>{w()}</i></main>;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .hydratable(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains("<main${_$ssrHydrationKey()}>"), "Output was:\n{code}");
+    assert!(code.contains("<i>${_$escape(w())}</i>"), "Output was:\n{code}");
+    assert!(code.contains("_$ssr`<div${_$ssrAttribute(\"id\""), "Output was:\n{code}");
+    assert!(code.contains(">${_$escape(y())}</div>`"), "Output was:\n{code}");
+    assert_eq!(code.matches("_$ssrHydrationKey()").count(), 1, "Output was:\n{code}");
+}
+
+#[test]
+fn test_no_hydration_covers_expression_children() {
+    let code = transform_with(
+        r#"const a = <NoHydration>{cond() && <b>{z()}</b>}</NoHydration>;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .hydratable(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(code.contains("cond() && _$ssr`<b>${_$escape(z())}</b>`"), "Output was:\n{code}");
+    assert!(!code.contains("ssrHydrationKey"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Islands
+// ============================================================================
+
+#[test]
+fn test_islands_wrap_imported_components() {
+    let options = TransformOptions::builder()
+        .generate(GenerateMode::Ssr)
+        .hydratable(true)
+        .islands(true)
+        .filename("page.jsx")
+        .build()
+        .unwrap();
+    let output = transform(
+        r#"import Counter from "./Counter";
+import { Show } from "solid-js";
+const Local = () => <p />;
+const a = <main><Counter start={1} /><Local /><Show when={x()}><b /></Show></main>;"#,
+        Some(options),
+    )
+    .unwrap();
+    let code = normalize(&output.code);
+    assert_eq!(output.islands.len(), 1, "Output was:\n{code}");
+    let island = &output.islands[0];
+    assert_eq!(
+        (island.component.as_str(), island.source.as_str(), island.export.as_str()),
+        ("Counter", "./Counter", "default")
+    );
+    assert!(island.id.ends_with(":0"), "id was {}", island.id);
+    assert!(
+        code.contains(&format!(
+            "<!--island:{}-->${{_$escape(_$createComponent(Counter, {{ start: 1 }}))}}<!--/island-->",
+            island.id
+        )),
+        "Output was:\n{code}"
+    );
+    assert_eq!(code.matches("<!--island:").count(), 1, "Output was:\n{code}");
+}
+
+#[test]
+fn test_islands_nested_components_hydrate_with_their_island() {
+    let options = TransformOptions::builder()
+        .generate(GenerateMode::Ssr)
+        .hydratable(true)
+        .islands(true)
+        .filename("page.jsx")
+        .build()
+        .unwrap();
+    let output = transform(
+        r#"import { Card, Button } from "./ui";
+const a = <Card>{open() && <Button />}<Button /></Card>;
+const b = <NoHydration><Button /></NoHydration>;"#,
+        Some(options),
+    )
+    .unwrap();
+    let code = normalize(&output.code);
+    let islands: Vec<_> = output.islands.iter().map(|i| &i.component).collect();
+    assert_eq!(islands, ["Card"], "Output was:\n{code}");
+    assert_eq!(output.islands[0].export, "Card");
+    assert_eq!(code.matches("<!--island:").count(), 1, "Output was:\n{code}");
+}
+
+#[test]
+fn test_islands_ids_differ_between_files() {
+    let source = r#"import A from "./A"; const a = <A />;"#;
+    let island_id = |filename| {
+        let options = TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .hydratable(true)
+            .islands(true)
+            .filename(filename)
+            .build()
+            .unwrap();
+        let output = transform(source, Some(options)).unwrap();
+        output.islands[0].id.clone()
+    };
+    assert_eq!(island_id("a.jsx"), island_id("a.jsx"));
+    assert_ne!(island_id("a.jsx"), island_id("b.jsx"));
+}
+
+#[test]
+fn test_islands_require_hydratable_ssr() {
+    let dom = TransformOptions::builder().islands(true).build();
+    assert!(matches!(dom, Err(OptionsError::IslandsWithoutHydratableSsr)));
+    let ssr = TransformOptions::builder()
+        .generate(GenerateMode::Ssr)
+        .islands(true)
+        .build();
+    assert!(matches!(ssr, Err(OptionsError::IslandsWithoutHydratableSsr)));
+    let output = transform_with(
+        r#"import A from "./A"; const a = <A />;"#,
+        TransformOptions::builder()
+            .generate(GenerateMode::Ssr)
+            .hydratable(true)
+            .build()
+            .unwrap(),
+    );
+    assert!(!output.contains("island"), "Output was:\n{output}");
+}
+
+// ============================================================================
+// Controlled Form Controls
+// ============================================================================
+
+#[test]
+fn test_controlled_input_sets_properties() {
+    let code = transform_dom(r#"<input value={text()} checked={done()} />"#);
+    assert!(code.contains("effect(() => _el$1.value = text())"), "Output was:\n{code}");
+    assert!(code.contains("effect(() => _el$1.checked = done())"), "Output was:\n{code}");
+    assert!(!code.contains(".setAttribute("), "Output was:\n{code}");
+}
+
+#[test]
+fn test_svg_value_stays_an_attribute() {
+    let code = transform_dom(
+        r#"<svg><rect value={v()} /><foreignObject><input value={v()} /></foreignObject></svg>"#,
+    );
+    assert!(
+        code.contains(r#"_$setAttribute(_el$2, "value", v())"#),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains("effect(() => _el$3.value = v())"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_static_textarea_value_sets_property() {
+    let code = transform_dom(r#"<div><textarea value="draft" /><input value="init" /></div>"#);
+    assert!(code.contains(r#"_el$2.value = "draft";"#), "Output was:\n{code}");
+    assert!(code.contains("<textarea></textarea>"), "Output was:\n{code}");
+    assert!(code.contains(r#"<input value="init">"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_controlled_values() {
+    let code = transform_ssr(r#"<div><input value={text()} checked={done()} /><textarea value={draft()} /></div>"#);
+    assert!(
+        code.contains(r#"_$ssrAttribute("value", _$escape(text(), true), false)"#),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains(r#"_$ssrAttribute("checked", done(), true)"#), "Output was:\n{code}");
+    assert!(code.contains("<textarea>${_$escape(draft())}</textarea>"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_select_value_set_after_options() {
+    let code = transform_dom(
+        r#"<select value={selected()}><For each={options()}>{o => <option value={o}>{o}</option>}</For></select>"#,
+    );
+    let insert = code.find("insert(_el$").expect("missing insert");
+    let value = code
+        .find("effect(() => _el$2.value = selected())")
+        .unwrap_or_else(|| panic!("Output was:\n{code}"));
+    assert!(insert < value, "Output was:\n{code}");
+}
+
+#[test]
+fn test_option_value_set_before_select_value() {
+    let code = transform_dom(
+        r#"<select value={selected()}><option value={first()}>{label()}</option></select>"#,
+    );
+    let insert = code.find("insert(_el$2").expect("missing insert");
+    let option = code
+        .find("effect(() => _el$2.value = first())")
+        .unwrap_or_else(|| panic!("Output was:\n{code}"));
+    let select = code
+        .find("effect(() => _el$1.value = selected())")
+        .unwrap_or_else(|| panic!("Output was:\n{code}"));
+    assert!(insert < option && option < select, "Output was:\n{code}");
+}
+
+#[test]
+fn test_nested_static_select_value_after_options() {
+    let code = transform_dom(r#"<div><select value="b">{options()}</select></div>"#);
+    let insert = code.find("insert(_el$2").expect("missing insert");
+    let value = code
+        .find(r#"_el$2.value = "b";"#)
+        .unwrap_or_else(|| panic!("Output was:\n{code}"));
+    assert!(insert < value, "Output was:\n{code}");
+}
+
+// ============================================================================
+// iframe srcdoc
+// ============================================================================
+
+#[test]
+fn test_srcdoc_static_escaped_once() {
+    let source = r#"<iframe srcdoc="&lt;p class=&quot;a&quot;&gt;hi&lt;/p&gt;" />"#;
+    let expected = "<p class=&quot;a&quot;>hi</p>";
+    let dom = transform_dom(source);
+    assert!(dom.contains(expected), "Output was:\n{dom}");
+    let ssr = transform_ssr(source);
+    assert!(ssr.contains(expected), "Output was:\n{ssr}");
+
+    let dom = transform_dom(r#"<iframe srcdoc="<b>hi</b>" />"#);
+    assert!(dom.contains(r#"srcdoc="<b>hi</b>""#), "Output was:\n{dom}");
+}
+
+#[test]
+fn test_srcdoc_dynamic() {
+    let dom = transform_dom(r#"<iframe srcdoc={html()} />"#);
+    assert!(dom.contains(r#"_$setAttribute(_el$1, "srcdoc", html())"#), "Output was:\n{dom}");
+    let ssr = transform_ssr(r#"<iframe srcdoc={html()} />"#);
+    assert!(
+        ssr.contains(r#"_$ssrAttribute("srcdoc", _$escape(html(), true), false)"#),
+        "Output was:\n{ssr}"
+    );
+}
+
+// ============================================================================
+// data-* and aria-* Attributes
+// ============================================================================
+
+#[test]
+fn test_data_aria_dynamic_use_set_attribute() {
+    let code = transform_dom(r#"<input data-value={v()} aria-checked={c()} />"#);
+    assert!(
+        code.contains(r#"setAttribute(_el$1, "data-value", v())"#),
+        "Output was:\n{code}"
+    );
+    assert!(
+        code.contains(r#"setAttribute(_el$1, "aria-checked", c())"#),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_data_aria_literals_inlined() {
+    let code = transform_dom(r#"<div data-count={3} aria-hidden={true} data-kind={"card"} />"#);
+    assert!(
+        code.contains(r#"<div data-count="3" aria-hidden="true" data-kind="card"></div>"#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("_$effect("), "Output was:\n{code}");
+
+    let code = transform_ssr(r#"<div data-count={3} aria-label={label()} />"#);
+    assert!(code.contains(r#"data-count="3""#), "Output was:\n{code}");
+    assert!(
+        code.contains(r#"_$ssrAttribute("aria-label", _$escape(label(), true), false)"#),
+        "Output was:\n{code}"
+    );
+}
+
+// ============================================================================
+// Attribute Aliases
+// ============================================================================
+
+#[test]
+fn test_aliases_apply_to_dynamic_attributes() {
+    let code = transform_dom(r#"<div><label htmlFor={f()} /><input readOnly /><input tabIndex={i()} /></div>"#);
+    assert!(code.contains("<div><label></label><input readonly><input></div>"), "Output was:\n{code}");
+    assert!(code.contains(r#"_$setAttribute(_el$2, "for", f())"#), "Output was:\n{code}");
+    assert!(code.contains(r#"_$setAttribute(_el$3, "tabindex", i())"#), "Output was:\n{code}");
+
+    let code = transform_ssr(r#"<div><label htmlFor={f()} /><input readOnly={r()} /></div>"#);
+    assert!(code.contains(r#"_$ssrAttribute("for", _$escape(f(), true), false)"#), "Output was:\n{code}");
+    assert!(code.contains(r#"_$ssrAttribute("readonly", r(), true)"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_prop_aliases() {
+    let code = transform_dom(r#"<div><input readonly={r()} /><video playsinline={p()} /></div>"#);
+    assert!(code.contains("effect(() => _el$2.readOnly = r())"), "Output was:\n{code}");
+    assert!(code.contains("effect(() => _el$3.playsInline = p())"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_svg_attributes_keep_case() {
+    let code = transform_dom(r#"<svg viewBox={v()} preserveAspectRatio="none"><path className={c()} /></svg>"#);
+    assert!(code.contains(r#"<svg preserveAspectRatio="none">"#), "Output was:\n{code}");
+    assert!(code.contains(r#"_$setAttribute(_el$1, "viewBox", v())"#), "Output was:\n{code}");
+    assert!(code.contains(r#"_$setAttribute(_el$2, "class", c())"#), "Output was:\n{code}");
+
+    let code = transform_ssr(r#"<svg viewBox={v()}><path className={c()} /></svg>"#);
+    assert!(code.contains(r#"_$ssrAttribute("viewBox", _$escape(v(), true), false)"#), "Output was:\n{code}");
+    assert!(code.contains(r#"<path class="${_$escape(c(), true)}">"#), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Web Component Slots
+// ============================================================================
+
+#[test]
+fn test_slots_dom() {
+    let code = transform_dom(
+        r#"<my-card><slot name="icon"></slot><slot name={n()} /><span slot="title">t</span><my-badge slot={s()} /></my-card>"#,
+    );
+    assert!(
+        code.contains(r#"<my-card><slot name="icon"></slot><slot></slot><span slot="title">t</span><my-badge></my-badge></my-card>"#),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains(r#"setAttribute(_el$3, "name", n())"#), "Output was:\n{code}");
+    assert!(code.contains(r#"setAttribute(_el$4, "slot", s())"#), "Output was:\n{code}");
+    assert!(!code.contains(".slot ="), "Output was:\n{code}");
+}
+
+#[test]
+fn test_slots_ssr() {
+    let code = transform_ssr(
+        r#"<my-card><slot name={n()} /><span slot="title">t</span><my-badge slot={s()} /></my-card>"#,
+    );
+    assert!(
+        code.contains(r#"<slot${_$ssrAttribute("name", _$escape(n(), true), false)}></slot>"#),
+        "Output was:\n{code}"
+    );
+    assert!(code.contains(r#"<span slot="title">t</span>"#), "Output was:\n{code}");
+    assert!(
+        code.contains(r#"<my-badge${_$ssrAttribute("slot", _$escape(s(), true), false)}></my-badge>"#),
+        "Output was:\n{code}"
+    );
+}
+
+// ============================================================================
+// Static textContent
+// ============================================================================
+
+#[test]
+fn test_static_text_content_inlined() {
+    let code = transform_dom(r#"<div><p textContent="a & <b>" /><p textContent={"lit"} /></div>"#);
+    assert!(
+        code.contains("<div><p>a &amp; &lt;b></p><p>lit</p></div>"),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains(".textContent ="), "Output was:\n{code}");
+
+    let code = transform_ssr(r#"<div><p textContent="a & b" />{x()}</div>"#);
+    assert!(code.contains("<p>a &amp; b</p>"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dynamic_text_content_kept() {
+    let code = transform_dom(r#"<p textContent={text()} />"#);
+    assert!(code.contains("effect(() => _el$1.textContent = text())"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_inner_text_assigned_at_runtime() {
+    let code = transform_dom(r#"<div><p innerText="a <b>" /><p innerText={text()} /></div>"#);
+    assert!(code.contains("_$template(`<div><p></p><p></p></div>`)"), "Output was:\n{code}");
+    assert!(code.contains(r#"_el$2.innerText = "a <b>";"#), "Output was:\n{code}");
+    assert!(code.contains("_$effect(() => _el$3.innerText = text())"), "Output was:\n{code}");
+
+    let code = transform_ssr(r#"<div><p innerText="a <b>" /><p innerText={text()} /></div>"#);
+    assert!(code.contains("<p>a &lt;b></p><p>${_$escape(text())}</p>"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Boolean and Nullish Attributes
+// ============================================================================
+
+#[test]
+fn test_boolean_literal_attributes_static() {
+    let code = transform_dom(r#"<div><button disabled={false} /><button disabled={true} /></div>"#);
+    assert!(
+        code.contains("<div><button></button><button disabled></button></div>"),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("effect("), "Output was:\n{code}");
+
+    let code = transform_ssr(r#"<div><input hidden={false} /><input hidden={true} />{x()}</div>"#);
+    assert!(code.contains("<input><input hidden>"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dynamic_boolean_attributes() {
+    let code = transform_dom(r#"<svg><path hidden={h()} /><g>{x()}</g></svg>"#);
+    assert!(code.contains(r#"setBoolAttribute(_el$2, "hidden", h())"#), "Output was:\n{code}");
+
+    let code = transform_ssr(r#"<button disabled={busy()} />"#);
+    assert!(code.contains(r#"ssrAttribute("disabled", busy(), true)"#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_nullish_attributes_removed() {
+    let code = transform_dom(r#"<a href={url()} />"#);
+    assert!(code.contains(r#"_$setAttribute(_el$1, "href", url())"#), "Output was:\n{code}");
+    assert!(
+        code.contains("import { template as _$template, effect as _$effect, setAttribute as _$setAttribute }"),
+        "Output was:\n{code}"
+    );
+
+    let code = transform_ssr(r#"<a href={url()} />"#);
+    assert!(
+        code.contains(r#"_$ssrAttribute("href", _$escape(url(), true), false)"#),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_nullish_literal_attributes_static() {
+    let source =
+        r#"<div><a title={null} href={undefined} /><input disabled={void 0} />{x()}</div>"#;
+    let code = transform_dom(source);
+    assert!(
+        code.contains("_$template(`<div><a></a><input><!></div>`)"),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("setAttribute"), "Output was:\n{code}");
+
+    let code = transform_ssr(source);
+    assert!(code.contains("<div><a></a><input>"), "Output was:\n{code}");
+    assert!(!code.contains("ssrAttribute"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Children Prop Precedence
+// ============================================================================
+
+#[test]
+fn test_children_attr_kept_without_jsx_children() {
+    let code = transform_dom("<Comp children={items()}>\n  {/* nothing */}\n</Comp>");
+    assert!(code.contains("return items();"), "Output was:\n{code}");
+
+    let code = transform_ssr("<Comp children={items()}>\n</Comp>");
+    assert!(code.contains("return items();"), "Output was:\n{code}");
+    assert!(!code.contains("return undefined;"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_jsx_children_override_children_attr() {
+    let code = transform_dom(r#"<Comp children={items()}>{other()}</Comp>"#);
+    assert!(code.contains("return other();"), "Output was:\n{code}");
+    assert!(!code.contains("items()"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_repeated_children_attr_single_getter() {
+    let code = transform_dom(r#"<Comp children={first} children={second()} />"#);
+    assert_eq!(code.matches("children()").count(), 1, "Output was:\n{code}");
+    assert!(code.contains("return second();"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Spread on Void Elements
+// ============================================================================
+
+#[test]
+fn test_dom_void_spread_skips_children() {
+    let code = transform_dom(r#"<input {...props} />"#);
+    assert!(
+        code.contains("spread(_el$1, props, false, true)"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_dom_void_spread_static_attrs() {
+    let code = transform_dom(r#"<input type="checkbox" {...props} name="n" />"#);
+    // Attributes before the spread stay in the template
+    assert!(code.contains(r#"<input type="checkbox">"#), "Output was:\n{code}");
+    // Attributes after it must override the spread
+    assert!(
+        code.contains(r#"spread(_el$1, _$mergeProps(props, { name: "n" }), false, true)"#),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_ssr_void_spread_omits_children() {
+    let code = transform_ssr(r#"<img {...props} alt="a&b" />"#);
+    assert!(code.contains(r#"ssrElement("img", {"#), "Output was:\n{code}");
+    assert!(!code.contains("null, false"), "Output was:\n{code}");
+    // ssrElement escapes values itself
+    assert!(code.contains(r#""alt": "a&b""#), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Object-Literal Spreads
+// ============================================================================
+
+#[test]
+fn test_dom_object_spread_split_into_attributes() {
+    let code = transform_dom(
+        r#"<div {...{ class: "a", onClick: fn, "aria-label": label() }} id="x" />"#,
+    );
+    assert!(!code.contains("spread("), "Output was:\n{code}");
+    assert!(code.contains(r#"<div class="a" id="x">"#), "Output was:\n{code}");
+    assert!(code.contains("$$click = fn"), "Output was:\n{code}");
+    assert!(
+        code.contains(r#"setAttribute(_el$1, "aria-label", label())"#),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_dom_object_spread_kept_when_not_static() {
+    for source in [
+        r#"<div {...{ [key]: 1 }} />"#,
+        r#"<div {...{ title: t(), ...rest }} />"#,
+        r#"<div {...{ ref: el }} />"#,
+        r#"<div {...{ get title() { return t(); } }} />"#,
+    ] {
+        let code = transform_dom(source);
+        assert!(code.contains("spread("), "Output was:\n{code}");
+    }
+}
+
+#[test]
+fn test_object_spread_on_component_untouched() {
+    let code = transform_dom(r#"<Comp {...{ a: 1 }} />"#);
+    assert!(code.contains("mergeProps({ a: 1 })"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_object_spread_split_into_attributes() {
+    let code = transform_ssr(r#"<div {...({ class: "a", title: t() })} />"#);
+    assert!(!code.contains("ssrElement"), "Output was:\n{code}");
+    assert!(code.contains(r#"<div class="a""#), "Output was:\n{code}");
+    assert!(code.contains(r#"ssrAttribute("title""#), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Literal Attributes
+// ============================================================================
+
+#[test]
+fn test_dom_literal_attrs_inline() {
+    let code = transform_dom(
+        r#"<td tabindex={-1} colspan={2} contenteditable={true} hidden={true} title={"t"} />"#,
+    );
+    assert!(
+        code.contains(r#"<td tabindex="-1" colspan="2" contenteditable="true" hidden title="t">"#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("effect"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_literal_select_value_stays_property() {
+    let code = transform_dom(r#"<select value={1}><option value={1}>a</option></select>"#);
+    assert!(code.contains(r#"<option value="1">"#), "Output was:\n{code}");
+    assert!(code.contains(r#"_el$1.value = "1""#), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_literal_attrs_static() {
+    let code = transform_ssr(r#"<td tabindex={0} colspan={2} spellcheck={false} className={"x"}>{x()}</td>"#);
+    assert!(
+        code.contains(r#"<td tabindex="0" colspan="2" spellcheck="false" class="x">"#),
+        "Output was:\n{code}"
+    );
+    assert!(!code.contains("ssrAttribute"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Mixed Text and Expression Children
+// ============================================================================
+
+#[test]
+fn test_dom_literal_children_merge_into_text() {
+    let code = transform_dom(r#"<div>a{"b"}c{x()}d{1}</div>"#);
+    assert!(code.contains("<div>abc<!>d1</div>"), "Output was:\n{code}");
+    assert_eq!(code.matches("insert(").count(), 1, "Output was:\n{code}");
+    assert!(code.contains("_el$1.firstChild.nextSibling;"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_literal_children_merge_into_text() {
+    let code = transform_ssr(r#"<div>a{"b"}c{x()}d{1}{null}</div>"#);
+    assert!(code.contains("<div>abc${_$escape(x())}d1</div>"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_component_mixed_children_stay_reactive() {
+    let code = transform_dom(r#"<Comp>Hello {name()}!</Comp>"#);
+    assert!(code.contains("() => name()"), "Output was:\n{code}");
+    let hello = code.find(r#""Hello ""#).expect(&code);
+    let name = code.find("() => name()").expect(&code);
+    let bang = code.find(r#""!""#).expect(&code);
+    assert!(hello < name && name < bang, "Output was:\n{code}");
+}
+
+// ============================================================================
+// Spread Children
+// ============================================================================
+
+#[test]
+fn test_dom_spread_children_insert() {
+    let code = transform_dom(r#"<div>{...items}</div>"#);
+    assert!(code.contains("insert(_el$1, () => items)"), "Output was:\n{code}");
+    assert!(!code.contains("spread child"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_spread_children_with_siblings() {
+    let code = transform_dom(r#"<div>Items: {...props.items}<b /></div>"#);
+    assert!(code.contains("<div>Items: <!><b></b></div>"), "Output was:\n{code}");
+    assert!(
+        code.contains("insert(_el$1, () => props.items, _el$2)"),
+        "Output was:\n{code}"
+    );
+}
+
+#[test]
+fn test_ssr_spread_children() {
+    let code = transform_ssr(r#"<div>{...items}</div>"#);
+    assert!(code.contains("<div>${_$escape(items)}</div>"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Static Hoisting
+// ============================================================================
+
+#[test]
+fn test_dom_static_tree_clones_template() {
+    let code = transform_dom(r#"function App() { return <div class="a"><span>hi</span></div>; }"#);
+    assert!(code.contains("return _tmpl$1.cloneNode(true);"), "Output was:\n{code}");
+    assert!(!code.contains("=> {"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Callback Children
+// ============================================================================
+
+#[test]
+fn test_dom_show_callback_children() {
+    let code = transform_dom(
+        r#"<Show when={user()}>
+            {u => <Profile user={u()} />}
+        </Show>"#,
+    );
+    assert!(code.contains("children: (u) =>"), "Output was:\n{code}");
+    assert!(!code.contains("get children()"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_dom_parenthesized_callback_children() {
+    let code = transform_dom(r#"<Show when={user()}>{/* name */}{(u => u.name)}</Show>"#);
+    assert!(code.contains("children: ((u) => u.name)"), "Output was:\n{code}");
+    assert!(!code.contains("get children()"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_show_callback_children() {
+    let code = transform_ssr(
+        r#"<Show when={user()}>
+            {/* profile */}
+            {(u) => <Profile user={u()} />}
+        </Show>"#,
+    );
+    assert!(code.contains("children: (u) => _$createComponent(Profile"), "Output was:\n{code}");
+    assert!(!code.contains("get children()"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_ssr_parenthesized_callback_children() {
+    let code = transform_ssr(r#"<Show when={user()}>{(function (u) { return u.name; })}</Show>"#);
+    assert!(code.contains("children: (function(u)"), "Output was:\n{code}");
+    assert!(!code.contains("get children()"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Pre-parsed Programs
+// ============================================================================
+
+#[test]
+fn test_transform_program_matches_transform() {
+    use oxc_allocator::Allocator;
+    use oxc_codegen::Codegen;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    let source = r#"const App = () => <div class={cls()} onClick={go}>{count()}</div>;"#;
+    for generate in [GenerateMode::Dom, GenerateMode::Ssr] {
+        let options = TransformOptions::builder()
+            .generate(generate)
+            .build()
+            .unwrap();
+        let allocator = Allocator::default();
+        let mut program = Parser::new(&allocator, source, SourceType::jsx())
+            .parse()
+            .program;
+        transform_program(&mut program, &allocator, &options).unwrap();
+        let code = Codegen::new().build(&program).code;
+
+        assert_eq!(
+            normalize(&code),
+            normalize(&transform(source, Some(options)).unwrap().code)
+        );
+    }
+}
+
+#[test]
+fn test_transform_program_with_scoping_reuses_semantic() {
+    use oxc_allocator::Allocator;
+    use oxc_codegen::Codegen;
+    use oxc_parser::Parser;
+    use oxc_semantic::SemanticBuilder;
+    use oxc_span::SourceType;
+
+    let source = r#"
+import { isServer } from "solid-js/web";
+import type { Props } from "./types";
+const App = (props: Props) => <div title={isServer ? "s" : "c"}>{props.name}</div>;
+"#;
+    for (generate, strip_types) in [(GenerateMode::Dom, true), (GenerateMode::Ssr, false)] {
+        let options = TransformOptions::builder()
+            .generate(generate)
+            .filename("app.tsx")
+            .strip_types(strip_types)
+            .fold_environment(true)
+            .build()
+            .unwrap();
+        let allocator = Allocator::default();
+        let mut program = Parser::new(&allocator, source, SourceType::tsx())
+            .parse()
+            .program;
+        // The semantic a linter would have run on, handed over afterwards
+        let semantic = SemanticBuilder::new().build(&program).semantic;
+        assert!(semantic.scoping().symbols_len() > 0);
+        let scoping = semantic.into_scoping();
+        transform_program_with_scoping(&mut program, &allocator, &options, scoping).unwrap();
+        let code = Codegen::new().build(&program).code;
+
+        let expected = transform(source, Some(options)).unwrap().code;
+        assert_eq!(normalize(&code), normalize(&expected));
+    }
+}
+
+// ============================================================================
+// Options Builder
+// ============================================================================
+
+#[test]
+fn test_options_builder_sets_fields() {
+    let options = TransformOptions::builder()
+        .generate(GenerateMode::Ssr)
+        .hydratable(true)
+        .custom_elements(["ion-*"])
+        .dev(false)
+        .define_constant("__DEV__", "false")
+        .build()
+        .unwrap();
+    assert!(options.generate == GenerateMode::Ssr);
+    assert!(options.hydratable);
+    assert_eq!(options.custom_elements, vec!["ion-*"]);
+    assert_eq!(options.dev, Some(false));
+    assert_eq!(options.define["__DEV__"], "false");
+    // Unset options keep the Solid defaults
+    assert_eq!(options.module_name, "solid-js/web");
+    assert!(options.delegate_events);
+}
+
+#[test]
+fn test_options_builder_rejects_hydratable_universal() {
+    let result = TransformOptions::builder()
+        .generate(GenerateMode::Universal)
+        .module_name("my-renderer")
+        .hydratable(true)
+        .build();
+    let Err(error) = result else {
+        panic!("hydratable universal output should be rejected");
+    };
+    assert_eq!(error, OptionsError::HydratableUniversal);
+    assert!(error.to_string().contains("hydratable"), "{error}");
+}
+
+#[test]
+fn test_options_builder_requires_renderer_module() {
+    let result = TransformOptions::builder()
+        .generate(GenerateMode::Universal)
+        .build();
+    assert!(matches!(result, Err(OptionsError::MissingRendererModule)));
+
+    let options = TransformOptions::builder()
+        .generate(GenerateMode::Universal)
+        .module_name("my-renderer")
+        .build()
+        .unwrap();
+    assert_eq!(options.module_name, "my-renderer");
+}
+
+// ============================================================================
+// Transform Cache
+// ============================================================================
+
+fn cache_options(filename: &str, generate: GenerateMode) -> TransformOptions<'_> {
+    TransformOptions::builder()
+        .filename(filename)
+        .generate(generate)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn test_transform_cache_reuses_unchanged_files() {
+    let cache = TransformCache::new();
+    let source = r#"<div class="a">{count()}</div>"#;
+
+    let first = cache
+        .transform(source, Some(cache_options("App.jsx", GenerateMode::Dom)))
+        .unwrap();
+    let second = cache
+        .transform(source, Some(cache_options("App.jsx", GenerateMode::Dom)))
+        .unwrap();
+    assert!(std::sync::Arc::ptr_eq(&first, &second));
+    assert_eq!(
+        first.code,
+        transform(source, Some(cache_options("App.jsx", GenerateMode::Dom)))
+            .unwrap()
+            .code
+    );
+
+    // Other options get their own entry
+    let ssr = cache
+        .transform(source, Some(cache_options("App.jsx", GenerateMode::Ssr)))
+        .unwrap();
+    assert!(ssr.code.contains("ssr"), "{}", ssr.code);
+    assert_eq!(cache.len(), 2);
+
+    // An edit replaces the file's entry
+    let edited = r#"<div class="b">{count()}</div>"#;
+    let changed = cache
+        .transform(edited, Some(cache_options("App.jsx", GenerateMode::Dom)))
+        .unwrap();
+    assert!(changed.code.contains("class=\"b\""), "{}", changed.code);
+    assert_eq!(cache.len(), 2);
+    assert!(cache
+        .get(source, &cache_options("App.jsx", GenerateMode::Dom))
+        .is_none());
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_transform_cache_skips_failures() {
+    let cache = TransformCache::new();
+    assert!(cache.transform("<div>", None).is_err());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_options_cache_key() {
+    let key = |options: TransformOptions| options.cache_key();
+    assert_eq!(
+        key(cache_options("App.jsx", GenerateMode::Dom)),
+        key(cache_options("App.jsx", GenerateMode::Dom))
+    );
+    assert_ne!(
+        key(cache_options("App.jsx", GenerateMode::Dom)),
+        key(cache_options("Other.jsx", GenerateMode::Dom))
+    );
+
+    let defines = |pairs: &[(&str, &str)]| {
+        pairs
+            .iter()
+            .fold(TransformOptions::builder(), |builder, (k, v)| {
+                builder.define_constant(*k, *v)
+            })
+            .build()
+            .unwrap()
+            .cache_key()
+    };
+    assert_eq!(
+        defines(&[("A", "1"), ("B", "2")]),
+        defines(&[("B", "2"), ("A", "1")])
+    );
+    assert_ne!(defines(&[("A", "1")]), defines(&[("A", "2")]));
+}
+
+// ============================================================================
+// Batch Transforms
+// ============================================================================
+
+fn batch_options(filename: &str) -> Result<TransformOptions<'_>, TransformError> {
+    Ok(TransformOptions::builder().filename(filename).build()?)
+}
+
+#[test]
+fn test_transform_files_in_input_order() {
+    let files: Vec<(String, String)> = (0..8)
+        .map(|i| (format!("file{i}.jsx"), format!("const a = <div>{i}</div>;")))
+        .collect();
+    let progress = std::sync::Mutex::new(Vec::new());
+    let results = transform_files(&files, batch_options, |filename, index, total| {
+        assert_eq!(total, 8);
+        progress.lock().unwrap().push((filename.to_string(), index));
+    });
+
+    assert_eq!(results.len(), 8);
+    for (i, result) in results.iter().enumerate() {
+        let code = &result.as_ref().unwrap().code;
+        let expected = format!("<div>{i}</div>");
+        assert!(code.contains(&expected), "Output was:\n{code}");
+    }
+    let mut progress = progress.into_inner().unwrap();
+    progress.sort_by_key(|(_, index)| *index);
+    let finished: Vec<_> = progress.iter().map(|(name, _)| name).collect();
+    let filenames: Vec<_> = files.iter().map(|(name, _)| name).collect();
+    assert_eq!(finished, filenames);
+}
+
+#[test]
+fn test_transform_files_keeps_going_after_errors() {
+    let files = [
+        ("bad.jsx", "const a = <div>;"),
+        ("good.jsx", "const b = <p />;"),
+    ];
+    let results = transform_files(&files, batch_options, |_, _, _| {});
+    match &results[0] {
+        Err(TransformError::Parse(errors)) => assert_eq!(errors[0].filename, "bad.jsx"),
+        other => panic!("expected a parse error, got {other:?}"),
+    }
+    assert!(results[1].as_ref().unwrap().code.contains("<p></p>"));
+}
+
+#[test]
+fn test_transform_files_reports_rejected_options_per_file() {
+    let files = [
+        ("universal.jsx", "const a = <div />;"),
+        ("good.jsx", "const b = <p />;"),
+    ];
+    fn options(filename: &str) -> Result<TransformOptions<'_>, TransformError> {
+        let generate = if filename == "universal.jsx" {
+            GenerateMode::Universal
+        } else {
+            GenerateMode::Dom
+        };
+        Ok(TransformOptions::builder()
+            .filename(filename)
+            .generate(generate)
+            .hydratable(true)
+            .build()?)
+    }
+    let results = transform_files(&files, options, |_, _, _| {});
+    assert!(matches!(
+        results[0],
+        Err(TransformError::InvalidOptions(
+            OptionsError::HydratableUniversal
+        ))
+    ));
+    assert!(results[1].as_ref().unwrap().code.contains("<p></p>"));
+}
+
+// ============================================================================
+// Files Without JSX
+// ============================================================================
+
+#[test]
+fn test_files_without_jsx_are_returned_unchanged() {
+    let source = "export const double = (n) =>  n * 2; // no JSX\nif (a < b) run();\n";
+    let options = TransformOptions::builder()
+        .filename("math.js")
+        .source_map(true)
+        .build()
+        .unwrap();
+    let output = transform(source, Some(options)).unwrap();
+    assert!(!output.transformed);
+    assert_eq!(output.code, source);
+    assert!(output.map.is_none());
+
+    // Not even parsed
+    let output = transform("const = ;", Some(cache_options("broken.js", GenerateMode::Dom))).unwrap();
+    assert!(!output.transformed);
+
+    // TypeScript files can't contain JSX
+    let output = transform("const xs = <string[]>ys;", Some(cache_options("cast.ts", GenerateMode::Dom))).unwrap();
+    assert!(!output.transformed);
+}
+
+#[test]
+fn test_files_with_jsx_or_other_work_are_transformed() {
+    let output = transform("const a = <div />;", None).unwrap();
+    assert!(output.transformed);
+
+    // Only a string looks like JSX: transformed, to be safe
+    let output = transform(r#"const html = "<b>";"#, None).unwrap();
+    assert!(output.transformed);
+
+    let output = transform(r#"import { isServer } from "solid-js/web"; run(isServer);"#, None).unwrap();
+    assert!(output.transformed);
+    assert!(output.code.contains("run(false)"), "{}", output.code);
+
+    let options = TransformOptions::builder()
+        .define_constant("import.meta.env.SSR", "true")
+        .build()
+        .unwrap();
+    let output = transform("if (import.meta.env.SSR) run();", Some(options)).unwrap();
+    assert!(output.transformed);
+
+    let options = TransformOptions::builder()
+        .filename("types.ts")
+        .strip_types(true)
+        .build()
+        .unwrap();
+    let output = transform("const n: number = 1;", Some(options)).unwrap();
+    assert!(output.transformed);
+    assert!(output.code.contains("const n = 1;"), "{}", output.code);
+}
+
+#[test]
+fn test_require_import_source_transforms_matching_files() {
+    let options = || {
+        TransformOptions::builder()
+            .require_import_source(Some("solid-js"))
+            .build()
+            .unwrap()
+    };
+    let source = "/** @jsxImportSource solid-js */\nconst a = <div class=\"x\" />;\n";
+    let output = transform(source, Some(options())).unwrap();
+    assert!(output.transformed);
+    let template = "_$template(`<div class=\"x\">";
+    assert!(output.code.contains(template), "{}", output.code);
+
+    for source in [
+        "const a = <div class=\"x\" />;\n",
+        "/** @jsxImportSource react */\nconst a = <div class=\"x\" />;\n",
+        "// @jsxImportSource solid-js/h\nconst a = <div class=\"x\" />;\n",
+    ] {
+        let output = transform(source, Some(options())).unwrap();
+        assert!(!output.transformed, "{source}");
+        assert_eq!(output.code, source);
+    }
+
+    // Without the option the pragma is irrelevant
+    let output = transform("/** @jsxImportSource react */\nconst a = <div />;", None).unwrap();
+    assert!(output.code.contains("template("), "{}", output.code);
+}
+
+#[test]
+fn test_transform_program_skips_other_import_sources() {
+    use oxc_allocator::Allocator;
+    use oxc_codegen::Codegen;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    let source = "/* @jsxImportSource preact */\nconst a = <div class=\"x\" />;\n";
+    let allocator = Allocator::default();
+    let mut program = Parser::new(&allocator, source, SourceType::jsx())
+        .parse()
+        .program;
+    let options = TransformOptions::builder()
+        .require_import_source(Some("solid-js"))
+        .build()
+        .unwrap();
+    transform_program(&mut program, &allocator, &options).unwrap();
+    let code = Codegen::new().build(&program).code;
+    assert!(code.contains("<div class=\"x\" />"), "Output was:\n{code}");
+}
+
+// ============================================================================
+// Template Extraction
+// ============================================================================
+
+#[test]
+fn test_extract_templates() {
+    let source = r#"
+const App = () => (
+  <main class="app  dark" classList={{ active: on(), "a b": x }}>
+    <h1 class:title={big()} id={1}>Hello {name()}</h1>
+    <Button onClick={go}><span class="icon" /></Button>
+    <input disabled {...rest} />
+  </main>
+);
+"#;
+    let templates = extract_templates(source, None).expect("should parse");
+    assert_eq!(templates.len(), 2);
+
+    let main = &templates[0];
+    assert_eq!((main.line, main.column), (3, 3));
+    assert_eq!(main.root.tag, "main");
+    assert_eq!(main.root.classes, vec!["app", "dark", "active", "a", "b"]);
+    assert!(main.root.has_dynamic_children);
+    assert_eq!(main.root.children.len(), 2);
+
+    let h1 = &main.root.children[0];
+    assert_eq!(h1.classes, vec!["title"]);
+    assert_eq!(h1.text, vec!["Hello "]);
+    assert_eq!(
+        h1.attributes[1],
+        TemplateAttribute {
+            name: "id".to_string(),
+            value: Some("1".to_string()),
+            dynamic: false,
+        }
+    );
+
+    let input = &main.root.children[1];
+    assert_eq!(input.attributes[0].value.as_deref(), Some("true"));
+    assert!(input.attributes[1].dynamic);
+
+    // Elements under a component start their own template
+    assert_eq!(templates[1].root.tag, "span");
+    assert_eq!(templates[1].root.classes, vec!["icon"]);
+}
+
+#[test]
+fn test_extract_templates_parse_error() {
+    let error = extract_templates("const a = <div>", None).expect_err("should fail to parse");
+    assert!(matches!(error, TransformError::Parse(_)));
+    assert!(!error.errors().is_empty());
+}
+
+// ============================================================================
+// Parse Errors
+// ============================================================================
+
+#[test]
+fn test_parse_error_is_structured() {
+    let options = TransformOptions::builder()
+        .filename("App.jsx")
+        .build()
+        .unwrap();
+    let Err(TransformError::Parse(errors)) =
+        transform("const a = 1;\nconst b = <div>;\n", Some(options))
+    else {
+        panic!("should fail to parse");
+    };
+
+    assert!(!errors.is_empty());
+    let error = &errors[0];
+    assert_eq!(error.filename, "App.jsx");
+    assert_eq!(error.line, 2);
+    assert!(error.code_frame.contains("> 2 | const b = <div>;"), "{}", error.code_frame);
+    assert!(error.code_frame.contains('^'), "{}", error.code_frame);
+    assert!(error.to_string().starts_with("App.jsx:2:"), "{}", error);
+}
+
+#[test]
+fn test_transform_ok() {
+    let Ok(result) = transform(r#"<div class="hello">world</div>"#, None) else {
+        panic!("should parse");
+    };
+    assert!(result.code.contains("template("));
+}
+
+#[test]
+fn test_jsx_attribute_value_is_unsupported() {
+    let source = "const a = <div title=<span /> />;\nconst b = <Comp slot=<></> />;\n";
+    for generate in [GenerateMode::Dom, GenerateMode::Ssr] {
+        let options = TransformOptions::builder()
+            .generate(generate)
+            .build()
+            .unwrap();
+        let Err(TransformError::UnsupportedSyntax(errors)) = transform(source, Some(options))
+        else {
+            panic!("JSX attribute values should be rejected");
+        };
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!((errors[0].line, errors[0].column), (1, 22));
+        assert!(errors[0].message.contains("`title={...}`"), "{}", errors[0].message);
+        assert_eq!((errors[1].line, errors[1].column), (2, 22));
+    }
+}
+
+#[test]
+fn test_transform_program_leaves_unsupported_program_untouched() {
+    use oxc_allocator::Allocator;
+    use oxc_codegen::Codegen;
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    let source = "const a = <div title=<span /> />;\n";
+    let allocator = Allocator::default();
+    let mut program = Parser::new(&allocator, source, SourceType::jsx())
+        .parse()
+        .program;
+    let options = TransformOptions::builder().build().unwrap();
+    let result = transform_program(&mut program, &allocator, &options);
+
+    assert!(matches!(result, Err(TransformError::UnsupportedSyntax(_))));
+    let code = Codegen::new().build(&program).code;
+    assert!(code.contains("<div title=<span /> />"), "Output was:\n{code}");
+}
+
+#[test]
+fn test_invalid_options_error() {
+    let mut options = TransformOptions::solid_defaults();
+    options.generate = GenerateMode::Universal;
+    let error = transform("<div />", Some(options)).expect_err("universal needs a renderer");
+
+    assert_eq!(
+        error,
+        TransformError::InvalidOptions(OptionsError::MissingRendererModule)
+    );
+    assert!(error.errors().is_empty());
+    assert!(error.to_string().starts_with("invalid options: "), "{error}");
+}
+
+#[test]
+fn test_code_frame_context() {
+    let source = "a\nb\nc\nd\nerror here\ne\nf\ng\nh";
+    let frame = code_frame(source, 8, 13);
+    assert_eq!(
+        frame,
+        "  3 | c\n  4 | d\n> 5 | error here\n    | ^^^^^\n  6 | e\n  7 | f\n  8 | g"
+    );
+}
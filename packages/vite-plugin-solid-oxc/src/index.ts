@@ -168,6 +168,11 @@ export default function solidOxc(options: SolidOxcOptions = {}): Plugin {
           sourceMap: true,
         });
 
+        // No JSX: keep the module as it is, without a source map
+        if (!result.transformed) {
+          return null;
+        }
+
         // Add HMR support in dev mode
         if (isDev && opts.hot !== false) {
           const hotCode = `